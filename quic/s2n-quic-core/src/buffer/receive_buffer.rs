@@ -150,7 +150,12 @@ impl ReceiveBuffer {
                     let next_block =
                         Self::align_offset(next.start(), Self::allocation_size(next.start()));
 
-                    if next.start() == end && current_block == next_block {
+                    // Only merge slots which share an allocation, since registered buffers
+                    // would otherwise be copied into a new allocation
+                    if next.start() == end
+                        && current_block == next_block
+                        && self.slots[idx].is_contiguous(next)
+                    {
                         if let Some(next) = self.slots.remove(idx + 1) {
                             self.slots[idx].unsplit(next);
                         } else {
@@ -165,12 +170,14 @@ impl ReceiveBuffer {
             }
 
             idx += 1;
-            self.allocate_request(idx, upper);
 
             if let Some(mid) = mid {
                 self.insert(idx, mid);
+                idx += 1;
             }
 
+            self.allocate_request(idx, upper);
+
             request = lower;
 
             if request.is_empty() {
@@ -185,6 +192,59 @@ impl ReceiveBuffer {
         Ok(())
     }
 
+    /// Registers a caller-provided buffer which receives the next bytes of the stream
+    ///
+    /// Data written at the current offset of the stream is copied directly into the spare
+    /// capacity of the buffer, rather than into an internal allocation. Chunks which are
+    /// popped from the receive buffer afterwards are views into the provided buffer.
+    ///
+    /// The buffer can only be registered if no data is currently buffered at the current
+    /// offset. In that case the buffer is returned as an error. Any data contained in the
+    /// buffer is discarded.
+    #[inline]
+    pub fn register_buffer(&mut self, mut buffer: BytesMut) -> Result<(), BytesMut> {
+        buffer.clear();
+
+        if buffer.capacity() == 0 {
+            return Err(buffer);
+        }
+
+        // Remove any allocated slots which haven't received any data yet
+        while self.slots.front().map_or(false, |slot| slot.is_empty()) {
+            self.slots.pop_front();
+        }
+
+        let start = self.start_offset;
+        let mut end = start.saturating_add(buffer.capacity() as u64);
+
+        if let Some(next) = self.slots.front() {
+            if next.start() == start {
+                return Err(buffer);
+            }
+
+            // stop at the data which was already received
+            end = end.min(next.start());
+        }
+
+        self.slots.push_front(Slot::new(start, end, buffer));
+
+        self.check_consistency();
+
+        Ok(())
+    }
+
+    /// Returns `true` if a buffer is allocated for the current offset of the stream
+    ///
+    /// This is the case if data was already received at the current offset or if a previously
+    /// registered or allocated buffer still has spare capacity. Calling
+    /// [`Self::register_buffer`] in that case would either fail or replace the existing buffer.
+    #[inline]
+    pub fn is_allocated_at_offset(&self) -> bool {
+        self.slots
+            .front()
+            .map_or(false, |slot| slot.start() == self.start_offset)
+    }
+
     /// Iterates over all of the chunks waiting to be received
     #[inline]
     pub fn iter(&self) -> impl Iterator<Item = &[u8]> {
@@ -287,9 +347,15 @@ impl ReceiveBuffer {
     fn allocate_request(&mut self, mut idx: usize, mut request: Request) {
         while !request.is_empty() {
             let size = Self::allocation_size(request.start());
-            let offset = Self::align_offset(request.start(), size);
-            let buffer = BytesMut::with_capacity(size);
+            let mut offset = Self::align_offset(request.start(), size);
             let end = offset + size as u64;
+
+            // registered buffers aren't aligned so make sure the allocation starts after them
+            if let Some(prev) = idx.checked_sub(1).and_then(|idx| self.slots.get(idx)) {
+                offset = offset.max(prev.end_allocated());
+            }
+
+            let buffer = BytesMut::with_capacity((end - offset) as usize);
             let mut slot = Slot::new(offset, end, buffer);
 
             let slot::Outcome { lower, mid, upper } = slot.try_write(request);
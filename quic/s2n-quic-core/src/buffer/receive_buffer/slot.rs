@@ -84,6 +84,12 @@ impl Slot {
         self.end = next.end;
     }
 
+    /// Returns `true` if the `next` slot directly follows this slot in memory
+    #[inline]
+    pub fn is_contiguous(&self, next: &Self) -> bool {
+        self.data.as_ptr().wrapping_add(self.data.len()) == next.data.as_ptr()
+    }
+
     #[inline]
     pub fn is_full(&self) -> bool {
         self.end() == self.end_allocated()
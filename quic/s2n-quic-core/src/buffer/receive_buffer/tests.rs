@@ -17,6 +17,10 @@ enum Op {
     Pop {
         watermark: Option<u16>,
     },
+    Register {
+        #[generator(0..BYTES.len())]
+        capacity: usize,
+    },
 }
 
 #[test]
@@ -38,6 +42,9 @@ fn model_test() {
                         assert!(!chunk.is_empty(), "popped chunks should never be empty");
                     }
                 }
+                Op::Register { capacity } => {
+                    let _ = buffer.register_buffer(BytesMut::with_capacity(capacity));
+                }
             }
         }
     })
@@ -762,3 +769,51 @@ fn allocation_size_test() {
         }
     }
 }
+
+#[test]
+fn register_buffer_test() {
+    let mut buf = ReceiveBuffer::new();
+
+    // Empty buffers can't be registered
+    assert!(buf.register_buffer(BytesMut::new()).is_err());
+    assert!(!buf.is_allocated_at_offset());
+
+    let target = BytesMut::with_capacity(8);
+    let target_ptr = target.as_ptr();
+    buf.register_buffer(target).unwrap();
+    assert!(buf.is_allocated_at_offset());
+
+    // Data is written into the registered buffer, even if it arrives out of order
+    buf.write_at(4u32.into(), &[4, 5, 6, 7, 8, 9]).unwrap();
+    assert_eq!(0, buf.len());
+    buf.write_at(0u32.into(), &[0, 1, 2, 3]).unwrap();
+    assert_eq!(10, buf.len());
+
+    let chunk = buf.pop().unwrap();
+    assert_eq!(&[0u8, 1, 2, 3, 4, 5, 6, 7], &*chunk);
+    assert_eq!(target_ptr, chunk.as_ptr());
+
+    // Data past the end of the registered buffer is stored in an internal allocation
+    let chunk = buf.pop().unwrap();
+    assert_eq!(&[8u8, 9], &*chunk);
+    assert_eq!(None, buf.pop());
+
+    // Buffers can't be registered if data is already buffered at the current offset
+    buf.write_at(10u32.into(), &[10, 11]).unwrap();
+    assert!(buf.is_allocated_at_offset());
+    assert!(buf.register_buffer(BytesMut::with_capacity(8)).is_err());
+    assert_eq!(&[10u8, 11], &*buf.pop().unwrap());
+
+    // Registered buffers are truncated to the data which was already received
+    buf.write_at(16u32.into(), &[16, 17]).unwrap();
+    let target = BytesMut::with_capacity(64);
+    let target_ptr = target.as_ptr();
+    buf.register_buffer(target).unwrap();
+    buf.write_at(12u32.into(), &[12, 13, 14, 15]).unwrap();
+
+    let chunk = buf.pop().unwrap();
+    assert_eq!(&[12u8, 13, 14, 15], &*chunk);
+    assert_eq!(target_ptr, chunk.as_ptr());
+    assert_eq!(&[16u8, 17], &*buf.pop().unwrap());
+    assert_eq!(18, buf.consumed_len());
+}
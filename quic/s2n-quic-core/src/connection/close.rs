@@ -3,6 +3,133 @@
 
 use crate::{application, crypto, transport};
 pub use crate::{frame::ConnectionClose, inet::SocketAddress};
+use core::fmt;
+
+/// The reason phrase of a CONNECTION_CLOSE frame received from the peer
+///
+/// The phrase is stored inline so [`Error`](crate::connection::Error) remains `Copy` and small.
+/// Phrases longer than [`Reason::MAX_LEN`] bytes are truncated, which is indicated by
+/// [`Reason::is_truncated`]. The complete phrase is available through the
+/// `ConnectionCloseReceived` event.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct Reason {
+    len: u8,
+    truncated: bool,
+    bytes: [u8; Reason::MAX_LEN],
+}
+
+impl Reason {
+    /// The maximum number of bytes that are retained from a reason phrase
+    pub const MAX_LEN: usize = 30;
+
+    /// An empty reason phrase
+    pub const EMPTY: Self = Self {
+        len: 0,
+        truncated: false,
+        bytes: [0; Self::MAX_LEN],
+    };
+
+    /// Creates a reason phrase from the given bytes, truncating it to [`Reason::MAX_LEN`]
+    ///
+    /// If the bytes are valid UTF-8, the phrase is truncated on a character boundary.
+    #[inline]
+    pub fn new(reason: &[u8]) -> Self {
+        let mut len = reason.len().min(Self::MAX_LEN);
+
+        if let Ok(reason) = core::str::from_utf8(reason) {
+            while !reason.is_char_boundary(len) {
+                len -= 1;
+            }
+        }
+
+        let mut bytes = [0; Self::MAX_LEN];
+        bytes[..len].copy_from_slice(&reason[..len]);
+
+        Self {
+            len: len as u8,
+            truncated: len < reason.len(),
+            bytes,
+        }
+    }
+
+    /// Returns the raw bytes of the reason phrase
+    #[inline]
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes[..self.len as usize]
+    }
+
+    /// Returns the reason phrase as a string, if it is valid UTF-8
+    #[inline]
+    pub fn as_str(&self) -> Option<&str> {
+        core::str::from_utf8(self.as_bytes()).ok()
+    }
+
+    /// Returns `true` if the reason phrase is empty
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns `true` if the peer sent a phrase longer than [`Reason::MAX_LEN`] bytes
+    #[inline]
+    pub fn is_truncated(&self) -> bool {
+        self.truncated
+    }
+}
+
+impl Default for Reason {
+    #[inline]
+    fn default() -> Self {
+        Self::EMPTY
+    }
+}
+
+impl From<&[u8]> for Reason {
+    #[inline]
+    fn from(reason: &[u8]) -> Self {
+        Self::new(reason)
+    }
+}
+
+impl From<&str> for Reason {
+    #[inline]
+    fn from(reason: &str) -> Self {
+        Self::new(reason.as_bytes())
+    }
+}
+
+impl fmt::Debug for Reason {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut f = f.debug_struct("Reason");
+        if let Some(reason) = self.as_str() {
+            f.field("reason", &reason);
+        } else {
+            f.field("reason", &self.as_bytes());
+        }
+        f.field("truncated", &self.truncated).finish()
+    }
+}
+
+impl fmt::Display for Reason {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(reason) = self.as_str() {
+            f.write_str(reason)?;
+        } else {
+            // the peer sent a non-UTF-8 reason so escape any bytes that aren't printable
+            for byte in self.as_bytes() {
+                for c in core::ascii::escape_default(*byte) {
+                    write!(f, "{}", c as char)?;
+                }
+            }
+        }
+
+        if self.truncated {
+            f.write_str("...")?;
+        }
+
+        Ok(())
+    }
+}
 
 /// Provides a hook for applications to rewrite CONNECTION_CLOSE frames
 ///
@@ -158,3 +285,33 @@ impl Formatter for Production {
         transport::Error::APPLICATION_ERROR.into()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reason_test() {
+        assert!(Reason::default().is_empty());
+
+        let reason = Reason::from("shutting down");
+        assert_eq!(reason.as_str(), Some("shutting down"));
+        assert!(!reason.is_truncated());
+
+        let long = [b'a'; Reason::MAX_LEN + 1];
+        let reason = Reason::new(&long);
+        assert_eq!(reason.as_bytes(), &long[..Reason::MAX_LEN]);
+        assert!(reason.is_truncated());
+
+        // truncation does not split a multi-byte character
+        let mut long = "a".repeat(Reason::MAX_LEN - 1);
+        long.push('\u{00e9}');
+        let reason = Reason::from(long.as_str());
+        assert_eq!(reason.as_str(), Some(&long[..Reason::MAX_LEN - 1]));
+        assert!(reason.is_truncated());
+
+        let invalid = Reason::new(&[b'o', b'k', 0xff]);
+        assert_eq!(invalid.as_str(), None);
+        assert_eq!(format!("{invalid}"), "ok\\xff");
+    }
+}
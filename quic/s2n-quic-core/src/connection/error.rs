@@ -2,7 +2,12 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    application, connection, crypto::CryptoError, endpoint, frame::ConnectionClose, transport,
+    application,
+    connection::{self, close::Reason},
+    crypto::CryptoError,
+    endpoint,
+    frame::ConnectionClose,
+    transport,
 };
 use core::{convert::TryInto, fmt, panic, time::Duration};
 
@@ -11,9 +16,12 @@ use core::{convert::TryInto, fmt, panic, time::Duration};
 #[non_exhaustive]
 pub enum Error {
     /// The connection was closed without an error
+    ///
+    /// If the peer closed the connection, `close_reason` contains the reason phrase it sent.
     #[non_exhaustive]
     Closed {
         initiator: endpoint::Location,
+        close_reason: Reason,
         source: &'static panic::Location<'static>,
     },
 
@@ -21,12 +29,15 @@ pub enum Error {
     ///
     /// This can occur either locally or by the peer. The argument contains
     /// the error code which the transport provided in order to close the
-    /// connection.
+    /// connection. If the peer closed the connection, `frame_type` is the type
+    /// of frame that triggered the error and `close_reason` contains the reason
+    /// phrase it sent.
     #[non_exhaustive]
     Transport {
         code: transport::error::Code,
         frame_type: u64,
         reason: &'static str,
+        close_reason: Reason,
         initiator: endpoint::Location,
         source: &'static panic::Location<'static>,
     },
@@ -35,10 +46,12 @@ pub enum Error {
     ///
     /// This can occur either locally or by the peer. The argument contains
     /// the error code which the application/ supplied in order to close the
-    /// connection.
+    /// connection. If the peer closed the connection, `close_reason` contains
+    /// the reason phrase it sent.
     #[non_exhaustive]
     Application {
         error: application::Error,
+        close_reason: Reason,
         initiator: endpoint::Location,
         source: &'static panic::Location<'static>,
     },
@@ -87,6 +100,22 @@ pub enum Error {
         source: &'static panic::Location<'static>,
     },
 
+    /// The requested operation is only available once the handshake has completed
+    ///
+    /// This does not close the connection and the operation can be retried later.
+    #[non_exhaustive]
+    HandshakeNotComplete {
+        source: &'static panic::Location<'static>,
+    },
+
+    /// Keying material can't be exported since retaining the TLS session was not enabled
+    ///
+    /// This does not close the connection.
+    #[non_exhaustive]
+    KeyingMaterialExporterDisabled {
+        source: &'static panic::Location<'static>,
+    },
+
     /// The connection was closed due to an unspecified reason
     #[non_exhaustive]
     Unspecified {
@@ -100,11 +129,14 @@ impl std::error::Error for Error {}
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
-            Self::Closed { initiator, .. } => write!(
-                f,
-                "The connection was closed without an error by {initiator}"
-            ),
-            Self::Transport { code, frame_type, reason, initiator, .. } => {
+            Self::Closed { initiator, close_reason, .. } => {
+                write!(
+                    f,
+                    "The connection was closed without an error by {initiator}"
+                )?;
+                fmt_close_reason(f, close_reason)
+            },
+            Self::Transport { code, frame_type, reason, close_reason, initiator, .. } => {
                 let error = transport::Error {
                     code: *code,
                     frame_type: (*frame_type).try_into().ok().unwrap_or_default(),
@@ -113,12 +145,16 @@ impl fmt::Display for Error {
                 write!(
                     f,
                     "The connection was closed on the transport level with error {error} by {initiator}"
-                )
+                )?;
+                fmt_close_reason(f, close_reason)
+            },
+            Self::Application { error, close_reason, initiator, .. } => {
+                write!(
+                    f,
+                    "The connection was closed on the application level with error {error:?} by {initiator}"
+                )?;
+                fmt_close_reason(f, close_reason)
             },
-            Self::Application { error, initiator, .. } => write!(
-                f,
-                "The connection was closed on the application level with error {error:?} by {initiator}"
-            ),
             Self::StatelessReset { .. } => write!(
                 f,
                 "The connection was reset by a stateless reset by {}",
@@ -149,6 +185,12 @@ impl fmt::Display for Error {
             Self::EndpointClosing { .. } => {
                 write!(f, "The connection attempt was rejected because the endpoint is closing")
             }
+            Self::HandshakeNotComplete { .. } => {
+                write!(f, "The operation requires the handshake to be complete")
+            }
+            Self::KeyingMaterialExporterDisabled { .. } => {
+                write!(f, "Exporting keying material was not enabled for the connection")
+            }
             Self::Unspecified { .. } => {
                 write!(f, "The connection was closed due to an unspecified reason")
             }
@@ -156,6 +198,14 @@ impl fmt::Display for Error {
     }
 }
 
+fn fmt_close_reason(f: &mut fmt::Formatter, close_reason: &Reason) -> fmt::Result {
+    if close_reason.is_empty() {
+        return Ok(());
+    }
+
+    write!(f, ": {close_reason}")
+}
+
 impl Error {
     /// Returns the [`panic::Location`] for the error
     pub fn source(&self) -> &'static panic::Location<'static> {
@@ -170,25 +220,54 @@ impl Error {
             Error::MaxHandshakeDurationExceeded { source, .. } => source,
             Error::ImmediateClose { source, .. } => source,
             Error::EndpointClosing { source } => source,
+            Error::HandshakeNotComplete { source } => source,
+            Error::KeyingMaterialExporterDisabled { source } => source,
             Error::Unspecified { source } => source,
         }
     }
 
+    /// Returns the reason phrase sent by the peer when it closed the connection, if any
+    pub fn close_reason(&self) -> Option<&Reason> {
+        match self {
+            Error::Closed { close_reason, .. }
+            | Error::Transport { close_reason, .. }
+            | Error::Application { close_reason, .. }
+                if !close_reason.is_empty() =>
+            {
+                Some(close_reason)
+            }
+            _ => None,
+        }
+    }
+
     #[track_caller]
-    fn from_transport_error(error: transport::Error, initiator: endpoint::Location) -> Self {
+    fn from_transport_error(
+        error: transport::Error,
+        close_reason: Reason,
+        initiator: endpoint::Location,
+    ) -> Self {
         let source = panic::Location::caller();
         match error.code {
             // The connection closed without an error
-            code if code == transport::Error::NO_ERROR.code => Self::Closed { initiator, source },
+            code if code == transport::Error::NO_ERROR.code => Self::Closed {
+                initiator,
+                close_reason,
+                source,
+            },
             // The connection closed without an error at the application layer
             code if code == transport::Error::APPLICATION_ERROR.code && initiator.is_remote() => {
-                Self::Closed { initiator, source }
+                Self::Closed {
+                    initiator,
+                    close_reason,
+                    source,
+                }
             }
             // The connection closed with an actual error
             _ => Self::Transport {
                 code: error.code,
                 frame_type: error.frame_type.into(),
                 reason: error.reason,
+                close_reason,
                 initiator,
                 source,
             },
@@ -200,7 +279,11 @@ impl Error {
     #[doc(hidden)]
     pub fn closed(initiator: endpoint::Location) -> Error {
         let source = panic::Location::caller();
-        Error::Closed { initiator, source }
+        Error::Closed {
+            initiator,
+            close_reason: Reason::EMPTY,
+            source,
+        }
     }
 
     #[inline]
@@ -261,6 +344,7 @@ impl Error {
         let source = panic::Location::caller();
         Error::Application {
             error,
+            close_reason: Reason::EMPTY,
             initiator: endpoint::Location::Local,
             source,
         }
@@ -274,6 +358,22 @@ impl Error {
         Error::EndpointClosing { source }
     }
 
+    #[inline]
+    #[track_caller]
+    #[doc(hidden)]
+    pub fn handshake_not_complete() -> Error {
+        let source = panic::Location::caller();
+        Error::HandshakeNotComplete { source }
+    }
+
+    #[inline]
+    #[track_caller]
+    #[doc(hidden)]
+    pub fn keying_material_exporter_disabled() -> Error {
+        let source = panic::Location::caller();
+        Error::KeyingMaterialExporterDisabled { source }
+    }
+
     #[inline]
     #[track_caller]
     #[doc(hidden)]
@@ -356,6 +456,9 @@ pub fn as_frame<'a, F: connection::close::Formatter>(
         Error::MaxHandshakeDurationExceeded { .. } => None,
         Error::ImmediateClose { .. } => None,
         Error::EndpointClosing { .. } => None,
+        // These errors are only returned to the application and never close the connection
+        Error::HandshakeNotComplete { .. } => None,
+        Error::KeyingMaterialExporterDisabled { .. } => None,
         Error::Unspecified { .. } => {
             let error =
                 transport::Error::INTERNAL_ERROR.with_reason("an unspecified error occurred");
@@ -381,7 +484,7 @@ impl application::error::TryInto for Error {
 impl From<transport::Error> for Error {
     #[track_caller]
     fn from(error: transport::Error) -> Self {
-        Self::from_transport_error(error, endpoint::Location::Local)
+        Self::from_transport_error(error, Reason::EMPTY, endpoint::Location::Local)
     }
 }
 
@@ -395,6 +498,10 @@ impl From<CryptoError> for Error {
 impl<'a> From<ConnectionClose<'a>> for Error {
     #[track_caller]
     fn from(error: ConnectionClose) -> Self {
+        // the reason phrase is copied inline so we don't allocate anything
+        // in the event of an error
+        let close_reason = error.reason.map(Reason::new).unwrap_or_default();
+
         if let Some(frame_type) = error.frame_type {
             let error = transport::Error {
                 code: transport::error::Code::new(error.error_code),
@@ -403,11 +510,12 @@ impl<'a> From<ConnectionClose<'a>> for Error {
                 reason: "",
                 frame_type,
             };
-            Self::from_transport_error(error, endpoint::Location::Remote)
+            Self::from_transport_error(error, close_reason, endpoint::Location::Remote)
         } else {
             let source = panic::Location::caller();
             Self::Application {
                 error: error.error_code.into(),
+                close_reason,
                 initiator: endpoint::Location::Remote,
                 source,
             }
@@ -441,6 +549,8 @@ impl From<Error> for std::io::ErrorKind {
             Error::MaxHandshakeDurationExceeded { .. } => ErrorKind::TimedOut,
             Error::ImmediateClose { .. } => ErrorKind::Other,
             Error::EndpointClosing { .. } => ErrorKind::Other,
+            Error::HandshakeNotComplete { .. } => ErrorKind::NotConnected,
+            Error::KeyingMaterialExporterDisabled { .. } => ErrorKind::Unsupported,
             Error::Unspecified { .. } => ErrorKind::Other,
         }
     }
@@ -481,3 +591,60 @@ impl From<CryptoError> for ProcessingError {
         ProcessingError::CryptoError(inner_error)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::varint::VarInt;
+
+    /// Connection errors are returned by value from every stream operation so make sure they
+    /// stay small
+    #[test]
+    fn error_size_test() {
+        assert!(core::mem::size_of::<Error>() <= 48 + core::mem::size_of::<Reason>());
+    }
+
+    #[test]
+    fn peer_close_test() {
+        let frame = ConnectionClose {
+            error_code: VarInt::from_u8(1),
+            frame_type: Some(VarInt::from_u8(0x08)),
+            reason: Some(b"stream limit exceeded"),
+        };
+        let error = Error::from(frame);
+        assert!(matches!(
+            error,
+            Error::Transport {
+                frame_type: 0x08,
+                initiator: endpoint::Location::Remote,
+                ..
+            }
+        ));
+        assert_eq!(
+            error.close_reason().and_then(Reason::as_str),
+            Some("stream limit exceeded")
+        );
+        assert!(error.to_string().ends_with(": stream limit exceeded"));
+
+        let frame = ConnectionClose {
+            error_code: VarInt::from_u8(99),
+            frame_type: None,
+            reason: Some(b"going away"),
+        };
+        let error = Error::from(frame);
+        assert!(matches!(
+            error,
+            Error::Application {
+                initiator: endpoint::Location::Remote,
+                ..
+            }
+        ));
+        assert_eq!(
+            error.close_reason().and_then(Reason::as_str),
+            Some("going away")
+        );
+
+        // local errors don't have a close reason
+        assert_eq!(Error::application(99u8.into()).close_reason(), None);
+    }
+}
@@ -0,0 +1,91 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Configures how a connection actively keeps itself alive
+
+use crate::transport::parameters::ValidationError;
+use core::time::Duration;
+
+/// Configures the PING frames a connection sends to keep itself alive
+///
+/// By default, PING frames are sent at an interval derived from the idle timeout and the
+/// `max_keep_alive_period` of the connection limits.
+///
+/// # Examples
+///
+/// ```rust
+/// use core::time::Duration;
+/// use s2n_quic_core::connection::KeepAlive;
+///
+/// let keep_alive = KeepAlive::default()
+///     .with_interval(Duration::from_secs(15))
+///     .unwrap()
+///     .with_jitter(Duration::from_secs(2))
+///     .unwrap()
+///     .with_backoff(Duration::from_secs(120))
+///     .unwrap();
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct KeepAlive {
+    interval: Option<Duration>,
+    jitter: Duration,
+    max_backoff_interval: Option<Duration>,
+}
+
+impl KeepAlive {
+    /// Sets the interval at which PING frames are sent while the connection is otherwise idle
+    ///
+    /// The interval is capped to ensure PING frames are sent before the idle timeout of the
+    /// connection expires.
+    pub fn with_interval(mut self, interval: Duration) -> Result<Self, ValidationError> {
+        if interval.is_zero() {
+            return Err(ValidationError::new("keep-alive interval must be non-zero"));
+        }
+
+        self.interval = Some(interval);
+        Ok(self)
+    }
+
+    /// Sets the maximum amount of random jitter that is subtracted from each interval
+    ///
+    /// Jitter prevents many connections from sending PING frames at the same time. At most
+    /// half of the current interval is used as jitter.
+    pub fn with_jitter(mut self, jitter: Duration) -> Result<Self, ValidationError> {
+        self.jitter = jitter;
+        Ok(self)
+    }
+
+    /// Enables backing off when the path is idle for a long time
+    ///
+    /// Each consecutive PING frame that is sent without any other activity on the connection
+    /// doubles the interval, up to `max_interval`. The interval is restored as soon as the
+    /// application or the peer sends data.
+    pub fn with_backoff(mut self, max_interval: Duration) -> Result<Self, ValidationError> {
+        if max_interval.is_zero() {
+            return Err(ValidationError::new(
+                "keep-alive backoff interval must be non-zero",
+            ));
+        }
+
+        self.max_backoff_interval = Some(max_interval);
+        Ok(self)
+    }
+
+    #[doc(hidden)]
+    #[inline]
+    pub fn interval(&self) -> Option<Duration> {
+        self.interval
+    }
+
+    #[doc(hidden)]
+    #[inline]
+    pub fn jitter(&self) -> Duration {
+        self.jitter
+    }
+
+    #[doc(hidden)]
+    #[inline]
+    pub fn max_backoff_interval(&self) -> Option<Duration> {
+        self.max_backoff_interval
+    }
+}
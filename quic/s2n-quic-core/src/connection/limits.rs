@@ -2,14 +2,15 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    ack,
+    ack, application,
+    crypto::application::key_update,
     event::{api::SocketAddress, IntoEvent},
     inet, stream,
     transport::parameters::{
         AckDelayExponent, ActiveConnectionIdLimit, InitialFlowControlLimits, InitialMaxData,
         InitialMaxStreamDataBidiLocal, InitialMaxStreamDataBidiRemote, InitialMaxStreamDataUni,
         InitialMaxStreamsBidi, InitialMaxStreamsUni, InitialStreamLimits, MaxAckDelay,
-        MaxDatagramFrameSize, MaxIdleTimeout, TransportParameters,
+        MaxDatagramFrameSize, MaxIdleTimeout, ResetStreamAtSupport, TransportParameters,
     },
 };
 use core::{convert::TryInto, time::Duration};
@@ -61,9 +62,19 @@ pub struct Limits {
     pub(crate) ack_elicitation_interval: u8,
     pub(crate) ack_ranges_limit: u8,
     pub(crate) max_send_buffer_size: stream::limits::MaxSendBufferSize,
+    pub(crate) max_connection_send_buffer_size: u64,
     pub(crate) max_handshake_duration: Duration,
     pub(crate) max_keep_alive_period: Duration,
     pub(crate) max_datagram_frame_size: MaxDatagramFrameSize,
+    pub(crate) reset_stream_at_support: ResetStreamAtSupport,
+    /// The support for reliable stream resets advertised to the peer
+    ///
+    /// Unlike `reset_stream_at_support`, this isn't disabled if the peer doesn't support
+    /// the extension.
+    pub(crate) local_reset_stream_at_support: ResetStreamAtSupport,
+    pub(crate) key_update_policy: key_update::Policy,
+    pub(crate) drain_refusal_error: application::Error,
+    pub(crate) keying_material_exporter: bool,
 }
 
 impl Default for Limits {
@@ -73,7 +84,8 @@ impl Default for Limits {
 }
 
 macro_rules! setter {
-    ($name:ident, $field:ident, $inner:ty) => {
+    ($(#[$attr:meta])* $name:ident, $field:ident, $inner:ty) => {
+        $(#[$attr])*
         pub fn $name(mut self, value: $inner) -> Result<Self, ValidationError> {
             self.$field = value.try_into()?;
             Ok(self)
@@ -81,6 +93,28 @@ macro_rules! setter {
     };
 }
 
+macro_rules! window_setter {
+    ($name:ident, $field:ident) => {
+        pub fn $name(mut self, value: u64) -> Result<Self, ValidationError> {
+            validate_window(value)?;
+            self.$field = value.try_into()?;
+            Ok(self)
+        }
+    };
+}
+
+/// Receive windows are tracked as 32 bit values so larger windows are rejected
+#[inline]
+fn validate_window(value: u64) -> Result<(), ValidationError> {
+    if value > u32::MAX as u64 {
+        return Err(ValidationError::new(
+            "data windows cannot be greater than u32::MAX",
+        ));
+    }
+
+    Ok(())
+}
+
 impl Limits {
     pub const fn new() -> Self {
         Self {
@@ -99,29 +133,33 @@ impl Limits {
             ack_elicitation_interval: ack::Settings::RECOMMENDED.ack_elicitation_interval,
             ack_ranges_limit: ack::Settings::RECOMMENDED.ack_ranges_limit,
             max_send_buffer_size: stream::Limits::RECOMMENDED.max_send_buffer_size,
+            max_connection_send_buffer_size: u64::MAX,
             max_handshake_duration: MAX_HANDSHAKE_DURATION_DEFAULT,
             max_keep_alive_period: MAX_KEEP_ALIVE_PERIOD_DEFAULT,
             max_datagram_frame_size: MaxDatagramFrameSize::DEFAULT,
+            reset_stream_at_support: ResetStreamAtSupport::Disabled,
+            local_reset_stream_at_support: ResetStreamAtSupport::Disabled,
+            key_update_policy: key_update::Policy {
+                max_packets: None,
+                max_bytes: None,
+                max_interval: None,
+            },
+            drain_refusal_error: application::Error::UNKNOWN,
+            keying_material_exporter: false,
         }
     }
 
     setter!(with_max_idle_timeout, max_idle_timeout, Duration);
-    setter!(with_data_window, data_window, u64);
-    setter!(
+    window_setter!(with_data_window, data_window);
+    window_setter!(
         with_bidirectional_local_data_window,
-        bidirectional_local_data_window,
-        u64
+        bidirectional_local_data_window
     );
-    setter!(
+    window_setter!(
         with_bidirectional_remote_data_window,
-        bidirectional_remote_data_window,
-        u64
-    );
-    setter!(
-        with_unidirectional_data_window,
-        unidirectional_data_window,
-        u64
+        bidirectional_remote_data_window
     );
+    window_setter!(with_unidirectional_data_window, unidirectional_data_window);
 
     /// Sets both the max local and remote limits for bidirectional streams.
     #[deprecated(
@@ -177,6 +215,17 @@ impl Limits {
     setter!(with_ack_elicitation_interval, ack_elicitation_interval, u8);
     setter!(with_max_ack_ranges, ack_ranges_limit, u8);
     setter!(with_max_send_buffer_size, max_send_buffer_size, u32);
+    setter!(
+        /// Sets the maximum amount of data buffered for sending across all streams of a connection
+        ///
+        /// The limit applies in addition to `with_max_send_buffer_size`, which limits the amount of
+        /// data buffered by each stream. Streams are blocked from sending data once the limit is
+        /// exhausted until buffered data is acknowledged by the peer. By default, only the
+        /// per-stream limit applies.
+        with_max_connection_send_buffer_size,
+        max_connection_send_buffer_size,
+        u64
+    );
     setter!(
         with_max_handshake_duration,
         max_handshake_duration,
@@ -184,6 +233,75 @@ impl Limits {
     );
     setter!(with_max_keep_alive_period, max_keep_alive_period, Duration);
 
+    /// Enables support for reliable stream resets (`RESET_STREAM_AT` frames)
+    ///
+    /// Both peers need to enable the extension before streams can be reset with a reliable size.
+    pub fn with_reliable_stream_reset(mut self, enabled: bool) -> Result<Self, ValidationError> {
+        self.reset_stream_at_support = enabled.into();
+        self.local_reset_stream_at_support = enabled.into();
+        Ok(self)
+    }
+
+    /// Sets the number of packets protected with a 1-RTT key before a key update is initiated
+    ///
+    /// Keys are always updated before reaching the confidentiality limit of the negotiated AEAD.
+    /// By default, only that limit applies.
+    pub fn with_key_update_packet_limit(mut self, value: u64) -> Result<Self, ValidationError> {
+        if value == 0 {
+            return Err(ValidationError::new(
+                "key update packet limit must be greater than zero",
+            ));
+        }
+        self.key_update_policy.max_packets = Some(value);
+        Ok(self)
+    }
+
+    /// Sets the number of payload bytes protected with a 1-RTT key before a key update is
+    /// initiated
+    pub fn with_key_update_byte_limit(mut self, value: u64) -> Result<Self, ValidationError> {
+        if value == 0 {
+            return Err(ValidationError::new(
+                "key update byte limit must be greater than zero",
+            ));
+        }
+        self.key_update_policy.max_bytes = Some(value);
+        Ok(self)
+    }
+
+    /// Sets the amount of time a 1-RTT key is used before a key update is initiated
+    pub fn with_key_update_interval(mut self, value: Duration) -> Result<Self, ValidationError> {
+        if value.is_zero() {
+            return Err(ValidationError::new(
+                "key update interval must be greater than zero",
+            ));
+        }
+        self.key_update_policy.max_interval = Some(value);
+        Ok(self)
+    }
+
+    /// Sets the application error code used to refuse streams which the peer opens while the
+    /// connection is draining
+    ///
+    /// Refused streams are reset and the peer is asked to stop sending with this code, which lets
+    /// it retry the requests on another connection. By default, `application::Error::UNKNOWN`
+    /// is used.
+    pub fn with_drain_refusal_error(
+        mut self,
+        value: application::Error,
+    ) -> Result<Self, ValidationError> {
+        self.drain_refusal_error = value;
+        Ok(self)
+    }
+
+    /// Enables exporting keying material from the TLS session once the handshake completes
+    ///
+    /// This keeps the TLS session, including its secrets, alive for the lifetime of the
+    /// connection. By default, the session is discarded once the handshake completes.
+    pub fn with_keying_material_exporter(mut self, enabled: bool) -> Result<Self, ValidationError> {
+        self.keying_material_exporter = enabled;
+        Ok(self)
+    }
+
     // internal APIs
 
     #[doc(hidden)]
@@ -191,6 +309,11 @@ impl Limits {
     pub fn load_peer<A, B, C, D>(&mut self, peer_parameters: &TransportParameters<A, B, C, D>) {
         self.max_idle_timeout
             .load_peer(&peer_parameters.max_idle_timeout);
+
+        // the extension is only used if both peers support it
+        if !peer_parameters.reset_stream_at_support.is_enabled() {
+            self.reset_stream_at_support = ResetStreamAtSupport::Disabled;
+        }
     }
 
     #[doc(hidden)]
@@ -239,6 +362,12 @@ impl Limits {
         }
     }
 
+    #[doc(hidden)]
+    #[inline]
+    pub fn max_connection_send_buffer_size(&self) -> u64 {
+        self.max_connection_send_buffer_size
+    }
+
     #[doc(hidden)]
     #[inline]
     pub fn max_idle_timeout(&self) -> Option<Duration> {
@@ -256,6 +385,114 @@ impl Limits {
     pub fn max_keep_alive_period(&self) -> Duration {
         self.max_keep_alive_period
     }
+
+    #[doc(hidden)]
+    #[inline]
+    pub fn reliable_stream_reset(&self) -> bool {
+        self.reset_stream_at_support.is_enabled()
+    }
+
+    #[doc(hidden)]
+    #[inline]
+    pub fn local_reliable_stream_reset(&self) -> bool {
+        self.local_reset_stream_at_support.is_enabled()
+    }
+
+    #[doc(hidden)]
+    #[inline]
+    pub fn drain_refusal_error(&self) -> application::Error {
+        self.drain_refusal_error
+    }
+
+    #[doc(hidden)]
+    #[inline]
+    pub fn keying_material_exporter(&self) -> bool {
+        self.keying_material_exporter
+    }
+
+    #[doc(hidden)]
+    #[inline]
+    pub fn key_update_policy(&self) -> key_update::Policy {
+        self.key_update_policy
+    }
+}
+
+/// A limit which can be updated after the connection has been established
+///
+/// Data windows are in bytes and stream limits are the number of streams the
+/// peer is allowed to have open concurrently.
+///
+/// `max_idle_timeout` can't be updated. It is exchanged once as a transport parameter and
+/// the effective value is the minimum of both endpoints' values. Raising it would not be
+/// honored by the peer, and lowering it would break the commitment to send a
+/// CONNECTION_CLOSE before abandoning the connection within the advertised timeout.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Update {
+    DataWindow(u64),
+    BidirectionalLocalDataWindow(u64),
+    BidirectionalRemoteDataWindow(u64),
+    UnidirectionalDataWindow(u64),
+    MaxOpenRemoteBidirectionalStreams(u64),
+    MaxOpenRemoteUnidirectionalStreams(u64),
+}
+
+impl Update {
+    /// Checks that the value is in range for the limit
+    ///
+    /// Values are validated in the same way as the initial limits configured with [`Limits`].
+    pub fn validate(self) -> Result<Self, ValidationError> {
+        match self {
+            Self::DataWindow(value)
+            | Self::BidirectionalLocalDataWindow(value)
+            | Self::BidirectionalRemoteDataWindow(value)
+            | Self::UnidirectionalDataWindow(value) => validate_window(value)?,
+            Self::MaxOpenRemoteBidirectionalStreams(value) => {
+                InitialMaxStreamsBidi::try_from(value)?;
+            }
+            Self::MaxOpenRemoteUnidirectionalStreams(value) => {
+                InitialMaxStreamsUni::try_from(value)?;
+            }
+        }
+
+        Ok(self)
+    }
+}
+
+/// The reason a limit couldn't be updated on an active connection
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[non_exhaustive]
+pub enum UpdateError {
+    /// The value is out of range for the limit
+    Invalid(ValidationError),
+    /// The connection was closed
+    Connection(crate::connection::Error),
+}
+
+impl core::fmt::Display for UpdateError {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match self {
+            Self::Invalid(error) => error.fmt(f),
+            Self::Connection(error) => error.fmt(f),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for UpdateError {}
+
+impl From<ValidationError> for UpdateError {
+    #[inline]
+    fn from(error: ValidationError) -> Self {
+        Self::Invalid(error)
+    }
+}
+
+impl From<crate::connection::Error> for UpdateError {
+    #[inline]
+    fn from(error: crate::connection::Error) -> Self {
+        Self::Connection(error)
+    }
 }
 
 /// Creates limits for a given connection
@@ -269,3 +506,46 @@ impl Limiter for Limits {
         *self
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn window_limit_test() {
+        let max = u32::MAX as u64;
+
+        assert!(Limits::new().with_data_window(max).is_ok());
+        assert!(Limits::new().with_data_window(max + 1).is_err());
+        assert!(Limits::new()
+            .with_bidirectional_local_data_window(max + 1)
+            .is_err());
+        assert!(Limits::new()
+            .with_bidirectional_remote_data_window(max + 1)
+            .is_err());
+        assert!(Limits::new()
+            .with_unidirectional_data_window(max + 1)
+            .is_err());
+
+        assert!(Update::DataWindow(max).validate().is_ok());
+        assert!(Update::DataWindow(max + 1).validate().is_err());
+        assert!(Update::UnidirectionalDataWindow(max + 1)
+            .validate()
+            .is_err());
+    }
+
+    #[test]
+    fn stream_limit_update_test() {
+        let max = 2u64.pow(60);
+
+        assert!(Update::MaxOpenRemoteBidirectionalStreams(max)
+            .validate()
+            .is_ok());
+        assert!(Update::MaxOpenRemoteBidirectionalStreams(max + 1)
+            .validate()
+            .is_err());
+        assert!(Update::MaxOpenRemoteUnidirectionalStreams(max + 1)
+            .validate()
+            .is_err());
+    }
+}
@@ -4,8 +4,10 @@
 pub mod close;
 pub mod error;
 pub mod id;
+pub mod keep_alive;
 pub mod limits;
 
 pub use error::{Error, ProcessingError};
 pub use id::{InitialId, LocalId, PeerId, UnboundedId};
+pub use keep_alive::KeepAlive;
 pub use limits::Limits;
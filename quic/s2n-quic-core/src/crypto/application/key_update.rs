@@ -0,0 +1,51 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::event::{builder::KeyUpdateCause, IntoEvent};
+use core::time::Duration;
+
+/// Limits after which 1-RTT keys are proactively rotated
+///
+/// Keys are always rotated before reaching the AEAD confidentiality limit, regardless of the
+/// policy. Each limit is disabled by default.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Policy {
+    /// The number of packets to protect with a key before it is rotated
+    pub max_packets: Option<u64>,
+    /// The number of payload bytes to protect with a key before it is rotated
+    pub max_bytes: Option<u64>,
+    /// The amount of time a key is used before it is rotated
+    pub max_interval: Option<Duration>,
+}
+
+/// The reason a key update was initiated
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Cause {
+    /// The peer initiated the key update
+    Peer,
+    /// The key is approaching the AEAD confidentiality limit
+    ConfidentialityLimit,
+    /// The key has protected the configured number of packets
+    PacketLimit,
+    /// The key has protected the configured number of bytes
+    ByteLimit,
+    /// The key has been in use for the configured interval
+    Interval,
+    /// The application requested the key update
+    Application,
+}
+
+impl IntoEvent<KeyUpdateCause> for Cause {
+    #[inline]
+    fn into_event(self) -> KeyUpdateCause {
+        match self {
+            Self::Peer => KeyUpdateCause::PeerInitiated,
+            Self::ConfidentialityLimit => KeyUpdateCause::ConfidentialityLimit,
+            Self::PacketLimit => KeyUpdateCause::PacketLimit,
+            Self::ByteLimit => KeyUpdateCause::ByteLimit,
+            Self::Interval => KeyUpdateCause::Interval,
+            Self::Application => KeyUpdateCause::Application,
+        }
+    }
+}
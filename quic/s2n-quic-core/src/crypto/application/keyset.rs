@@ -3,10 +3,13 @@
 
 use crate::{
     connection::ProcessingError,
-    crypto::{application::limited, OneRttKey, ProtectedPayload},
+    crypto::{
+        application::{key_update::Cause, limited},
+        BatchPayload, OneRttKey, ProtectedPayload,
+    },
     packet::{
         encoding::PacketEncodingError,
-        number::PacketNumber,
+        number::{PacketNumber, PacketNumberRange},
         short::{CleartextShort, EncryptedShort},
         KeyPhase,
     },
@@ -22,6 +25,17 @@ pub struct KeySet<K> {
 
     key_derivation_timer: Timer,
 
+    /// Fires when the key update interval of the policy has elapsed
+    key_update_timer: Timer,
+
+    /// A key update which was requested but not yet initiated
+    requested_update: Option<Cause>,
+
+    /// The largest packet number the peer has acknowledged
+    largest_acked_packet_number: Option<PacketNumber>,
+
+    handshake_confirmed: bool,
+
     //= https://www.rfc-editor.org/rfc/rfc9001#section-6.6
     //# In addition to counting packets sent, endpoints MUST count the number
     //# of received packets that fail authentication during the lifetime of a
@@ -63,6 +77,10 @@ impl<K: OneRttKey> KeySet<K> {
         Self {
             key_phase: KeyPhase::Zero,
             key_derivation_timer: Default::default(),
+            key_update_timer: Default::default(),
+            requested_update: None,
+            largest_acked_packet_number: None,
+            handshake_confirmed: false,
             packet_decryption_failures: 0,
             aead_integrity_limit,
             generation: 0,
@@ -75,6 +93,89 @@ impl<K: OneRttKey> KeySet<K> {
     fn rotate_phase(&mut self) {
         self.generation += 1;
         self.key_phase = KeyPhase::next_phase(self.key_phase);
+        self.requested_update = None;
+    }
+
+    /// Signals the handshake is confirmed, which allows key updates to be initiated
+    pub fn on_handshake_confirmed(&mut self, now: Timestamp) {
+        self.handshake_confirmed = true;
+        self.arm_key_update_timer(now);
+    }
+
+    /// Requests a key update to be initiated as soon as it is permitted
+    ///
+    /// Requests made while a key update is already pending are merged with the pending update.
+    pub fn request_update(&mut self, cause: Cause) {
+        self.requested_update.get_or_insert(cause);
+    }
+
+    /// Returns the reason the next packet will be encrypted in the next phase, if any
+    pub fn update_cause(&self) -> Option<Cause> {
+        //= https://www.rfc-editor.org/rfc/rfc9001#section-6.6
+        //# Endpoints MUST initiate a key update
+        //# before sending more protected packets than the confidentiality limit
+        //# for the selected AEAD permits.
+        if self.active_key().needs_update(&self.limits) {
+            return Some(Cause::ConfidentialityLimit);
+        }
+
+        //= https://www.rfc-editor.org/rfc/rfc9001#section-6.1
+        //# An endpoint MUST NOT initiate a key update prior to having confirmed
+        //# the handshake (Section 4.1.2).
+        if !self.handshake_confirmed {
+            return None;
+        }
+
+        // The next key isn't available until the previous update has completed
+        if self.key_update_in_progress() {
+            return None;
+        }
+
+        //= https://www.rfc-editor.org/rfc/rfc9001#section-6.1
+        //# An endpoint MUST NOT initiate a subsequent key update unless it has
+        //# received an acknowledgment for a packet that was sent protected with
+        //# keys from the current key phase.
+        if self.generation > 0 && !self.is_current_phase_acknowledged() {
+            return None;
+        }
+
+        self.requested_update
+            .or_else(|| self.active_key().policy_update_cause(&self.limits))
+    }
+
+    /// Records a packet which was sent with the key for the given phase
+    pub fn on_packet_sent(&mut self, packet_number: PacketNumber, key_phase: KeyPhase) {
+        self.crypto[key_phase].on_packet_sent(packet_number);
+    }
+
+    /// Records a range of packets which were acknowledged by the peer
+    pub fn on_packet_ack(&mut self, packet_number_range: &PacketNumberRange) {
+        let end = packet_number_range.end();
+        if self
+            .largest_acked_packet_number
+            .map_or(true, |largest| largest < end)
+        {
+            self.largest_acked_packet_number = Some(end);
+        }
+    }
+
+    /// Returns `true` if the peer acknowledged a packet sent with the key for the current phase
+    fn is_current_phase_acknowledged(&self) -> bool {
+        // Packets with higher packet numbers are protected with the same or newer keys so any
+        // acknowledged packet following the first packet in the current phase qualifies
+        match (
+            self.active_key().first_packet_number(),
+            self.largest_acked_packet_number,
+        ) {
+            (Some(first), Some(largest)) => largest >= first,
+            _ => false,
+        }
+    }
+
+    fn arm_key_update_timer(&mut self, now: Timestamp) {
+        if let Some(interval) = self.limits.key_update_policy.max_interval {
+            self.key_update_timer.set(now + interval);
+        }
     }
 
     /// Derive a new key based on the active key, and store it in the non-active slot
@@ -109,13 +210,15 @@ impl<K: OneRttKey> KeySet<K> {
     /// Passes the key for the the requested phase to a callback function. Integrity limits are
     /// enforced.
     ///
-    /// Returns the decrypted packet and generation if the key phase was rotated.
+    /// Returns the decrypted packet, along with the generation and the cause of the update if
+    /// the key phase was rotated.
     pub fn decrypt_packet<'a>(
         &mut self,
         packet: EncryptedShort<'a>,
         largest_acknowledged_packet_number: PacketNumber,
+        now: Timestamp,
         pto: Timestamp,
-    ) -> Result<(CleartextShort<'a>, Option<u16>), ProcessingError> {
+    ) -> Result<(CleartextShort<'a>, Option<(u16, Cause)>), ProcessingError> {
         let mut phase_to_use = self.key_phase() as u8;
         let packet_phase = packet.key_phase();
         let phase_switch = phase_to_use != (packet_phase as u8);
@@ -148,7 +251,14 @@ impl<K: OneRttKey> KeySet<K> {
 
         match result {
             Ok(packet) => {
-                let generation = if packet_phase != self.key_phase() {
+                let update = if packet_phase != self.key_phase() {
+                    // If we already sent packets with the next key, the update was initiated
+                    // locally. Otherwise the peer initiated it.
+                    let cause = match self.update_cause() {
+                        Some(cause) if self.crypto[packet_phase].encrypted_packets() > 0 => cause,
+                        _ => Cause::Peer,
+                    };
+
                     //= https://www.rfc-editor.org/rfc/rfc9001#section-6.2
                     //# Sending keys MUST be updated before sending an
                     //# acknowledgement for the packet that was received with updated keys.
@@ -173,12 +283,13 @@ impl<K: OneRttKey> KeySet<K> {
                     //# retain old keys for some time after unprotecting a packet sent using
                     //# the new keys.
                     self.set_derivation_timer(pto);
-                    Some(self.generation)
+                    self.arm_key_update_timer(now);
+                    Some((self.generation, cause))
                 } else {
                     None
                 };
 
-                Ok((packet, generation))
+                Ok((packet, update))
             }
             Err(err) => {
                 //= https://www.rfc-editor.org/rfc/rfc9001#section-6.6
@@ -210,11 +321,7 @@ impl<K: OneRttKey> KeySet<K> {
 
     /// This is the KeyPhase that should be used to encrypt a given packet.
     pub fn encryption_phase(&self) -> KeyPhase {
-        //= https://www.rfc-editor.org/rfc/rfc9001#section-6.6
-        //# Endpoints MUST initiate a key update
-        //# before sending more protected packets than the confidentiality limit
-        //# for the selected AEAD permits.
-        if self.active_key().needs_update(&self.limits) {
+        if self.update_cause().is_some() {
             return KeyPhase::next_phase(self.key_phase());
         }
 
@@ -250,15 +357,125 @@ impl<K: OneRttKey> KeySet<K> {
         }
 
         let r = f(buffer, self.crypto[phase].key(), phase)?;
+        let payload_len = r.0.len().saturating_sub(r.0.header_len);
 
         //= https://www.rfc-editor.org/rfc/rfc9001#section-6.6
         //# Endpoints MUST count the number of encrypted packets for each set of
         //# keys.
-        self.crypto[phase].on_packet_encryption(&self.limits);
+        self.crypto[phase].on_packet_encryption(payload_len, &self.limits);
 
         Ok(r)
     }
 
+    /// Returns the phase to encode a packet which will be sealed later as part of a batch
+    ///
+    /// `pending` is the number of packets already encoded for the batch which have not been
+    /// sealed yet. `None` is returned if the packet should be sealed immediately with
+    /// [`Self::encrypt_packet`] instead, which is the case when sealing the batch would move the
+    /// key into its update window or past a limit of the key update policy.
+    pub fn batch_encryption_phase(&self, pending: usize) -> Option<KeyPhase> {
+        let phase = self.encryption_phase();
+        let key = &self.crypto[phase];
+        let packets = pending as u64 + 1;
+
+        if key.remaining_encryptions() <= self.limits.key_update_window.saturating_add(packets) {
+            return None;
+        }
+
+        let policy = &self.limits.key_update_policy;
+
+        if policy.max_packets.map_or(false, |max| {
+            key.encrypted_packets().saturating_add(packets) >= max
+        }) {
+            return None;
+        }
+
+        let max_mtu: u16 = self.limits.max_mtu.into();
+        if policy.max_bytes.map_or(false, |max| {
+            key.encrypted_bytes()
+                .saturating_add(packets.saturating_mul(max_mtu as u64))
+                >= max
+        }) {
+            return None;
+        }
+
+        Some(phase)
+    }
+
+    /// Seals a batch of payloads with the key for the provided phase
+    ///
+    /// The phase should be queried with [`Self::batch_encryption_phase`] before encoding the
+    /// packet headers. Payloads are sealed in order until the confidentiality limit of the key is
+    /// reached, at which point a key update is requested. Returns the number of payloads which
+    /// were passed to the key, with the outcome for each stored in its `result` field. The
+    /// remaining payloads need to be sealed with the key for the next [`Self::encryption_phase`].
+    ///
+    /// Only the payloads which were sealed successfully are counted towards the limit.
+    pub fn encrypt_batch(&mut self, phase: KeyPhase, payloads: &mut [BatchPayload]) -> usize {
+        let key = &mut self.crypto[phase];
+
+        //= https://www.rfc-editor.org/rfc/rfc9001#section-6.6
+        //# If the total number of encrypted packets with the same key
+        //# exceeds the confidentiality limit for the selected AEAD, the endpoint
+        //# MUST stop using those keys.
+        let len = key.remaining_encryptions().min(payloads.len() as u64) as usize;
+        let (sealed, remaining) = payloads.split_at_mut(len);
+
+        key.key().encrypt_batch(sealed);
+
+        //= https://www.rfc-editor.org/rfc/rfc9001#section-6.6
+        //# Endpoints MUST count the number of encrypted packets for each set of
+        //# keys.
+        for payload in sealed.iter().filter(|payload| payload.result.is_ok()) {
+            key.on_packet_encryption(payload.payload.len(), &self.limits);
+        }
+
+        if !remaining.is_empty() {
+            //= https://www.rfc-editor.org/rfc/rfc9001#section-6.6
+            //# Endpoints MUST initiate a key update
+            //# before sending more protected packets than the confidentiality limit
+            //# for the selected AEAD permits.
+            self.request_update(Cause::ConfidentialityLimit);
+        }
+
+        len
+    }
+
+    /// Opens a batch of payloads with the key for the current phase
+    ///
+    /// Packets which signal a different key phase must be opened individually with
+    /// [`Self::decrypt_packet`], as they may rotate the key phase. The outcome for each payload
+    /// is stored in its `result` field.
+    pub fn decrypt_batch(&mut self, payloads: &mut [BatchPayload]) -> Result<(), ProcessingError> {
+        let key = &mut self.crypto[self.key_phase];
+
+        key.key().decrypt_batch(payloads);
+
+        for payload in payloads.iter() {
+            key.on_packet_decryption(&self.limits);
+
+            //= https://www.rfc-editor.org/rfc/rfc9001#section-6.6
+            //# In addition to counting packets sent, endpoints MUST count the number
+            //# of received packets that fail authentication during the lifetime of a
+            //# connection.
+            if payload.result.is_err() {
+                self.packet_decryption_failures += 1;
+            }
+        }
+
+        //= https://www.rfc-editor.org/rfc/rfc9001#section-6.6
+        //# If the total number of received packets that fail
+        //# authentication within the connection, across all keys, exceeds the
+        //# integrity limit for the selected AEAD, the endpoint MUST immediately
+        //# close the connection with a connection error of type
+        //# AEAD_LIMIT_REACHED and not process any more packets.
+        if self.decryption_error_count() > self.aead_integrity_limit {
+            return Err(transport::Error::AEAD_LIMIT_REACHED.into());
+        }
+
+        Ok(())
+    }
+
     pub fn on_timeout(&mut self, timestamp: Timestamp) {
         if self
             .key_derivation_timer
@@ -270,6 +487,10 @@ impl<K: OneRttKey> KeySet<K> {
             //# the PTO after having received a packet protected using the new keys.
             self.derive_and_store_next_key();
         }
+
+        if self.key_update_timer.poll_expiration(timestamp).is_ready() {
+            self.request_update(Cause::Interval);
+        }
     }
 
     pub fn key_phase(&self) -> KeyPhase {
@@ -280,6 +501,11 @@ impl<K: OneRttKey> KeySet<K> {
         &self.crypto[self.key_phase]
     }
 
+    /// Returns the key for the given phase
+    pub fn key(&self, key_phase: KeyPhase) -> &K {
+        self.crypto[key_phase].key()
+    }
+
     pub fn active_key_mut(&mut self) -> &mut limited::Key<K> {
         &mut self.crypto[self.key_phase]
     }
@@ -297,6 +523,7 @@ impl<K> timer::Provider for KeySet<K> {
     #[inline]
     fn timers<Q: timer::Query>(&self, query: &mut Q) -> timer::Result {
         self.key_derivation_timer.timers(query)?;
+        self.key_update_timer.timers(query)?;
         Ok(())
     }
 }
@@ -433,6 +660,7 @@ mod tests {
                 encrypted_packet,
                 PacketNumberSpace::ApplicationData.new_packet_number(VarInt::from_u8(0)),
                 clock.get_time(),
+                clock.get_time(),
             )
             .is_err());
         assert_eq!(keyset.decryption_error_count(), 1);
@@ -476,6 +704,7 @@ mod tests {
                     encrypted_packet,
                     PacketNumberSpace::ApplicationData.new_packet_number(VarInt::from_u8(0)),
                     clock.get_time(),
+                    clock.get_time(),
                 )
                 .err(),
             Some(ProcessingError::ConnectionError(
@@ -596,4 +825,277 @@ mod tests {
             Err(PacketEncodingError::AeadLimitReached(_))
         ));
     }
+
+    #[test]
+    fn test_encrypt_batch() {
+        let key = TestKey::default();
+        let mut keyset = KeySet::new(key, Default::default());
+        let mut payloads = [[0u8; 32]; 4];
+        let header = [0u8; 8];
+        let mut batch: Vec<_> = payloads
+            .iter_mut()
+            .enumerate()
+            .map(|(packet_number, payload)| {
+                BatchPayload::new(packet_number as u64, &header, payload)
+            })
+            .collect();
+
+        let phase = keyset.batch_encryption_phase(batch.len()).unwrap();
+        assert_eq!(keyset.encrypt_batch(phase, &mut batch), 4);
+        assert!(batch.iter().all(|payload| payload.result.is_ok()));
+
+        // each sealed payload in the batch counts towards the confidentiality limit
+        assert_eq!(keyset.active_key().encrypted_packets(), 4);
+    }
+
+    #[test]
+    fn test_encrypt_batch_failure() {
+        let key = TestKey {
+            fail_on_encrypt: true,
+            ..Default::default()
+        };
+        let mut keyset = KeySet::new(key, Default::default());
+        let mut payloads = [[0u8; 32]; 4];
+        let header = [0u8; 8];
+        let mut batch: Vec<_> = payloads
+            .iter_mut()
+            .map(|payload| BatchPayload::new(0, &header, payload))
+            .collect();
+
+        assert_eq!(keyset.encrypt_batch(KeyPhase::Zero, &mut batch), 4);
+        assert!(batch.iter().all(|payload| payload.result.is_err()));
+
+        // payloads which failed to seal are not counted towards the confidentiality limit
+        assert_eq!(keyset.active_key().encrypted_packets(), 0);
+    }
+
+    #[test]
+    fn test_batch_encryption_phase() {
+        let mut limits = limited::Limits::default();
+        limits.key_update_window = 10;
+        let key = TestKey {
+            confidentiality_limit: 20,
+            ..Default::default()
+        };
+        let mut keyset = KeySet::new(key, limits);
+
+        assert_eq!(keyset.batch_encryption_phase(0), Some(KeyPhase::Zero));
+        assert_eq!(keyset.batch_encryption_phase(8), Some(KeyPhase::Zero));
+
+        // the batch would move the key into the update window
+        assert_eq!(keyset.batch_encryption_phase(9), None);
+
+        // the key update policy limits are also respected
+        let mut limits = limited::Limits::default();
+        limits.key_update_policy.max_packets = Some(4);
+        keyset = KeySet::new(TestKey::default(), limits);
+        assert_eq!(keyset.batch_encryption_phase(2), Some(KeyPhase::Zero));
+        assert_eq!(keyset.batch_encryption_phase(3), None);
+    }
+
+    #[test]
+    fn test_decrypt_batch() {
+        let key = TestKey {
+            integrity_limit: 4,
+            fail_on_decrypt: true,
+            ..Default::default()
+        };
+        let mut keyset = KeySet::new(key, Default::default());
+        let mut payloads = [[0u8; 32]; 4];
+        let header = [0u8; 8];
+        let mut batch: Vec<_> = payloads
+            .iter_mut()
+            .map(|payload| BatchPayload::new(0, &header, payload))
+            .collect();
+
+        assert!(keyset.decrypt_batch(&mut batch).is_ok());
+        assert!(batch.iter().all(|payload| payload.result.is_err()));
+        assert_eq!(keyset.decryption_error_count(), 4);
+
+        // the failures are counted towards the integrity limit
+        assert_eq!(
+            keyset.decrypt_batch(&mut batch[..1]),
+            Err(transport::Error::AEAD_LIMIT_REACHED.into())
+        );
+    }
+
+    #[test]
+    fn test_encrypt_batch_aead_limit() {
+        let key = TestKey {
+            confidentiality_limit: 3,
+            ..Default::default()
+        };
+        let mut keyset = KeySet::new(key, Default::default());
+        let mut payloads = [[0u8; 32]; 4];
+        let header = [0u8; 8];
+        let mut batch: Vec<_> = payloads
+            .iter_mut()
+            .map(|payload| BatchPayload::new(0, &header, payload))
+            .collect();
+
+        // the payloads are sealed up to the limit of the key
+        assert_eq!(keyset.encrypt_batch(KeyPhase::Zero, &mut batch), 3);
+        assert_eq!(keyset.crypto[KeyPhase::Zero].encrypted_packets(), 3);
+
+        // the rest of the batch is sealed after the key update
+        assert_eq!(keyset.update_cause(), Some(Cause::ConfidentialityLimit));
+        let phase = keyset.encryption_phase();
+        assert_eq!(phase, KeyPhase::One);
+        assert_eq!(keyset.encrypt_batch(phase, &mut batch[3..]), 1);
+        assert_eq!(keyset.crypto[KeyPhase::One].encrypted_packets(), 1);
+    }
+
+    fn encrypt(keyset: &mut KeySet<TestKey>) -> KeyPhase {
+        let mut encoder_bytes = [0; 512];
+        let buffer = EncoderBuffer::new(&mut encoder_bytes);
+        let mut decoder_bytes = [0; 512];
+        let mut encryption_phase = None;
+
+        keyset
+            .encrypt_packet(buffer, |buffer, _key, phase| {
+                encryption_phase = Some(phase);
+                let payload = ProtectedPayload::new(0, &mut decoder_bytes);
+
+                Ok((payload, buffer))
+            })
+            .unwrap();
+
+        encryption_phase.unwrap()
+    }
+
+    #[test]
+    fn test_key_update_policy_packet_limit() {
+        let mut limits = limited::Limits::default();
+        limits.key_update_policy.max_packets = Some(2);
+        let mut keyset = KeySet::new(TestKey::default(), limits);
+
+        for _ in 0..3 {
+            assert_eq!(encrypt(&mut keyset), KeyPhase::Zero);
+        }
+
+        //= https://www.rfc-editor.org/rfc/rfc9001#section-6.1
+        //= type=test
+        //# An endpoint MUST NOT initiate a key update prior to having confirmed
+        //# the handshake (Section 4.1.2).
+        assert_eq!(keyset.update_cause(), None);
+
+        keyset.on_handshake_confirmed(Clock::default().get_time());
+        assert_eq!(keyset.update_cause(), Some(Cause::PacketLimit));
+        assert_eq!(encrypt(&mut keyset), KeyPhase::One);
+    }
+
+    #[test]
+    fn test_key_update_policy_byte_limit() {
+        let mut limits = limited::Limits::default();
+        limits.key_update_policy.max_bytes = Some(1000);
+        let mut keyset = KeySet::new(TestKey::default(), limits);
+        keyset.on_handshake_confirmed(Clock::default().get_time());
+
+        assert_eq!(encrypt(&mut keyset), KeyPhase::Zero);
+        assert_eq!(keyset.active_key().encrypted_bytes(), 512);
+        assert_eq!(keyset.update_cause(), None);
+
+        assert_eq!(encrypt(&mut keyset), KeyPhase::Zero);
+        assert_eq!(keyset.update_cause(), Some(Cause::ByteLimit));
+        assert_eq!(encrypt(&mut keyset), KeyPhase::One);
+    }
+
+    #[test]
+    fn test_key_update_policy_interval() {
+        let mut clock = Clock::default();
+        let mut limits = limited::Limits::default();
+        limits.key_update_policy.max_interval = Some(Duration::from_secs(10));
+        let mut keyset = KeySet::new(TestKey::default(), limits);
+        keyset.on_handshake_confirmed(clock.get_time());
+
+        clock.inc_by(Duration::from_secs(5));
+        keyset.on_timeout(clock.get_time());
+        assert_eq!(keyset.update_cause(), None);
+
+        clock.inc_by(Duration::from_secs(5));
+        keyset.on_timeout(clock.get_time());
+        assert_eq!(keyset.update_cause(), Some(Cause::Interval));
+        assert_eq!(keyset.encryption_phase(), KeyPhase::One);
+
+        // completing the update clears the request
+        keyset.rotate_phase();
+        assert_eq!(keyset.update_cause(), None);
+    }
+
+    #[test]
+    fn test_key_update_request() {
+        let mut clock = Clock::default();
+        let mut keyset = KeySet::new(TestKey::default(), Default::default());
+
+        keyset.request_update(Cause::Application);
+        assert_eq!(keyset.update_cause(), None);
+
+        keyset.on_handshake_confirmed(clock.get_time());
+        assert_eq!(keyset.update_cause(), Some(Cause::Application));
+        assert_eq!(keyset.encryption_phase(), KeyPhase::One);
+
+        // complete the update and start deriving the next key
+        keyset.rotate_phase();
+        keyset.set_derivation_timer(clock.get_time() + Duration::from_millis(10));
+
+        // a subsequent update waits for the next key to be derived
+        keyset.request_update(Cause::Application);
+        assert_eq!(keyset.update_cause(), None);
+        assert_eq!(keyset.encryption_phase(), KeyPhase::One);
+
+        clock.inc_by(Duration::from_millis(10));
+        keyset.on_timeout(clock.get_time());
+        assert_eq!(keyset.update_cause(), None);
+
+        // the update is initiated once a packet in the current phase is acknowledged
+        let sent = packet_number(1);
+        keyset.on_packet_sent(sent, keyset.key_phase());
+        keyset.on_packet_ack(&PacketNumberRange::new(sent, sent));
+        assert_eq!(keyset.update_cause(), Some(Cause::Application));
+        assert_eq!(keyset.encryption_phase(), KeyPhase::Zero);
+    }
+
+    //= https://www.rfc-editor.org/rfc/rfc9001#section-6.1
+    //= type=test
+    //# An endpoint MUST NOT initiate a subsequent key update unless it has
+    //# received an acknowledgment for a packet that was sent protected with
+    //# keys from the current key phase.
+    #[test]
+    fn test_key_update_requires_ack() {
+        let clock = Clock::default();
+        let mut keyset = KeySet::new(TestKey::default(), Default::default());
+        keyset.on_handshake_confirmed(clock.get_time());
+
+        // packets sent in the previous phase
+        keyset.on_packet_sent(packet_number(1), keyset.key_phase());
+        keyset.on_packet_sent(packet_number(2), keyset.key_phase());
+
+        // the first update does not require an acknowledgement
+        keyset.request_update(Cause::Application);
+        assert_eq!(keyset.update_cause(), Some(Cause::Application));
+        keyset.rotate_phase();
+
+        keyset.on_packet_sent(packet_number(3), keyset.key_phase());
+        keyset.on_packet_sent(packet_number(4), keyset.key_phase());
+        keyset.request_update(Cause::Application);
+
+        // nothing has been acknowledged
+        assert_eq!(keyset.update_cause(), None);
+
+        // acknowledging a packet from the previous phase isn't sufficient
+        keyset.on_packet_ack(&PacketNumberRange::new(packet_number(1), packet_number(2)));
+        assert_eq!(keyset.update_cause(), None);
+
+        // acknowledging a packet from the current phase allows the update
+        keyset.on_packet_ack(&PacketNumberRange::new(packet_number(4), packet_number(4)));
+        assert_eq!(keyset.update_cause(), Some(Cause::Application));
+
+        // acknowledgements received out of order don't regress the largest acknowledged
+        keyset.on_packet_ack(&PacketNumberRange::new(packet_number(1), packet_number(1)));
+        assert_eq!(keyset.update_cause(), Some(Cause::Application));
+    }
+
+    fn packet_number(value: u8) -> PacketNumber {
+        PacketNumberSpace::ApplicationData.new_packet_number(VarInt::from_u8(value))
+    }
 }
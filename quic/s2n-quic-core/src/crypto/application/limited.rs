@@ -1,7 +1,14 @@
 // Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{crypto::OneRttKey, path::MaxMtu};
+use crate::{
+    crypto::{
+        application::key_update::{Cause, Policy},
+        OneRttKey,
+    },
+    packet::number::PacketNumber,
+    path::MaxMtu,
+};
 
 //= https://www.rfc-editor.org/rfc/rfc9001#section-6.6
 //# Endpoints MUST count the number of encrypted packets for each set of
@@ -12,8 +19,11 @@ pub struct Key<K> {
     // Keeping encrypted_packets out of the key allow keys to be immutable, which allows optimizations
     // later on.
     encrypted_packets: u64,
+    encrypted_bytes: u64,
     decrypted_packets: u64,
     confidentiality_limit: u64,
+    /// The packet number of the first packet which was sent with the key
+    first_packet_number: Option<PacketNumber>,
 }
 
 #[derive(Copy, Clone, Debug)]
@@ -27,6 +37,8 @@ pub struct Limits {
     pub opener_optimization_threshold: u64,
     /// The maximum MTU the connection will ever encrypt/decrypt
     pub max_mtu: MaxMtu,
+    /// The limits at which a key update will be proactively initiated
+    pub key_update_policy: Policy,
 }
 
 impl Default for Limits {
@@ -36,6 +48,7 @@ impl Default for Limits {
             sealer_optimization_threshold: 100,
             opener_optimization_threshold: 100,
             max_mtu: MaxMtu::default(),
+            key_update_policy: Policy::default(),
         }
     }
 }
@@ -53,7 +66,9 @@ impl<K: OneRttKey> Key<K> {
             confidentiality_limit: key.aead_confidentiality_limit(),
             key,
             encrypted_packets: 0,
+            encrypted_bytes: 0,
             decrypted_packets: 0,
+            first_packet_number: None,
         }
     }
 
@@ -65,6 +80,13 @@ impl<K: OneRttKey> Key<K> {
         self.encrypted_packets >= self.confidentiality_limit
     }
 
+    /// The number of packets that can be encrypted before the key expires
+    #[inline]
+    pub fn remaining_encryptions(&self) -> u64 {
+        self.confidentiality_limit
+            .saturating_sub(self.encrypted_packets)
+    }
+
     /// If the key is within the update window, an update should be initiated.
     #[inline]
     pub fn needs_update(&self, limits: &Limits) -> bool {
@@ -74,6 +96,28 @@ impl<K: OneRttKey> Key<K> {
                 .saturating_sub(limits.key_update_window))
     }
 
+    /// Returns the reason a key update should be initiated by the key update policy, if any
+    #[inline]
+    pub fn policy_update_cause(&self, limits: &Limits) -> Option<Cause> {
+        let policy = &limits.key_update_policy;
+
+        if policy
+            .max_packets
+            .map_or(false, |max| self.encrypted_packets >= max)
+        {
+            return Some(Cause::PacketLimit);
+        }
+
+        if policy
+            .max_bytes
+            .map_or(false, |max| self.encrypted_bytes >= max)
+        {
+            return Some(Cause::ByteLimit);
+        }
+
+        None
+    }
+
     pub fn derive_next_key(&self) -> K {
         self.key.derive_next_key()
     }
@@ -84,14 +128,32 @@ impl<K: OneRttKey> Key<K> {
     }
 
     #[inline]
-    pub fn on_packet_encryption(&mut self, limits: &Limits) {
+    pub fn encrypted_bytes(&self) -> u64 {
+        self.encrypted_bytes
+    }
+
+    #[inline]
+    pub fn on_packet_encryption(&mut self, payload_len: usize, limits: &Limits) {
         self.encrypted_packets += 1;
+        self.encrypted_bytes = self.encrypted_bytes.saturating_add(payload_len as u64);
 
         if self.encrypted_packets == limits.sealer_optimization_threshold {
             self.key.update_sealer_pmtu(limits.max_mtu.into());
         }
     }
 
+    /// Records the packet number of a packet which was sent with the key
+    #[inline]
+    pub fn on_packet_sent(&mut self, packet_number: PacketNumber) {
+        self.first_packet_number.get_or_insert(packet_number);
+    }
+
+    /// Returns the packet number of the first packet which was sent with the key, if any
+    #[inline]
+    pub fn first_packet_number(&self) -> Option<PacketNumber> {
+        self.first_packet_number
+    }
+
     #[inline]
     pub fn on_packet_decryption(&mut self, limits: &Limits) {
         self.decrypted_packets += 1;
@@ -28,6 +28,46 @@ pub trait HeaderKey: Send {
     /// Returns the sample size needed for the header protection
     /// buffer
     fn sealing_sample_len(&self) -> usize;
+
+    /// Derives header protection masks for a batch of packets, to be
+    /// used for opening.
+    ///
+    /// `ciphertext_samples` contains one `opening_sample_len` sample for each mask in `masks`,
+    /// stored contiguously.
+    #[inline]
+    fn opening_header_protection_masks(
+        &self,
+        ciphertext_samples: &[u8],
+        masks: &mut [HeaderProtectionMask],
+    ) {
+        let sample_len = self.opening_sample_len();
+        debug_assert_eq!(ciphertext_samples.len(), sample_len * masks.len());
+        for (idx, mask) in masks.iter_mut().enumerate() {
+            let offset = idx * sample_len;
+            let sample = &ciphertext_samples[offset..offset + sample_len];
+            *mask = self.opening_header_protection_mask(sample);
+        }
+    }
+
+    /// Derives header protection masks for a batch of packets, to be
+    /// used for sealing.
+    ///
+    /// `ciphertext_samples` contains one `sealing_sample_len` sample for each mask in `masks`,
+    /// stored contiguously.
+    #[inline]
+    fn sealing_header_protection_masks(
+        &self,
+        ciphertext_samples: &[u8],
+        masks: &mut [HeaderProtectionMask],
+    ) {
+        let sample_len = self.sealing_sample_len();
+        debug_assert_eq!(ciphertext_samples.len(), sample_len * masks.len());
+        for (idx, mask) in masks.iter_mut().enumerate() {
+            let offset = idx * sample_len;
+            let sample = &ciphertext_samples[offset..offset + sample_len];
+            *mask = self.sealing_header_protection_mask(sample);
+        }
+    }
 }
 
 //= https://www.rfc-editor.org/rfc/rfc9001#section-5.4.1
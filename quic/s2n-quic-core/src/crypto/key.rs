@@ -3,6 +3,31 @@
 
 use crate::crypto::CryptoError;
 
+/// A packet payload that is sealed or opened as part of a batch
+///
+/// See [`Key::encrypt_batch`] and [`Key::decrypt_batch`].
+#[derive(Debug)]
+pub struct BatchPayload<'a> {
+    pub packet_number: u64,
+    pub header: &'a [u8],
+    /// The packet payload, including space for the trailing tag
+    pub payload: &'a mut [u8],
+    /// The result of the last batch operation on the payload
+    pub result: Result<(), CryptoError>,
+}
+
+impl<'a> BatchPayload<'a> {
+    #[inline]
+    pub fn new(packet_number: u64, header: &'a [u8], payload: &'a mut [u8]) -> Self {
+        Self {
+            packet_number,
+            header,
+            payload,
+            result: Ok(()),
+        }
+    }
+}
+
 /// A trait for crypto keys
 pub trait Key: Send {
     /// Decrypt a payload
@@ -21,6 +46,28 @@ pub trait Key: Send {
         payload: &mut [u8],
     ) -> Result<(), CryptoError>;
 
+    /// Decrypt a batch of payloads
+    ///
+    /// The outcome for each payload is stored in its `result` field. Implementations may override
+    /// the default, which decrypts each payload in order, to interleave work across packets.
+    #[inline]
+    fn decrypt_batch(&self, payloads: &mut [BatchPayload]) {
+        for payload in payloads {
+            payload.result = self.decrypt(payload.packet_number, payload.header, payload.payload);
+        }
+    }
+
+    /// Encrypt a batch of payloads
+    ///
+    /// The outcome for each payload is stored in its `result` field. Implementations may override
+    /// the default, which encrypts each payload in order, to interleave work across packets.
+    #[inline]
+    fn encrypt_batch(&self, payloads: &mut [BatchPayload]) {
+        for payload in payloads {
+            payload.result = self.encrypt(payload.packet_number, payload.header, payload.payload);
+        }
+    }
+
     /// Length of the appended tag
     fn tag_len(&self) -> usize;
 
@@ -48,6 +95,7 @@ pub mod testing {
         pub integrity_limit: u64,
         pub derivations: u64,
         pub fail_on_decrypt: bool,
+        pub fail_on_encrypt: bool,
     }
 
     impl Key {
@@ -65,6 +113,7 @@ pub mod testing {
                 integrity_limit: 64,
                 derivations: 0,
                 fail_on_decrypt: false,
+                fail_on_encrypt: false,
             }
         }
     }
@@ -91,6 +140,10 @@ pub mod testing {
             _header: &[u8],
             _payload: &mut [u8],
         ) -> Result<(), CryptoError> {
+            if self.fail_on_encrypt {
+                return Err(CryptoError::INTERNAL_ERROR);
+            }
+
             Ok(())
         }
 
@@ -131,6 +184,7 @@ pub mod testing {
                 confidentiality_limit: self.confidentiality_limit,
                 derivations: self.derivations + 1,
                 fail_on_decrypt: self.fail_on_decrypt,
+                fail_on_encrypt: self.fail_on_encrypt,
             }
         }
 
@@ -141,6 +141,7 @@ pub mod label;
 pub mod one_rtt;
 pub mod packet_protection;
 pub mod payload;
+pub mod provider;
 pub mod retry;
 pub mod tls;
 pub mod zero_rtt;
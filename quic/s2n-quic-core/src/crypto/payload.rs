@@ -148,7 +148,7 @@ impl<'a> EncryptedPayload<'a> {
     }
 }
 
-fn header_protection_sample(
+pub(crate) fn header_protection_sample(
     buffer: DecoderBuffer,
     header_len: usize,
     sample_len: usize,
@@ -0,0 +1,67 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Derivation of packet protection keys from TLS traffic secrets
+//!
+//! TLS providers negotiate the cipher suite and the traffic secrets for each encryption
+//! level. A [`Provider`] turns those secrets into the keys which protect QUIC packets, which
+//! allows the packet protection implementation to be chosen independently of the TLS library
+//! performing the handshake.
+
+use crate::{
+    crypto::{tls::CipherSuite, CryptoSuite},
+    endpoint,
+};
+use core::fmt;
+
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
+
+/// The client and server traffic secrets for a single encryption level
+#[derive(Clone, Copy)]
+pub struct SecretPair<'a> {
+    pub client: &'a [u8],
+    pub server: &'a [u8],
+}
+
+impl<'a> fmt::Debug for SecretPair<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // don't print the actual secrets
+        f.debug_struct("SecretPair")
+            .field("client_len", &self.client.len())
+            .field("server_len", &self.server.len())
+            .finish()
+    }
+}
+
+/// Derives packet protection keys from the secrets negotiated by a TLS provider
+///
+/// Initial and Retry keys don't depend on the TLS handshake and are derived through the
+/// [`InitialKey`](crate::crypto::InitialKey) and [`RetryKey`](crate::crypto::RetryKey) traits.
+pub trait Provider: CryptoSuite {
+    /// Derives the Handshake keys for the `endpoint` from the handshake traffic secrets
+    ///
+    /// Returns `None` if the cipher suite is not supported.
+    fn handshake_keys(
+        endpoint: endpoint::Type,
+        cipher_suite: CipherSuite,
+        secrets: SecretPair,
+    ) -> Option<(Self::HandshakeKey, Self::HandshakeHeaderKey)>;
+
+    /// Derives the 1-RTT keys for the `endpoint` from the application traffic secrets
+    ///
+    /// Returns `None` if the cipher suite is not supported.
+    fn one_rtt_keys(
+        endpoint: endpoint::Type,
+        cipher_suite: CipherSuite,
+        secrets: SecretPair,
+    ) -> Option<(Self::OneRttKey, Self::OneRttHeaderKey)>;
+
+    /// Derives the 0-RTT keys from the client early traffic secret
+    ///
+    /// Returns `None` if the cipher suite is not supported.
+    fn zero_rtt_keys(
+        cipher_suite: CipherSuite,
+        secret: &[u8],
+    ) -> Option<(Self::ZeroRttKey, Self::ZeroRttHeaderKey)>;
+}
@@ -0,0 +1,325 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Conformance tests for [`Provider`] implementations using the
+//! [RFC 9001 Appendix A](https://www.rfc-editor.org/rfc/rfc9001#appendix-A) test vectors
+
+use super::{Provider, SecretPair};
+use crate::{
+    crypto::{
+        header_crypto::{LONG_HEADER_MASK, SHORT_HEADER_MASK},
+        initial::{
+            EXAMPLE_CLIENT_INITIAL_HEADER, EXAMPLE_CLIENT_INITIAL_PAYLOAD,
+            EXAMPLE_CLIENT_INITIAL_PROTECTED_PACKET, EXAMPLE_DCID, EXAMPLE_SERVER_INITIAL_HEADER,
+            EXAMPLE_SERVER_INITIAL_PAYLOAD, EXAMPLE_SERVER_INITIAL_PROTECTED_PACKET,
+        },
+        tls::CipherSuite,
+        BatchPayload, HeaderKey, HeaderProtectionMask, InitialKey, Key,
+    },
+    endpoint,
+};
+use hex_literal::hex;
+
+/// A protected packet along with its unprotected components
+pub struct Vector {
+    pub header: &'static [u8],
+    pub packet_number: u64,
+    pub payload: &'static [u8],
+    pub protected_packet: &'static [u8],
+}
+
+//= https://www.rfc-editor.org/rfc/rfc9001#appendix-A.2
+//# The client sends an Initial packet.
+pub const CLIENT_INITIAL: Vector = Vector {
+    header: &EXAMPLE_CLIENT_INITIAL_HEADER,
+    packet_number: 2,
+    payload: &EXAMPLE_CLIENT_INITIAL_PAYLOAD,
+    protected_packet: &EXAMPLE_CLIENT_INITIAL_PROTECTED_PACKET,
+};
+
+//= https://www.rfc-editor.org/rfc/rfc9001#appendix-A.3
+//# The server sends the following payload in response, including an ACK
+//# frame, a CRYPTO frame, and no PADDING frames:
+pub const SERVER_INITIAL: Vector = Vector {
+    header: &EXAMPLE_SERVER_INITIAL_HEADER,
+    packet_number: 1,
+    payload: &EXAMPLE_SERVER_INITIAL_PAYLOAD,
+    protected_packet: &EXAMPLE_SERVER_INITIAL_PROTECTED_PACKET,
+};
+
+//= https://www.rfc-editor.org/rfc/rfc9001#appendix-A.5
+//# secret
+//#     = 9ac312a7f877468ebe69422748ad00a1
+//#       5443f18203a07d6060f688f30f21632b
+pub const CHACHA20_SECRET: [u8; 32] =
+    hex!("9ac312a7f877468ebe69422748ad00a15443f18203a07d6060f688f30f21632b");
+
+//= https://www.rfc-editor.org/rfc/rfc9001#appendix-A.5
+//# The following shows the steps involved in protecting a minimal packet
+//# with an empty Destination Connection ID.  This packet contains a
+//# single PING frame (that is, a payload of just 0x01) and has a packet
+//# number of 654360564.  In this example, using a packet number of
+//# length 3 (that is, 49140 is encoded) avoids having to pad the payload
+//# of the packet; PADDING frames would be needed if the packet number is
+//# encoded on fewer bytes.
+//#
+//# pn                 = 654360564 (decimal)
+//# nonce              = e0459b3474bdd0e46d417eb0
+//# unprotected header = 4200bff4
+//# payload plaintext  = 01
+//# payload ciphertext = 655e5cd55c41f69080575d7999c25a5bfb
+//#
+//# The resulting ciphertext is the minimum size possible.  One byte is
+//# skipped to produce the sample for header protection.
+//#
+//# sample = 5e5cd55c41f69080575d7999c25a5bfb
+//# mask   = aefefe7d03
+//# header = 4cfe4189
+//#
+//# The protected packet is the smallest possible packet size of 21
+//# bytes.
+//#
+//# packet = 4cfe4189655e5cd55c41f69080575d7999c25a5bfb
+pub const CHACHA20_SHORT_HEADER: Vector = Vector {
+    header: &hex!("4200bff4"),
+    packet_number: 654_360_564,
+    payload: &hex!("01"),
+    protected_packet: &hex!("4cfe4189655e5cd55c41f69080575d7999c25a5bfb"),
+};
+
+/// Runs all of the RFC 9001 test vectors against the provider
+pub fn conformance<P: Provider>() {
+    initial::<P>();
+    handshake::<P>();
+    one_rtt::<P>();
+    batch::<P>();
+}
+
+/// Checks the Initial packet protection of the provider
+pub fn initial<P: Provider>() {
+    let client = P::InitialKey::new_client(&EXAMPLE_DCID);
+    let server = P::InitialKey::new_server(&EXAMPLE_DCID);
+
+    check(&client, &server, &CLIENT_INITIAL);
+    check(&server, &client, &SERVER_INITIAL);
+}
+
+/// Checks the Handshake keys derived by the provider
+pub fn handshake<P: Provider>() {
+    let secrets = SecretPair {
+        client: &CHACHA20_SECRET,
+        server: &CHACHA20_SECRET,
+    };
+    let cipher_suite = CipherSuite::TLS_CHACHA20_POLY1305_SHA256;
+
+    let server = P::handshake_keys(endpoint::Type::Server, cipher_suite, secrets)
+        .expect("ChaCha20-Poly1305 should be supported");
+    let client = P::handshake_keys(endpoint::Type::Client, cipher_suite, secrets)
+        .expect("ChaCha20-Poly1305 should be supported");
+
+    check(&server, &client, &CHACHA20_SHORT_HEADER);
+}
+
+/// Checks the 1-RTT keys derived by the provider
+pub fn one_rtt<P: Provider>() {
+    let secrets = SecretPair {
+        client: &CHACHA20_SECRET,
+        server: &CHACHA20_SECRET,
+    };
+    let cipher_suite = CipherSuite::TLS_CHACHA20_POLY1305_SHA256;
+
+    let server = P::one_rtt_keys(endpoint::Type::Server, cipher_suite, secrets)
+        .expect("ChaCha20-Poly1305 should be supported");
+    let client = P::one_rtt_keys(endpoint::Type::Client, cipher_suite, secrets)
+        .expect("ChaCha20-Poly1305 should be supported");
+
+    check(&server, &client, &CHACHA20_SHORT_HEADER);
+}
+
+/// Checks that the 1-RTT keys derived by the provider produce the same packets when they're
+/// sealed and opened in a batch as when they're sealed and opened one at a time
+pub fn batch<P: Provider>() {
+    let secrets = SecretPair {
+        client: &CHACHA20_SECRET,
+        server: &CHACHA20_SECRET,
+    };
+    let cipher_suite = CipherSuite::TLS_CHACHA20_POLY1305_SHA256;
+
+    let server = P::one_rtt_keys(endpoint::Type::Server, cipher_suite, secrets)
+        .expect("ChaCha20-Poly1305 should be supported");
+    let client = P::one_rtt_keys(endpoint::Type::Client, cipher_suite, secrets)
+        .expect("ChaCha20-Poly1305 should be supported");
+
+    let tag_len = server.0.tag_len();
+    let packet_numbers: Vec<u64> = (0..8)
+        .map(|index| CHACHA20_SHORT_HEADER.packet_number + index)
+        .collect();
+
+    // short header packets with a 3 byte packet number, a PING frame and a different number of
+    // PADDING frames
+    let packets: Vec<Vec<u8>> = packet_numbers
+        .iter()
+        .enumerate()
+        .map(|(index, packet_number)| {
+            let mut packet = vec![CHACHA20_SHORT_HEADER.header[0]];
+            packet.extend_from_slice(&packet_number.to_be_bytes()[5..]);
+            packet.push(0x01);
+            packet.resize(packet.len() + index + tag_len, 0);
+            packet
+        })
+        .collect();
+
+    // seal the packets one at a time
+    let mut expected = packets.clone();
+    for (packet, packet_number) in expected.iter_mut().zip(&packet_numbers) {
+        let (header, payload) = packet.split_at_mut(CHACHA20_SHORT_HEADER.header.len());
+        server.0.encrypt(*packet_number, header, payload).unwrap();
+
+        let sample = sample(packet, server.1.sealing_sample_len());
+        let mask = server.1.sealing_header_protection_mask(sample);
+        apply_mask(packet, CHACHA20_SHORT_HEADER.header, &mask);
+    }
+
+    let mut sealed = packets.clone();
+
+    // seal the packets together
+    {
+        let mut payloads: Vec<BatchPayload> = sealed
+            .iter_mut()
+            .zip(&packet_numbers)
+            .map(|(packet, packet_number)| {
+                let (header, payload) = packet.split_at_mut(CHACHA20_SHORT_HEADER.header.len());
+                BatchPayload::new(*packet_number, header, payload)
+            })
+            .collect();
+        server.0.encrypt_batch(&mut payloads);
+        assert!(payloads.iter().all(|payload| payload.result.is_ok()));
+    }
+    let masks = derive_masks(
+        &sealed,
+        &server.1,
+        server.1.sealing_sample_len(),
+        HeaderKey::sealing_header_protection_masks,
+    );
+    for (packet, mask) in sealed.iter_mut().zip(&masks) {
+        apply_mask(packet, CHACHA20_SHORT_HEADER.header, mask);
+    }
+
+    assert_eq!(sealed, expected);
+
+    // open the packets together
+    let mut opened = sealed;
+    let masks = derive_masks(
+        &opened,
+        &client.1,
+        client.1.opening_sample_len(),
+        HeaderKey::opening_header_protection_masks,
+    );
+    for (packet, mask) in opened.iter_mut().zip(&masks) {
+        apply_mask(packet, CHACHA20_SHORT_HEADER.header, mask);
+    }
+    {
+        let mut payloads: Vec<BatchPayload> = opened
+            .iter_mut()
+            .zip(&packet_numbers)
+            .map(|(packet, packet_number)| {
+                let (header, payload) = packet.split_at_mut(CHACHA20_SHORT_HEADER.header.len());
+                BatchPayload::new(*packet_number, header, payload)
+            })
+            .collect();
+        client.0.decrypt_batch(&mut payloads);
+        assert!(payloads.iter().all(|payload| payload.result.is_ok()));
+    }
+
+    for (packet, expected) in opened.iter().zip(&packets) {
+        let len = packet.len() - tag_len;
+        assert_eq!(packet[..len], expected[..len]);
+    }
+}
+
+/// Returns the header protection sample of a short header packet
+fn sample(packet: &[u8], sample_len: usize) -> &[u8] {
+    let sample_offset = packet_number_offset(CHACHA20_SHORT_HEADER.header) + 4;
+    &packet[sample_offset..][..sample_len]
+}
+
+/// Derives the header protection masks for the short header packets together
+fn derive_masks<H: HeaderKey>(
+    packets: &[Vec<u8>],
+    header_key: &H,
+    sample_len: usize,
+    derive: fn(&H, &[u8], &mut [HeaderProtectionMask]),
+) -> Vec<HeaderProtectionMask> {
+    let samples: Vec<u8> = packets
+        .iter()
+        .flat_map(|packet| sample(packet, sample_len).iter().copied())
+        .collect();
+    let mut masks = vec![HeaderProtectionMask::default(); packets.len()];
+    derive(header_key, &samples, &mut masks);
+    masks
+}
+
+/// Seals the vector with the `sealer` keys and opens it with the `opener` keys, asserting
+/// that the output matches the vector
+pub fn check<K: Key, H: HeaderKey>(sealer: &(K, H), opener: &(K, H), vector: &Vector) {
+    seal(sealer, vector);
+    open(opener, vector);
+}
+
+fn seal<K: Key, H: HeaderKey>((key, header_key): &(K, H), vector: &Vector) {
+    let header_len = vector.header.len();
+    let mut packet = vec![0; vector.protected_packet.len()];
+
+    // any remaining bytes after the payload are PADDING frames or the tag
+    packet[..header_len].copy_from_slice(vector.header);
+    packet[header_len..][..vector.payload.len()].copy_from_slice(vector.payload);
+
+    let (header, payload) = packet.split_at_mut(header_len);
+    key.encrypt(vector.packet_number, header, payload).unwrap();
+
+    let sample_offset = packet_number_offset(vector.header) + 4;
+    let sample = &packet[sample_offset..][..header_key.sealing_sample_len()];
+    let mask = header_key.sealing_header_protection_mask(sample);
+    apply_mask(&mut packet, vector.header, &mask);
+
+    assert_eq!(packet, vector.protected_packet);
+}
+
+fn open<K: Key, H: HeaderKey>((key, header_key): &(K, H), vector: &Vector) {
+    let header_len = vector.header.len();
+    let mut packet = vector.protected_packet.to_vec();
+
+    let sample_offset = packet_number_offset(vector.header) + 4;
+    let sample = &packet[sample_offset..][..header_key.opening_sample_len()];
+    let mask = header_key.opening_header_protection_mask(sample);
+    apply_mask(&mut packet, vector.header, &mask);
+
+    assert_eq!(&packet[..header_len], vector.header);
+
+    let (header, payload) = packet.split_at_mut(header_len);
+    key.decrypt(vector.packet_number, header, payload).unwrap();
+
+    let (payload, padding) =
+        payload[..payload.len() - key.tag_len()].split_at(vector.payload.len());
+    assert_eq!(payload, vector.payload);
+    assert!(padding.iter().all(|b| *b == 0), "padding should be zeroed");
+}
+
+fn packet_number_offset(header: &[u8]) -> usize {
+    let packet_number_len = (header[0] & 0b11) as usize + 1;
+    header.len() - packet_number_len
+}
+
+fn apply_mask(packet: &mut [u8], header: &[u8], mask: &[u8]) {
+    let first_byte_mask = if header[0] & 0x80 == 0x80 {
+        LONG_HEADER_MASK
+    } else {
+        SHORT_HEADER_MASK
+    };
+    packet[0] ^= mask[0] & first_byte_mask;
+
+    let packet_number_range = packet_number_offset(header)..header.len();
+    for (byte, mask) in packet[packet_number_range].iter_mut().zip(&mask[1..]) {
+        *byte ^= mask;
+    }
+}
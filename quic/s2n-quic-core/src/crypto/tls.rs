@@ -116,9 +116,95 @@ pub trait Session: crate::crypto::CryptoSuite + Sized + Send + Debug {
         &mut self,
         context: &mut C,
     ) -> core::task::Poll<Result<(), crate::transport::Error>>;
+
+    /// Returns the parameters which were negotiated during the handshake
+    ///
+    /// This is queried once the handshake has completed, before the session is discarded.
+    /// Implementations which are unable to provide the details may return `None`.
+    fn handshake_details(&self) -> Option<HandshakeDetails> {
+        None
+    }
+
+    /// Fills `output` with keying material derived from the session, as described in
+    /// [RFC 8446 Section 7.5](https://www.rfc-editor.org/rfc/rfc8446#section-7.5)
+    ///
+    /// This is only available once the handshake has completed.
+    fn export_keying_material(
+        &self,
+        label: &[u8],
+        context: &[u8],
+        output: &mut [u8],
+    ) -> Result<(), crate::crypto::CryptoError> {
+        let _ = (label, context, output);
+        Err(crate::crypto::CryptoError::INTERNAL_ERROR
+            .with_reason("keying material exporters are not supported"))
+    }
+}
+
+/// The parameters negotiated by a completed TLS handshake
+#[cfg(feature = "alloc")]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HandshakeDetails {
+    /// The DER-encoded certificate chain presented by the peer, starting with the end-entity
+    /// certificate
+    ///
+    /// This is empty if the peer did not present any certificates, which is the case for
+    /// clients when client authentication is not enabled.
+    pub peer_cert_chain: alloc::vec::Vec<Bytes>,
+    /// The negotiated cipher suite
+    pub cipher_suite: CipherSuite,
+    /// The group used for the key exchange, if it is a registered group
+    ///
+    /// This is `None` if the TLS provider doesn't expose the negotiated group, which is the
+    /// case for the rustls provider since rustls 0.20 has no accessor for it.
+    pub key_exchange_group: Option<NamedGroup>,
+    /// The signature scheme used by the server to authenticate the handshake
+    ///
+    /// This is `None` if the TLS provider doesn't expose the negotiated signature scheme, which
+    /// is the case for the rustls provider since rustls 0.20 has no accessor for it.
+    pub signature_scheme: Option<SignatureScheme>,
+}
+
+/// A key exchange group, identified by its IANA code point
+///
+/// See the [TLS Supported Groups](https://www.iana.org/assignments/tls-parameters/tls-parameters.xhtml#tls-parameters-8)
+/// registry for the list of values.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct NamedGroup(pub u16);
+
+impl NamedGroup {
+    pub const SECP256R1: Self = Self(0x0017);
+    pub const SECP384R1: Self = Self(0x0018);
+    pub const SECP521R1: Self = Self(0x0019);
+    pub const X25519: Self = Self(0x001d);
+    pub const X448: Self = Self(0x001e);
+}
+
+/// A signature scheme, identified by its IANA code point
+///
+/// See the [TLS SignatureScheme](https://www.iana.org/assignments/tls-parameters/tls-parameters.xhtml#tls-signaturescheme)
+/// registry for the list of values.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SignatureScheme(pub u16);
+
+impl SignatureScheme {
+    pub const RSA_PKCS1_SHA256: Self = Self(0x0401);
+    pub const RSA_PKCS1_SHA384: Self = Self(0x0501);
+    pub const RSA_PKCS1_SHA512: Self = Self(0x0601);
+    pub const ECDSA_SECP256R1_SHA256: Self = Self(0x0403);
+    pub const ECDSA_SECP384R1_SHA384: Self = Self(0x0503);
+    pub const ECDSA_SECP521R1_SHA512: Self = Self(0x0603);
+    pub const RSA_PSS_RSAE_SHA256: Self = Self(0x0804);
+    pub const RSA_PSS_RSAE_SHA384: Self = Self(0x0805);
+    pub const RSA_PSS_RSAE_SHA512: Self = Self(0x0806);
+    pub const ED25519: Self = Self(0x0807);
+    pub const ED448: Self = Self(0x0808);
+    pub const RSA_PSS_PSS_SHA256: Self = Self(0x0809);
+    pub const RSA_PSS_PSS_SHA384: Self = Self(0x080a);
+    pub const RSA_PSS_PSS_SHA512: Self = Self(0x080b);
 }
 
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
 #[allow(non_camel_case_types)]
 pub enum CipherSuite {
     TLS_AES_128_GCM_SHA256,
@@ -231,6 +231,38 @@ impl<S: tls::Session, C: tls::Session> Pair<S, C> {
     pub fn finish(&self) {
         self.client.context.finish(&self.server.context);
 
+        // if the sessions expose the negotiated parameters, make sure they agree
+        if let (Some(client), Some(server)) = (
+            self.client.session.handshake_details(),
+            self.server.session.handshake_details(),
+        ) {
+            assert_eq!(client.cipher_suite, server.cipher_suite);
+            assert!(
+                !client.peer_cert_chain.is_empty(),
+                "client did not receive the server certificate chain"
+            );
+        }
+
+        // if the sessions support exporting keying material, both peers should derive the same
+        // output
+        let mut client_output = [0u8; 32];
+        let mut server_output = [0u8; 32];
+        if let (Ok(()), Ok(())) = (
+            self.client.session.export_keying_material(
+                b"EXPORTER-test",
+                b"context",
+                &mut client_output,
+            ),
+            self.server.session.export_keying_material(
+                b"EXPORTER-test",
+                b"context",
+                &mut server_output,
+            ),
+        ) {
+            assert_eq!(client_output, server_output);
+            assert_ne!(client_output, [0u8; 32]);
+        }
+
         assert_eq!(
             self.client.context.transport_parameters.as_ref().unwrap(),
             TEST_SERVER_TRANSPORT_PARAMS,
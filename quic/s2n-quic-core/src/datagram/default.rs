@@ -118,7 +118,7 @@ impl super::Endpoint for Endpoint {
 /// Old datagrams will be popped off the queue in favor of new datagrams if the
 /// queue capacity is reached.
 pub struct Receiver {
-    queue: VecDeque<Bytes>,
+    queue: Queue<Bytes>,
     capacity: usize,
     waker: Option<Waker>,
     max_datagram_frame_size: u64,
@@ -185,6 +185,10 @@ impl super::Receiver for Receiver {
             w.wake();
         }
     }
+
+    fn buffered_bytes(&self) -> u64 {
+        self.queue.bytes()
+    }
 }
 
 // A builder for the default datagram receiver
@@ -221,7 +225,7 @@ impl ReceiverBuilder {
     /// Builds the datagram receiver
     pub fn build(self) -> Result<Receiver, core::convert::Infallible> {
         Ok(Receiver {
-            queue: VecDeque::with_capacity(self.queue_capacity),
+            queue: Queue::with_capacity(self.queue_capacity),
             capacity: self.queue_capacity,
             waker: None,
             max_datagram_frame_size: self.max_datagram_frame_size,
@@ -249,7 +253,7 @@ impl ReceiverBuilder {
 ///
 #[derive(Debug)]
 pub struct Sender {
-    queue: VecDeque<Datagram>,
+    queue: Queue<Datagram>,
     capacity: usize,
     min_packet_space: usize,
     max_packet_space: usize,
@@ -265,6 +269,82 @@ pub struct Datagram {
     pub data: Bytes,
 }
 
+/// A datagram which can be stored in a [`Queue`]
+trait Entry {
+    fn len(&self) -> usize;
+}
+
+impl Entry for Bytes {
+    fn len(&self) -> usize {
+        Bytes::len(self)
+    }
+}
+
+impl Entry for Datagram {
+    fn len(&self) -> usize {
+        self.data.len()
+    }
+}
+
+/// A queue of datagrams which keeps track of the number of bytes it holds
+#[derive(Debug)]
+struct Queue<T> {
+    entries: VecDeque<T>,
+    bytes: u64,
+}
+
+impl<T: Entry> Queue<T> {
+    fn with_capacity(capacity: usize) -> Self {
+        Self {
+            entries: VecDeque::with_capacity(capacity),
+            bytes: 0,
+        }
+    }
+
+    /// Returns the number of bytes held by all of the datagrams in the queue
+    fn bytes(&self) -> u64 {
+        self.bytes
+    }
+
+    fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    fn push_back(&mut self, entry: T) {
+        self.bytes += entry.len() as u64;
+        self.entries.push_back(entry);
+    }
+
+    fn push_front(&mut self, entry: T) {
+        self.bytes += entry.len() as u64;
+        self.entries.push_front(entry);
+    }
+
+    fn pop_front(&mut self) -> Option<T> {
+        let entry = self.entries.pop_front()?;
+        self.bytes -= entry.len() as u64;
+        Some(entry)
+    }
+
+    fn retain<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&T) -> bool,
+    {
+        let bytes = &mut self.bytes;
+        self.entries.retain(|entry| {
+            let retain = f(entry);
+            if !retain {
+                *bytes -= entry.len() as u64;
+            }
+            retain
+        });
+    }
+}
+
 #[non_exhaustive]
 #[derive(Debug, PartialEq)]
 pub enum DatagramError {
@@ -501,6 +581,10 @@ impl super::Sender for Sender {
             w.wake();
         }
     }
+
+    fn buffered_bytes(&self) -> u64 {
+        self.queue.bytes()
+    }
 }
 
 /// A builder for the default datagram sender
@@ -537,7 +621,7 @@ impl SenderBuilder {
     /// Builds the datagram sender into a provider
     pub fn build(self) -> Result<Sender, core::convert::Infallible> {
         Ok(Sender {
-            queue: VecDeque::with_capacity(self.queue_capacity),
+            queue: Queue::with_capacity(self.queue_capacity),
             capacity: self.queue_capacity,
             max_datagram_payload: self.max_datagram_payload,
             max_packet_space: 0,
@@ -612,6 +696,11 @@ mod tests {
         let datagram_2 = bytes::Bytes::from_static(&[7, 8, 9]);
         assert_eq!(default_sender.send_datagram(datagram_0), Ok(()));
         assert_eq!(default_sender.send_datagram(datagram_1), Ok(()));
+        assert_eq!(
+            super::super::Sender::buffered_bytes(&default_sender),
+            6,
+            "queued datagrams should be accounted for"
+        );
         // Attempting to send a third datagram will result in an error, since the queue
         // is at capacity
         assert_eq!(
@@ -829,6 +918,27 @@ mod tests {
         assert!(receiver.queue.pop_front().is_none());
     }
 
+    #[test]
+    fn queue_bytes_test() {
+        let mut queue = Queue::with_capacity(4);
+        queue.push_back(bytes::Bytes::from_static(&[1, 2, 3]));
+        queue.push_back(bytes::Bytes::from_static(&[4, 5]));
+        queue.push_front(bytes::Bytes::from_static(&[6]));
+        assert_eq!(queue.bytes(), 6);
+
+        assert_eq!(queue.pop_front(), Some(bytes::Bytes::from_static(&[6])));
+        assert_eq!(queue.bytes(), 5);
+
+        queue.retain(|datagram| datagram.len() > 2);
+        assert_eq!(queue.len(), 1);
+        assert_eq!(queue.bytes(), 3);
+
+        assert!(queue.pop_front().is_some());
+        assert!(queue.pop_front().is_none());
+        assert!(queue.is_empty());
+        assert_eq!(queue.bytes(), 0);
+    }
+
     #[test]
     fn recv_datagram() {
         let mut receiver = Receiver::builder().build().unwrap();
@@ -81,6 +81,13 @@ pub trait Receiver: 'static + Send {
 
     /// A callback used to notify the application in the case of a connection error
     fn on_connection_error(&mut self, error: connection::Error);
+
+    /// Returns the number of bytes which are currently held by the receiver
+    ///
+    /// This is used by the endpoint to account for its memory usage.
+    fn buffered_bytes(&self) -> u64 {
+        0
+    }
 }
 
 /// Allows users to configure the behavior of sending datagrams.
@@ -95,6 +102,13 @@ pub trait Sender: 'static + Send {
 
     /// A callback used to notify the application in the case of a connection error
     fn on_connection_error(&mut self, error: connection::Error);
+
+    /// Returns the number of bytes which are currently queued by the sender
+    ///
+    /// This is used by the endpoint to account for its memory usage.
+    fn buffered_bytes(&self) -> u64 {
+        0
+    }
 }
 
 /// A packet will be available during the on_transmit callback. Use the methods
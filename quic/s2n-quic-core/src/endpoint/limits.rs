@@ -5,6 +5,7 @@ use crate::{
     event::{api::SocketAddress, IntoEvent, Timestamp},
     inet,
 };
+use core::time::Duration;
 
 /// Outcome describes how the library should proceed on a connection attempt. The implementor will
 /// use information from the ConnectionAttempt object to determine how the library should handle
@@ -71,6 +72,9 @@ pub struct ConnectionAttempt<'a> {
     /// Number of open connections
     pub connection_count: usize,
 
+    /// Number of bytes currently buffered by the endpoint across all connections
+    pub buffered_bytes: u64,
+
     /// The unverified address of the connecting peer
     /// This address comes from the datagram
     pub remote_address: SocketAddress<'a>,
@@ -88,10 +92,110 @@ impl<'a> ConnectionAttempt<'a> {
         Self {
             inflight_handshakes,
             connection_count,
+            buffered_bytes: 0,
             remote_address: remote_address.into_event(),
             timestamp,
         }
     }
+
+    #[doc(hidden)]
+    pub fn with_buffered_bytes(mut self, buffered_bytes: u64) -> Self {
+        self.buffered_bytes = buffered_bytes;
+        self
+    }
+}
+
+/// Describes how the endpoint should react to its current memory usage
+///
+/// Each variant includes the actions of the variants before it.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum MemoryPressure {
+    /// The endpoint is not under memory pressure
+    ///
+    /// Use `MemoryPressure::none()` to construct this variant
+    #[non_exhaustive]
+    None,
+
+    /// Stop extending the flow control windows advertised to peers
+    ///
+    /// Use `MemoryPressure::shrink_windows()` to construct this variant
+    #[non_exhaustive]
+    ShrinkWindows,
+
+    /// Close the connection which buffers the most data among the connections which
+    /// haven't made progress on their streams for at least `min_idle_time`
+    ///
+    /// Use `MemoryPressure::close_largest_consumer()` to construct this variant
+    #[non_exhaustive]
+    CloseLargestConsumer { min_idle_time: Duration },
+}
+
+impl MemoryPressure {
+    /// The endpoint is not under memory pressure
+    pub fn none() -> Self {
+        Self::None
+    }
+
+    /// Stop extending the flow control windows advertised to peers
+    pub fn shrink_windows() -> Self {
+        Self::ShrinkWindows
+    }
+
+    /// Close the connection which buffers the most data among the connections which
+    /// haven't made progress on their streams for at least `min_idle_time`
+    ///
+    /// Connections which are still making progress are never closed, since they are
+    /// expected to release their buffers.
+    pub fn close_largest_consumer(min_idle_time: Duration) -> Self {
+        Self::CloseLargestConsumer { min_idle_time }
+    }
+
+    /// Returns `true` if flow control windows should no longer be extended
+    pub fn shrinks_windows(&self) -> bool {
+        !matches!(self, Self::None)
+    }
+
+    /// Returns the minimum idle time of the largest consumer, if it should be closed
+    pub fn closes_largest_consumer(&self) -> Option<Duration> {
+        match self {
+            Self::CloseLargestConsumer { min_idle_time } => Some(*min_idle_time),
+            _ => None,
+        }
+    }
+}
+
+impl Default for MemoryPressure {
+    fn default() -> Self {
+        Self::None
+    }
+}
+
+/// Describes the amount of memory which is currently used by the endpoint
+#[non_exhaustive]
+#[derive(Debug)]
+pub struct MemoryUsage {
+    /// Number of bytes currently buffered by the endpoint across all connections
+    ///
+    /// This includes stream send and receive buffers, datagram queues and the
+    /// packet buffers of the endpoint.
+    pub buffered_bytes: u64,
+
+    /// Number of open connections
+    pub connection_count: usize,
+
+    pub timestamp: Timestamp,
+}
+
+impl MemoryUsage {
+    #[doc(hidden)]
+    pub fn new(buffered_bytes: u64, connection_count: usize, timestamp: Timestamp) -> Self {
+        Self {
+            buffered_bytes,
+            connection_count,
+            timestamp,
+        }
+    }
 }
 
 pub trait Limiter: 'static + Send {
@@ -119,4 +223,26 @@ pub trait Limiter: 'static + Send {
     /// }
     /// ```
     fn on_connection_attempt(&mut self, info: &ConnectionAttempt) -> Outcome;
+
+    /// Called by the endpoint whenever it has made progress on its connections. The implementor
+    /// returns the `MemoryPressure` the endpoint is under based on the `MemoryUsage`.
+    ///
+    /// By default, the endpoint is never considered to be under memory pressure.
+    fn on_memory_usage(&mut self, info: &MemoryUsage) -> MemoryPressure {
+        let _ = info;
+        MemoryPressure::none()
+    }
+
+    /// Returns the maximum number of bytes which can be buffered for sending across all
+    /// connections of the endpoint
+    ///
+    /// Once the limit is reached, streams on all connections are blocked from buffering more
+    /// data until enough of it is acknowledged by the peers. The limit is applied in addition
+    /// to the per-connection limits and can be exceeded by the data which is buffered before
+    /// the endpoint observes the usage.
+    ///
+    /// By default, only the per-connection limits apply.
+    fn max_send_buffer_size(&self) -> Option<u64> {
+        None
+    }
 }
@@ -199,6 +199,13 @@ pub mod api {
         HandshakeDone {},
         #[non_exhaustive]
         Datagram { len: u16 },
+        #[non_exhaustive]
+        ResetStreamAt {
+            id: u64,
+            error_code: u64,
+            final_size: u64,
+            reliable_size: u64,
+        },
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
@@ -240,6 +247,32 @@ pub mod api {
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
+    #[doc = " The reason a key update occurred"]
+    pub enum KeyUpdateCause {
+        #[non_exhaustive]
+        #[doc = " The keys were installed as part of the handshake"]
+        Negotiated {},
+        #[non_exhaustive]
+        #[doc = " The peer initiated a key update"]
+        PeerInitiated {},
+        #[non_exhaustive]
+        #[doc = " The key approached the AEAD confidentiality limit"]
+        ConfidentialityLimit {},
+        #[non_exhaustive]
+        #[doc = " The key protected the packet count configured in the key update policy"]
+        PacketLimit {},
+        #[non_exhaustive]
+        #[doc = " The key protected the byte count configured in the key update policy"]
+        ByteLimit {},
+        #[non_exhaustive]
+        #[doc = " The key was in use for the interval configured in the key update policy"]
+        Interval {},
+        #[non_exhaustive]
+        #[doc = " The application requested a key update"]
+        Application {},
+    }
+    #[derive(Clone, Debug)]
+    #[non_exhaustive]
     #[doc = " A context from which the event is being emitted"]
     #[doc = ""]
     #[doc = " An event can occur in the context of an Endpoint or Connection"]
@@ -305,6 +338,19 @@ pub mod api {
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
+    pub enum MemoryPressure {
+        #[non_exhaustive]
+        #[doc = " The endpoint is not under memory pressure"]
+        None {},
+        #[non_exhaustive]
+        #[doc = " Flow control windows advertised to peers are no longer extended"]
+        ShrinkWindows {},
+        #[non_exhaustive]
+        #[doc = " The connection which buffers the most data is closed"]
+        CloseLargestConsumer {},
+    }
+    #[derive(Clone, Debug)]
+    #[non_exhaustive]
     pub enum KeySpace {
         #[non_exhaustive]
         Initial {},
@@ -468,6 +514,20 @@ pub mod api {
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
+    #[doc = " The rate at which the application expects to send data"]
+    pub enum ExpectedSendRate {
+        #[non_exhaustive]
+        #[doc = " The application has not declared an expected send rate"]
+        Unspecified {},
+        #[non_exhaustive]
+        #[doc = " The application does not expect to send any data"]
+        Idle {},
+        #[non_exhaustive]
+        #[doc = " The application expects to send at most `bytes_per_second`"]
+        Limited { bytes_per_second: u64 },
+    }
+    #[derive(Clone, Debug)]
+    #[non_exhaustive]
     #[allow(non_camel_case_types)]
     pub enum CipherSuite {
         #[non_exhaustive]
@@ -501,6 +561,10 @@ pub mod api {
         #[doc = " The round trip time estimate was updated"]
         Rtt {},
         #[non_exhaustive]
+        #[doc = " HyStart++ completed Conservative Slow Start without the round trip time"]
+        #[doc = " returning to the baseline measured when Conservative Slow Start was entered"]
+        ConservativeSlowStart {},
+        #[non_exhaustive]
         #[doc = " Slow Start exited due to a reason other than those above"]
         #[doc = ""]
         #[doc = " With the Cubic congestion controller, this reason is used after the initial exiting of"]
@@ -728,9 +792,14 @@ pub mod api {
     #[derive(Clone, Debug)]
     #[non_exhaustive]
     #[doc = " Crypto key updated"]
+    #[doc = ""]
+    #[doc = " 1-RTT key updates are reported once they complete, which is when the first packet protected"]
+    #[doc = " with the new keys is received from the peer. This includes locally initiated updates, which"]
+    #[doc = " start when the first packet is sent with the new keys."]
     pub struct KeyUpdate {
         pub key_type: KeyType,
         pub cipher_suite: CipherSuite,
+        pub cause: KeyUpdateCause,
     }
     impl Event for KeyUpdate {
         const NAME: &'static str = "security:key_update";
@@ -883,6 +952,21 @@ pub mod api {
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
+    #[doc = " The TLS handshake completed with the given parameters"]
+    pub struct TlsHandshakeCompleted<'a> {
+        pub cipher_suite: CipherSuite,
+        #[doc = " The IANA code point of the key exchange group, if it is a registered group"]
+        pub key_exchange_group: Option<u16>,
+        #[doc = " The IANA code point of the signature scheme used by the server"]
+        pub signature_scheme: Option<u16>,
+        #[doc = " The DER-encoded certificate chain presented by the peer"]
+        pub peer_cert_chain: &'a [&'a [u8]],
+    }
+    impl<'a> Event for TlsHandshakeCompleted<'a> {
+        const NAME: &'static str = "tls:handshake_completed";
+    }
+    #[derive(Clone, Debug)]
+    #[non_exhaustive]
     pub struct RxStreamProgress {
         pub bytes: usize,
     }
@@ -907,6 +991,18 @@ pub mod api {
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
+    #[doc = " A PING frame was sent to keep the connection alive"]
+    pub struct KeepAlivePingSent {
+        #[doc = " The interval after which the PING frame was sent"]
+        pub interval: Duration,
+        #[doc = " The number of consecutive keep-alive PING frames sent while the connection was idle"]
+        pub consecutive_pings: u32,
+    }
+    impl Event for KeepAlivePingSent {
+        const NAME: &'static str = "connectivity:keep_alive_ping_sent";
+    }
+    #[derive(Clone, Debug)]
+    #[non_exhaustive]
     #[doc = " The maximum transmission unit (MTU) for the path has changed"]
     pub struct MtuUpdated {
         pub path_id: u64,
@@ -963,6 +1059,60 @@ pub mod api {
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
+    #[doc = " The application has declared the rate at which it expects to send data"]
+    pub struct ExpectedSendRateUpdated {
+        pub path_id: u64,
+        pub rate: ExpectedSendRate,
+    }
+    impl Event for ExpectedSendRateUpdated {
+        const NAME: &'static str = "recovery:expected_send_rate_updated";
+    }
+    #[derive(Clone, Debug)]
+    #[non_exhaustive]
+    #[doc = " The server was blocked by the anti-amplification limit during the handshake and needed"]
+    #[doc = " an additional flight from the peer before sending more data"]
+    pub struct HandshakeAmplificationLimited {
+        pub path_id: u64,
+        #[doc = " The number of extra flights caused by the anti-amplification limit so far"]
+        pub extra_flights: u32,
+    }
+    impl Event for HandshakeAmplificationLimited {
+        const NAME: &'static str = "connectivity:handshake_amplification_limited";
+    }
+    #[derive(Clone, Debug)]
+    #[non_exhaustive]
+    #[doc = " The peer closed the connection with a CONNECTION_CLOSE frame"]
+    pub struct ConnectionCloseReceived<'a> {
+        #[doc = " The error the peer closed the connection with"]
+        pub error: crate::connection::Error,
+        #[doc = " The reason phrase sent by the peer, if any"]
+        pub reason: Option<&'a [u8]>,
+    }
+    impl<'a> Event for ConnectionCloseReceived<'a> {
+        const NAME: &'static str = "connectivity:connection_close_received";
+    }
+    #[derive(Clone, Debug)]
+    #[non_exhaustive]
+    #[doc = " A batch of 1-RTT packets was sealed together"]
+    pub struct PacketsSealed {
+        #[doc = " The number of packets in the batch"]
+        pub count: usize,
+    }
+    impl Event for PacketsSealed {
+        const NAME: &'static str = "security:packets_sealed";
+    }
+    #[derive(Clone, Debug)]
+    #[non_exhaustive]
+    #[doc = " A batch of 1-RTT packets was opened together"]
+    pub struct PacketsOpened {
+        #[doc = " The number of packets in the batch"]
+        pub count: usize,
+    }
+    impl Event for PacketsOpened {
+        const NAME: &'static str = "security:packets_opened";
+    }
+    #[derive(Clone, Debug)]
+    #[non_exhaustive]
     #[doc = " QUIC version"]
     pub struct VersionInformation<'a> {
         pub server_versions: &'a [u32],
@@ -1035,6 +1185,17 @@ pub mod api {
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
+    #[doc = " The memory pressure the endpoint is under changed"]
+    pub struct EndpointMemoryPressureUpdated {
+        #[doc = " The number of bytes buffered by the endpoint across all connections"]
+        pub buffered_bytes: u64,
+        pub pressure: MemoryPressure,
+    }
+    impl Event for EndpointMemoryPressureUpdated {
+        const NAME: &'static str = "transport:memory_pressure_updated";
+    }
+    #[derive(Clone, Debug)]
+    #[non_exhaustive]
     #[doc = " Emitted when the platform sends at least one packet"]
     pub struct PlatformTx {
         #[doc = " The number of packets sent"]
@@ -1339,6 +1500,16 @@ pub mod api {
             }
         }
     }
+    impl IntoEvent<builder::Frame> for &crate::frame::ResetStreamAt {
+        fn into_event(self) -> builder::Frame {
+            builder::Frame::ResetStreamAt {
+                id: self.stream_id.as_u64(),
+                error_code: self.application_error_code.as_u64(),
+                final_size: self.final_size.as_u64(),
+                reliable_size: self.reliable_size.as_u64(),
+            }
+        }
+    }
     impl IntoEvent<builder::Frame> for &crate::frame::StopSending {
         fn into_event(self) -> builder::Frame {
             builder::Frame::StopSending {
@@ -1519,6 +1690,18 @@ pub mod api {
             }
         }
     }
+    impl IntoEvent<builder::MemoryPressure> for crate::endpoint::limits::MemoryPressure {
+        fn into_event(self) -> builder::MemoryPressure {
+            use crate::endpoint::limits::MemoryPressure;
+            match self {
+                MemoryPressure::None => builder::MemoryPressure::None {},
+                MemoryPressure::ShrinkWindows => builder::MemoryPressure::ShrinkWindows {},
+                MemoryPressure::CloseLargestConsumer { .. } => {
+                    builder::MemoryPressure::CloseLargestConsumer {}
+                }
+            }
+        }
+    }
     impl CipherSuite {
         pub fn as_str(&self) -> &'static str {
             match self {
@@ -1806,8 +1989,9 @@ pub mod tracing {
             let api::KeyUpdate {
                 key_type,
                 cipher_suite,
+                cause,
             } = event;
-            tracing :: event ! (target : "key_update" , parent : id , tracing :: Level :: DEBUG , key_type = tracing :: field :: debug (key_type) , cipher_suite = tracing :: field :: debug (cipher_suite));
+            tracing :: event ! (target : "key_update" , parent : id , tracing :: Level :: DEBUG , key_type = tracing :: field :: debug (key_type) , cipher_suite = tracing :: field :: debug (cipher_suite) , cause = tracing :: field :: debug (cause));
         }
         #[inline]
         fn on_key_space_discarded(
@@ -1990,6 +2174,22 @@ pub mod tracing {
             tracing :: event ! (target : "tls_server_hello" , parent : id , tracing :: Level :: DEBUG , payload = tracing :: field :: debug (payload));
         }
         #[inline]
+        fn on_tls_handshake_completed(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            _meta: &api::ConnectionMeta,
+            event: &api::TlsHandshakeCompleted,
+        ) {
+            let id = context.id();
+            let api::TlsHandshakeCompleted {
+                cipher_suite,
+                key_exchange_group,
+                signature_scheme,
+                peer_cert_chain,
+            } = event;
+            tracing :: event ! (target : "tls_handshake_completed" , parent : id , tracing :: Level :: DEBUG , cipher_suite = tracing :: field :: debug (cipher_suite) , key_exchange_group = tracing :: field :: debug (key_exchange_group) , signature_scheme = tracing :: field :: debug (signature_scheme) , peer_cert_chain = tracing :: field :: debug (peer_cert_chain));
+        }
+        #[inline]
         fn on_rx_stream_progress(
             &mut self,
             context: &mut Self::ConnectionContext,
@@ -2023,6 +2223,20 @@ pub mod tracing {
             tracing :: event ! (target : "keep_alive_timer_expired" , parent : id , tracing :: Level :: DEBUG , timeout = tracing :: field :: debug (timeout));
         }
         #[inline]
+        fn on_keep_alive_ping_sent(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            _meta: &api::ConnectionMeta,
+            event: &api::KeepAlivePingSent,
+        ) {
+            let id = context.id();
+            let api::KeepAlivePingSent {
+                interval,
+                consecutive_pings,
+            } = event;
+            tracing :: event ! (target : "keep_alive_ping_sent" , parent : id , tracing :: Level :: DEBUG , interval = tracing :: field :: debug (interval) , consecutive_pings = tracing :: field :: debug (consecutive_pings));
+        }
+        #[inline]
         fn on_mtu_updated(
             &mut self,
             context: &mut Self::ConnectionContext,
@@ -2094,6 +2308,64 @@ pub mod tracing {
             tracing :: event ! (target : "bbr_state_changed" , parent : id , tracing :: Level :: DEBUG , path_id = tracing :: field :: debug (path_id) , state = tracing :: field :: debug (state));
         }
         #[inline]
+        fn on_expected_send_rate_updated(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            _meta: &api::ConnectionMeta,
+            event: &api::ExpectedSendRateUpdated,
+        ) {
+            let id = context.id();
+            let api::ExpectedSendRateUpdated { path_id, rate } = event;
+            tracing :: event ! (target : "expected_send_rate_updated" , parent : id , tracing :: Level :: DEBUG , path_id = tracing :: field :: debug (path_id) , rate = tracing :: field :: debug (rate));
+        }
+        #[inline]
+        fn on_handshake_amplification_limited(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            _meta: &api::ConnectionMeta,
+            event: &api::HandshakeAmplificationLimited,
+        ) {
+            let id = context.id();
+            let api::HandshakeAmplificationLimited {
+                path_id,
+                extra_flights,
+            } = event;
+            tracing :: event ! (target : "handshake_amplification_limited" , parent : id , tracing :: Level :: DEBUG , path_id = tracing :: field :: debug (path_id) , extra_flights = tracing :: field :: debug (extra_flights));
+        }
+        #[inline]
+        fn on_connection_close_received(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            _meta: &api::ConnectionMeta,
+            event: &api::ConnectionCloseReceived,
+        ) {
+            let id = context.id();
+            let api::ConnectionCloseReceived { error, reason } = event;
+            tracing :: event ! (target : "connection_close_received" , parent : id , tracing :: Level :: DEBUG , error = tracing :: field :: debug (error) , reason = tracing :: field :: debug (reason));
+        }
+        #[inline]
+        fn on_packets_sealed(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            _meta: &api::ConnectionMeta,
+            event: &api::PacketsSealed,
+        ) {
+            let id = context.id();
+            let api::PacketsSealed { count } = event;
+            tracing :: event ! (target : "packets_sealed" , parent : id , tracing :: Level :: DEBUG , count = tracing :: field :: debug (count));
+        }
+        #[inline]
+        fn on_packets_opened(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            _meta: &api::ConnectionMeta,
+            event: &api::PacketsOpened,
+        ) {
+            let id = context.id();
+            let api::PacketsOpened { count } = event;
+            tracing :: event ! (target : "packets_opened" , parent : id , tracing :: Level :: DEBUG , count = tracing :: field :: debug (count));
+        }
+        #[inline]
         fn on_version_information(
             &mut self,
             meta: &api::EndpointMeta,
@@ -2189,6 +2461,22 @@ pub mod tracing {
             tracing :: event ! (target : "endpoint_connection_attempt_failed" , parent : parent , tracing :: Level :: DEBUG , error = tracing :: field :: debug (error));
         }
         #[inline]
+        fn on_endpoint_memory_pressure_updated(
+            &mut self,
+            meta: &api::EndpointMeta,
+            event: &api::EndpointMemoryPressureUpdated,
+        ) {
+            let parent = match meta.endpoint_type {
+                api::EndpointType::Client {} => self.client.id(),
+                api::EndpointType::Server {} => self.server.id(),
+            };
+            let api::EndpointMemoryPressureUpdated {
+                buffered_bytes,
+                pressure,
+            } = event;
+            tracing :: event ! (target : "endpoint_memory_pressure_updated" , parent : parent , tracing :: Level :: DEBUG , buffered_bytes = tracing :: field :: debug (buffered_bytes) , pressure = tracing :: field :: debug (pressure));
+        }
+        #[inline]
         fn on_platform_tx(&mut self, meta: &api::EndpointMeta, event: &api::PlatformTx) {
             let parent = match meta.endpoint_type {
                 api::EndpointType::Client {} => self.client.id(),
@@ -2638,6 +2926,12 @@ pub mod builder {
         Datagram {
             len: u16,
         },
+        ResetStreamAt {
+            id: u64,
+            error_code: u64,
+            final_size: u64,
+            reliable_size: u64,
+        },
     }
     impl IntoEvent<api::Frame> for Frame {
         #[inline]
@@ -2726,6 +3020,17 @@ pub mod builder {
                 Self::Datagram { len } => Datagram {
                     len: len.into_event(),
                 },
+                Self::ResetStreamAt {
+                    id,
+                    error_code,
+                    final_size,
+                    reliable_size,
+                } => ResetStreamAt {
+                    id: id.into_event(),
+                    error_code: error_code.into_event(),
+                    final_size: final_size.into_event(),
+                    reliable_size: reliable_size.into_event(),
+                },
             }
         }
     }
@@ -2804,6 +3109,39 @@ pub mod builder {
         }
     }
     #[derive(Clone, Debug)]
+    #[doc = " The reason a key update occurred"]
+    pub enum KeyUpdateCause {
+        #[doc = " The keys were installed as part of the handshake"]
+        Negotiated,
+        #[doc = " The peer initiated a key update"]
+        PeerInitiated,
+        #[doc = " The key approached the AEAD confidentiality limit"]
+        ConfidentialityLimit,
+        #[doc = " The key protected the packet count configured in the key update policy"]
+        PacketLimit,
+        #[doc = " The key protected the byte count configured in the key update policy"]
+        ByteLimit,
+        #[doc = " The key was in use for the interval configured in the key update policy"]
+        Interval,
+        #[doc = " The application requested a key update"]
+        Application,
+    }
+    impl IntoEvent<api::KeyUpdateCause> for KeyUpdateCause {
+        #[inline]
+        fn into_event(self) -> api::KeyUpdateCause {
+            use api::KeyUpdateCause::*;
+            match self {
+                Self::Negotiated => Negotiated {},
+                Self::PeerInitiated => PeerInitiated {},
+                Self::ConfidentialityLimit => ConfidentialityLimit {},
+                Self::PacketLimit => PacketLimit {},
+                Self::ByteLimit => ByteLimit {},
+                Self::Interval => Interval {},
+                Self::Application => Application {},
+            }
+        }
+    }
+    #[derive(Clone, Debug)]
     #[doc = " A context from which the event is being emitted"]
     #[doc = ""]
     #[doc = " An event can occur in the context of an Endpoint or Connection"]
@@ -2895,6 +3233,26 @@ pub mod builder {
         }
     }
     #[derive(Clone, Debug)]
+    pub enum MemoryPressure {
+        #[doc = " The endpoint is not under memory pressure"]
+        None,
+        #[doc = " Flow control windows advertised to peers are no longer extended"]
+        ShrinkWindows,
+        #[doc = " The connection which buffers the most data is closed"]
+        CloseLargestConsumer,
+    }
+    impl IntoEvent<api::MemoryPressure> for MemoryPressure {
+        #[inline]
+        fn into_event(self) -> api::MemoryPressure {
+            use api::MemoryPressure::*;
+            match self {
+                Self::None => None {},
+                Self::ShrinkWindows => ShrinkWindows {},
+                Self::CloseLargestConsumer => CloseLargestConsumer {},
+            }
+        }
+    }
+    #[derive(Clone, Debug)]
     pub enum KeySpace {
         Initial,
         Handshake,
@@ -3150,6 +3508,29 @@ pub mod builder {
         }
     }
     #[derive(Clone, Debug)]
+    #[doc = " The rate at which the application expects to send data"]
+    pub enum ExpectedSendRate {
+        #[doc = " The application has not declared an expected send rate"]
+        Unspecified,
+        #[doc = " The application does not expect to send any data"]
+        Idle,
+        #[doc = " The application expects to send at most `bytes_per_second`"]
+        Limited { bytes_per_second: u64 },
+    }
+    impl IntoEvent<api::ExpectedSendRate> for ExpectedSendRate {
+        #[inline]
+        fn into_event(self) -> api::ExpectedSendRate {
+            use api::ExpectedSendRate::*;
+            match self {
+                Self::Unspecified => Unspecified {},
+                Self::Idle => Idle {},
+                Self::Limited { bytes_per_second } => Limited {
+                    bytes_per_second: bytes_per_second.into_event(),
+                },
+            }
+        }
+    }
+    #[derive(Clone, Debug)]
     #[allow(non_camel_case_types)]
     pub enum CipherSuite {
         TLS_AES_128_GCM_SHA256,
@@ -3193,6 +3574,9 @@ pub mod builder {
         Ecn,
         #[doc = " The round trip time estimate was updated"]
         Rtt,
+        #[doc = " HyStart++ completed Conservative Slow Start without the round trip time"]
+        #[doc = " returning to the baseline measured when Conservative Slow Start was entered"]
+        ConservativeSlowStart,
         #[doc = " Slow Start exited due to a reason other than those above"]
         #[doc = ""]
         #[doc = " With the Cubic congestion controller, this reason is used after the initial exiting of"]
@@ -3208,6 +3592,7 @@ pub mod builder {
                 Self::PacketLoss => PacketLoss {},
                 Self::Ecn => Ecn {},
                 Self::Rtt => Rtt {},
+                Self::ConservativeSlowStart => ConservativeSlowStart {},
                 Self::Other => Other {},
             }
         }
@@ -3593,9 +3978,14 @@ pub mod builder {
     }
     #[derive(Clone, Debug)]
     #[doc = " Crypto key updated"]
+    #[doc = ""]
+    #[doc = " 1-RTT key updates are reported once they complete, which is when the first packet protected"]
+    #[doc = " with the new keys is received from the peer. This includes locally initiated updates, which"]
+    #[doc = " start when the first packet is sent with the new keys."]
     pub struct KeyUpdate {
         pub key_type: KeyType,
         pub cipher_suite: CipherSuite,
+        pub cause: KeyUpdateCause,
     }
     impl IntoEvent<api::KeyUpdate> for KeyUpdate {
         #[inline]
@@ -3603,10 +3993,12 @@ pub mod builder {
             let KeyUpdate {
                 key_type,
                 cipher_suite,
+                cause,
             } = self;
             api::KeyUpdate {
                 key_type: key_type.into_event(),
                 cipher_suite: cipher_suite.into_event(),
+                cause: cause.into_event(),
             }
         }
     }
@@ -3857,6 +4249,34 @@ pub mod builder {
         }
     }
     #[derive(Clone, Debug)]
+    #[doc = " The TLS handshake completed with the given parameters"]
+    pub struct TlsHandshakeCompleted<'a> {
+        pub cipher_suite: CipherSuite,
+        #[doc = " The IANA code point of the key exchange group, if it is a registered group"]
+        pub key_exchange_group: Option<u16>,
+        #[doc = " The IANA code point of the signature scheme used by the server"]
+        pub signature_scheme: Option<u16>,
+        #[doc = " The DER-encoded certificate chain presented by the peer"]
+        pub peer_cert_chain: &'a [&'a [u8]],
+    }
+    impl<'a> IntoEvent<api::TlsHandshakeCompleted<'a>> for TlsHandshakeCompleted<'a> {
+        #[inline]
+        fn into_event(self) -> api::TlsHandshakeCompleted<'a> {
+            let TlsHandshakeCompleted {
+                cipher_suite,
+                key_exchange_group,
+                signature_scheme,
+                peer_cert_chain,
+            } = self;
+            api::TlsHandshakeCompleted {
+                cipher_suite: cipher_suite.into_event(),
+                key_exchange_group: key_exchange_group.into_event(),
+                signature_scheme: signature_scheme.into_event(),
+                peer_cert_chain: peer_cert_chain.into_event(),
+            }
+        }
+    }
+    #[derive(Clone, Debug)]
     pub struct RxStreamProgress {
         pub bytes: usize,
     }
@@ -3896,6 +4316,27 @@ pub mod builder {
         }
     }
     #[derive(Clone, Debug)]
+    #[doc = " A PING frame was sent to keep the connection alive"]
+    pub struct KeepAlivePingSent {
+        #[doc = " The interval after which the PING frame was sent"]
+        pub interval: Duration,
+        #[doc = " The number of consecutive keep-alive PING frames sent while the connection was idle"]
+        pub consecutive_pings: u32,
+    }
+    impl IntoEvent<api::KeepAlivePingSent> for KeepAlivePingSent {
+        #[inline]
+        fn into_event(self) -> api::KeepAlivePingSent {
+            let KeepAlivePingSent {
+                interval,
+                consecutive_pings,
+            } = self;
+            api::KeepAlivePingSent {
+                interval: interval.into_event(),
+                consecutive_pings: consecutive_pings.into_event(),
+            }
+        }
+    }
+    #[derive(Clone, Debug)]
     #[doc = " The maximum transmission unit (MTU) for the path has changed"]
     pub struct MtuUpdated {
         pub path_id: u64,
@@ -4002,6 +4443,91 @@ pub mod builder {
         }
     }
     #[derive(Clone, Debug)]
+    #[doc = " The application has declared the rate at which it expects to send data"]
+    pub struct ExpectedSendRateUpdated {
+        pub path_id: u64,
+        pub rate: ExpectedSendRate,
+    }
+    impl IntoEvent<api::ExpectedSendRateUpdated> for ExpectedSendRateUpdated {
+        #[inline]
+        fn into_event(self) -> api::ExpectedSendRateUpdated {
+            let ExpectedSendRateUpdated { path_id, rate } = self;
+            api::ExpectedSendRateUpdated {
+                path_id: path_id.into_event(),
+                rate: rate.into_event(),
+            }
+        }
+    }
+    #[derive(Clone, Debug)]
+    #[doc = " The server was blocked by the anti-amplification limit during the handshake and needed"]
+    #[doc = " an additional flight from the peer before sending more data"]
+    pub struct HandshakeAmplificationLimited {
+        pub path_id: u64,
+        #[doc = " The number of extra flights caused by the anti-amplification limit so far"]
+        pub extra_flights: u32,
+    }
+    impl IntoEvent<api::HandshakeAmplificationLimited> for HandshakeAmplificationLimited {
+        #[inline]
+        fn into_event(self) -> api::HandshakeAmplificationLimited {
+            let HandshakeAmplificationLimited {
+                path_id,
+                extra_flights,
+            } = self;
+            api::HandshakeAmplificationLimited {
+                path_id: path_id.into_event(),
+                extra_flights: extra_flights.into_event(),
+            }
+        }
+    }
+    #[derive(Clone, Debug)]
+    #[doc = " The peer closed the connection with a CONNECTION_CLOSE frame"]
+    pub struct ConnectionCloseReceived<'a> {
+        #[doc = " The error the peer closed the connection with"]
+        pub error: crate::connection::Error,
+        #[doc = " The reason phrase sent by the peer, if any"]
+        pub reason: Option<&'a [u8]>,
+    }
+    impl<'a> IntoEvent<api::ConnectionCloseReceived<'a>> for ConnectionCloseReceived<'a> {
+        #[inline]
+        fn into_event(self) -> api::ConnectionCloseReceived<'a> {
+            let ConnectionCloseReceived { error, reason } = self;
+            api::ConnectionCloseReceived {
+                error: error.into_event(),
+                reason: reason.into_event(),
+            }
+        }
+    }
+    #[derive(Clone, Debug)]
+    #[doc = " A batch of 1-RTT packets was sealed together"]
+    pub struct PacketsSealed {
+        #[doc = " The number of packets in the batch"]
+        pub count: usize,
+    }
+    impl IntoEvent<api::PacketsSealed> for PacketsSealed {
+        #[inline]
+        fn into_event(self) -> api::PacketsSealed {
+            let PacketsSealed { count } = self;
+            api::PacketsSealed {
+                count: count.into_event(),
+            }
+        }
+    }
+    #[derive(Clone, Debug)]
+    #[doc = " A batch of 1-RTT packets was opened together"]
+    pub struct PacketsOpened {
+        #[doc = " The number of packets in the batch"]
+        pub count: usize,
+    }
+    impl IntoEvent<api::PacketsOpened> for PacketsOpened {
+        #[inline]
+        fn into_event(self) -> api::PacketsOpened {
+            let PacketsOpened { count } = self;
+            api::PacketsOpened {
+                count: count.into_event(),
+            }
+        }
+    }
+    #[derive(Clone, Debug)]
     #[doc = " QUIC version"]
     pub struct VersionInformation<'a> {
         pub server_versions: &'a [u32],
@@ -4117,6 +4643,26 @@ pub mod builder {
         }
     }
     #[derive(Clone, Debug)]
+    #[doc = " The memory pressure the endpoint is under changed"]
+    pub struct EndpointMemoryPressureUpdated {
+        #[doc = " The number of bytes buffered by the endpoint across all connections"]
+        pub buffered_bytes: u64,
+        pub pressure: MemoryPressure,
+    }
+    impl IntoEvent<api::EndpointMemoryPressureUpdated> for EndpointMemoryPressureUpdated {
+        #[inline]
+        fn into_event(self) -> api::EndpointMemoryPressureUpdated {
+            let EndpointMemoryPressureUpdated {
+                buffered_bytes,
+                pressure,
+            } = self;
+            api::EndpointMemoryPressureUpdated {
+                buffered_bytes: buffered_bytes.into_event(),
+                pressure: pressure.into_event(),
+            }
+        }
+    }
+    #[derive(Clone, Debug)]
     #[doc = " Emitted when the platform sends at least one packet"]
     pub struct PlatformTx {
         #[doc = " The number of packets sent"]
@@ -4828,19 +5374,31 @@ mod traits {
             let _ = meta;
             let _ = event;
         }
-        #[doc = "Called when the `RxStreamProgress` event is triggered"]
+        #[doc = "Called when the `TlsHandshakeCompleted` event is triggered"]
         #[inline]
-        fn on_rx_stream_progress(
+        fn on_tls_handshake_completed(
             &mut self,
             context: &mut Self::ConnectionContext,
             meta: &ConnectionMeta,
-            event: &RxStreamProgress,
+            event: &TlsHandshakeCompleted,
         ) {
             let _ = context;
             let _ = meta;
             let _ = event;
         }
-        #[doc = "Called when the `TxStreamProgress` event is triggered"]
+        #[doc = "Called when the `RxStreamProgress` event is triggered"]
+        #[inline]
+        fn on_rx_stream_progress(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &ConnectionMeta,
+            event: &RxStreamProgress,
+        ) {
+            let _ = context;
+            let _ = meta;
+            let _ = event;
+        }
+        #[doc = "Called when the `TxStreamProgress` event is triggered"]
         #[inline]
         fn on_tx_stream_progress(
             &mut self,
@@ -4864,6 +5422,18 @@ mod traits {
             let _ = meta;
             let _ = event;
         }
+        #[doc = "Called when the `KeepAlivePingSent` event is triggered"]
+        #[inline]
+        fn on_keep_alive_ping_sent(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &ConnectionMeta,
+            event: &KeepAlivePingSent,
+        ) {
+            let _ = context;
+            let _ = meta;
+            let _ = event;
+        }
         #[doc = "Called when the `MtuUpdated` event is triggered"]
         #[inline]
         fn on_mtu_updated(
@@ -4924,6 +5494,66 @@ mod traits {
             let _ = meta;
             let _ = event;
         }
+        #[doc = "Called when the `ExpectedSendRateUpdated` event is triggered"]
+        #[inline]
+        fn on_expected_send_rate_updated(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &ConnectionMeta,
+            event: &ExpectedSendRateUpdated,
+        ) {
+            let _ = context;
+            let _ = meta;
+            let _ = event;
+        }
+        #[doc = "Called when the `HandshakeAmplificationLimited` event is triggered"]
+        #[inline]
+        fn on_handshake_amplification_limited(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &ConnectionMeta,
+            event: &HandshakeAmplificationLimited,
+        ) {
+            let _ = context;
+            let _ = meta;
+            let _ = event;
+        }
+        #[doc = "Called when the `ConnectionCloseReceived` event is triggered"]
+        #[inline]
+        fn on_connection_close_received(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &ConnectionMeta,
+            event: &ConnectionCloseReceived,
+        ) {
+            let _ = context;
+            let _ = meta;
+            let _ = event;
+        }
+        #[doc = "Called when the `PacketsSealed` event is triggered"]
+        #[inline]
+        fn on_packets_sealed(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &ConnectionMeta,
+            event: &PacketsSealed,
+        ) {
+            let _ = context;
+            let _ = meta;
+            let _ = event;
+        }
+        #[doc = "Called when the `PacketsOpened` event is triggered"]
+        #[inline]
+        fn on_packets_opened(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &ConnectionMeta,
+            event: &PacketsOpened,
+        ) {
+            let _ = context;
+            let _ = meta;
+            let _ = event;
+        }
         #[doc = "Called when the `VersionInformation` event is triggered"]
         #[inline]
         fn on_version_information(&mut self, meta: &EndpointMeta, event: &VersionInformation) {
@@ -4982,6 +5612,16 @@ mod traits {
             let _ = meta;
             let _ = event;
         }
+        #[doc = "Called when the `EndpointMemoryPressureUpdated` event is triggered"]
+        #[inline]
+        fn on_endpoint_memory_pressure_updated(
+            &mut self,
+            meta: &EndpointMeta,
+            event: &EndpointMemoryPressureUpdated,
+        ) {
+            let _ = meta;
+            let _ = event;
+        }
         #[doc = "Called when the `PlatformTx` event is triggered"]
         #[inline]
         fn on_platform_tx(&mut self, meta: &EndpointMeta, event: &PlatformTx) {
@@ -5456,6 +6096,16 @@ mod traits {
             (self.1).on_tls_server_hello(&mut context.1, meta, event);
         }
         #[inline]
+        fn on_tls_handshake_completed(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &ConnectionMeta,
+            event: &TlsHandshakeCompleted,
+        ) {
+            (self.0).on_tls_handshake_completed(&mut context.0, meta, event);
+            (self.1).on_tls_handshake_completed(&mut context.1, meta, event);
+        }
+        #[inline]
         fn on_rx_stream_progress(
             &mut self,
             context: &mut Self::ConnectionContext,
@@ -5486,6 +6136,16 @@ mod traits {
             (self.1).on_keep_alive_timer_expired(&mut context.1, meta, event);
         }
         #[inline]
+        fn on_keep_alive_ping_sent(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &ConnectionMeta,
+            event: &KeepAlivePingSent,
+        ) {
+            (self.0).on_keep_alive_ping_sent(&mut context.0, meta, event);
+            (self.1).on_keep_alive_ping_sent(&mut context.1, meta, event);
+        }
+        #[inline]
         fn on_mtu_updated(
             &mut self,
             context: &mut Self::ConnectionContext,
@@ -5536,6 +6196,56 @@ mod traits {
             (self.1).on_bbr_state_changed(&mut context.1, meta, event);
         }
         #[inline]
+        fn on_expected_send_rate_updated(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &ConnectionMeta,
+            event: &ExpectedSendRateUpdated,
+        ) {
+            (self.0).on_expected_send_rate_updated(&mut context.0, meta, event);
+            (self.1).on_expected_send_rate_updated(&mut context.1, meta, event);
+        }
+        #[inline]
+        fn on_handshake_amplification_limited(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &ConnectionMeta,
+            event: &HandshakeAmplificationLimited,
+        ) {
+            (self.0).on_handshake_amplification_limited(&mut context.0, meta, event);
+            (self.1).on_handshake_amplification_limited(&mut context.1, meta, event);
+        }
+        #[inline]
+        fn on_connection_close_received(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &ConnectionMeta,
+            event: &ConnectionCloseReceived,
+        ) {
+            (self.0).on_connection_close_received(&mut context.0, meta, event);
+            (self.1).on_connection_close_received(&mut context.1, meta, event);
+        }
+        #[inline]
+        fn on_packets_sealed(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &ConnectionMeta,
+            event: &PacketsSealed,
+        ) {
+            (self.0).on_packets_sealed(&mut context.0, meta, event);
+            (self.1).on_packets_sealed(&mut context.1, meta, event);
+        }
+        #[inline]
+        fn on_packets_opened(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &ConnectionMeta,
+            event: &PacketsOpened,
+        ) {
+            (self.0).on_packets_opened(&mut context.0, meta, event);
+            (self.1).on_packets_opened(&mut context.1, meta, event);
+        }
+        #[inline]
         fn on_version_information(&mut self, meta: &EndpointMeta, event: &VersionInformation) {
             (self.0).on_version_information(meta, event);
             (self.1).on_version_information(meta, event);
@@ -5587,6 +6297,15 @@ mod traits {
             (self.1).on_endpoint_connection_attempt_failed(meta, event);
         }
         #[inline]
+        fn on_endpoint_memory_pressure_updated(
+            &mut self,
+            meta: &EndpointMeta,
+            event: &EndpointMemoryPressureUpdated,
+        ) {
+            (self.0).on_endpoint_memory_pressure_updated(meta, event);
+            (self.1).on_endpoint_memory_pressure_updated(meta, event);
+        }
+        #[inline]
         fn on_platform_tx(&mut self, meta: &EndpointMeta, event: &PlatformTx) {
             (self.0).on_platform_tx(meta, event);
             (self.1).on_platform_tx(meta, event);
@@ -5687,6 +6406,11 @@ mod traits {
             &mut self,
             event: builder::EndpointConnectionAttemptFailed,
         );
+        #[doc = "Publishes a `EndpointMemoryPressureUpdated` event to the publisher's subscriber"]
+        fn on_endpoint_memory_pressure_updated(
+            &mut self,
+            event: builder::EndpointMemoryPressureUpdated,
+        );
         #[doc = "Publishes a `PlatformTx` event to the publisher's subscriber"]
         fn on_platform_tx(&mut self, event: builder::PlatformTx);
         #[doc = "Publishes a `PlatformTxError` event to the publisher's subscriber"]
@@ -5783,6 +6507,16 @@ mod traits {
             self.subscriber.on_event(&self.meta, &event);
         }
         #[inline]
+        fn on_endpoint_memory_pressure_updated(
+            &mut self,
+            event: builder::EndpointMemoryPressureUpdated,
+        ) {
+            let event = event.into_event();
+            self.subscriber
+                .on_endpoint_memory_pressure_updated(&self.meta, &event);
+            self.subscriber.on_event(&self.meta, &event);
+        }
+        #[inline]
         fn on_platform_tx(&mut self, event: builder::PlatformTx) {
             let event = event.into_event();
             self.subscriber.on_platform_tx(&self.meta, &event);
@@ -5900,12 +6634,16 @@ mod traits {
         fn on_tls_client_hello(&mut self, event: builder::TlsClientHello);
         #[doc = "Publishes a `TlsServerHello` event to the publisher's subscriber"]
         fn on_tls_server_hello(&mut self, event: builder::TlsServerHello);
+        #[doc = "Publishes a `TlsHandshakeCompleted` event to the publisher's subscriber"]
+        fn on_tls_handshake_completed(&mut self, event: builder::TlsHandshakeCompleted);
         #[doc = "Publishes a `RxStreamProgress` event to the publisher's subscriber"]
         fn on_rx_stream_progress(&mut self, event: builder::RxStreamProgress);
         #[doc = "Publishes a `TxStreamProgress` event to the publisher's subscriber"]
         fn on_tx_stream_progress(&mut self, event: builder::TxStreamProgress);
         #[doc = "Publishes a `KeepAliveTimerExpired` event to the publisher's subscriber"]
         fn on_keep_alive_timer_expired(&mut self, event: builder::KeepAliveTimerExpired);
+        #[doc = "Publishes a `KeepAlivePingSent` event to the publisher's subscriber"]
+        fn on_keep_alive_ping_sent(&mut self, event: builder::KeepAlivePingSent);
         #[doc = "Publishes a `MtuUpdated` event to the publisher's subscriber"]
         fn on_mtu_updated(&mut self, event: builder::MtuUpdated);
         #[doc = "Publishes a `SlowStartExited` event to the publisher's subscriber"]
@@ -5916,6 +6654,19 @@ mod traits {
         fn on_pacing_rate_updated(&mut self, event: builder::PacingRateUpdated);
         #[doc = "Publishes a `BbrStateChanged` event to the publisher's subscriber"]
         fn on_bbr_state_changed(&mut self, event: builder::BbrStateChanged);
+        #[doc = "Publishes a `ExpectedSendRateUpdated` event to the publisher's subscriber"]
+        fn on_expected_send_rate_updated(&mut self, event: builder::ExpectedSendRateUpdated);
+        #[doc = "Publishes a `HandshakeAmplificationLimited` event to the publisher's subscriber"]
+        fn on_handshake_amplification_limited(
+            &mut self,
+            event: builder::HandshakeAmplificationLimited,
+        );
+        #[doc = "Publishes a `ConnectionCloseReceived` event to the publisher's subscriber"]
+        fn on_connection_close_received(&mut self, event: builder::ConnectionCloseReceived);
+        #[doc = "Publishes a `PacketsSealed` event to the publisher's subscriber"]
+        fn on_packets_sealed(&mut self, event: builder::PacketsSealed);
+        #[doc = "Publishes a `PacketsOpened` event to the publisher's subscriber"]
+        fn on_packets_opened(&mut self, event: builder::PacketsOpened);
         #[doc = r" Returns the QUIC version negotiated for the current connection, if any"]
         fn quic_version(&self) -> u32;
         #[doc = r" Returns the [`Subject`] for the current publisher"]
@@ -6248,6 +6999,15 @@ mod traits {
             self.subscriber.on_event(&self.meta, &event);
         }
         #[inline]
+        fn on_tls_handshake_completed(&mut self, event: builder::TlsHandshakeCompleted) {
+            let event = event.into_event();
+            self.subscriber
+                .on_tls_handshake_completed(self.context, &self.meta, &event);
+            self.subscriber
+                .on_connection_event(self.context, &self.meta, &event);
+            self.subscriber.on_event(&self.meta, &event);
+        }
+        #[inline]
         fn on_rx_stream_progress(&mut self, event: builder::RxStreamProgress) {
             let event = event.into_event();
             self.subscriber
@@ -6275,6 +7035,15 @@ mod traits {
             self.subscriber.on_event(&self.meta, &event);
         }
         #[inline]
+        fn on_keep_alive_ping_sent(&mut self, event: builder::KeepAlivePingSent) {
+            let event = event.into_event();
+            self.subscriber
+                .on_keep_alive_ping_sent(self.context, &self.meta, &event);
+            self.subscriber
+                .on_connection_event(self.context, &self.meta, &event);
+            self.subscriber.on_event(&self.meta, &event);
+        }
+        #[inline]
         fn on_mtu_updated(&mut self, event: builder::MtuUpdated) {
             let event = event.into_event();
             self.subscriber
@@ -6320,6 +7089,54 @@ mod traits {
             self.subscriber.on_event(&self.meta, &event);
         }
         #[inline]
+        fn on_expected_send_rate_updated(&mut self, event: builder::ExpectedSendRateUpdated) {
+            let event = event.into_event();
+            self.subscriber
+                .on_expected_send_rate_updated(self.context, &self.meta, &event);
+            self.subscriber
+                .on_connection_event(self.context, &self.meta, &event);
+            self.subscriber.on_event(&self.meta, &event);
+        }
+        #[inline]
+        fn on_handshake_amplification_limited(
+            &mut self,
+            event: builder::HandshakeAmplificationLimited,
+        ) {
+            let event = event.into_event();
+            self.subscriber
+                .on_handshake_amplification_limited(self.context, &self.meta, &event);
+            self.subscriber
+                .on_connection_event(self.context, &self.meta, &event);
+            self.subscriber.on_event(&self.meta, &event);
+        }
+        #[inline]
+        fn on_connection_close_received(&mut self, event: builder::ConnectionCloseReceived) {
+            let event = event.into_event();
+            self.subscriber
+                .on_connection_close_received(self.context, &self.meta, &event);
+            self.subscriber
+                .on_connection_event(self.context, &self.meta, &event);
+            self.subscriber.on_event(&self.meta, &event);
+        }
+        #[inline]
+        fn on_packets_sealed(&mut self, event: builder::PacketsSealed) {
+            let event = event.into_event();
+            self.subscriber
+                .on_packets_sealed(self.context, &self.meta, &event);
+            self.subscriber
+                .on_connection_event(self.context, &self.meta, &event);
+            self.subscriber.on_event(&self.meta, &event);
+        }
+        #[inline]
+        fn on_packets_opened(&mut self, event: builder::PacketsOpened) {
+            let event = event.into_event();
+            self.subscriber
+                .on_packets_opened(self.context, &self.meta, &event);
+            self.subscriber
+                .on_connection_event(self.context, &self.meta, &event);
+            self.subscriber.on_event(&self.meta, &event);
+        }
+        #[inline]
         fn quic_version(&self) -> u32 {
             self.quic_version
         }
@@ -6368,14 +7185,21 @@ pub mod testing {
         pub path_challenge_updated: u32,
         pub tls_client_hello: u32,
         pub tls_server_hello: u32,
+        pub tls_handshake_completed: u32,
         pub rx_stream_progress: u32,
         pub tx_stream_progress: u32,
         pub keep_alive_timer_expired: u32,
+        pub keep_alive_ping_sent: u32,
         pub mtu_updated: u32,
         pub slow_start_exited: u32,
         pub delivery_rate_sampled: u32,
         pub pacing_rate_updated: u32,
         pub bbr_state_changed: u32,
+        pub expected_send_rate_updated: u32,
+        pub handshake_amplification_limited: u32,
+        pub connection_close_received: u32,
+        pub packets_sealed: u32,
+        pub packets_opened: u32,
         pub version_information: u32,
         pub endpoint_packet_sent: u32,
         pub endpoint_packet_received: u32,
@@ -6383,6 +7207,7 @@ pub mod testing {
         pub endpoint_datagram_received: u32,
         pub endpoint_datagram_dropped: u32,
         pub endpoint_connection_attempt_failed: u32,
+        pub endpoint_memory_pressure_updated: u32,
         pub platform_tx: u32,
         pub platform_tx_error: u32,
         pub platform_rx: u32,
@@ -6446,14 +7271,21 @@ pub mod testing {
                 path_challenge_updated: 0,
                 tls_client_hello: 0,
                 tls_server_hello: 0,
+                tls_handshake_completed: 0,
                 rx_stream_progress: 0,
                 tx_stream_progress: 0,
                 keep_alive_timer_expired: 0,
+                keep_alive_ping_sent: 0,
                 mtu_updated: 0,
                 slow_start_exited: 0,
                 delivery_rate_sampled: 0,
                 pacing_rate_updated: 0,
                 bbr_state_changed: 0,
+                expected_send_rate_updated: 0,
+                handshake_amplification_limited: 0,
+                connection_close_received: 0,
+                packets_sealed: 0,
+                packets_opened: 0,
                 version_information: 0,
                 endpoint_packet_sent: 0,
                 endpoint_packet_received: 0,
@@ -6461,6 +7293,7 @@ pub mod testing {
                 endpoint_datagram_received: 0,
                 endpoint_datagram_dropped: 0,
                 endpoint_connection_attempt_failed: 0,
+                endpoint_memory_pressure_updated: 0,
                 platform_tx: 0,
                 platform_tx_error: 0,
                 platform_rx: 0,
@@ -6832,6 +7665,17 @@ pub mod testing {
                 self.output.push(format!("{meta:?} {event:?}"));
             }
         }
+        fn on_tls_handshake_completed(
+            &mut self,
+            _context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::TlsHandshakeCompleted,
+        ) {
+            self.tls_handshake_completed += 1;
+            if self.location.is_some() {
+                self.output.push(format!("{meta:?} {event:?}"));
+            }
+        }
         fn on_rx_stream_progress(
             &mut self,
             _context: &mut Self::ConnectionContext,
@@ -6865,6 +7709,17 @@ pub mod testing {
                 self.output.push(format!("{meta:?} {event:?}"));
             }
         }
+        fn on_keep_alive_ping_sent(
+            &mut self,
+            _context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::KeepAlivePingSent,
+        ) {
+            self.keep_alive_ping_sent += 1;
+            if self.location.is_some() {
+                self.output.push(format!("{meta:?} {event:?}"));
+            }
+        }
         fn on_mtu_updated(
             &mut self,
             _context: &mut Self::ConnectionContext,
@@ -6920,6 +7775,61 @@ pub mod testing {
                 self.output.push(format!("{meta:?} {event:?}"));
             }
         }
+        fn on_expected_send_rate_updated(
+            &mut self,
+            _context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::ExpectedSendRateUpdated,
+        ) {
+            self.expected_send_rate_updated += 1;
+            if self.location.is_some() {
+                self.output.push(format!("{meta:?} {event:?}"));
+            }
+        }
+        fn on_handshake_amplification_limited(
+            &mut self,
+            _context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::HandshakeAmplificationLimited,
+        ) {
+            self.handshake_amplification_limited += 1;
+            if self.location.is_some() {
+                self.output.push(format!("{meta:?} {event:?}"));
+            }
+        }
+        fn on_connection_close_received(
+            &mut self,
+            _context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::ConnectionCloseReceived,
+        ) {
+            self.connection_close_received += 1;
+            if self.location.is_some() {
+                self.output.push(format!("{meta:?} {event:?}"));
+            }
+        }
+        fn on_packets_sealed(
+            &mut self,
+            _context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::PacketsSealed,
+        ) {
+            self.packets_sealed += 1;
+            if self.location.is_some() {
+                self.output.push(format!("{meta:?} {event:?}"));
+            }
+        }
+        fn on_packets_opened(
+            &mut self,
+            _context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::PacketsOpened,
+        ) {
+            self.packets_opened += 1;
+            if self.location.is_some() {
+                self.output.push(format!("{meta:?} {event:?}"));
+            }
+        }
         fn on_version_information(
             &mut self,
             meta: &api::EndpointMeta,
@@ -6976,6 +7886,14 @@ pub mod testing {
             self.endpoint_connection_attempt_failed += 1;
             self.output.push(format!("{meta:?} {event:?}"));
         }
+        fn on_endpoint_memory_pressure_updated(
+            &mut self,
+            meta: &api::EndpointMeta,
+            event: &api::EndpointMemoryPressureUpdated,
+        ) {
+            self.endpoint_memory_pressure_updated += 1;
+            self.output.push(format!("{meta:?} {event:?}"));
+        }
         fn on_platform_tx(&mut self, meta: &api::EndpointMeta, event: &api::PlatformTx) {
             self.platform_tx += 1;
             self.output.push(format!("{meta:?} {event:?}"));
@@ -7053,14 +7971,21 @@ pub mod testing {
         pub path_challenge_updated: u32,
         pub tls_client_hello: u32,
         pub tls_server_hello: u32,
+        pub tls_handshake_completed: u32,
         pub rx_stream_progress: u32,
         pub tx_stream_progress: u32,
         pub keep_alive_timer_expired: u32,
+        pub keep_alive_ping_sent: u32,
         pub mtu_updated: u32,
         pub slow_start_exited: u32,
         pub delivery_rate_sampled: u32,
         pub pacing_rate_updated: u32,
         pub bbr_state_changed: u32,
+        pub expected_send_rate_updated: u32,
+        pub handshake_amplification_limited: u32,
+        pub connection_close_received: u32,
+        pub packets_sealed: u32,
+        pub packets_opened: u32,
         pub version_information: u32,
         pub endpoint_packet_sent: u32,
         pub endpoint_packet_received: u32,
@@ -7068,6 +7993,7 @@ pub mod testing {
         pub endpoint_datagram_received: u32,
         pub endpoint_datagram_dropped: u32,
         pub endpoint_connection_attempt_failed: u32,
+        pub endpoint_memory_pressure_updated: u32,
         pub platform_tx: u32,
         pub platform_tx_error: u32,
         pub platform_rx: u32,
@@ -7121,14 +8047,21 @@ pub mod testing {
                 path_challenge_updated: 0,
                 tls_client_hello: 0,
                 tls_server_hello: 0,
+                tls_handshake_completed: 0,
                 rx_stream_progress: 0,
                 tx_stream_progress: 0,
                 keep_alive_timer_expired: 0,
+                keep_alive_ping_sent: 0,
                 mtu_updated: 0,
                 slow_start_exited: 0,
                 delivery_rate_sampled: 0,
                 pacing_rate_updated: 0,
                 bbr_state_changed: 0,
+                expected_send_rate_updated: 0,
+                handshake_amplification_limited: 0,
+                connection_close_received: 0,
+                packets_sealed: 0,
+                packets_opened: 0,
                 version_information: 0,
                 endpoint_packet_sent: 0,
                 endpoint_packet_received: 0,
@@ -7136,6 +8069,7 @@ pub mod testing {
                 endpoint_datagram_received: 0,
                 endpoint_datagram_dropped: 0,
                 endpoint_connection_attempt_failed: 0,
+                endpoint_memory_pressure_updated: 0,
                 platform_tx: 0,
                 platform_tx_error: 0,
                 platform_rx: 0,
@@ -7185,6 +8119,14 @@ pub mod testing {
             let event = event.into_event();
             self.output.push(format!("{event:?}"));
         }
+        fn on_endpoint_memory_pressure_updated(
+            &mut self,
+            event: builder::EndpointMemoryPressureUpdated,
+        ) {
+            self.endpoint_memory_pressure_updated += 1;
+            let event = event.into_event();
+            self.output.push(format!("{event:?}"));
+        }
         fn on_platform_tx(&mut self, event: builder::PlatformTx) {
             self.platform_tx += 1;
             let event = event.into_event();
@@ -7456,6 +8398,13 @@ pub mod testing {
                 self.output.push(format!("{event:?}"));
             }
         }
+        fn on_tls_handshake_completed(&mut self, event: builder::TlsHandshakeCompleted) {
+            self.tls_handshake_completed += 1;
+            let event = event.into_event();
+            if self.location.is_some() {
+                self.output.push(format!("{event:?}"));
+            }
+        }
         fn on_rx_stream_progress(&mut self, event: builder::RxStreamProgress) {
             self.rx_stream_progress += 1;
             let event = event.into_event();
@@ -7477,6 +8426,13 @@ pub mod testing {
                 self.output.push(format!("{event:?}"));
             }
         }
+        fn on_keep_alive_ping_sent(&mut self, event: builder::KeepAlivePingSent) {
+            self.keep_alive_ping_sent += 1;
+            let event = event.into_event();
+            if self.location.is_some() {
+                self.output.push(format!("{event:?}"));
+            }
+        }
         fn on_mtu_updated(&mut self, event: builder::MtuUpdated) {
             self.mtu_updated += 1;
             let event = event.into_event();
@@ -7512,6 +8468,44 @@ pub mod testing {
                 self.output.push(format!("{event:?}"));
             }
         }
+        fn on_expected_send_rate_updated(&mut self, event: builder::ExpectedSendRateUpdated) {
+            self.expected_send_rate_updated += 1;
+            let event = event.into_event();
+            if self.location.is_some() {
+                self.output.push(format!("{event:?}"));
+            }
+        }
+        fn on_handshake_amplification_limited(
+            &mut self,
+            event: builder::HandshakeAmplificationLimited,
+        ) {
+            self.handshake_amplification_limited += 1;
+            let event = event.into_event();
+            if self.location.is_some() {
+                self.output.push(format!("{event:?}"));
+            }
+        }
+        fn on_connection_close_received(&mut self, event: builder::ConnectionCloseReceived) {
+            self.connection_close_received += 1;
+            let event = event.into_event();
+            if self.location.is_some() {
+                self.output.push(format!("{event:?}"));
+            }
+        }
+        fn on_packets_sealed(&mut self, event: builder::PacketsSealed) {
+            self.packets_sealed += 1;
+            let event = event.into_event();
+            if self.location.is_some() {
+                self.output.push(format!("{event:?}"));
+            }
+        }
+        fn on_packets_opened(&mut self, event: builder::PacketsOpened) {
+            self.packets_opened += 1;
+            let event = event.into_event();
+            if self.location.is_some() {
+                self.output.push(format!("{event:?}"));
+            }
+        }
         fn quic_version(&self) -> u32 {
             1
         }
@@ -92,6 +92,7 @@ impl AckElicitable for crate::frame::PathChallenge<'_> {}
 impl AckElicitable for crate::frame::PathResponse<'_> {}
 impl AckElicitable for crate::frame::Ping {}
 impl AckElicitable for crate::frame::ResetStream {}
+impl AckElicitable for crate::frame::ResetStreamAt {}
 impl AckElicitable for crate::frame::RetireConnectionId {}
 impl AckElicitable for crate::frame::StopSending {}
 impl<Data> AckElicitable for crate::frame::Stream<Data> {}
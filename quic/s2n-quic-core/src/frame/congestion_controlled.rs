@@ -52,6 +52,7 @@ impl CongestionControlled for crate::frame::PathChallenge<'_> {}
 impl CongestionControlled for crate::frame::PathResponse<'_> {}
 impl CongestionControlled for crate::frame::Ping {}
 impl CongestionControlled for crate::frame::ResetStream {}
+impl CongestionControlled for crate::frame::ResetStreamAt {}
 impl CongestionControlled for crate::frame::RetireConnectionId {}
 impl CongestionControlled for crate::frame::StopSending {}
 impl CongestionControlled for crate::frame::StreamsBlocked {}
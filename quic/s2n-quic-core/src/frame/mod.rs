@@ -255,6 +255,7 @@ frames! {
     connection_close_tag => connection_close, handle_connection_close_frame, ConnectionClose['a];
     handshake_done_tag => handshake_done, handle_handshake_done_frame, HandshakeDone;
     datagram_tag => datagram, handle_datagram_frame, Datagram[Data];
+    reset_stream_at_tag => reset_stream_at, handle_reset_stream_at_frame, ResetStreamAt;
 }
 
 #[derive(Clone, Copy, Debug, Default)]
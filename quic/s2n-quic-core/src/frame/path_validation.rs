@@ -100,6 +100,7 @@ impl Probing for crate::frame::PathResponse<'_> {
 }
 impl Probing for crate::frame::Ping {}
 impl Probing for crate::frame::ResetStream {}
+impl Probing for crate::frame::ResetStreamAt {}
 impl Probing for crate::frame::RetireConnectionId {}
 impl Probing for crate::frame::StopSending {}
 impl<Data> Probing for crate::frame::Stream<Data> {}
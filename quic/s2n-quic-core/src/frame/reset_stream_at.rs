@@ -0,0 +1,60 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::varint::VarInt;
+
+// https://datatracker.ietf.org/doc/html/draft-ietf-quic-reliable-stream-reset-06#section-4
+// The RESET_STREAM_AT frame (type=0x24) is used to abruptly terminate the
+// sending part of a stream, while guaranteeing the delivery of stream data up
+// to the reliable size.
+
+macro_rules! reset_stream_at_tag {
+    () => {
+        0x24u8
+    };
+}
+
+// https://datatracker.ietf.org/doc/html/draft-ietf-quic-reliable-stream-reset-06#section-4
+// RESET_STREAM_AT Frame {
+//   Type (i) = 0x24,
+//   Stream ID (i),
+//   Application Protocol Error Code (i),
+//   Final Size (i),
+//   Reliable Size (i),
+// }
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct ResetStreamAt {
+    /// A variable-length integer encoding of the Stream ID of the
+    /// stream being terminated.
+    pub stream_id: VarInt,
+
+    /// A variable-length integer containing the application protocol
+    /// error code which indicates why the stream is being closed.
+    pub application_error_code: VarInt,
+
+    /// A variable-length integer indicating the final size of
+    /// the stream by the RESET_STREAM_AT sender, in unit of bytes.
+    pub final_size: VarInt,
+
+    /// A variable-length integer indicating the amount of data, in unit of
+    /// bytes, that needs to be delivered to the application even though the
+    /// stream was reset.
+    pub reliable_size: VarInt,
+}
+
+impl ResetStreamAt {
+    pub const fn tag(&self) -> u8 {
+        reset_stream_at_tag!()
+    }
+}
+
+simple_frame_codec!(
+    ResetStreamAt {
+        stream_id,
+        application_error_code,
+        final_size,
+        reliable_size
+    },
+    reset_stream_at_tag!()
+);
@@ -546,6 +546,15 @@ impl Strategy for Frame {
                 }
                 .into()
             },
+            |rand, _data, _cap| {
+                frame::ResetStreamAt {
+                    stream_id: rand.gen_varint(),
+                    application_error_code: rand.gen_varint(),
+                    final_size: rand.gen_varint(),
+                    reliable_size: rand.gen_varint(),
+                }
+                .into()
+            },
         ];
 
         let index = rand.gen_range(0..frames.len() as u64) as usize;
@@ -25,6 +25,9 @@ pub trait Queue {
     fn push<M: Message<Handle = Self::Handle>>(&mut self, message: M) -> Result<Outcome, Error>;
 
     /// Returns the pending messages as a mutable slice
+    ///
+    /// The last entry holds the most recently pushed message. Messages which were segmented with
+    /// GSO are appended to the payload of the entry they were pushed into.
     fn as_slice_mut(&mut self) -> &mut [Self::Entry];
 
     /// Returns the number of remaining datagrams that can be transmitted
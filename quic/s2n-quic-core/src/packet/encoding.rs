@@ -2,13 +2,18 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    crypto::{HeaderKey, Key as CryptoKey, ProtectedPayload},
+    crypto::{
+        BatchPayload, EncryptedPayload, HeaderKey, HeaderProtectionMask, Key as CryptoKey,
+        ProtectedPayload,
+    },
     packet::{
         number::{PacketNumber, PacketNumberLen},
         stateless_reset,
     },
 };
-use s2n_codec::{Encoder, EncoderBuffer, EncoderLenEstimator, EncoderValue};
+use s2n_codec::{
+    DecoderBuffer, DecoderError, Encoder, EncoderBuffer, EncoderLenEstimator, EncoderValue,
+};
 
 pub trait PacketPayloadLenCursor: EncoderValue {
     fn new() -> Self;
@@ -95,6 +100,88 @@ impl<'a> PacketEncodingError<'a> {
     }
 }
 
+/// A packet which has been encoded but not yet sealed
+///
+/// The buffer holds the cleartext header, packet number and payload, followed by space for the
+/// crypto tag.
+#[derive(Debug)]
+pub struct UnsealedPacket<'a> {
+    pub packet_number: PacketNumber,
+    pub packet_number_len: PacketNumberLen,
+    pub header_len: usize,
+    pub buffer: &'a mut [u8],
+}
+
+impl<'a> UnsealedPacket<'a> {
+    /// Returns the length of the packet, including the crypto tag
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.buffer.len()
+    }
+
+    /// Returns `true` if the packet is empty
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.buffer.is_empty()
+    }
+
+    /// Returns the payload to seal the packet as part of a batch
+    ///
+    /// See [`crate::crypto::Key::encrypt_batch`].
+    #[inline]
+    pub fn batch_payload(&mut self) -> BatchPayload<'_> {
+        let header_with_pn_len = self.header_len + self.packet_number_len.bytesize();
+
+        debug_assert!(
+            header_with_pn_len < self.buffer.len(),
+            "header len ({}) should be less than payload ({})",
+            header_with_pn_len,
+            self.buffer.len()
+        );
+
+        let (header, payload) = self.buffer.split_at_mut(header_with_pn_len);
+        BatchPayload::new(self.packet_number.as_crypto_nonce(), header, payload)
+    }
+
+    /// Returns the ciphertext sample used to derive the header protection mask
+    ///
+    /// This must only be called once the payload has been sealed.
+    #[inline]
+    pub fn header_protection_sample(&self, sample_len: usize) -> Result<&[u8], DecoderError> {
+        crate::crypto::payload::header_protection_sample(
+            DecoderBuffer::new(&self.buffer[..]),
+            self.header_len,
+            sample_len,
+        )
+    }
+
+    /// Applies header protection to the sealed packet with a previously derived mask
+    #[inline]
+    pub fn protect(self, mask: HeaderProtectionMask) -> ProtectedPayload<'a> {
+        let payload = EncryptedPayload::new(self.header_len, self.packet_number_len, self.buffer);
+        crate::crypto::header_crypto::apply_header_protection(mask, payload)
+    }
+
+    /// Seals and header-protects the packet
+    #[inline]
+    pub fn seal<K: CryptoKey, H: HeaderKey>(
+        mut self,
+        key: &K,
+        header_key: &H,
+    ) -> ProtectedPayload<'a> {
+        let payload = self.batch_payload();
+        key.encrypt(payload.packet_number, payload.header, payload.payload)
+            .expect("encryption should always work");
+
+        let sample = self
+            .header_protection_sample(header_key.sealing_sample_len())
+            .expect("header protection should always work");
+        let mask = header_key.sealing_header_protection_mask(sample);
+
+        self.protect(mask)
+    }
+}
+
 pub trait PacketEncoder<K: CryptoKey, H: HeaderKey, Payload: PacketPayloadEncoder>: Sized {
     type PayloadLenCursor: PacketPayloadLenCursor;
 
@@ -109,13 +196,38 @@ pub trait PacketEncoder<K: CryptoKey, H: HeaderKey, Payload: PacketPayloadEncode
 
     // Encodes, encrypts, and header-protects a packet into a buffer
     fn encode_packet<'a>(
+        self,
+        key: &K,
+        header_key: &H,
+        largest_acknowledged_packet_number: PacketNumber,
+        min_packet_len: Option<usize>,
+        buffer: EncoderBuffer<'a>,
+    ) -> Result<(ProtectedPayload<'a>, EncoderBuffer<'a>), PacketEncodingError<'a>> {
+        let (packet, remaining) = self.encode_unsealed_packet(
+            key,
+            header_key,
+            largest_acknowledged_packet_number,
+            min_packet_len,
+            buffer,
+        )?;
+
+        // SUCCESS!!!
+
+        Ok((packet.seal(key, header_key), remaining))
+    }
+
+    /// Encodes a packet into a buffer without sealing it
+    ///
+    /// Space for the crypto tag is reserved at the end of the packet. The returned packet can be
+    /// sealed on its own or as part of a batch.
+    fn encode_unsealed_packet<'a>(
         mut self,
         key: &K,
         header_key: &H,
         largest_acknowledged_packet_number: PacketNumber,
         min_packet_len: Option<usize>,
         mut buffer: EncoderBuffer<'a>,
-    ) -> Result<(ProtectedPayload<'a>, EncoderBuffer<'a>), PacketEncodingError<'a>> {
+    ) -> Result<(UnsealedPacket<'a>, EncoderBuffer<'a>), PacketEncodingError<'a>> {
         let packet_number = self.packet_number();
 
         // Truncate the packet number from the largest_acknowledged_packet_number.
@@ -254,18 +366,17 @@ pub trait PacketEncoder<K: CryptoKey, H: HeaderKey, Payload: PacketPayloadEncode
         let actual_payload_len = buffer.len() + key.tag_len() - header_len;
         payload_len_cursor.update(&mut buffer, actual_payload_len);
 
-        // Encrypt the written payload. Note that the tag is appended to the
-        // buffer in the `encrypt` function.
-        let (encrypted_payload, remaining) =
-            crate::crypto::encrypt(key, packet_number, packet_number_len, header_len, buffer)
-                .expect("encryption should always work");
-
-        // Protect the packet
-        let protected_payload = crate::crypto::protect(header_key, encrypted_payload)
-            .expect("header protection should always work");
+        // Reserve space for the crypto tag, which is written when the packet is sealed
+        buffer.write_repeated(key.tag_len(), 0);
 
-        // SUCCESS!!!
+        let (packet, remaining) = buffer.split_off();
+        let packet = UnsealedPacket {
+            packet_number,
+            packet_number_len,
+            header_len,
+            buffer: packet,
+        };
 
-        Ok((protected_payload, remaining))
+        Ok((packet, EncoderBuffer::new(remaining)))
     }
 }
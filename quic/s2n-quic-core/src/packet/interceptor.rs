@@ -65,6 +65,15 @@ pub trait Interceptor: 'static + Send {
         let _ = payload;
     }
 
+    /// Returns `true` if [`Self::intercept_tx_datagram`] observes or modifies datagrams
+    ///
+    /// Outgoing 1-RTT packets are sealed in batches only when this returns `false`, since the
+    /// interceptor would otherwise observe the datagrams before they are protected.
+    #[inline(always)]
+    fn intercepts_tx_datagram(&self) -> bool {
+        true
+    }
+
     #[inline(always)]
     fn intercept_tx_payload(
         &mut self,
@@ -81,7 +90,12 @@ pub trait Interceptor: 'static + Send {
 #[derive(Debug, Default)]
 pub struct Disabled(());
 
-impl Interceptor for Disabled {}
+impl Interceptor for Disabled {
+    #[inline(always)]
+    fn intercepts_tx_datagram(&self) -> bool {
+        false
+    }
+}
 
 impl<A, B> Interceptor for (A, B)
 where
@@ -121,6 +135,11 @@ where
         self.1.intercept_tx_datagram(subject, datagram, payload);
     }
 
+    #[inline(always)]
+    fn intercepts_tx_datagram(&self) -> bool {
+        self.0.intercepts_tx_datagram() || self.1.intercepts_tx_datagram()
+    }
+
     #[inline(always)]
     fn intercept_tx_payload(
         &mut self,
@@ -183,4 +202,9 @@ where
     ) {
         self.tx.havoc(&mut self.random, payload);
     }
+
+    #[inline]
+    fn intercepts_tx_datagram(&self) -> bool {
+        false
+    }
 }
@@ -47,6 +47,12 @@ impl KeyPhase {
         }
     }
 
+    /// Sets the key phase bit in the first byte of a packet
+    #[inline]
+    pub fn set_packet_tag(self, tag: &mut Tag) {
+        *tag = (*tag & !KEY_PHASE_MASK) | self.into_packet_tag_mask();
+    }
+
     #[must_use]
     pub fn next_phase(self) -> Self {
         PHASES[(((self as u8) + 1) % 2) as usize]
@@ -65,6 +71,18 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_set_packet_tag() {
+        for i in 0..255 {
+            for phase in PHASES {
+                let mut tag = i;
+                phase.set_packet_tag(&mut tag);
+                assert_eq!(KeyPhase::from_tag(tag), phase);
+                assert_eq!(tag & !KEY_PHASE_MASK, i & !KEY_PHASE_MASK);
+            }
+        }
+    }
+
     #[test]
     fn test_next_phase() {
         for i in 0..254 {
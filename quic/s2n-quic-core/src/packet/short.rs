@@ -4,7 +4,10 @@
 use crate::{
     connection,
     connection::id::ConnectionInfo,
-    crypto::{CryptoError, EncryptedPayload, OneRttHeaderKey, OneRttKey, ProtectedPayload},
+    crypto::{
+        BatchPayload, CryptoError, EncryptedPayload, HeaderProtectionMask, OneRttHeaderKey,
+        OneRttKey, ProtectedPayload,
+    },
     packet::{
         decoding::HeaderDecoder,
         encoding::{PacketEncoder, PacketPayloadEncoder},
@@ -16,7 +19,9 @@ use crate::{
     },
     transport,
 };
-use s2n_codec::{CheckedRange, DecoderBufferMut, DecoderBufferMutResult, Encoder, EncoderValue};
+use s2n_codec::{
+    CheckedRange, DecoderBufferMut, DecoderBufferMutResult, DecoderError, Encoder, EncoderValue,
+};
 
 //= https://www.rfc-editor.org/rfc/rfc9000#section-17.3.1
 //# 1-RTT Packet {
@@ -164,6 +169,27 @@ impl<'a> ProtectedShort<'a> {
         self,
         header_key: &H,
         largest_acknowledged_packet_number: PacketNumber,
+    ) -> Result<EncryptedShort<'a>, CryptoError> {
+        let sample = self.header_protection_sample(header_key.opening_sample_len())?;
+        let mask = header_key.opening_header_protection_mask(sample);
+
+        self.unprotect_with_mask(mask, largest_acknowledged_packet_number)
+    }
+
+    /// Returns the ciphertext sample used to derive the header protection mask
+    #[inline]
+    pub fn header_protection_sample(&self, sample_len: usize) -> Result<&[u8], DecoderError> {
+        self.payload.header_protection_sample(sample_len)
+    }
+
+    /// Removes header protection with a previously derived mask
+    ///
+    /// This allows the masks for several packets to be derived together with
+    /// [`crate::crypto::HeaderKey::opening_header_protection_masks`].
+    pub fn unprotect_with_mask(
+        self,
+        mask: HeaderProtectionMask,
+        largest_acknowledged_packet_number: PacketNumber,
     ) -> Result<EncryptedShort<'a>, CryptoError> {
         let Short {
             spin_bit,
@@ -173,7 +199,11 @@ impl<'a> ProtectedShort<'a> {
         } = self;
 
         let (truncated_packet_number, payload) =
-            crate::crypto::unprotect(header_key, PacketNumberSpace::ApplicationData, payload)?;
+            crate::crypto::header_crypto::remove_header_protection(
+                PacketNumberSpace::ApplicationData,
+                mask,
+                payload,
+            )?;
 
         let key_phase = KeyPhase::from_tag(payload.get_tag());
 
@@ -198,6 +228,17 @@ impl<'a> ProtectedShort<'a> {
 
 impl<'a> EncryptedShort<'a> {
     pub fn decrypt<C: OneRttKey>(self, crypto: &C) -> Result<CleartextShort<'a>, transport::Error> {
+        let mut packet = self.into_batch();
+
+        let payload = packet.batch_payload();
+        crypto.decrypt(payload.packet_number, payload.header, payload.payload)?;
+
+        packet.into_decrypted(crypto.tag_len())
+    }
+
+    /// Splits the packet so it can be decrypted as part of a batch
+    #[inline]
+    pub fn into_batch(self) -> BatchShort<'a> {
         let Short {
             spin_bit,
             key_phase,
@@ -206,9 +247,77 @@ impl<'a> EncryptedShort<'a> {
             payload,
         } = self;
 
-        let (header, payload) = crate::crypto::decrypt(crypto, packet_number, payload)?;
+        let (header, payload) = payload.split_mut();
+
+        BatchShort {
+            spin_bit,
+            key_phase,
+            destination_connection_id,
+            packet_number,
+            header,
+            payload,
+        }
+    }
+
+    #[inline]
+    pub fn key_phase(&self) -> KeyPhase {
+        self.key_phase
+    }
 
-        let header = header.into_less_safe_slice();
+    #[inline]
+    pub fn destination_connection_id(&self) -> &[u8] {
+        self.payload
+            .get_checked_range(&self.destination_connection_id)
+            .into_less_safe_slice()
+    }
+}
+
+/// An [`EncryptedShort`] packet which has been split to be decrypted as part of a batch
+///
+/// See [`crate::crypto::Key::decrypt_batch`].
+#[derive(Debug)]
+pub struct BatchShort<'a> {
+    spin_bit: SpinBit,
+    key_phase: KeyPhase,
+    destination_connection_id: CheckedRange,
+    packet_number: PacketNumber,
+    header: &'a mut [u8],
+    payload: &'a mut [u8],
+}
+
+impl<'a> BatchShort<'a> {
+    #[inline]
+    pub fn key_phase(&self) -> KeyPhase {
+        self.key_phase
+    }
+
+    #[inline]
+    pub fn packet_number(&self) -> PacketNumber {
+        self.packet_number
+    }
+
+    /// Returns the payload to decrypt the packet as part of a batch
+    #[inline]
+    pub fn batch_payload(&mut self) -> BatchPayload<'_> {
+        BatchPayload::new(
+            self.packet_number.as_crypto_nonce(),
+            self.header,
+            self.payload,
+        )
+    }
+
+    /// Converts a successfully decrypted packet into a [`CleartextShort`]
+    ///
+    /// `tag_len` is the length of the tag appended by the key which decrypted the packet.
+    pub fn into_decrypted(self, tag_len: usize) -> Result<CleartextShort<'a>, transport::Error> {
+        let BatchShort {
+            spin_bit,
+            key_phase,
+            destination_connection_id,
+            packet_number,
+            header,
+            payload,
+        } = self;
 
         //= https://www.rfc-editor.org/rfc/rfc9000#section-17.3.1
         //# An endpoint MUST treat receipt of a
@@ -221,8 +330,13 @@ impl<'a> EncryptedShort<'a> {
             );
         }
 
+        let header: &'a [u8] = header;
         let destination_connection_id = destination_connection_id.get(header);
 
+        // remove the key tag from payload
+        let payload_len = payload.len() - tag_len;
+        let payload = DecoderBufferMut::new(&mut payload[..payload_len]);
+
         Ok(Short {
             spin_bit,
             key_phase,
@@ -231,18 +345,6 @@ impl<'a> EncryptedShort<'a> {
             payload,
         })
     }
-
-    #[inline]
-    pub fn key_phase(&self) -> KeyPhase {
-        self.key_phase
-    }
-
-    #[inline]
-    pub fn destination_connection_id(&self) -> &[u8] {
-        self.payload
-            .get_checked_range(&self.destination_connection_id)
-            .into_less_safe_slice()
-    }
 }
 
 impl<'a> CleartextShort<'a> {
@@ -14,7 +14,7 @@ use crate::{
             probe_bw::{CyclePhase, PROBE_BW_FULL_LOSS_COUNT},
         },
         congestion_controller,
-        congestion_controller::Publisher,
+        congestion_controller::{ExpectedSendRate, Publisher},
         CongestionController, RttEstimator,
     },
     time::Timestamp,
@@ -250,6 +250,8 @@ pub struct BbrCongestionController {
     //= https://tools.ietf.org/id/draft-cardwell-iccrg-bbr-congestion-control-02#2.1
     //# True if the connection has fully utilized its cwnd at any point in the last packet-timed round trip.
     cwnd_limited_in_round: bool,
+    /// The rate at which the application has declared it expects to send
+    expected_send_rate: ExpectedSendRate,
 }
 
 type BytesInFlight = Counter<u32>;
@@ -307,6 +309,16 @@ impl CongestionController for BbrCongestionController {
             self.cwnd_limited_in_round |= self.is_congestion_limited();
         }
 
+        // Mark the rate sample as app-limited if the application has declared it does not
+        // need the full congestion window, so these samples do not lower the bandwidth
+        // estimate or cause a bandwidth probe to be considered unsuccessful.
+        let app_limited = app_limited.map(|app_limited| {
+            app_limited
+                || self
+                    .expected_send_rate
+                    .is_app_limited(self.cwnd, rtt_estimator)
+        });
+
         self.bw_estimator
             .on_packet_sent(prior_bytes_in_flight, sent_bytes, app_limited, time_sent)
     }
@@ -532,6 +544,17 @@ impl CongestionController for BbrCongestionController {
     fn send_quantum(&self) -> Option<usize> {
         Some(self.pacer.send_quantum())
     }
+
+    #[inline]
+    fn on_expected_send_rate(&mut self, expected_send_rate: ExpectedSendRate) {
+        if expected_send_rate == ExpectedSendRate::Idle {
+            // The application will not be sending, so any packets currently in flight
+            // will not reflect the available bandwidth of the path
+            self.bw_estimator.on_app_limited(*self.bytes_in_flight);
+        }
+
+        self.expected_send_rate = expected_send_rate;
+    }
 }
 
 impl BbrCongestionController {
@@ -582,6 +605,7 @@ impl BbrCongestionController {
             pacer: Pacer::new(max_datagram_size),
             try_fast_path: false,
             cwnd_limited_in_round: false,
+            expected_send_rate: ExpectedSendRate::Unspecified,
         }
     }
     /// The bandwidth-delay product
@@ -11,8 +11,8 @@ use crate::{
         bandwidth::{Bandwidth, PacketInfo, RateSample},
         bbr,
         bbr::{probe_bw::CyclePhase, probe_rtt, BbrCongestionController, State},
-        congestion_controller::{PathPublisher, Publisher},
-        CongestionController,
+        congestion_controller::{ExpectedSendRate, PathPublisher, Publisher},
+        CongestionController, RttEstimator,
     },
     time::{Clock, NoopClock},
 };
@@ -1012,6 +1012,43 @@ fn on_mtu_update() {
     assert_eq!(bbr.cwnd, 200_000);
 }
 
+#[test]
+fn on_expected_send_rate() {
+    let mut bbr = BbrCongestionController::new(MINIMUM_MTU);
+    let mut publisher = event::testing::Publisher::no_snapshot();
+    let mut publisher = PathPublisher::new(&mut publisher, path::Id::test_id());
+    let rtt_estimator = RttEstimator::default();
+    let now = NoopClock.get_time();
+
+    // The application is sending enough to fill the congestion window
+    let packet_info = bbr.on_packet_sent(now, 1200, Some(false), &rtt_estimator, &mut publisher);
+    assert!(!packet_info.is_app_limited);
+
+    // The application has declared it will send far less than the congestion window allows,
+    // so packets are marked app limited even if there was data available to send
+    bbr.on_expected_send_rate(ExpectedSendRate::Limited {
+        bytes_per_second: 1000,
+    });
+    let packet_info = bbr.on_packet_sent(now, 1200, Some(false), &rtt_estimator, &mut publisher);
+    assert!(packet_info.is_app_limited);
+
+    // Declaring the connection idle marks the bytes currently in flight as app limited
+    let mut bbr = BbrCongestionController::new(MINIMUM_MTU);
+    bbr.on_packet_sent(now, 1200, Some(false), &rtt_estimator, &mut publisher);
+    assert!(!bbr.bw_estimator.is_app_limited());
+    bbr.on_expected_send_rate(ExpectedSendRate::Idle);
+    assert!(bbr.bw_estimator.is_app_limited());
+
+    // Unspecified reverts to only using the `app_limited` value provided by the transport
+    let mut bbr = BbrCongestionController::new(MINIMUM_MTU);
+    bbr.on_expected_send_rate(ExpectedSendRate::Limited {
+        bytes_per_second: 1000,
+    });
+    bbr.on_expected_send_rate(ExpectedSendRate::Unspecified);
+    let packet_info = bbr.on_packet_sent(now, 1200, Some(false), &rtt_estimator, &mut publisher);
+    assert!(!packet_info.is_app_limited);
+}
+
 /// Helper method to move the given BBR congestion controller into the
 /// ProbeBW state with the given CyclePhase
 fn enter_probe_bw_state<Pub: Publisher>(
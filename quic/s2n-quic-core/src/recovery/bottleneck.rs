@@ -0,0 +1,657 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! A bottleneck link simulation for evaluating congestion controllers
+//!
+//! Each [`Flow`] drives a [`CongestionController`] that continuously sends packets into a
+//! shared first-in, first-out queue which drains at the bottleneck rate and drops packets once
+//! it is full. Packets that make it through the queue are acknowledged after the round trip
+//! propagation delay. Packets dropped by the queue are declared lost once a later packet is
+//! acknowledged or, for a tail loss, once the probe timeout has elapsed.
+//!
+//! Running a [`Simulation`] produces a [`Report`] with the throughput of each flow along with
+//! the fairness, utilization and queueing delay of the bottleneck.
+
+use crate::{
+    event,
+    packet::number::PacketNumberSpace,
+    path,
+    path::MINIMUM_MTU,
+    random,
+    recovery::{
+        bandwidth::Bandwidth, congestion_controller::PathPublisher, CongestionController,
+        RttEstimator,
+    },
+    time::{Clock, NoopClock, Timestamp},
+};
+use alloc::{boxed::Box, collections::VecDeque, vec::Vec};
+use core::{fmt, time::Duration};
+use num_rational::Ratio;
+
+#[cfg(test)]
+mod tests;
+
+/// The parameters of the simulated network
+#[derive(Clone, Copy, Debug)]
+pub struct Network {
+    /// The rate at which the bottleneck queue is drained
+    pub bandwidth: Bandwidth,
+    /// The rate at which each flow can transmit into the bottleneck queue
+    pub sender_bandwidth: Bandwidth,
+    /// The round trip propagation delay, excluding any time spent in the queue
+    pub rtt: Duration,
+    /// The number of bytes the queue can hold before packets are dropped
+    pub queue_capacity: u64,
+    /// The size of each packet transmitted by the flows
+    pub max_datagram_size: u16,
+    /// The amount of simulated time to run for
+    pub duration: Duration,
+    /// The interval at which flows are sampled
+    pub sample_interval: Duration,
+}
+
+impl Network {
+    /// Creates a network with the given bottleneck bandwidth and round trip time
+    ///
+    /// The queue is sized to hold one bandwidth-delay product and flows can transmit
+    /// at ten times the bottleneck rate.
+    pub fn new(bandwidth: Bandwidth, rtt: Duration) -> Self {
+        Self {
+            bandwidth,
+            sender_bandwidth: bandwidth * Ratio::new(10, 1),
+            rtt,
+            queue_capacity: bandwidth * rtt,
+            max_datagram_size: MINIMUM_MTU,
+            duration: Duration::from_secs(10),
+            sample_interval: Duration::from_millis(100),
+        }
+    }
+
+    /// Sets the number of bytes the queue can hold before packets are dropped
+    pub fn with_queue_capacity(mut self, queue_capacity: u64) -> Self {
+        self.queue_capacity = queue_capacity;
+        self
+    }
+
+    /// Sets the amount of simulated time to run for
+    pub fn with_duration(mut self, duration: Duration) -> Self {
+        self.duration = duration;
+        self
+    }
+
+    /// Returns the bandwidth-delay product of the bottleneck, in bytes
+    pub fn bdp(&self) -> u64 {
+        self.bandwidth * self.rtt
+    }
+}
+
+/// A bulk transfer driven by a congestion controller
+#[derive(Clone, Debug)]
+pub struct Flow<CC: CongestionController> {
+    name: &'static str,
+    congestion_controller: CC,
+    start: Duration,
+    stop: Option<Duration>,
+}
+
+impl<CC: CongestionController> Flow<CC> {
+    /// Creates a flow that starts sending at the beginning of the simulation
+    pub fn new(congestion_controller: CC) -> Self {
+        Self {
+            name: core::any::type_name::<CC>().split("::").last().unwrap(),
+            congestion_controller,
+            start: Duration::ZERO,
+            stop: None,
+        }
+    }
+
+    /// Sets the name of the flow in the report
+    ///
+    /// Defaults to the name of the congestion controller type.
+    pub fn with_name(mut self, name: &'static str) -> Self {
+        self.name = name;
+        self
+    }
+
+    /// Sets the time at which the flow starts sending
+    pub fn with_start(mut self, start: Duration) -> Self {
+        self.start = start;
+        self
+    }
+
+    /// Sets the time at which the flow stops sending
+    pub fn with_stop(mut self, stop: Duration) -> Self {
+        self.stop = Some(stop);
+        self
+    }
+}
+
+/// Runs a set of flows over a shared bottleneck
+pub struct Simulation {
+    network: Network,
+    flows: Vec<Box<dyn Sender>>,
+}
+
+impl fmt::Debug for Simulation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Simulation")
+            .field("network", &self.network)
+            .field("flows", &self.flows.len())
+            .finish()
+    }
+}
+
+impl Simulation {
+    pub fn new(network: Network) -> Self {
+        Self {
+            network,
+            flows: Vec::new(),
+        }
+    }
+
+    /// Adds a flow to the simulation
+    pub fn with_flow<CC: CongestionController>(mut self, flow: Flow<CC>) -> Self {
+        let time_zero = NoopClock.get_time();
+        let max_datagram_size = self.network.max_datagram_size;
+        self.flows
+            .push(Box::new(Endpoint::new(flow, max_datagram_size, time_zero)));
+        self
+    }
+
+    /// Runs the simulation to completion
+    pub fn run(mut self) -> Report {
+        let network = self.network;
+        let time_zero = NoopClock.get_time();
+        let end = time_zero + network.duration;
+        let mut queue = Queue::new(&network, time_zero);
+        let mut now = time_zero;
+        let mut next_sample = time_zero + network.sample_interval;
+
+        loop {
+            for flow in self.flows.iter_mut() {
+                flow.on_timeout(now, &network, &mut queue);
+            }
+
+            let next = self
+                .flows
+                .iter()
+                .filter_map(|flow| flow.next_expiration())
+                .min()
+                .unwrap_or(end)
+                .min(end);
+
+            while next_sample <= next {
+                for flow in self.flows.iter_mut() {
+                    flow.on_sample(next_sample - time_zero);
+                }
+                next_sample += network.sample_interval;
+            }
+
+            if now >= end {
+                break;
+            }
+
+            // make sure time always moves forward
+            now = next.max(now + Duration::from_micros(1));
+        }
+
+        let flows: Vec<_> = self.flows.iter().map(|flow| flow.report(end)).collect();
+        let capacity = network.bandwidth * network.duration;
+
+        Report {
+            utilization: queue.departed_bytes as f64 / capacity as f64,
+            fairness: fairness(
+                flows
+                    .iter()
+                    .map(|flow| flow.throughput.as_bytes_per_second()),
+            ),
+            queueing_delay: queue.delay_stats(),
+            dropped_packets: queue.dropped_packets,
+            flows,
+        }
+    }
+}
+
+/// Computes Jain's fairness index over the given throughputs
+///
+/// The index ranges from `1 / n`, when a single flow receives all of the throughput, to `1`,
+/// when all flows receive an equal share.
+pub fn fairness<I: IntoIterator<Item = u64>>(throughputs: I) -> f64 {
+    let mut count = 0.0;
+    let mut sum = 0.0;
+    let mut sum_of_squares = 0.0;
+
+    for throughput in throughputs {
+        let throughput = throughput as f64;
+        count += 1.0;
+        sum += throughput;
+        sum_of_squares += throughput * throughput;
+    }
+
+    if sum_of_squares == 0.0 {
+        return 1.0;
+    }
+
+    sum * sum / (count * sum_of_squares)
+}
+
+/// The results of a simulation
+#[derive(Clone)]
+pub struct Report {
+    /// The results of each flow, in the order they were added
+    pub flows: Vec<FlowReport>,
+    /// The fraction of the bottleneck capacity used over the course of the simulation
+    pub utilization: f64,
+    /// Jain's fairness index over the throughput of each flow
+    pub fairness: f64,
+    /// The amount of time packets spent waiting in the bottleneck queue
+    pub queueing_delay: QueueingDelay,
+    /// The number of packets dropped by the bottleneck queue
+    pub dropped_packets: u64,
+}
+
+impl fmt::Debug for Report {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // Print out rounded values rather than floating point numbers to normalize any
+        // differences between platforms.
+        f.debug_struct("Report")
+            .field(
+                "utilization",
+                &format_args!("{}%", (self.utilization * 100.0 + 0.5) as u64),
+            )
+            .field(
+                "fairness",
+                &format_args!("{:.2}", (self.fairness * 100.0 + 0.5) as u64 as f64 / 100.0),
+            )
+            .field("queueing_delay", &self.queueing_delay)
+            .field("dropped_packets", &self.dropped_packets)
+            .field("flows", &self.flows)
+            .finish()
+    }
+}
+
+/// The distribution of time packets spent waiting in the bottleneck queue
+#[derive(Clone, Copy, Default)]
+pub struct QueueingDelay {
+    pub mean: Duration,
+    pub p95: Duration,
+    pub max: Duration,
+}
+
+impl fmt::Debug for QueueingDelay {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("QueueingDelay")
+            .field("mean", &format_args!("{}ms", self.mean.as_millis()))
+            .field("p95", &format_args!("{}ms", self.p95.as_millis()))
+            .field("max", &format_args!("{}ms", self.max.as_millis()))
+            .finish()
+    }
+}
+
+/// The results of a single flow
+#[derive(Clone)]
+pub struct FlowReport {
+    pub name: &'static str,
+    /// The rate at which bytes were acknowledged while the flow was active
+    pub throughput: Bandwidth,
+    /// The total number of bytes acknowledged
+    pub delivered_bytes: u64,
+    /// The number of packets declared lost
+    pub lost_packets: u64,
+    /// The state of the flow at each sample interval
+    pub samples: Vec<Sample>,
+}
+
+impl fmt::Debug for FlowReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let kbps = self.throughput.as_bytes_per_second() * 8 / 1000;
+
+        f.debug_struct("FlowReport")
+            .field("name", &self.name)
+            .field(
+                "throughput",
+                &format_args!("{}.{}Mbps", kbps / 1000, kbps % 1000 / 100),
+            )
+            .field("lost_packets", &self.lost_packets)
+            .finish()
+    }
+}
+
+/// The state of a flow at a point in time
+#[derive(Clone, Copy, Debug)]
+pub struct Sample {
+    /// The offset from the start of the simulation
+    pub time: Duration,
+    pub congestion_window: u32,
+    pub bytes_in_flight: u32,
+    pub smoothed_rtt: Duration,
+    /// The total number of bytes acknowledged up to this point
+    pub delivered_bytes: u64,
+}
+
+/// A first-in, first-out queue in front of the bottleneck link that drops packets when full
+struct Queue {
+    bandwidth: Bandwidth,
+    rtt: Duration,
+    capacity: u64,
+    end: Timestamp,
+    /// The time at which the link finishes transmitting all of the queued packets
+    idle_at: Timestamp,
+    /// The departure time and size of each queued packet
+    packets: VecDeque<(Timestamp, u16)>,
+    queued_bytes: u64,
+    departed_bytes: u64,
+    dropped_packets: u64,
+    delays: Vec<Duration>,
+}
+
+impl Queue {
+    fn new(network: &Network, time_zero: Timestamp) -> Self {
+        Self {
+            bandwidth: network.bandwidth,
+            rtt: network.rtt,
+            capacity: network.queue_capacity,
+            end: time_zero + network.duration,
+            idle_at: time_zero,
+            packets: VecDeque::new(),
+            queued_bytes: 0,
+            departed_bytes: 0,
+            dropped_packets: 0,
+            delays: Vec::new(),
+        }
+    }
+
+    /// Enqueues a packet, returning the time its acknowledgement will arrive at the sender,
+    /// or `None` if the packet was dropped
+    fn enqueue(&mut self, now: Timestamp, len: u16) -> Option<Timestamp> {
+        while let Some((departure, len)) = self.packets.front().copied() {
+            if departure > now {
+                break;
+            }
+            self.packets.pop_front();
+            self.queued_bytes -= len as u64;
+        }
+
+        if self.queued_bytes + len as u64 > self.capacity {
+            self.dropped_packets += 1;
+            return None;
+        }
+
+        let transmit_start = self.idle_at.max(now);
+        let departure = transmit_start + len as u64 / self.bandwidth;
+        self.idle_at = departure;
+        self.packets.push_back((departure, len));
+        self.queued_bytes += len as u64;
+        self.delays.push(transmit_start - now);
+
+        if departure <= self.end {
+            self.departed_bytes += len as u64;
+        }
+
+        Some(departure + self.rtt)
+    }
+
+    fn delay_stats(&mut self) -> QueueingDelay {
+        if self.delays.is_empty() {
+            return QueueingDelay::default();
+        }
+
+        self.delays.sort_unstable();
+
+        let len = self.delays.len();
+        let sum: Duration = self.delays.iter().sum();
+
+        QueueingDelay {
+            mean: sum / len as u32,
+            p95: self.delays[(len - 1) * 95 / 100],
+            max: self.delays[len - 1],
+        }
+    }
+}
+
+/// Object-safe interface over flows with differing congestion controller types
+trait Sender {
+    fn on_timeout(&mut self, now: Timestamp, network: &Network, queue: &mut Queue);
+
+    fn next_expiration(&self) -> Option<Timestamp>;
+
+    fn on_sample(&mut self, time: Duration);
+
+    fn report(&self, end: Timestamp) -> FlowReport;
+}
+
+#[derive(Debug)]
+struct SentPacket<PacketInfo> {
+    time_sent: Timestamp,
+    info: PacketInfo,
+    /// The time the acknowledgement arrives, or `None` if the packet was dropped
+    ack_time: Option<Timestamp>,
+}
+
+struct Endpoint<CC: CongestionController> {
+    name: &'static str,
+    congestion_controller: CC,
+    max_datagram_size: u16,
+    start: Timestamp,
+    stop: Option<Timestamp>,
+    rtt_estimator: RttEstimator,
+    random: random::testing::Generator,
+    sent_packets: VecDeque<SentPacket<CC::PacketInfo>>,
+    /// The next time the flow will attempt to transmit
+    transmit_at: Option<Timestamp>,
+    delivered_bytes: u64,
+    lost_packets: u64,
+    samples: Vec<Sample>,
+}
+
+impl<CC: CongestionController> Endpoint<CC> {
+    fn new(flow: Flow<CC>, max_datagram_size: u16, time_zero: Timestamp) -> Self {
+        let start = time_zero + flow.start;
+
+        Self {
+            name: flow.name,
+            congestion_controller: flow.congestion_controller,
+            max_datagram_size,
+            start,
+            stop: flow.stop.map(|stop| time_zero + stop),
+            rtt_estimator: RttEstimator::default(),
+            random: random::testing::Generator::default(),
+            sent_packets: VecDeque::new(),
+            transmit_at: Some(start),
+            delivered_bytes: 0,
+            lost_packets: 0,
+            samples: Vec::new(),
+        }
+    }
+
+    fn on_acks_and_losses(&mut self, now: Timestamp) {
+        let mut publisher = event::testing::Publisher::no_snapshot();
+        let mut publisher = PathPublisher::new(&mut publisher, path::Id::test_id());
+
+        // acknowledgements arrive in the order the packets departed the queue so find
+        // the newest packet that has been acknowledged
+        let mut newest_acked = None;
+        for (index, packet) in self.sent_packets.iter().enumerate() {
+            match packet.ack_time {
+                Some(ack_time) if ack_time <= now => newest_acked = Some(index),
+                Some(_) => break,
+                None => {}
+            }
+        }
+
+        let mut lost = Vec::new();
+
+        if let Some(newest_acked) = newest_acked {
+            let mut bytes_acknowledged = 0;
+            let mut newest = None;
+
+            for packet in self.sent_packets.drain(..=newest_acked) {
+                if packet.ack_time.is_some() {
+                    bytes_acknowledged += self.max_datagram_size as usize;
+                    newest = Some(packet);
+                } else {
+                    // any dropped packets sent before an acknowledged packet are lost
+                    lost.push(packet);
+                }
+            }
+
+            let newest = newest.expect("newest_acked refers to an acknowledged packet");
+
+            self.rtt_estimator.update_rtt(
+                Duration::ZERO,
+                now - newest.time_sent,
+                now,
+                true,
+                PacketNumberSpace::ApplicationData,
+            );
+            self.congestion_controller.on_rtt_update(
+                newest.time_sent,
+                now,
+                &self.rtt_estimator,
+                &mut publisher,
+            );
+            self.congestion_controller.on_ack(
+                newest.time_sent,
+                bytes_acknowledged,
+                newest.info,
+                &self.rtt_estimator,
+                &mut self.random,
+                now,
+                &mut publisher,
+            );
+            self.delivered_bytes += bytes_acknowledged as u64;
+        }
+
+        // declare tail losses once the probe timeout has elapsed
+        let pto = self
+            .rtt_estimator
+            .pto_period(1, PacketNumberSpace::ApplicationData);
+        while let Some(packet) = self.sent_packets.front() {
+            if packet.ack_time.is_some() || !(packet.time_sent + pto).has_elapsed(now) {
+                break;
+            }
+            lost.extend(self.sent_packets.pop_front());
+        }
+
+        let mut prev_lost = false;
+        for packet in lost {
+            self.congestion_controller.on_packet_lost(
+                self.max_datagram_size as u32,
+                packet.info,
+                false,
+                !prev_lost,
+                &mut self.random,
+                now,
+                &mut publisher,
+            );
+            self.lost_packets += 1;
+            prev_lost = true;
+        }
+    }
+
+    fn on_transmit(&mut self, now: Timestamp, queue: &mut Queue, sender_bandwidth: Bandwidth) {
+        let is_active =
+            self.start.has_elapsed(now) && self.stop.map_or(true, |stop| !stop.has_elapsed(now));
+
+        if !is_active {
+            self.transmit_at = Some(self.start).filter(|start| !start.has_elapsed(now));
+            return;
+        }
+
+        if let Some(transmit_at) = self.transmit_at {
+            if !transmit_at.has_elapsed(now) {
+                // the previous packet is still being serialized
+                return;
+            }
+        }
+
+        if self.congestion_controller.is_congestion_limited()
+            && !self.congestion_controller.requires_fast_retransmission()
+        {
+            // wait for an acknowledgement or loss to open the window
+            self.transmit_at = None;
+            return;
+        }
+
+        if let Some(edt) = self.congestion_controller.earliest_departure_time() {
+            if !edt.has_elapsed(now) {
+                self.transmit_at = Some(edt);
+                return;
+            }
+        }
+
+        let mut publisher = event::testing::Publisher::no_snapshot();
+        let mut publisher = PathPublisher::new(&mut publisher, path::Id::test_id());
+
+        let len = self.max_datagram_size;
+        let info = self.congestion_controller.on_packet_sent(
+            now,
+            len as usize,
+            Some(false),
+            &self.rtt_estimator,
+            &mut publisher,
+        );
+        let ack_time = queue.enqueue(now, len);
+
+        self.sent_packets.push_back(SentPacket {
+            time_sent: now,
+            info,
+            ack_time,
+        });
+
+        // the next packet can be sent once this one has been serialized
+        self.transmit_at = Some(now + len as u64 / sender_bandwidth);
+    }
+}
+
+impl<CC: CongestionController> Sender for Endpoint<CC> {
+    fn on_timeout(&mut self, now: Timestamp, network: &Network, queue: &mut Queue) {
+        self.on_acks_and_losses(now);
+        self.on_transmit(now, queue, network.sender_bandwidth);
+    }
+
+    fn next_expiration(&self) -> Option<Timestamp> {
+        let ack_time = self.sent_packets.iter().find_map(|packet| packet.ack_time);
+
+        let loss_time = self
+            .sent_packets
+            .front()
+            .filter(|packet| packet.ack_time.is_none())
+            .map(|packet| {
+                packet.time_sent
+                    + self
+                        .rtt_estimator
+                        .pto_period(1, PacketNumberSpace::ApplicationData)
+            });
+
+        [ack_time, loss_time, self.transmit_at]
+            .into_iter()
+            .flatten()
+            .min()
+    }
+
+    fn on_sample(&mut self, time: Duration) {
+        self.samples.push(Sample {
+            time,
+            congestion_window: self.congestion_controller.congestion_window(),
+            bytes_in_flight: self.congestion_controller.bytes_in_flight(),
+            smoothed_rtt: self.rtt_estimator.smoothed_rtt(),
+            delivered_bytes: self.delivered_bytes,
+        });
+    }
+
+    fn report(&self, end: Timestamp) -> FlowReport {
+        let stop = self.stop.map_or(end, |stop| stop.min(end));
+
+        FlowReport {
+            name: self.name,
+            throughput: Bandwidth::new(
+                self.delivered_bytes,
+                stop.saturating_duration_since(self.start),
+            ),
+            delivered_bytes: self.delivered_bytes,
+            lost_packets: self.lost_packets,
+            samples: self.samples.clone(),
+        }
+    }
+}
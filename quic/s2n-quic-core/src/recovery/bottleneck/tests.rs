@@ -0,0 +1,218 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::recovery::{bbr::BbrCongestionController, CubicCongestionController};
+use insta::assert_debug_snapshot;
+use plotters::prelude::*;
+use std::{env, path::PathBuf};
+
+const CHART_DIMENSIONS: (u32, u32) = (1024, 768);
+
+/// A 10Mbps link with a 50ms round trip time
+fn network() -> Network {
+    Network::new(
+        Bandwidth::new(10_000_000 / 8, Duration::from_secs(1)),
+        Duration::from_millis(50),
+    )
+    .with_duration(Duration::from_secs(20))
+}
+
+fn cubic() -> CubicCongestionController {
+    CubicCongestionController::new(MINIMUM_MTU)
+}
+
+fn bbr() -> BbrCongestionController {
+    BbrCongestionController::new(MINIMUM_MTU)
+}
+
+#[test]
+fn fairness_test() {
+    assert_eq!(fairness([]), 1.0);
+    assert_eq!(fairness([100]), 1.0);
+    assert_eq!(fairness([100, 100, 100]), 1.0);
+    assert_eq!(fairness([100, 0]), 0.5);
+    assert_eq!(fairness([100, 0, 0, 0]), 0.25);
+}
+
+#[test]
+fn queue_test() {
+    let time_zero = NoopClock.get_time();
+    let network = Network::new(
+        Bandwidth::new(1000, Duration::from_millis(1)),
+        Duration::from_millis(10),
+    )
+    .with_queue_capacity(2000);
+    let mut queue = Queue::new(&network, time_zero);
+
+    // each packet takes 1ms to serialize and is acknowledged 10ms after it departs
+    assert_eq!(
+        queue.enqueue(time_zero, 1000),
+        Some(time_zero + Duration::from_millis(11))
+    );
+    assert_eq!(
+        queue.enqueue(time_zero, 1000),
+        Some(time_zero + Duration::from_millis(12))
+    );
+    // the queue is full
+    assert_eq!(queue.enqueue(time_zero, 1000), None);
+
+    // the first packet has departed
+    let now = time_zero + Duration::from_millis(1);
+    assert_eq!(
+        queue.enqueue(now, 1000),
+        Some(time_zero + Duration::from_millis(13))
+    );
+
+    assert_eq!(queue.dropped_packets, 1);
+    assert_eq!(queue.delay_stats().max, Duration::from_millis(1));
+}
+
+// These simulations are too slow for Miri
+#[test]
+#[cfg_attr(miri, ignore)]
+fn cubic_single_flow_test() {
+    let simulation = Simulation::new(network()).with_flow(Flow::new(cubic()));
+
+    finish("Cubic Single Flow", simulation);
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn bbr_single_flow_test() {
+    let simulation = Simulation::new(network()).with_flow(Flow::new(bbr()));
+
+    finish("Bbr Single Flow", simulation);
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn cubic_competing_flows_test() {
+    let simulation = Simulation::new(network())
+        .with_flow(Flow::new(cubic()))
+        .with_flow(Flow::new(cubic()).with_start(Duration::from_secs(5)));
+
+    finish("Cubic Competing Flows", simulation);
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn bbr_competing_flows_test() {
+    let simulation = Simulation::new(network())
+        .with_flow(Flow::new(bbr()))
+        .with_flow(Flow::new(bbr()).with_start(Duration::from_secs(5)));
+
+    finish("Bbr Competing Flows", simulation);
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn cubic_vs_bbr_test() {
+    let simulation = Simulation::new(network())
+        .with_flow(Flow::new(cubic()))
+        .with_flow(Flow::new(bbr()));
+
+    finish("Cubic vs Bbr", simulation);
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn cubic_vs_bbr_shallow_buffer_test() {
+    let network = network();
+    let network = network.with_queue_capacity(network.bdp() / 4);
+    let simulation = Simulation::new(network)
+        .with_flow(Flow::new(cubic()))
+        .with_flow(Flow::new(bbr()));
+
+    finish("Cubic vs Bbr Shallow Buffer", simulation);
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn cubic_flow_departure_test() {
+    let simulation = Simulation::new(network())
+        .with_flow(Flow::new(cubic()).with_stop(Duration::from_secs(10)))
+        .with_flow(Flow::new(cubic()).with_start(Duration::from_secs(2)));
+
+    finish("Cubic Flow Departure", simulation);
+}
+
+fn finish(name: &str, simulation: Simulation) {
+    let report = simulation.run();
+    let filename: String = name.split_whitespace().collect();
+
+    if let Ok(dir) = env::var("RECOVERY_SIM_DIR") {
+        let mut path = PathBuf::new();
+        path.push(dir);
+        path.push(&filename);
+        path.set_extension("svg");
+        plot(name, &report, &path);
+    } else {
+        assert_debug_snapshot!(filename, report);
+    }
+}
+
+/// Plots the throughput of each flow over each sample interval
+fn plot(name: &str, report: &Report, path: &PathBuf) {
+    let root_area = SVGBackend::new(path, CHART_DIMENSIONS).into_drawing_area();
+    root_area.fill(&WHITE).expect("Could not fill chart");
+    root_area
+        .titled(name, ("sans-serif", 40))
+        .expect("Could not add title");
+
+    let series: Vec<Vec<(f64, f64)>> = report
+        .flows
+        .iter()
+        .map(|flow| {
+            let mut prev = (Duration::ZERO, 0);
+            flow.samples
+                .iter()
+                .map(|sample| {
+                    let interval = (sample.time - prev.0).as_secs_f64();
+                    let bytes = sample.delivered_bytes - prev.1;
+                    prev = (sample.time, sample.delivered_bytes);
+                    let mbps = bytes as f64 * 8.0 / interval / 1_000_000.0;
+                    (sample.time.as_secs_f64(), mbps)
+                })
+                .collect()
+        })
+        .collect();
+
+    let max_x = series.iter().flatten().map(|(x, _)| *x).fold(0.0, f64::max);
+    let max_y = series
+        .iter()
+        .flatten()
+        .map(|(_, y)| *y)
+        .fold(0.0, f64::max)
+        // Add a 5% buffer
+        * 1.05;
+
+    let mut ctx = ChartBuilder::on(&root_area)
+        .set_label_area_size(LabelAreaPosition::Left, 120)
+        .set_label_area_size(LabelAreaPosition::Bottom, 60)
+        .margin(20)
+        .margin_top(40)
+        .build_cartesian_2d(0.0..max_x, 0.0..max_y)
+        .expect("Could not build chart");
+
+    ctx.configure_mesh()
+        .x_desc("Time (s)")
+        .label_style(("sans-serif", 20))
+        .y_desc("Throughput (Mbps)")
+        .draw()
+        .expect("Could not configure mesh");
+
+    for (idx, (flow, points)) in report.flows.iter().zip(series).enumerate() {
+        let color = Palette99::pick(idx).to_rgba();
+        ctx.draw_series(LineSeries::new(points, color.stroke_width(2)))
+            .expect("Could not draw series")
+            .label(flow.name)
+            .legend(move |(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], color));
+    }
+
+    ctx.configure_series_labels()
+        .background_style(WHITE)
+        .border_style(BLACK)
+        .draw()
+        .expect("Could not draw labels");
+}
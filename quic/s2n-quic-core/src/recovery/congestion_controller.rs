@@ -17,7 +17,7 @@ use crate::{
     },
     time::Timestamp,
 };
-use core::fmt::Debug;
+use core::{fmt::Debug, time::Duration};
 use num_rational::Ratio;
 use num_traits::ToPrimitive;
 
@@ -46,6 +46,61 @@ impl<'a> PathInfo<'a> {
     }
 }
 
+/// The rate at which the application expects to send data on a connection
+///
+/// Congestion controllers normally infer whether a connection is application-limited
+/// from the state of the send buffers at the time a packet is transmitted. Applications
+/// that know ahead of time they will be idle, or will send at a bounded rate (e.g. a
+/// video stream producing frames at a fixed interval), can declare it so the congestion
+/// controller does not grow the congestion window or probe for bandwidth the application
+/// will not use.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ExpectedSendRate {
+    /// The application has not declared an expected send rate
+    Unspecified,
+    /// The application does not expect to send any data
+    Idle,
+    /// The application expects to send at most `bytes_per_second`
+    Limited { bytes_per_second: u64 },
+}
+
+impl Default for ExpectedSendRate {
+    #[inline]
+    fn default() -> Self {
+        Self::Unspecified
+    }
+}
+
+impl ExpectedSendRate {
+    /// Returns `true` if the expected send rate can be satisfied without using
+    /// the entire `congestion_window`
+    #[inline]
+    pub fn is_app_limited(&self, congestion_window: u32, rtt_estimator: &RttEstimator) -> bool {
+        match self {
+            Self::Unspecified => false,
+            Self::Idle => true,
+            Self::Limited { bytes_per_second } => {
+                let rate = Bandwidth::new(*bytes_per_second, Duration::from_secs(1));
+                rate * rtt_estimator.smoothed_rtt() < congestion_window as u64
+            }
+        }
+    }
+}
+
+impl IntoEvent<event::builder::ExpectedSendRate> for ExpectedSendRate {
+    #[inline]
+    fn into_event(self) -> event::builder::ExpectedSendRate {
+        match self {
+            Self::Unspecified => event::builder::ExpectedSendRate::Unspecified,
+            Self::Idle => event::builder::ExpectedSendRate::Idle,
+            Self::Limited { bytes_per_second } => {
+                event::builder::ExpectedSendRate::Limited { bytes_per_second }
+            }
+        }
+    }
+}
+
 pub trait Publisher {
     /// Invoked when the congestion controller has exited the Slow Start phase
     fn on_slow_start_exited(&mut self, cause: SlowStartExitCause, congestion_window: u32);
@@ -241,6 +296,15 @@ pub trait CongestionController: 'static + Clone + Send + Debug {
     fn send_quantum(&self) -> Option<usize> {
         None
     }
+
+    /// Invoked when the application declares the rate at which it expects to send data
+    ///
+    /// Congestion controllers may use the `expected_send_rate` in addition to the `app_limited`
+    /// value provided in `on_packet_sent` to determine if the connection is application-limited.
+    #[inline]
+    fn on_expected_send_rate(&mut self, expected_send_rate: ExpectedSendRate) {
+        let _ = expected_send_rate;
+    }
 }
 
 #[cfg(any(test, feature = "testing"))]
@@ -395,6 +459,7 @@ pub mod testing {
             pub loss_bursts: u32,
             pub app_limited: Option<bool>,
             pub slow_start: bool,
+            pub expected_send_rate: ExpectedSendRate,
         }
 
         impl Default for CongestionController {
@@ -413,6 +478,7 @@ pub mod testing {
                     loss_bursts: 0,
                     app_limited: None,
                     slow_start: true,
+                    expected_send_rate: ExpectedSendRate::Unspecified,
                 }
             }
         }
@@ -519,6 +585,10 @@ pub mod testing {
             fn earliest_departure_time(&self) -> Option<Timestamp> {
                 None
             }
+
+            fn on_expected_send_rate(&mut self, expected_send_rate: ExpectedSendRate) {
+                self.expected_send_rate = expected_send_rate;
+            }
         }
     }
 }
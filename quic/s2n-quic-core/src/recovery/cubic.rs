@@ -6,9 +6,9 @@ use crate::{
     event::builder::SlowStartExitCause,
     random,
     recovery::{
-        congestion_controller::{self, CongestionController, Publisher},
+        congestion_controller::{self, CongestionController, ExpectedSendRate, Publisher},
         cubic::{FastRetransmission::*, State::*},
-        hybrid_slow_start::HybridSlowStart,
+        hybrid_slow_start::{self, HybridSlowStart},
         pacing::Pacer,
         RttEstimator,
     },
@@ -167,6 +167,8 @@ pub struct CubicCongestionController {
     bytes_in_flight: BytesInFlight,
     time_of_last_sent_packet: Option<Timestamp>,
     under_utilized: bool,
+    // The rate at which the application has declared it expects to send
+    expected_send_rate: ExpectedSendRate,
     // The highest number of bytes in flight seen when an ACK was received,
     // since the last congestion event.
     bytes_in_flight_hi: BytesInFlight,
@@ -225,6 +227,13 @@ impl CongestionController for CubicCongestionController {
             // returns true if there are more than 3 MTU's of space left in the cwnd, or less than
             // half the cwnd is utilized in slow start.
             self.under_utilized = app_limited && self.is_congestion_window_under_utilized();
+
+            // If the application has declared it does not need the full congestion window
+            // to achieve its expected send rate, growing the window further would only
+            // inflate it beyond what the application will use.
+            self.under_utilized |= self
+                .expected_send_rate
+                .is_app_limited(self.congestion_window(), rtt_estimator);
         } else {
             // We don't externally determine `app_limited` in the Initial and Handshake packet
             // spaces, so set under_utilized based on is_congestion_window_under_utilized alone
@@ -260,7 +269,7 @@ impl CongestionController for CubicCongestionController {
     ) {
         // Update the Slow Start algorithm each time the RTT
         // estimate is updated to find the slow start threshold.
-        self.slow_start.on_rtt_update(
+        let exit_cause = self.slow_start.on_rtt_update(
             self.congestion_window,
             time_sent,
             self.time_of_last_sent_packet
@@ -269,7 +278,10 @@ impl CongestionController for CubicCongestionController {
         );
 
         if self.state.is_slow_start() && self.congestion_window >= self.slow_start.threshold {
-            publisher.on_slow_start_exited(SlowStartExitCause::Rtt, self.congestion_window());
+            // If the threshold wasn't set by this update, the congestion window has exceeded
+            // a previously determined slow start threshold
+            let cause = exit_cause.unwrap_or(SlowStartExitCause::Other);
+            publisher.on_slow_start_exited(cause, self.congestion_window());
             //= https://www.rfc-editor.org/rfc/rfc8312#section-4.8
             //# In the case when CUBIC runs the hybrid slow start [HR08], it may exit
             //# the first slow start without incurring any packet loss and thus W_max
@@ -491,15 +503,29 @@ impl CongestionController for CubicCongestionController {
     fn earliest_departure_time(&self) -> Option<Timestamp> {
         self.pacer.earliest_departure_time()
     }
+
+    #[inline]
+    fn on_expected_send_rate(&mut self, expected_send_rate: ExpectedSendRate) {
+        self.expected_send_rate = expected_send_rate;
+    }
 }
 
 impl CubicCongestionController {
     // max_datagram_size is the current max_datagram_size, and is
     // expected to be 1200 when the congestion controller is created.
     pub fn new(max_datagram_size: u16) -> Self {
+        Self::with_slow_start(max_datagram_size, Default::default())
+    }
+
+    /// Creates a `CubicCongestionController` using the given algorithm to exit the initial
+    /// slow start
+    pub fn with_slow_start(
+        max_datagram_size: u16,
+        slow_start: hybrid_slow_start::SlowStart,
+    ) -> Self {
         Self {
             cubic: Cubic::new(max_datagram_size),
-            slow_start: HybridSlowStart::new(max_datagram_size),
+            slow_start: HybridSlowStart::new(max_datagram_size, slow_start),
             pacer: Pacer::default(),
             max_datagram_size,
             congestion_window: CubicCongestionController::initial_window(max_datagram_size) as f32,
@@ -507,6 +533,7 @@ impl CubicCongestionController {
             bytes_in_flight: Counter::new(0),
             time_of_last_sent_packet: None,
             under_utilized: true,
+            expected_send_rate: ExpectedSendRate::Unspecified,
             bytes_in_flight_hi: Counter::new(0),
         }
     }
@@ -864,7 +891,20 @@ impl Cubic {
 
 #[non_exhaustive]
 #[derive(Debug, Default)]
-pub struct Endpoint {}
+pub struct Endpoint {
+    slow_start: hybrid_slow_start::SlowStart,
+}
+
+impl Endpoint {
+    /// Sets the algorithm used to exit the initial slow start
+    ///
+    /// Defaults to `SlowStart::HybridSlowStart`, or `SlowStart::HyStartPlusPlus` if the
+    /// `S2N_UNSTABLE_USE_HYSTART_PP` environment variable is set.
+    pub fn with_slow_start(mut self, slow_start: hybrid_slow_start::SlowStart) -> Self {
+        self.slow_start = slow_start;
+        self
+    }
+}
 
 impl congestion_controller::Endpoint for Endpoint {
     type CongestionController = CubicCongestionController;
@@ -873,7 +913,7 @@ impl congestion_controller::Endpoint for Endpoint {
         &mut self,
         path_info: congestion_controller::PathInfo,
     ) -> Self::CongestionController {
-        CubicCongestionController::new(path_info.max_datagram_size)
+        CubicCongestionController::with_slow_start(path_info.max_datagram_size, self.slow_start)
     }
 }
 
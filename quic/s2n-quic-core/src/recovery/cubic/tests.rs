@@ -288,6 +288,56 @@ fn on_packet_sent() {
     assert_delta!(cc.slow_start.threshold, 100_000.0, 0.001);
 }
 
+#[test]
+fn on_rtt_update_hystart_plus_plus() {
+    let mut cc = CubicCongestionController::with_slow_start(
+        1000,
+        hybrid_slow_start::SlowStart::HyStartPlusPlus,
+    );
+    let mut publisher = event::testing::Publisher::snapshot();
+    let mut publisher = PathPublisher::new(&mut publisher, path::Id::test_id());
+    let mut rtt_estimator = RttEstimator::default();
+    let now = NoopClock.get_time();
+
+    cc.congestion_window = 100_000.0;
+
+    // The latest RTT increases from 100ms to 130ms after the first round and remains there
+    for round in 0..=6 {
+        let latest_rtt = if round == 0 { 100 } else { 130 };
+        rtt_estimator.update_rtt(
+            Duration::from_millis(0),
+            Duration::from_millis(latest_rtt),
+            now,
+            true,
+            PacketNumberSpace::ApplicationData,
+        );
+
+        let round_start = now + Duration::from_secs(round);
+        cc.on_packet_sent(
+            round_start + Duration::from_millis(1),
+            1,
+            None,
+            &rtt_estimator,
+            &mut publisher,
+        );
+
+        assert!(cc.state.is_slow_start());
+
+        for _ in 0..8 {
+            cc.on_rtt_update(round_start, round_start, &rtt_estimator, &mut publisher);
+        }
+
+        if round >= 1 {
+            // Conservative Slow Start was entered in the second round
+            assert!(cc.slow_start.is_conservative_slow_start() || round == 6);
+        }
+    }
+
+    // The 5th round of Conservative Slow Start completed, so slow start was exited
+    assert!(!cc.state.is_slow_start());
+    assert_delta!(cc.slow_start.threshold, 100_000.0, 0.001);
+}
+
 #[test]
 fn on_packet_sent_application_limited() {
     let mut cc = CubicCongestionController::new(1000);
@@ -957,6 +1007,55 @@ fn on_packet_ack_utilized_then_under_utilized() {
     assert_eq!(cc.congestion_window(), cwnd);
 }
 
+#[test]
+fn on_packet_ack_expected_send_rate() {
+    let mut cc = CubicCongestionController::new(5000);
+    let mut publisher = event::testing::Publisher::snapshot();
+    let mut publisher = PathPublisher::new(&mut publisher, path::Id::test_id());
+    let now = NoopClock.get_time();
+    let mut rtt_estimator = RttEstimator::default();
+    let random = &mut random::testing::Generator::default();
+    rtt_estimator.update_rtt(
+        Duration::from_secs(0),
+        Duration::from_millis(200),
+        now,
+        true,
+        PacketNumberSpace::ApplicationData,
+    );
+    cc.congestion_window = 100_000.0;
+    cc.state = SlowStart;
+
+    // The application declares it expects to send 100KB/s, which only requires
+    // 100KB/s * 200ms = 20KB of the congestion window
+    cc.on_expected_send_rate(ExpectedSendRate::Limited {
+        bytes_per_second: 100_000,
+    });
+
+    // Even though the application was not app limited at the time of sending,
+    // the window does not grow beyond what the declared rate requires
+    cc.on_packet_sent(now, 60_000, Some(false), &rtt_estimator, &mut publisher);
+    assert!(cc.under_utilized);
+    cc.on_ack(now, 10_000, (), &rtt_estimator, random, now, &mut publisher);
+    assert_eq!(cc.congestion_window(), 100_000);
+
+    // The application declares it expects to send 1MB/s, which requires a 200KB window
+    cc.on_expected_send_rate(ExpectedSendRate::Limited {
+        bytes_per_second: 1_000_000,
+    });
+    cc.on_packet_sent(now, 10_000, Some(false), &rtt_estimator, &mut publisher);
+    assert!(!cc.under_utilized);
+    cc.on_ack(now, 10_000, (), &rtt_estimator, random, now, &mut publisher);
+    let cwnd = cc.congestion_window();
+    assert!(cwnd > 100_000);
+
+    // The application declares it will be idle
+    cc.on_expected_send_rate(ExpectedSendRate::Idle);
+    cc.on_packet_sent(now, 10_000, Some(false), &rtt_estimator, &mut publisher);
+    assert!(cc.under_utilized);
+    cc.on_ack(now, 10_000, (), &rtt_estimator, random, now, &mut publisher);
+    assert_eq!(cc.congestion_window(), cwnd);
+}
+
 #[test]
 fn on_packet_ack_congestion_avoidance_max_cwnd() {
     let mut cc = CubicCongestionController::new(5000);
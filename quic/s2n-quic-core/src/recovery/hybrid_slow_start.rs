@@ -1,29 +1,56 @@
 // Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::time::Timestamp;
+use crate::{event::builder::SlowStartExitCause, time::Timestamp};
 use core::time::Duration;
-#[cfg(not(feature = "std"))]
-use num_traits::Float as _;
+
+/// The algorithm used to exit the initial slow start before congestion is experienced
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SlowStart {
+    /// Hybrid Slow Start, which exits slow start as soon as an increase in the
+    /// round trip time is detected
+    HybridSlowStart,
+    /// HyStart++, as specified in [RFC 9406](https://www.rfc-editor.org/rfc/rfc9406), which
+    /// enters Conservative Slow Start when an increase in the round trip time is detected
+    /// and only exits slow start if the increase persists for several rounds
+    HyStartPlusPlus,
+}
+
+impl Default for SlowStart {
+    /// Defaults to `HybridSlowStart`, unless the `S2N_UNSTABLE_USE_HYSTART_PP`
+    /// environment variable is set
+    fn default() -> Self {
+        if use_hystart_parameter() {
+            Self::HyStartPlusPlus
+        } else {
+            Self::HybridSlowStart
+        }
+    }
+}
 
 /// An implementation of the Hybrid Slow Start algorithm described in
 /// "Hybrid Slow Start for High-Bandwidth and Long-Distance Networks"
 /// https://pdfs.semanticscholar.org/25e9/ef3f03315782c7f1cbcd31b587857adae7d1.pdf
 /// Most of the constants have been updated since this paper. This constants used in
 /// this implementation are based on https://github.com/torvalds/linux/blob/net/ipv4/tcp_cubic.c
+///
+/// When configured with `SlowStart::HyStartPlusPlus`, the HyStart++ algorithm described in
+/// RFC 9406 is used instead.
 #[derive(Clone, Debug)]
 pub struct HybridSlowStart {
+    algorithm: SlowStart,
     sample_count: usize,
     last_min_rtt: Option<Duration>,
     cur_min_rtt: Option<Duration>,
     pub(super) threshold: f32,
     max_datagram_size: u16,
     rtt_round_end_time: Option<Timestamp>,
-    use_hystart_plus_plus: bool,
-    ss_growth_divisor: f32,
-    css_count: usize,
-    css_baseline_min_rtt: Duration,
-    css_threshold: f32,
+    /// The minimum RTT of the round in which Conservative Slow Start was entered,
+    /// or `None` if not in Conservative Slow Start
+    css_baseline_min_rtt: Option<Duration>,
+    /// The number of rounds completed in Conservative Slow Start
+    css_rounds: usize,
 }
 
 /// Minimum slow start threshold in multiples of the max_datagram_size.
@@ -59,6 +86,9 @@ const MAX_DELAY_THRESHOLD: Duration = Duration::from_millis(16);
 const CSS_GROWTH_DIVISOR: f32 = 4.0;
 /// Maximum rounds for CSS phase
 const CSS_ROUNDS: usize = 5;
+/// Maximum congestion window increase per acknowledgement for HyStart++, in multiples of
+/// the max_datagram_size. RFC 9406 recommends a limit of 8 for connections that aren't paced.
+const CWND_INCREMENT_LIMIT: f32 = 8.0;
 /// environment variable for using hystart++
 #[cfg(feature = "std")]
 const USE_HYSTART_PLUS_PLUS: &str = "S2N_UNSTABLE_USE_HYSTART_PP";
@@ -66,8 +96,9 @@ const USE_HYSTART_PLUS_PLUS: &str = "S2N_UNSTABLE_USE_HYSTART_PP";
 impl HybridSlowStart {
     /// Constructs a new `HybridSlowStart`. `max_datagram_size` is used for determining
     /// the minimum slow start threshold.
-    pub fn new(max_datagram_size: u16) -> Self {
+    pub fn new(max_datagram_size: u16, algorithm: SlowStart) -> Self {
         Self {
+            algorithm,
             sample_count: 0,
             last_min_rtt: None,
             cur_min_rtt: None,
@@ -77,11 +108,8 @@ impl HybridSlowStart {
             threshold: f32::MAX,
             max_datagram_size,
             rtt_round_end_time: None,
-            use_hystart_plus_plus: Self::use_hystart_parameter(),
-            ss_growth_divisor: 1.0,
-            css_count: 0,
-            css_baseline_min_rtt: Duration::ZERO,
-            css_threshold: f32::MAX,
+            css_baseline_min_rtt: None,
+            css_rounds: 0,
         }
     }
 
@@ -90,22 +118,26 @@ impl HybridSlowStart {
     /// a number of samples has increased since the last
     /// round of samples and if so will set the slow start
     /// threshold.
+    ///
+    /// Returns the reason slow start should be exited if the
+    /// slow start threshold was set by this update.
     pub fn on_rtt_update(
         &mut self,
         congestion_window: f32,
         time_sent: Timestamp,
         time_of_last_sent_packet: Timestamp,
         rtt: Duration,
-    ) {
+    ) -> Option<SlowStartExitCause> {
         let ss_threshold_found = self.threshold < f32::MAX;
-        if congestion_window >= self.threshold || (self.use_hystart_plus_plus && ss_threshold_found)
-        {
+        let use_hystart_plus_plus = self.algorithm == SlowStart::HyStartPlusPlus;
+
+        if congestion_window >= self.threshold || (use_hystart_plus_plus && ss_threshold_found) {
             //= https://tools.ietf.org/id/draft-ietf-tcpm-hystartplusplus-04.txt#section-4.3
             //# An implementation SHOULD use HyStart++ only for the initial slow
             //# start (when ssthresh is at its initial value of arbitrarily high per
             //# [RFC5681]) and fall back to using traditional slow start for the
             //# remainder of the connection lifetime.
-            return;
+            return None;
         }
 
         // An RTT round is over when a packet that was sent after the packet that
@@ -115,6 +147,21 @@ impl HybridSlowStart {
             .map_or(true, |end_time| time_sent >= end_time);
 
         if rtt_round_is_over {
+            if self.css_baseline_min_rtt.is_some() {
+                // If the transition into CSS happened in the middle of a round, that
+                // partial round counts towards the limit (RFC 9406 Section 4.2)
+                self.css_rounds += 1;
+
+                if self.css_rounds >= CSS_ROUNDS {
+                    // The delay increase persisted for CSS_ROUNDS rounds so enter
+                    // congestion avoidance
+                    self.threshold = congestion_window;
+                    self.css_baseline_min_rtt = None;
+                    self.css_rounds = 0;
+                    return Some(SlowStartExitCause::ConservativeSlowStart);
+                }
+            }
+
             // Start a new round and save the previous min RTT
             self.last_min_rtt = self.cur_min_rtt;
             self.cur_min_rtt = None;
@@ -124,61 +171,87 @@ impl HybridSlowStart {
             self.rtt_round_end_time = Some(time_of_last_sent_packet);
         }
 
-        if self.sample_count < N_SAMPLING {
+        // HyStart++ tracks the minimum over every sample in the round, while Hybrid
+        // Slow Start only considers the first N_SAMPLING samples
+        if use_hystart_plus_plus || self.sample_count < N_SAMPLING {
             // Sample the delay, saving the minimum
             self.cur_min_rtt = Some(rtt.min(self.cur_min_rtt.unwrap_or(rtt)));
         }
 
         self.sample_count += 1;
 
+        let (last_min_rtt, cur_min_rtt) = match (self.last_min_rtt, self.cur_min_rtt) {
+            (Some(last_min_rtt), Some(cur_min_rtt)) => (last_min_rtt, cur_min_rtt),
+            _ => return None,
+        };
+
+        if use_hystart_plus_plus {
+            if self.sample_count < N_SAMPLING {
+                return None;
+            }
+
+            if let Some(css_baseline_min_rtt) = self.css_baseline_min_rtt {
+                if cur_min_rtt < css_baseline_min_rtt {
+                    // The min RTT dropped below the baseline, indicating the exit from
+                    // slow start was spurious, so resume slow start
+                    self.css_baseline_min_rtt = None;
+                    self.css_rounds = 0;
+                }
+            } else if self.delay_increase_is_over_threshold(last_min_rtt, cur_min_rtt) {
+                // Exit slow start and enter Conservative Slow Start
+                self.css_baseline_min_rtt = Some(cur_min_rtt);
+                self.css_rounds = 0;
+            }
+
+            return None;
+        }
+
         // We've gathered enough samples and there have been at least 2 RTT rounds
         // to compare, so check if the delay has increased between the rounds
-        if let (N_SAMPLING, Some(last_min_rtt), Some(cur_min_rtt)) =
-            (self.sample_count, self.last_min_rtt, self.cur_min_rtt)
+        if self.sample_count == N_SAMPLING
+            && self.delay_increase_is_over_threshold(last_min_rtt, cur_min_rtt)
+            && congestion_window >= self.low_ssthresh()
         {
-            if congestion_window >= self.css_threshold {
-                self.css_count += 1;
-                if cur_min_rtt < self.css_baseline_min_rtt {
-                    // resume slow start
-                    self.css_threshold = self.threshold;
-                    self.ss_growth_divisor = 1.0;
-                    self.css_count = 0;
-                }
-                if self.css_count >= CSS_ROUNDS {
-                    // exit slow start phase
-                    self.threshold = congestion_window;
-                    self.css_threshold = f32::MAX;
-                    self.ss_growth_divisor = 1.0;
-                }
-            } else {
-                let threshold = last_min_rtt / THRESHOLD_DIVIDEND;
-                // Clamp n to the min and max thresholds
-                let threshold = threshold.min(MAX_DELAY_THRESHOLD).max(MIN_DELAY_THRESHOLD);
-                let delay_increase_is_over_threshold = cur_min_rtt >= last_min_rtt + threshold;
-                let congestion_window_is_above_minimum = congestion_window >= self.low_ssthresh();
-
-                if self.use_hystart_plus_plus {
-                    // if delay is beyond threshold, go into css phase
-                    if delay_increase_is_over_threshold {
-                        self.css_threshold = congestion_window;
-                        self.css_baseline_min_rtt = cur_min_rtt;
-                        self.ss_growth_divisor = CSS_GROWTH_DIVISOR;
-                        self.css_count = 0;
-                    }
-                } else if delay_increase_is_over_threshold && congestion_window_is_above_minimum {
-                    self.threshold = congestion_window;
-                }
-            }
+            self.threshold = congestion_window;
+            return Some(SlowStartExitCause::Rtt);
         }
+
+        None
+    }
+
+    /// Returns true if Conservative Slow Start has been entered
+    pub fn is_conservative_slow_start(&self) -> bool {
+        self.css_baseline_min_rtt.is_some()
     }
 
     /// return cwnd increment during slow start phase
     /// should be called from on_packet_ack
     pub fn cwnd_increment(&self, sent_bytes: usize) -> f32 {
-        if cfg!(debug_assertions) && !self.use_hystart_plus_plus {
-            assert!((self.ss_growth_divisor - 1.0).abs() < f32::EPSILON);
+        let mut increment = sent_bytes as f32;
+
+        if self.algorithm == SlowStart::HyStartPlusPlus {
+            //= https://tools.ietf.org/id/draft-ietf-tcpm-hystartplusplus-04.txt#section-4.2
+            //# For each arriving ACK in slow start, where N is the number of
+            //# previously unacknowledged bytes acknowledged in the arriving ACK:
+            //#
+            //#    Update the cwnd
+            //#
+            //#    -  cwnd = cwnd + min (N, L * SMSS)
+            increment = increment.min(CWND_INCREMENT_LIMIT * self.max_datagram_size as f32);
         }
-        (sent_bytes as f32) / self.ss_growth_divisor
+
+        if self.is_conservative_slow_start() {
+            //= https://tools.ietf.org/id/draft-ietf-tcpm-hystartplusplus-04.txt#section-4.2
+            //# For each arriving ACK in CSS, where N is the number of previously
+            //# unacknowledged bytes acknowledged in the arriving ACK:
+            //#
+            //#    Update the cwnd
+            //#
+            //#    -  cwnd = cwnd + (min (N, L * SMSS) / CSS_GROWTH_DIVISOR)
+            increment /= CSS_GROWTH_DIVISOR;
+        }
+
+        increment
     }
 
     /// Called when a congestion event is experienced. Sets the
@@ -187,39 +260,51 @@ impl HybridSlowStart {
     /// early enough to avoid further congestion.
     pub fn on_congestion_event(&mut self, ssthresh: f32) {
         self.threshold = self.threshold.min(ssthresh).max(self.low_ssthresh());
-        self.ss_growth_divisor = 1.0;
-        self.css_threshold = f32::MAX;
+        self.css_baseline_min_rtt = None;
+        self.css_rounds = 0;
+    }
+
+    fn delay_increase_is_over_threshold(
+        &self,
+        last_min_rtt: Duration,
+        cur_min_rtt: Duration,
+    ) -> bool {
+        let threshold = last_min_rtt / THRESHOLD_DIVIDEND;
+        // Clamp n to the min and max thresholds
+        let threshold = threshold.min(MAX_DELAY_THRESHOLD).max(MIN_DELAY_THRESHOLD);
+        cur_min_rtt >= last_min_rtt + threshold
     }
 
     fn low_ssthresh(&self) -> f32 {
         LOW_SSTHRESH * self.max_datagram_size as f32
     }
+}
 
-    #[cfg(feature = "std")]
-    fn use_hystart_parameter() -> bool {
-        use once_cell::sync::OnceCell;
-        static USE_HYSTART_PP: OnceCell<bool> = OnceCell::new();
-        *USE_HYSTART_PP.get_or_init(|| std::env::var(USE_HYSTART_PLUS_PLUS).is_ok())
-    }
+#[cfg(feature = "std")]
+fn use_hystart_parameter() -> bool {
+    use once_cell::sync::OnceCell;
+    static USE_HYSTART_PP: OnceCell<bool> = OnceCell::new();
+    *USE_HYSTART_PP.get_or_init(|| std::env::var(USE_HYSTART_PLUS_PLUS).is_ok())
+}
 
-    #[cfg(not(feature = "std"))]
-    fn use_hystart_parameter() -> bool {
-        false
-    }
+#[cfg(not(feature = "std"))]
+fn use_hystart_parameter() -> bool {
+    false
 }
 
 #[cfg(test)]
 mod test {
     use crate::{
         assert_delta,
-        recovery::hybrid_slow_start::HybridSlowStart,
+        event::builder::SlowStartExitCause,
+        recovery::hybrid_slow_start::{HybridSlowStart, SlowStart},
         time::{Clock, NoopClock},
     };
     use core::time::Duration;
 
     #[test]
     fn on_congestion_event() {
-        let mut slow_start = HybridSlowStart::new(10);
+        let mut slow_start = HybridSlowStart::new(10, SlowStart::HybridSlowStart);
         slow_start.threshold = 501.0;
 
         // Setting a threshold lower than the current threshold
@@ -244,7 +329,7 @@ mod test {
 
     #[test]
     fn on_rtt_update_above_threshold() {
-        let mut slow_start = HybridSlowStart::new(10);
+        let mut slow_start = HybridSlowStart::new(10, SlowStart::HybridSlowStart);
         let time_zero = NoopClock.get_time();
         slow_start.threshold = 500.0;
 
@@ -257,7 +342,7 @@ mod test {
 
     #[test]
     fn on_rtt_update() {
-        let mut slow_start = HybridSlowStart::new(10);
+        let mut slow_start = HybridSlowStart::new(10, SlowStart::HybridSlowStart);
 
         assert_eq!(slow_start.sample_count, 0);
 
@@ -385,246 +470,168 @@ mod test {
         assert_delta!(slow_start.threshold, 5000.0, 0.001);
     }
 
-    #[test]
-    fn on_rtt_update_with_hystartplus_1() {
-        let mut slow_start = HybridSlowStart::new(10);
-        // use hystart++
-        slow_start.use_hystart_plus_plus = true;
-
-        assert_eq!(slow_start.sample_count, 0);
-
+    /// Acknowledges `samples` packets sent in the given round, all with the given RTT
+    fn ack_round(
+        slow_start: &mut HybridSlowStart,
+        round: u32,
+        samples: u32,
+        rtt: Duration,
+        congestion_window: f32,
+    ) -> Option<SlowStartExitCause> {
         let time_zero = NoopClock.get_time() + Duration::from_secs(10);
-
-        // -- Round 1 --
-
-        // t=0-9: Send packet #1-10
-        let time_of_last_sent_packet = time_zero + Duration::from_millis(9);
-
-        // t=10: Acknowledge packets #1-7 all with RTT 200
-        for i in 0..=6 {
-            slow_start.on_rtt_update(
-                1000.0,
-                time_zero + Duration::from_millis(i),
+        let round_start = time_zero + Duration::from_millis(100) * round;
+        // The round ends when the first packet sent in the next round is acknowledged
+        let time_of_last_sent_packet = round_start + Duration::from_millis(100);
+        let mut exit_cause = None;
+
+        for i in 0..samples {
+            let cause = slow_start.on_rtt_update(
+                congestion_window,
+                round_start + Duration::from_millis(i as u64),
                 time_of_last_sent_packet,
-                Duration::from_millis(200),
+                rtt,
             );
-            assert_eq!(slow_start.cur_min_rtt, Some(Duration::from_millis(200)));
+            exit_cause = exit_cause.or(cause);
         }
 
-        // This round will end when packet #10 is acknowledged
-        assert_eq!(
-            slow_start.rtt_round_end_time,
-            Some(time_of_last_sent_packet)
-        );
+        exit_cause
+    }
 
-        // t=11: Acknowledge packet #8 with RTT 100
-        slow_start.on_rtt_update(
-            1000.0,
-            time_zero + Duration::from_millis(7),
-            time_of_last_sent_packet,
-            Duration::from_millis(100),
-        );
+    #[test]
+    fn on_rtt_update_with_hystartplus_min_rtt() {
+        let mut slow_start = HybridSlowStart::new(10, SlowStart::HyStartPlusPlus);
+        let time_zero = NoopClock.get_time() + Duration::from_secs(10);
 
-        // The current minimum should now be 100
-        assert_eq!(slow_start.cur_min_rtt, Some(Duration::from_millis(100)));
+        ack_round(&mut slow_start, 0, 8, Duration::from_millis(200), 1000.0);
 
-        // t=11: Acknowledge packet #9 with RTT 50
+        // Unlike Hybrid Slow Start, HyStart++ tracks the minimum over every sample in the round
         slow_start.on_rtt_update(
             1000.0,
             time_zero + Duration::from_millis(8),
-            time_of_last_sent_packet,
+            time_zero + Duration::from_millis(100),
             Duration::from_millis(50),
         );
+        assert_eq!(slow_start.cur_min_rtt, Some(Duration::from_millis(50)));
+        assert_eq!(slow_start.sample_count, 9);
+    }
 
-        // The current minimum is still 100 because we've already collected N_SAMPLING samples
-        assert_eq!(slow_start.cur_min_rtt, Some(Duration::from_millis(100)));
-
-        // -- Round 2 --
-
-        // t=20-29: Send packet #11-20
-        let time_of_last_sent_packet = time_zero + Duration::from_millis(29);
+    #[test]
+    fn on_rtt_update_with_hystartplus_css_exit() {
+        let mut slow_start = HybridSlowStart::new(10, SlowStart::HyStartPlusPlus);
 
-        // t=30: Acknowledge packet #10 with RTT 400, ending the first round and starting the second
-        slow_start.on_rtt_update(
-            1000.0,
-            time_zero + Duration::from_millis(9),
-            time_of_last_sent_packet,
-            Duration::from_millis(400),
-        );
+        // -- Round 0 --
+        assert!(ack_round(&mut slow_start, 0, 8, Duration::from_millis(100), 1000.0).is_none());
+        assert!(!slow_start.is_conservative_slow_start());
 
-        // The last minimum is saved in last_min_rtt
+        // -- Round 1 --
+        // The min rtt increased, but below the threshold of 100ms / 8
+        assert!(ack_round(&mut slow_start, 1, 8, Duration::from_millis(112), 2000.0).is_none());
         assert_eq!(slow_start.last_min_rtt, Some(Duration::from_millis(100)));
-        // The current minimum is now 400 because we've started a new round
-        assert_eq!(slow_start.cur_min_rtt, Some(Duration::from_millis(400)));
-        // This round will end when packet #20 is acknowledged
-        assert_eq!(
-            slow_start.rtt_round_end_time,
-            Some(time_of_last_sent_packet)
-        );
-
-        // t=31: Acknowledge packets #11-16 all with RTT 500
-        for i in 20..=25 {
-            slow_start.on_rtt_update(
-                1000.0,
-                time_zero + Duration::from_millis(i),
-                time_of_last_sent_packet,
-                Duration::from_millis(500),
-            );
-            assert_eq!(slow_start.cur_min_rtt, Some(Duration::from_millis(400)));
-        }
+        assert!(!slow_start.is_conservative_slow_start());
+        assert_delta!(slow_start.cwnd_increment(40), 40.0, 0.001);
 
-        // t=32: Acknowledge packet #17, the 8th sample in Round 2
-        slow_start.on_rtt_update(
-            2000.0,
-            time_zero + Duration::from_millis(27),
-            time_of_last_sent_packet,
-            Duration::from_millis(112),
+        // -- Round 2 --
+        // The min rtt increased above the threshold of 112ms / 8, so enter CSS
+        assert!(ack_round(&mut slow_start, 2, 8, Duration::from_millis(130), 4000.0).is_none());
+        assert!(slow_start.is_conservative_slow_start());
+        assert_eq!(
+            slow_start.css_baseline_min_rtt,
+            Some(Duration::from_millis(130))
         );
-
-        assert_eq!(slow_start.last_min_rtt, Some(Duration::from_millis(100)));
-        assert_eq!(slow_start.cur_min_rtt, Some(Duration::from_millis(112)));
-        // The last ack was the 8th sample, but since the min rtt increased below the threshold,
-        // the slow start threshold remains the same
         assert_delta!(slow_start.threshold, f32::MAX, 0.001);
-
-        // -- Round 3 --
-
-        // t=40-49: Send packet #21-30
-        let time_of_last_sent_packet = time_zero + Duration::from_millis(49);
-
-        // t=50: Acknowledge packets 21-27
-        for i in 40..=46 {
-            slow_start.on_rtt_update(
-                1000.0,
-                time_zero + Duration::from_millis(i),
-                time_of_last_sent_packet,
-                Duration::from_millis(500),
-            );
-            assert_eq!(slow_start.cur_min_rtt, Some(Duration::from_millis(500)));
-        }
-        // t=51: Acknowledge packet 28, the 8th sample in Round 3
-        slow_start.on_rtt_update(
-            5000.0,
-            time_zero + Duration::from_millis(38),
-            time_of_last_sent_packet,
-            Duration::from_millis(126),
-        );
-
-        assert_eq!(slow_start.last_min_rtt, Some(Duration::from_millis(112)));
-        assert_eq!(slow_start.cur_min_rtt, Some(Duration::from_millis(126)));
-        // The last ack was the 8th sample, and since the min rtt increased above the threshold,
-        // the slow start cssthreshold was set to the current congestion window
-        // Also, cssbaseline is cur_min_rtt and ss_growth_divisor is 4.0
-        assert_delta!(slow_start.css_threshold, 5000.0, 0.001);
-        assert_eq!(slow_start.css_baseline_min_rtt, Duration::from_millis(126));
-        assert_delta!(slow_start.ss_growth_divisor, 4.0, 0.001);
-
-        // -- Round 4 --
-        // t=50-59: Send packet #31-40
-        // first round for CSS
-        let time_of_last_sent_packet = time_zero + Duration::from_millis(69);
-
-        // t=60: Acknowledge packets 31-37
-        for i in 60..=66 {
-            slow_start.on_rtt_update(
-                1000.0,
-                time_zero + Duration::from_millis(i),
-                time_of_last_sent_packet,
-                Duration::from_millis(500),
-            );
-            assert_eq!(slow_start.cur_min_rtt, Some(Duration::from_millis(500)));
+        // The congestion window grows more slowly in CSS
+        assert_delta!(slow_start.cwnd_increment(40), 10.0, 0.001);
+
+        // -- Rounds 3 to 6 --
+        // The partial round CSS was entered in counts towards CSS_ROUNDS
+        for round in 3..=6 {
+            assert!(ack_round(
+                &mut slow_start,
+                round,
+                8,
+                Duration::from_millis(130),
+                4000.0
+            )
+            .is_none());
+            assert!(slow_start.is_conservative_slow_start());
+            assert_eq!(slow_start.css_rounds, round as usize - 2);
         }
 
-        // t=51: Acknowledge packet 38, the 8th sample in Round 4
-        slow_start.on_rtt_update(
-            5000.0,
-            time_zero + Duration::from_millis(38),
-            time_of_last_sent_packet,
-            Duration::from_millis(130),
-        );
-
-        let cwnd_increment = slow_start.cwnd_increment(1000);
+        // -- Round 7 --
+        // Starting the round completes the 5th CSS round, exiting slow start
+        let exit_cause = ack_round(&mut slow_start, 7, 1, Duration::from_millis(130), 5000.0);
+        assert!(matches!(
+            exit_cause,
+            Some(SlowStartExitCause::ConservativeSlowStart)
+        ));
+        assert!(!slow_start.is_conservative_slow_start());
+        assert_delta!(slow_start.threshold, 5000.0, 0.001);
+        assert_delta!(slow_start.cwnd_increment(40), 40.0, 0.001);
 
-        assert_delta!(cwnd_increment, 250.0, 0.001);
-        assert_eq!(slow_start.last_min_rtt, Some(Duration::from_millis(126)));
-        assert_eq!(slow_start.css_baseline_min_rtt, Duration::from_millis(126));
-        assert_eq!(slow_start.cur_min_rtt, Some(Duration::from_millis(130)));
-        // because the cur_min_rtt is bigger than css_baseline_min_rtt, CSS phase should continue
-        assert_eq!(slow_start.css_count, 1);
+        // HyStart++ is only used for the initial slow start
+        assert!(ack_round(&mut slow_start, 8, 8, Duration::from_millis(300), 1000.0).is_none());
+        assert_delta!(slow_start.threshold, 5000.0, 0.001);
     }
 
     #[test]
-    fn on_rtt_update_with_hystartplus_2() {
-        let mut slow_start = HybridSlowStart::new(10);
-        // use hystart++
-        slow_start.use_hystart_plus_plus = true;
+    fn on_rtt_update_with_hystartplus_css_resume() {
+        let mut slow_start = HybridSlowStart::new(10, SlowStart::HyStartPlusPlus);
 
-        // emulate Round 1 and Round 2
-        let time_zero = NoopClock.get_time() + Duration::from_secs(10);
-        slow_start.cur_min_rtt = Some(Duration::from_millis(112));
+        ack_round(&mut slow_start, 0, 8, Duration::from_millis(100), 1000.0);
+        ack_round(&mut slow_start, 1, 8, Duration::from_millis(130), 2000.0);
+        assert!(slow_start.is_conservative_slow_start());
 
-        // -- Round 3 --
+        // Fewer than N_RTT_SAMPLE samples have been obtained in this round,
+        // so the min rtt isn't compared to the baseline yet
+        ack_round(&mut slow_start, 2, 7, Duration::from_millis(120), 2000.0);
+        assert!(slow_start.is_conservative_slow_start());
 
-        // t=40-49: Send packet #21-30
-        let time_of_last_sent_packet = time_zero + Duration::from_millis(49);
-
-        // t=50: Acknowledge packets 21-27
-        for i in 40..=46 {
-            slow_start.on_rtt_update(
-                1000.0,
-                time_zero + Duration::from_millis(i),
-                time_of_last_sent_packet,
-                Duration::from_millis(500),
-            );
-            assert_eq!(slow_start.cur_min_rtt, Some(Duration::from_millis(500)));
-        }
-        // t=51: Acknowledge packet 28, the 8th sample in Round 3
+        // The min rtt dropped below the baseline, so the CSS exit was spurious
+        // and slow start resumes
         slow_start.on_rtt_update(
-            5000.0,
-            time_zero + Duration::from_millis(38),
-            time_of_last_sent_packet,
-            Duration::from_millis(126),
+            2000.0,
+            NoopClock.get_time() + Duration::from_secs(10) + Duration::from_millis(207),
+            NoopClock.get_time() + Duration::from_secs(10) + Duration::from_millis(300),
+            Duration::from_millis(120),
         );
+        assert!(!slow_start.is_conservative_slow_start());
+        assert_eq!(slow_start.css_rounds, 0);
+        assert_delta!(slow_start.threshold, f32::MAX, 0.001);
+        assert_delta!(slow_start.cwnd_increment(40), 40.0, 0.001);
+    }
 
-        //lassert_eq!(slow_start.last_min_rtt, Some(Duration::from_millis(112)));
-        assert_eq!(slow_start.cur_min_rtt, Some(Duration::from_millis(126)));
-        // The last ack was the 8th sample, and since the min rtt increased above the threshold,
-        // the slow start cssthreshold was set to the current congestion window
-        // Also, cssbaseline is cur_min_rtt and ss_growth_divisor is 4.0
-        assert_delta!(slow_start.css_threshold, 5000.0, 0.001);
-        assert_eq!(slow_start.css_baseline_min_rtt, Duration::from_millis(126));
-        assert_delta!(slow_start.ss_growth_divisor, 4.0, 0.001);
-
-        // -- Round 4 --
-        // t=50-59: Send packet #31-40
-        // first round for CSS
-        let time_of_last_sent_packet = time_zero + Duration::from_millis(69);
-
-        // t=60: Acknowledge packets 31-37
-        for i in 60..=66 {
-            slow_start.on_rtt_update(
-                1000.0,
-                time_zero + Duration::from_millis(i),
-                time_of_last_sent_packet,
-                Duration::from_millis(500),
-            );
-            assert_eq!(slow_start.cur_min_rtt, Some(Duration::from_millis(500)));
-        }
-
-        // t=51: Acknowledge packet 38, the 8th sample in Round 4
-        slow_start.on_rtt_update(
-            5000.0,
-            time_zero + Duration::from_millis(38),
-            time_of_last_sent_packet,
-            Duration::from_millis(125),
-        );
+    #[test]
+    fn cwnd_increment_with_hystartplus_limit() {
+        // The increase per ACK is only limited with HyStart++
+        let slow_start = HybridSlowStart::new(10, SlowStart::HybridSlowStart);
+        assert_delta!(slow_start.cwnd_increment(1000), 1000.0, 0.001);
+
+        let mut slow_start = HybridSlowStart::new(10, SlowStart::HyStartPlusPlus);
+        assert_delta!(slow_start.cwnd_increment(80), 80.0, 0.001);
+        // The increase is limited to L * max_datagram_size
+        assert_delta!(slow_start.cwnd_increment(1000), 80.0, 0.001);
+
+        ack_round(&mut slow_start, 0, 8, Duration::from_millis(100), 1000.0);
+        ack_round(&mut slow_start, 1, 8, Duration::from_millis(130), 2000.0);
+        assert!(slow_start.is_conservative_slow_start());
+
+        // The limit is applied before dividing by CSS_GROWTH_DIVISOR
+        assert_delta!(slow_start.cwnd_increment(40), 10.0, 0.001);
+        assert_delta!(slow_start.cwnd_increment(1000), 20.0, 0.001);
+    }
 
-        assert_eq!(slow_start.last_min_rtt, Some(Duration::from_millis(126)));
-        assert_eq!(slow_start.css_baseline_min_rtt, Duration::from_millis(126));
-        assert_eq!(slow_start.cur_min_rtt, Some(Duration::from_millis(125)));
-        // because the cur_min_rtt is smaller than css_baseline_min_rtt, should resume slow start
-        assert_delta!(slow_start.ss_growth_divisor, 1.0, 0.001);
-        assert_delta!(slow_start.css_threshold, f32::MAX, 0.001);
-        assert_eq!(slow_start.css_count, 0);
+    #[test]
+    fn on_congestion_event_with_hystartplus() {
+        let mut slow_start = HybridSlowStart::new(10, SlowStart::HyStartPlusPlus);
+
+        ack_round(&mut slow_start, 0, 8, Duration::from_millis(100), 1000.0);
+        ack_round(&mut slow_start, 1, 8, Duration::from_millis(130), 2000.0);
+        assert!(slow_start.is_conservative_slow_start());
+
+        // Loss during CSS exits slow start
+        slow_start.on_congestion_event(1500.0);
+        assert!(!slow_start.is_conservative_slow_start());
+        assert_delta!(slow_start.threshold, 1500.0, 0.001);
+        assert_delta!(slow_start.cwnd_increment(40), 40.0, 0.001);
     }
 }
@@ -1,13 +1,16 @@
 // Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
 // SPDX-License-Identifier: Apache-2.0
 
-pub use congestion_controller::CongestionController;
+pub use congestion_controller::{CongestionController, ExpectedSendRate};
 pub use cubic::CubicCongestionController;
+pub use hybrid_slow_start::SlowStart;
 pub use rtt_estimator::*;
 pub use sent_packets::*;
 
 pub mod bandwidth;
 pub mod bbr;
+#[cfg(any(test, feature = "testing"))]
+pub mod bottleneck;
 pub mod congestion_controller;
 pub mod cubic;
 mod hybrid_slow_start;
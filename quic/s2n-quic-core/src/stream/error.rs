@@ -66,6 +66,19 @@ pub enum StreamError {
     NonEmptyOutput {
         source: &'static panic::Location<'static>,
     },
+    /// The stream was reset with a reliable size but the peer did not negotiate support
+    /// for reliable stream resets
+    #[non_exhaustive]
+    ReliableResetUnsupported {
+        source: &'static panic::Location<'static>,
+    },
+    /// The stream header can't be encoded as a QUIC variable-length integer
+    ///
+    /// Headers must be less than `2^62`.
+    #[non_exhaustive]
+    InvalidHeader {
+        source: &'static panic::Location<'static>,
+    },
 }
 
 #[cfg(feature = "std")]
@@ -98,6 +111,14 @@ impl fmt::Display for StreamError {
                 f,
                 "The stream was provided a non-empty placeholder buffer for receiving data."
             ),
+            Self::ReliableResetUnsupported { .. } => write!(
+                f,
+                "Reliable stream resets were not negotiated for the connection"
+            ),
+            Self::InvalidHeader { .. } => write!(
+                f,
+                "The stream header exceeds the maximum variable-length integer value"
+            ),
         }
     }
 }
@@ -115,6 +136,8 @@ impl StreamError {
             StreamError::NonWritable { source } => source,
             StreamError::SendingBlocked { source } => source,
             StreamError::NonEmptyOutput { source } => source,
+            StreamError::ReliableResetUnsupported { source } => source,
+            StreamError::InvalidHeader { source } => source,
         }
     }
 
@@ -181,6 +204,22 @@ impl StreamError {
         let source = panic::Location::caller();
         StreamError::NonEmptyOutput { source }
     }
+
+    #[track_caller]
+    #[inline]
+    #[doc(hidden)]
+    pub fn reliable_reset_unsupported() -> StreamError {
+        let source = panic::Location::caller();
+        StreamError::ReliableResetUnsupported { source }
+    }
+
+    #[track_caller]
+    #[inline]
+    #[doc(hidden)]
+    pub fn invalid_header() -> StreamError {
+        let source = panic::Location::caller();
+        StreamError::InvalidHeader { source }
+    }
 }
 
 impl application::error::TryInto for StreamError {
@@ -237,6 +276,8 @@ impl From<StreamError> for std::io::ErrorKind {
             StreamError::NonWritable { .. } => ErrorKind::Other,
             StreamError::SendingBlocked { .. } => ErrorKind::WouldBlock,
             StreamError::NonEmptyOutput { .. } => ErrorKind::InvalidInput,
+            StreamError::ReliableResetUnsupported { .. } => ErrorKind::Other,
+            StreamError::InvalidHeader { .. } => ErrorKind::InvalidInput,
         }
     }
 }
@@ -29,7 +29,7 @@
 //! ```
 
 use crate::{application, stream};
-use core::task::Poll;
+use core::{task::Poll, time::Duration};
 
 /// A request made on a stream
 #[derive(Default, Debug)]
@@ -54,6 +54,24 @@ impl<'a> Request<'a> {
         self
     }
 
+    /// Resets the tx stream with an error code, after delivering the first `reliable_size` bytes
+    ///
+    /// This requires both peers to support reliable stream resets.
+    pub fn reset_at(&mut self, error: application::Error, reliable_size: u64) -> &mut Self {
+        let tx = self.tx_mut();
+        tx.reset = Some(error);
+        tx.reliable_size = reliable_size;
+        self
+    }
+
+    /// Sets a deadline for the chunks sent on the tx stream
+    ///
+    /// See [`tx::Deadline`] for more details.
+    pub fn with_deadline(&mut self, lifetime: Duration, error: application::Error) -> &mut Self {
+        self.tx_mut().deadline = Some(tx::Deadline { lifetime, error });
+        self
+    }
+
     /// Flushes any pending tx data to be ACKed before unblocking
     pub fn flush(&mut self) -> &mut Self {
         self.tx_mut().flush = true;
@@ -72,6 +90,14 @@ impl<'a> Request<'a> {
         self
     }
 
+    /// Registers a buffer which data on the rx stream is directly written into
+    ///
+    /// See [`rx::Request::with_buffer`] for more details.
+    pub fn receive_into(&mut self, buffer: &'a mut bytes::BytesMut) -> &mut Self {
+        self.rx_mut().with_buffer(buffer);
+        self
+    }
+
     /// Requests the peer to stop sending data on the rx stream
     pub fn stop_sending(&mut self, error: application::Error) -> &mut Self {
         self.rx_mut().stop_sending = Some(error);
@@ -184,9 +210,16 @@ pub mod tx {
         /// this slice.
         pub chunks: Option<&'a mut [bytes::Bytes]>,
 
+        /// Optionally limits how long the chunks are retransmitted before the stream is reset
+        pub deadline: Option<Deadline>,
+
         /// Optionally reset the stream with an error
         pub reset: Option<application::Error>,
 
+        /// The number of bytes at the start of the stream which are still delivered when the
+        /// stream is reset
+        pub reliable_size: u64,
+
         /// Waits for an ACK on resets and finishes
         pub flush: bool,
 
@@ -198,6 +231,20 @@ pub mod tx {
         pub detached: bool,
     }
 
+    /// Limits the amount of time sent data remains useful to the peer
+    ///
+    /// The `lifetime` starts when the data is first transmitted. If the data is declared lost
+    /// after the lifetime has elapsed, it is not retransmitted and the stream is instead reset
+    /// with the provided `error`.
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    pub struct Deadline {
+        /// The amount of time after the first transmission in which lost data is retransmitted
+        pub lifetime: Duration,
+
+        /// The error used to reset the stream once the deadline has been missed
+        pub error: application::Error,
+    }
+
     /// The result of a tx request
     #[derive(Debug, PartialEq, Eq)]
     pub struct Response {
@@ -253,6 +300,11 @@ pub mod rx {
         /// bytes were consumed from the stream into the provided slice.
         pub chunks: Option<&'a mut [bytes::Bytes]>,
 
+        /// Optionally registers a buffer which received data is written into
+        ///
+        /// See [`Request::with_buffer`] for more details.
+        buffer: Option<&'a mut bytes::BytesMut>,
+
         /// Sets the low watermark for the rx stream
         ///
         /// If the watermark is set to `0`, the caller will be notified as soon as there is data
@@ -276,10 +328,32 @@ pub mod rx {
         pub detached: bool,
     }
 
+    impl<'a> Request<'a> {
+        /// Registers a buffer which received data is written into
+        ///
+        /// The spare capacity of the buffer is used to store the next bytes of the stream, which
+        /// avoids copying them into an internal buffer first. Chunks which are received
+        /// afterwards are views into the registered buffer.
+        ///
+        /// If the buffer was registered it will be replaced with an empty buffer. Otherwise it is
+        /// left untouched, which is the case if data is already buffered at the current offset
+        /// of the stream.
+        pub fn with_buffer(&mut self, buffer: &'a mut bytes::BytesMut) -> &mut Self {
+            self.buffer = Some(buffer);
+            self
+        }
+
+        /// Returns the buffer which was registered with the request, if any
+        pub fn buffer_mut(&mut self) -> Option<&mut bytes::BytesMut> {
+            self.buffer.as_deref_mut()
+        }
+    }
+
     impl<'a> Default for Request<'a> {
         fn default() -> Self {
             Self {
                 chunks: None,
+                buffer: None,
                 low_watermark: 0,
                 high_watermark: core::usize::MAX,
                 stop_sending: None,
@@ -493,13 +567,19 @@ mod tests {
             bytes::Bytes::from_static(&[2]),
             bytes::Bytes::from_static(&[3]),
         ];
+        let mut receive_buffer = bytes::BytesMut::with_capacity(4);
 
         request
             .send(&mut send_chunks)
             .finish()
             .flush()
-            .reset(application::Error::new(1).unwrap())
+            .with_deadline(
+                Duration::from_millis(100),
+                application::Error::new(3).unwrap(),
+            )
+            .reset_at(application::Error::new(1).unwrap(), 7)
             .receive(&mut receive_chunks)
+            .receive_into(&mut receive_buffer)
             .with_watermark(5, 10)
             .stop_sending(application::Error::new(2).unwrap());
 
@@ -510,7 +590,9 @@ mod tests {
                     chunks: Some(tx_chunks),
                     finish: true,
                     flush: true,
+                    deadline: Some(deadline),
                     reset: Some(reset),
+                    reliable_size: 7,
                     detached: false,
                 }),
                 rx: Some(rx::Request {
@@ -519,12 +601,18 @@ mod tests {
                     high_watermark: 10,
                     stop_sending: Some(stop_sending),
                     detached: false,
+                    ..
                 })
             } if reset == application::Error::new(1).unwrap()
+              && deadline.lifetime == Duration::from_millis(100)
+              && deadline.error == application::Error::new(3).unwrap()
               && stop_sending == application::Error::new(2).unwrap()
               && tx_chunks.len() == 1
               && rx_chunks.len() == 2
         ));
+
+        let rx_buffer = request.rx.as_mut().unwrap().buffer_mut().unwrap();
+        assert_eq!(rx_buffer.capacity(), 4);
     }
 
     #[test]
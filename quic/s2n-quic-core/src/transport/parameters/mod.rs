@@ -217,6 +217,12 @@ where
 #[derive(Copy, Clone, Debug, PartialEq)]
 pub struct ValidationError(&'static str);
 
+impl ValidationError {
+    pub(crate) const fn new(reason: &'static str) -> Self {
+        Self(reason)
+    }
+}
+
 const MAX_ENCODABLE_VALUE: ValidationError =
     ValidationError("provided value exceeds maximum encodable value");
 
@@ -893,6 +899,65 @@ impl TransportParameter for MigrationSupport {
 
 impl TransportParameterValidator for MigrationSupport {}
 
+// https://datatracker.ietf.org/doc/html/draft-ietf-quic-reliable-stream-reset-06#section-3
+// reset_stream_at (0x17f7586d2cb571): The reset_stream_at transport parameter
+//    is a zero-length value which indicates that the endpoint supports the
+//    RESET_STREAM_AT frame.
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ResetStreamAtSupport {
+    Enabled,
+    Disabled,
+}
+
+impl ResetStreamAtSupport {
+    #[inline]
+    pub fn is_enabled(self) -> bool {
+        matches!(self, Self::Enabled)
+    }
+}
+
+impl Default for ResetStreamAtSupport {
+    fn default() -> Self {
+        ResetStreamAtSupport::Disabled
+    }
+}
+
+impl From<bool> for ResetStreamAtSupport {
+    fn from(enabled: bool) -> Self {
+        if enabled {
+            Self::Enabled
+        } else {
+            Self::Disabled
+        }
+    }
+}
+
+impl TransportParameter for ResetStreamAtSupport {
+    type CodecValue = ();
+
+    const ID: TransportParameterId =
+        unsafe { TransportParameterId::new_unchecked(0x17f7586d2cb571) };
+
+    fn from_codec_value(_value: ()) -> Self {
+        ResetStreamAtSupport::Enabled
+    }
+
+    fn try_into_codec_value(&self) -> Option<&()> {
+        if let ResetStreamAtSupport::Enabled = self {
+            Some(&())
+        } else {
+            None
+        }
+    }
+
+    fn default_value() -> Self {
+        ResetStreamAtSupport::Disabled
+    }
+}
+
+impl TransportParameterValidator for ResetStreamAtSupport {}
+
 //= https://www.rfc-editor.org/rfc/rfc9000#section-18.2
 //# preferred_address (0x0d):  The server's preferred address is used to
 //#    effect a change in server address at the end of the handshake, as
@@ -1415,6 +1480,7 @@ impl_transport_parameters!(
         initial_max_streams_bidi: InitialMaxStreamsBidi,
         initial_max_streams_uni: InitialMaxStreamsUni,
         max_datagram_frame_size: MaxDatagramFrameSize,
+        reset_stream_at_support: ResetStreamAtSupport,
         ack_delay_exponent: AckDelayExponent,
         max_ack_delay: MaxAckDelay,
         migration_support: MigrationSupport,
@@ -1471,5 +1537,6 @@ impl<
         load!(ack_delay_exponent, ack_delay_exponent);
         load!(max_active_connection_ids, active_connection_id_limit);
         load!(max_datagram_frame_size, max_datagram_frame_size);
+        load!(local_reset_stream_at_support, reset_stream_at_support);
     }
 }
@@ -66,6 +66,7 @@ fn server_transport_parameters() -> ServerTransportParameters {
         initial_max_streams_bidi: integer_value.try_into().unwrap(),
         initial_max_streams_uni: integer_value.try_into().unwrap(),
         max_datagram_frame_size: MaxDatagramFrameSize::new(0u16).unwrap(),
+        reset_stream_at_support: ResetStreamAtSupport::Enabled,
         ack_delay_exponent: 2u8.try_into().unwrap(),
         max_ack_delay: integer_value.try_into().unwrap(),
         migration_support: MigrationSupport::Disabled,
@@ -108,6 +109,7 @@ fn client_transport_parameters() -> ClientTransportParameters {
         initial_max_streams_bidi: integer_value.try_into().unwrap(),
         initial_max_streams_uni: integer_value.try_into().unwrap(),
         max_datagram_frame_size: MaxDatagramFrameSize::new(0u16).unwrap(),
+        reset_stream_at_support: ResetStreamAtSupport::Enabled,
         ack_delay_exponent: 2u8.try_into().unwrap(),
         max_ack_delay: integer_value.try_into().unwrap(),
         migration_support: MigrationSupport::Disabled,
@@ -1,7 +1,8 @@
 // Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
 // SPDX-License-Identifier: Apache-2.0
 
-pub use s2n_quic_core::crypto::CryptoError as Error;
+use core::convert::TryFrom;
+pub use s2n_quic_core::crypto::{BatchPayload, CryptoError as Error};
 pub type Result<T = (), E = Error> = core::result::Result<T, E>;
 
 pub trait Aead {
@@ -23,4 +24,55 @@ pub trait Aead {
         payload: &mut [u8],
         tag: &Self::Tag,
     ) -> Result;
+
+    /// Encrypts a batch of packet payloads, each ending with space for the tag
+    ///
+    /// The nonce for each payload is derived from its packet number with the `nonce` function.
+    #[inline]
+    fn encrypt_batch<F>(&self, payloads: &mut [BatchPayload], mut nonce: F)
+    where
+        Self: Sized,
+        F: FnMut(u64) -> Self::Nonce,
+        for<'a> &'a mut Self::Tag: TryFrom<&'a mut [u8]>,
+    {
+        for entry in payloads {
+            entry.result = split_tag(entry.payload).and_then(|(payload, tag)| {
+                self.encrypt(&nonce(entry.packet_number), entry.header, payload, tag)
+            });
+        }
+    }
+
+    /// Decrypts a batch of packet payloads, each ending with the tag
+    ///
+    /// The nonce for each payload is derived from its packet number with the `nonce` function.
+    #[inline]
+    fn decrypt_batch<F>(&self, payloads: &mut [BatchPayload], mut nonce: F)
+    where
+        Self: Sized,
+        F: FnMut(u64) -> Self::Nonce,
+        for<'a> &'a mut Self::Tag: TryFrom<&'a mut [u8]>,
+    {
+        for entry in payloads {
+            entry.result = split_tag(entry.payload).and_then(|(payload, tag)| {
+                self.decrypt(&nonce(entry.packet_number), entry.header, payload, tag)
+            });
+        }
+    }
+}
+
+/// Splits a packet payload into the ciphertext and the trailing tag
+#[inline(always)]
+pub fn split_tag<'a, Tag>(payload: &'a mut [u8]) -> Result<(&'a mut [u8], &'a mut Tag)>
+where
+    &'a mut Tag: TryFrom<&'a mut [u8]>,
+{
+    let payload_len = payload
+        .len()
+        .checked_sub(core::mem::size_of::<Tag>())
+        .ok_or(Error::DECRYPT_ERROR)?;
+
+    let (payload, tag) = payload.split_at_mut(payload_len);
+    let tag = <&mut Tag>::try_from(tag).map_err(|_| Error::DECRYPT_ERROR)?;
+
+    Ok((payload, tag))
 }
@@ -3,6 +3,8 @@
 
 use crate::block::BatchMut;
 
+#[cfg(target_arch = "aarch64")]
+pub mod aarch64;
 #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
 pub mod x86;
 
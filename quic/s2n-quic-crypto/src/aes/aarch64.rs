@@ -0,0 +1,415 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! AES implementation using the ARMv8 cryptographic extensions
+//!
+//! Unlike AES-NI, the ARMv8 `AESE` and `AESD` instructions perform the AddRoundKey step at the
+//! _start_ of the round, rather than the end. The MixColumns step is also split into a separate
+//! `AESMC`/`AESIMC` instruction. As such, each `KeyRound` operation is shifted to line up with
+//! the AES-NI-shaped interface:
+//!
+//! * `xor` - `AESE(block, k[0])` which also performs SubBytes and ShiftRows for the first round
+//! * `encrypt` - `AESE(AESMC(block), k[i])`
+//! * `encrypt_finish` - `block ^ k[ROUNDS]`
+//!
+//! Because the first step differs between encryption and decryption, the two directions use
+//! separate key round types.
+
+use crate::{
+    aes,
+    arch::*,
+    block::{BatchMut, Block, Zeroed},
+};
+use core::arch::asm;
+use s2n_quic_core::assume;
+use zeroize::{Zeroize, Zeroizing};
+
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
+
+#[derive(Zeroize)]
+pub struct Key<const ROUNDS: usize> {
+    pub encrypt: EncryptionKey<ROUNDS>,
+    pub decrypt: DecryptionKey<ROUNDS>,
+}
+
+impl<const N: usize> super::aes128::EncryptionKey for Key<N> {
+    type Block = uint8x16_t;
+    type KeyRound = EncryptionKeyRound;
+
+    #[inline(always)]
+    fn keyround(&self, index: usize) -> &Self::KeyRound {
+        self.encrypt.keyround(index)
+    }
+}
+
+impl<const N: usize> super::aes128::DecryptionKey for Key<N> {
+    type Block = uint8x16_t;
+    type KeyRound = DecryptionKeyRound;
+
+    #[inline(always)]
+    fn keyround(&self, index: usize) -> &Self::KeyRound {
+        self.decrypt.keyround(index)
+    }
+}
+
+impl<const N: usize> super::aes256::EncryptionKey for Key<N> {
+    type Block = uint8x16_t;
+    type KeyRound = EncryptionKeyRound;
+
+    #[inline(always)]
+    fn keyround(&self, index: usize) -> &Self::KeyRound {
+        self.encrypt.keyround(index)
+    }
+}
+
+impl<const N: usize> super::aes256::DecryptionKey for Key<N> {
+    type Block = uint8x16_t;
+    type KeyRound = DecryptionKeyRound;
+
+    #[inline(always)]
+    fn keyround(&self, index: usize) -> &Self::KeyRound {
+        self.decrypt.keyround(index)
+    }
+}
+
+#[derive(Zeroize)]
+pub struct EncryptionKey<const ROUNDS: usize>([EncryptionKeyRound; ROUNDS]);
+
+impl<const N: usize> super::aes128::EncryptionKey for EncryptionKey<N> {
+    type Block = uint8x16_t;
+    type KeyRound = EncryptionKeyRound;
+
+    #[inline(always)]
+    fn keyround(&self, index: usize) -> &Self::KeyRound {
+        unsafe {
+            assume!(index < N);
+            self.0.get_unchecked(index)
+        }
+    }
+}
+
+impl<const N: usize> super::aes256::EncryptionKey for EncryptionKey<N> {
+    type Block = uint8x16_t;
+    type KeyRound = EncryptionKeyRound;
+
+    #[inline(always)]
+    fn keyround(&self, index: usize) -> &Self::KeyRound {
+        unsafe {
+            assume!(index < N);
+            self.0.get_unchecked(index)
+        }
+    }
+}
+
+#[derive(Zeroize)]
+pub struct DecryptionKey<const ROUNDS: usize>([DecryptionKeyRound; ROUNDS]);
+
+impl<const N: usize> super::aes128::DecryptionKey for DecryptionKey<N> {
+    type Block = uint8x16_t;
+    type KeyRound = DecryptionKeyRound;
+
+    #[inline(always)]
+    fn keyround(&self, index: usize) -> &Self::KeyRound {
+        unsafe {
+            assume!(index < N);
+            self.0.get_unchecked(index)
+        }
+    }
+}
+
+impl<const N: usize> super::aes256::DecryptionKey for DecryptionKey<N> {
+    type Block = uint8x16_t;
+    type KeyRound = DecryptionKeyRound;
+
+    #[inline(always)]
+    fn keyround(&self, index: usize) -> &Self::KeyRound {
+        unsafe {
+            assume!(index < N);
+            self.0.get_unchecked(index)
+        }
+    }
+}
+
+/// The maximum number of 32-bit words in an expanded key (AES-256)
+const MAX_WORDS: usize = 4 * (aes::aes256::ROUNDS + 1);
+
+// https://nvlpubs.nist.gov/nistpubs/FIPS/NIST.FIPS.197-upd1.pdf#page=17
+const RCON: [u32; 10] = [0x01, 0x02, 0x04, 0x08, 0x10, 0x20, 0x40, 0x80, 0x1b, 0x36];
+
+/// Expands the key into `ROUNDS` round keys, following the `KeyExpansion` routine in FIPS 197
+///
+/// There isn't an equivalent to `aeskeygenassist` on ARMv8 so the schedule is computed one word
+/// at a time, using `AESE` to apply the S-box.
+#[inline(always)]
+unsafe fn expand_key<const ROUNDS: usize>(key: &[u8]) -> Key<ROUNDS> {
+    debug_assert!(Neon::is_supported());
+
+    // the number of 32-bit words in the key
+    let nk = key.len() / 4;
+    let word_count = 4 * ROUNDS;
+
+    assume!(nk == 4 || nk == 8);
+    assume!(word_count <= MAX_WORDS);
+
+    let mut words = Zeroizing::new([0u32; MAX_WORDS]);
+
+    for (word, bytes) in words.iter_mut().zip(key.chunks_exact(4)) {
+        *word = u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+    }
+
+    for i in nk..word_count {
+        let mut temp = words[i - 1];
+
+        if i % nk == 0 {
+            // RotWord is a rotation of the bytes, which is a right rotation of the LE word
+            temp = sub_word(temp.rotate_right(8)) ^ RCON[i / nk - 1];
+        } else if nk > 6 && i % nk == 4 {
+            temp = sub_word(temp);
+        }
+
+        words[i] = words[i - nk] ^ temp;
+    }
+
+    let mut enc = [EncryptionKeyRound(uint8x16_t::zeroed()); ROUNDS];
+    let mut dec = [DecryptionKeyRound(uint8x16_t::zeroed()); ROUNDS];
+
+    for (idx, round) in enc.iter_mut().enumerate() {
+        let mut bytes = Zeroizing::new([0u8; 16]);
+        for (chunk, word) in bytes.chunks_exact_mut(4).zip(&words[idx * 4..]) {
+            chunk.copy_from_slice(&word.to_le_bytes());
+        }
+        *round = EncryptionKeyRound(uint8x16_t::from_array(*bytes));
+    }
+
+    // initialize the decryption half using the "equivalent inverse cipher"
+    //
+    // https://nvlpubs.nist.gov/nistpubs/FIPS/NIST.FIPS.197-upd1.pdf#page=25
+    let last = ROUNDS - 1;
+    dec[last] = DecryptionKeyRound(enc[last].0);
+    for idx in 1..last {
+        dec[idx] = DecryptionKeyRound(aesimc(enc[idx].0));
+    }
+    dec[0] = DecryptionKeyRound(enc[0].0);
+
+    Key {
+        encrypt: EncryptionKey(enc),
+        decrypt: DecryptionKey(dec),
+    }
+}
+
+/// Applies the AES S-box to each byte in the word
+#[inline]
+#[target_feature(enable = "neon,aes")]
+unsafe fn sub_word(word: u32) -> u32 {
+    // With the word broadcast to every column, ShiftRows has no effect on the state. Using a
+    // zeroed round key leaves only SubBytes.
+    let state = vreinterpretq_u8_u32(vdupq_n_u32(word));
+    let state = aese(state, uint8x16_t::zeroed());
+    vgetq_lane_u32(vreinterpretq_u32_u8(state), 0)
+}
+
+pub mod aes128 {
+    use super::*;
+    use crate::aes::aes128::KEY_LEN;
+
+    const ROUNDS: usize = aes::aes128::ROUNDS + 1;
+
+    pub type Key = super::Key<ROUNDS>;
+    pub type EncryptionKey = super::EncryptionKey<ROUNDS>;
+
+    impl Key {
+        #[inline(always)]
+        pub fn new(key: [u8; KEY_LEN]) -> Self {
+            unsafe {
+                debug_assert!(Neon::is_supported());
+                expand_key(&key)
+            }
+        }
+    }
+}
+
+pub mod aes256 {
+    use super::*;
+    use crate::aes::aes256::KEY_LEN;
+
+    const ROUNDS: usize = aes::aes256::ROUNDS + 1;
+
+    pub type Key = super::Key<ROUNDS>;
+    pub type EncryptionKey = super::EncryptionKey<ROUNDS>;
+
+    impl Key {
+        #[inline(always)]
+        pub fn new(key: [u8; KEY_LEN]) -> Self {
+            unsafe {
+                debug_assert!(Neon::is_supported());
+                expand_key(&key)
+            }
+        }
+    }
+}
+
+// The AES intrinsics in `core::arch::aarch64` were not stable as of the crate's MSRV so
+// the instructions are emitted with inline assembly instead.
+
+/// SubBytes(ShiftRows(state ^ key))
+#[inline]
+#[target_feature(enable = "neon,aes")]
+unsafe fn aese(mut state: uint8x16_t, key: uint8x16_t) -> uint8x16_t {
+    asm!(
+        "aese {state:v}.16b, {key:v}.16b",
+        state = inout(vreg) state,
+        key = in(vreg) key,
+        options(pure, nomem, nostack, preserves_flags)
+    );
+    state
+}
+
+/// SubBytes(ShiftRows(MixColumns(state) ^ key))
+#[inline]
+#[target_feature(enable = "neon,aes")]
+unsafe fn aesmc_aese(mut state: uint8x16_t, key: uint8x16_t) -> uint8x16_t {
+    asm!(
+        "aesmc {state:v}.16b, {state:v}.16b",
+        "aese {state:v}.16b, {key:v}.16b",
+        state = inout(vreg) state,
+        key = in(vreg) key,
+        options(pure, nomem, nostack, preserves_flags)
+    );
+    state
+}
+
+/// InvSubBytes(InvShiftRows(state ^ key))
+#[inline]
+#[target_feature(enable = "neon,aes")]
+unsafe fn aesd(mut state: uint8x16_t, key: uint8x16_t) -> uint8x16_t {
+    asm!(
+        "aesd {state:v}.16b, {key:v}.16b",
+        state = inout(vreg) state,
+        key = in(vreg) key,
+        options(pure, nomem, nostack, preserves_flags)
+    );
+    state
+}
+
+/// InvSubBytes(InvShiftRows(InvMixColumns(state) ^ key))
+#[inline]
+#[target_feature(enable = "neon,aes")]
+unsafe fn aesimc_aesd(mut state: uint8x16_t, key: uint8x16_t) -> uint8x16_t {
+    asm!(
+        "aesimc {state:v}.16b, {state:v}.16b",
+        "aesd {state:v}.16b, {key:v}.16b",
+        state = inout(vreg) state,
+        key = in(vreg) key,
+        options(pure, nomem, nostack, preserves_flags)
+    );
+    state
+}
+
+/// InvMixColumns(state)
+#[inline]
+#[target_feature(enable = "neon,aes")]
+unsafe fn aesimc(mut state: uint8x16_t) -> uint8x16_t {
+    asm!(
+        "aesimc {state:v}.16b, {state:v}.16b",
+        state = inout(vreg) state,
+        options(pure, nomem, nostack, preserves_flags)
+    );
+    state
+}
+
+#[derive(Clone, Copy)]
+#[repr(transparent)]
+pub struct EncryptionKeyRound(uint8x16_t);
+
+impl Default for EncryptionKeyRound {
+    #[inline(always)]
+    fn default() -> Self {
+        Self(uint8x16_t::zeroed())
+    }
+}
+
+impl zeroize::DefaultIsZeroes for EncryptionKeyRound {}
+
+impl super::KeyRound for EncryptionKeyRound {
+    type Block = uint8x16_t;
+
+    #[inline(always)]
+    fn xor<B: BatchMut<Block = uint8x16_t>>(&self, block: &mut B) {
+        unsafe {
+            debug_assert!(Neon::is_supported());
+            block.update(|_idx, b| *b = aese(*b, self.0));
+        }
+    }
+
+    #[inline(always)]
+    fn encrypt<B: BatchMut<Block = uint8x16_t>>(&self, block: &mut B) {
+        unsafe {
+            debug_assert!(Neon::is_supported());
+            block.update(|_idx, b| *b = aesmc_aese(*b, self.0));
+        }
+    }
+
+    #[inline(always)]
+    fn encrypt_finish<B: BatchMut<Block = uint8x16_t>>(&self, block: &mut B) {
+        block.update(|_idx, b| *b = b.xor(self.0));
+    }
+
+    #[inline(always)]
+    fn decrypt<B: BatchMut<Block = uint8x16_t>>(&self, _block: &mut B) {
+        unreachable!("encryption key rounds cannot be used for decryption")
+    }
+
+    #[inline(always)]
+    fn decrypt_finish<B: BatchMut<Block = uint8x16_t>>(&self, _block: &mut B) {
+        unreachable!("encryption key rounds cannot be used for decryption")
+    }
+}
+
+#[derive(Clone, Copy)]
+#[repr(transparent)]
+pub struct DecryptionKeyRound(uint8x16_t);
+
+impl Default for DecryptionKeyRound {
+    #[inline(always)]
+    fn default() -> Self {
+        Self(uint8x16_t::zeroed())
+    }
+}
+
+impl zeroize::DefaultIsZeroes for DecryptionKeyRound {}
+
+impl super::KeyRound for DecryptionKeyRound {
+    type Block = uint8x16_t;
+
+    #[inline(always)]
+    fn xor<B: BatchMut<Block = uint8x16_t>>(&self, block: &mut B) {
+        unsafe {
+            debug_assert!(Neon::is_supported());
+            block.update(|_idx, b| *b = aesd(*b, self.0));
+        }
+    }
+
+    #[inline(always)]
+    fn encrypt<B: BatchMut<Block = uint8x16_t>>(&self, _block: &mut B) {
+        unreachable!("decryption key rounds cannot be used for encryption")
+    }
+
+    #[inline(always)]
+    fn encrypt_finish<B: BatchMut<Block = uint8x16_t>>(&self, _block: &mut B) {
+        unreachable!("decryption key rounds cannot be used for encryption")
+    }
+
+    #[inline(always)]
+    fn decrypt<B: BatchMut<Block = uint8x16_t>>(&self, block: &mut B) {
+        unsafe {
+            debug_assert!(Neon::is_supported());
+            block.update(|_idx, b| *b = aesimc_aesd(*b, self.0));
+        }
+    }
+
+    #[inline(always)]
+    fn decrypt_finish<B: BatchMut<Block = uint8x16_t>>(&self, block: &mut B) {
+        block.update(|_idx, b| *b = b.xor(self.0));
+    }
+}
@@ -32,6 +32,9 @@ macro_rules! aes_impl {
                     #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
                     let impls = crate::aes::x86::testing::$name::implementations(impls);
 
+                    #[cfg(target_arch = "aarch64")]
+                    let impls = crate::aes::aarch64::testing::$name::implementations(impls);
+
                     #[cfg(test)]
                     let impls = super::rust_crypto::$name::implementations(impls);
 
@@ -2,7 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    aead,
+    aead::{self, BatchPayload},
     aes::Encrypt,
     aesgcm::{
         payload::{DecryptionPayload, Payload},
@@ -30,7 +30,7 @@ where
     C: Ctr,
     G: GHash,
 {
-    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64", target_arch = "aarch64"))]
     #[inline(always)]
     pub fn new(aes: A, ghash: G) -> Self {
         Self {
@@ -60,8 +60,18 @@ where
     B: Block + Batch<Block = B> + BatchMut,
     [B; N]: Batch<Block = B> + BatchMut + Zeroed,
 {
+    /// Computes the AES-GCM tag for the payload while applying the cipher stream
+    ///
+    /// If the caller has already encrypted the initial counter block, i.e. `ek0`, as part of a
+    /// batch, it can be passed in `encrypted_ek0` to avoid encrypting it again.
     #[inline(always)]
-    fn aesgcm<P: Payload<B>>(&self, nonce: &[u8; NONCE_LEN], aad: &[u8], mut payload: P) -> B {
+    fn aesgcm<P: Payload<B>>(
+        &self,
+        nonce: &[u8; NONCE_LEN],
+        aad: &[u8],
+        mut payload: P,
+        encrypted_ek0: Option<B>,
+    ) -> B {
         assert!(
             A::ROUNDS >= N,
             "The number of encryption rounds must be at least the batch size"
@@ -104,8 +114,9 @@ where
 
         // set to true if the ek0 counter can be encrypted alongside the last batch
         //
-        // this is only true if the batches have a spare slot at the end
-        let can_interleave_ek0 = batch_rem < N - 1;
+        // this is only true if the batches have a spare slot at the end and the caller hasn't
+        // already encrypted it
+        let can_interleave_ek0 = encrypted_ek0.is_none() && batch_rem < N - 1;
         let mut did_interleave_ek0 = false;
 
         required_ghash_blocks += payload_block_count;
@@ -118,6 +129,9 @@ where
 
         // generate the ek0 counter block to be encrypted and hashed at the end
         let mut ek0 = ctr.block();
+        if let Some(encrypted_ek0) = encrypted_ek0 {
+            ek0 = encrypted_ek0;
+        }
 
         /// Performs a single batch which will interleave the AES-CTR stream
         /// cipher with the previous GHash batch
@@ -248,7 +262,7 @@ where
                 assume!(cipher_blocks.len() > N - 1);
                 *cipher_blocks.get_unchecked(N - 1)
             };
-        } else {
+        } else if encrypted_ek0.is_none() {
             self.aes.encrypt(&mut ek0);
         }
 
@@ -259,6 +273,37 @@ where
         // finalize the ghash and xor the tag with the encrypted ek0
         self.ghash.finish(ghash_state).xor(ek0)
     }
+
+    /// Derives the nonces for a batch of packets and encrypts each of their `ek0` counter
+    /// blocks in a single pass
+    ///
+    /// Encrypting the blocks together lets the AES rounds for several packets run in parallel,
+    /// rather than paying for a separate, unpipelined encryption at the end of each packet.
+    #[inline(always)]
+    fn ek0_batch<F>(
+        &self,
+        payloads: &[BatchPayload],
+        nonce: &mut F,
+    ) -> ([[u8; NONCE_LEN]; N], [B; N])
+    where
+        F: FnMut(u64) -> [u8; NONCE_LEN],
+    {
+        unsafe {
+            assume!(payloads.len() <= N);
+        }
+
+        let mut nonces = [[0u8; NONCE_LEN]; N];
+        let mut ek0 = <[B; N]>::zeroed();
+
+        for (idx, entry) in payloads.iter().enumerate() {
+            nonces[idx] = nonce(entry.packet_number);
+            ek0[idx] = C::new(&nonces[idx]).block();
+        }
+
+        self.aes.encrypt(&mut ek0);
+
+        (nonces, ek0)
+    }
 }
 
 impl<A, G, C, B, const N: usize> aead::Aead for AesGcm<A, G, C, N>
@@ -281,7 +326,7 @@ where
         payload: &mut [u8],
         tag: &mut [u8; TAG_LEN],
     ) -> aead::Result {
-        *tag = Self::aesgcm(self, nonce, aad, payload).into_array();
+        *tag = Self::aesgcm(self, nonce, aad, payload, None).into_array();
         Ok(())
     }
 
@@ -296,28 +341,72 @@ where
         // wrap the payload in one that returns the payload block instead of the XOR'd
         let payload = DecryptionPayload(payload);
 
-        let expected_tag = Self::aesgcm(self, nonce, aad, payload);
-
-        // we don't want the compiler to perform any tag checks until the very end
-        compiler_fence(Ordering::SeqCst);
+        let expected_tag = Self::aesgcm(self, nonce, aad, payload, None);
 
-        let tag = B::from_array(*tag);
-        let eq_res = tag.ct_ensure_eq(expected_tag);
+        check_tag(tag, expected_tag)
+    }
 
-        // we don't want the compiler to reorder anything from the tag check
-        compiler_fence(Ordering::SeqCst);
+    #[inline(always)]
+    fn encrypt_batch<F>(&self, payloads: &mut [BatchPayload], mut nonce: F)
+    where
+        F: FnMut(u64) -> [u8; NONCE_LEN],
+    {
+        for batch in payloads.chunks_mut(N) {
+            let (nonces, ek0) = self.ek0_batch(batch, &mut nonce);
+
+            for (idx, entry) in batch.iter_mut().enumerate() {
+                entry.result = aead::split_tag(entry.payload).map(|(payload, tag)| {
+                    let ek0 = Some(ek0[idx]);
+                    *tag =
+                        Self::aesgcm(self, &nonces[idx], entry.header, payload, ek0).into_array();
+                });
+            }
+        }
+    }
 
-        eq_res.map_err(|_| {
-            // NOTE: We should ideally be zeroizing the payload when decryption fails
-            //       as the output could potentially have sensitive data. _However_,
-            //       in s2n-quic we zeroize all received packets anyway, so we would
-            //       end up zeroizing payloads twice. In the case that this code is used outside
-            //       of s2n-quic _please_ zeroize the `payload`.
-            aead::Error::DECRYPT_ERROR
-        })
+    #[inline(always)]
+    fn decrypt_batch<F>(&self, payloads: &mut [BatchPayload], mut nonce: F)
+    where
+        F: FnMut(u64) -> [u8; NONCE_LEN],
+    {
+        for batch in payloads.chunks_mut(N) {
+            let (nonces, ek0) = self.ek0_batch(batch, &mut nonce);
+
+            for (idx, entry) in batch.iter_mut().enumerate() {
+                entry.result = aead::split_tag(entry.payload).and_then(|(payload, tag)| {
+                    let ek0 = Some(ek0[idx]);
+                    let payload = DecryptionPayload(payload);
+                    let expected_tag = Self::aesgcm(self, &nonces[idx], entry.header, payload, ek0);
+
+                    check_tag(tag, expected_tag)
+                });
+            }
+        }
     }
 }
 
+/// Compares the received tag with the expected tag in constant time
+#[inline(always)]
+fn check_tag<B: Block>(tag: &[u8; TAG_LEN], expected_tag: B) -> aead::Result {
+    // we don't want the compiler to perform any tag checks until the very end
+    compiler_fence(Ordering::SeqCst);
+
+    let tag = B::from_array(*tag);
+    let eq_res = tag.ct_ensure_eq(expected_tag);
+
+    // we don't want the compiler to reorder anything from the tag check
+    compiler_fence(Ordering::SeqCst);
+
+    eq_res.map_err(|_| {
+        // NOTE: We should ideally be zeroizing the payload when decryption fails
+        //       as the output could potentially have sensitive data. _However_,
+        //       in s2n-quic we zeroize all received packets anyway, so we would
+        //       end up zeroizing payloads twice. In the case that this code is used outside
+        //       of s2n-quic _please_ zeroize the `payload`.
+        aead::Error::DECRYPT_ERROR
+    })
+}
+
 #[inline(always)]
 fn aad_blocks<B, const N: usize>(aad: &[u8]) -> ([B; N], usize)
 where
@@ -32,6 +32,9 @@ macro_rules! aesgcm_impl {
                     #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
                     crate::aesgcm::x86::testing::$name::implementations(&mut impls);
 
+                    #[cfg(target_arch = "aarch64")]
+                    crate::aesgcm::aarch64::testing::$name::implementations(&mut impls);
+
                     crate::aesgcm::ring::$name::implementations(&mut impls);
 
                     #[cfg(test)]
@@ -3,7 +3,7 @@
 
 use crate::{
     arch::*,
-    block::{Batch, BatchMut, Block, Zeroed},
+    block::{copy_128, Batch, BatchMut, Block, Zeroed},
 };
 use core::mem::size_of;
 use s2n_quic_core::assume;
@@ -161,49 +161,3 @@ impl M128iExt for __m128i {
         }
     }
 }
-
-/// Copies up to 16 bytes from `from` into `to`
-///
-/// This exists to avoid having to call memcpy
-#[inline(always)]
-unsafe fn copy_128(mut from: *const u8, mut to: *mut u8, mut len: usize) {
-    macro_rules! copy {
-        ($($len:expr),*) => {
-            $(
-                if let Some(next) = len.checked_sub($len) {
-                    len = next;
-                    *(to as *mut [u8; $len]) = *(from as *const [u8; $len]);
-                    from = from.add($len);
-                    to = to.add($len);
-                }
-            )*
-        }
-    }
-
-    copy!(128, 64, 32, 16, 8, 4, 2, 1);
-    let _ = from;
-    let _ = to;
-    let _ = len;
-}
-
-#[test]
-fn copy_128_test() {
-    for i in 0..LEN {
-        dbg!(i);
-
-        let mut expected = [1u8; LEN];
-
-        let mut source = [0u8; LEN];
-        for (a, b) in source.iter_mut().zip(&mut expected).take(i) {
-            *a = 2;
-            *b = 2;
-        }
-
-        let mut dest = [1u8; LEN];
-        unsafe {
-            copy_128(source.as_ptr(), dest.as_mut_ptr(), i);
-        }
-
-        assert_eq!(dest, expected);
-    }
-}
@@ -1,12 +1,16 @@
 // Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{aead::Aead, header_key::HeaderKey, iv};
-use ::ring::{aead, hkdf};
+use crate::{
+    aead::Aead,
+    backend::{aead, hkdf},
+    header_key::HeaderKey,
+    iv,
+};
 use core::fmt;
 use s2n_quic_core::{
     assume,
-    crypto::{label, CryptoError},
+    crypto::{label, BatchPayload, CryptoError},
 };
 use zeroize::{Zeroize, Zeroizing};
 
@@ -14,9 +18,12 @@ mod negotiated;
 #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
 #[path = "cipher_suite/x86.rs"]
 mod platform;
+#[cfg(target_arch = "aarch64")]
+#[path = "cipher_suite/aarch64.rs"]
+mod platform;
 mod ring;
 
-#[cfg(not(any(target_arch = "x86", target_arch = "x86_64")))]
+#[cfg(not(any(target_arch = "x86", target_arch = "x86_64", target_arch = "aarch64")))]
 use self::ring as platform;
 
 pub use negotiated::NegotiatedCipherSuite;
@@ -183,6 +190,18 @@ macro_rules! impl_cipher_suite {
                     Ok(())
                 }
 
+                #[inline]
+                fn decrypt_batch(&self, payloads: &mut [BatchPayload]) {
+                    self.key
+                        .decrypt_batch(payloads, |packet_number| self.iv.nonce(packet_number))
+                }
+
+                #[inline]
+                fn encrypt_batch(&self, payloads: &mut [BatchPayload]) {
+                    self.key
+                        .encrypt_batch(payloads, |packet_number| self.iv.nonce(packet_number))
+                }
+
                 #[inline]
                 fn tag_len(&self) -> usize {
                     $cipher.tag_len()
@@ -327,3 +346,6 @@ impl_cipher_suite!(
     u64::pow(2, 52), // Integrity limit
     tls_aes_128_gcm_sha256_test
 );
+
+#[cfg(test)]
+mod tests;
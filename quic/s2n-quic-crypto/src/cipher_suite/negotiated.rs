@@ -2,11 +2,11 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
+    backend::{aead, hkdf},
     cipher_suite::{TLS_AES_128_GCM_SHA256, TLS_AES_256_GCM_SHA384, TLS_CHACHA20_POLY1305_SHA256},
     header_key::HeaderKey,
 };
 use core::fmt;
-use ring::{aead, hkdf};
 use s2n_quic_core::crypto::{self, CryptoError};
 
 // ignore casing warnings in order to preserve the IANA name
@@ -107,6 +107,16 @@ impl crypto::Key for NegotiatedCipherSuite {
         ))
     }
 
+    #[inline]
+    fn decrypt_batch(&self, payloads: &mut [crypto::BatchPayload]) {
+        dispatch!(self, |cipher| cipher.decrypt_batch(payloads))
+    }
+
+    #[inline]
+    fn encrypt_batch(&self, payloads: &mut [crypto::BatchPayload]) {
+        dispatch!(self, |cipher| cipher.encrypt_batch(payloads))
+    }
+
     #[inline]
     fn tag_len(&self) -> usize {
         dispatch!(self, |cipher| cipher.tag_len())
@@ -0,0 +1,123 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::{TLS_AES_128_GCM_SHA256, TLS_AES_256_GCM_SHA384, TLS_CHACHA20_POLY1305_SHA256};
+use crate::backend::hkdf;
+use bolero::{check, generator::*};
+use s2n_quic_core::crypto::{BatchPayload, Key};
+
+#[derive(Debug, TypeGenerator)]
+struct Packet {
+    packet_number: u64,
+    #[generator(1..32)]
+    header_len: usize,
+    #[generator(0..1200)]
+    payload_len: usize,
+}
+
+macro_rules! batch_test {
+    ($name:ident, $cipher:ident, $digest:expr) => {
+        #[test]
+        #[cfg_attr(miri, ignore)] // This test is too expensive for miri to complete in a reasonable amount of time
+        fn $name() {
+            check!()
+                .with_generator((gen::<bool>(), gen::<Vec<Packet>>().with().len(0usize..16)))
+                .for_each(|(optimize, packets)| {
+                    let new_key = || {
+                        let secret = [1u8; 48];
+                        let len = $digest.hmac_algorithm().digest_algorithm().output_len;
+                        let secret = hkdf::Prk::new_less_safe($digest, &secret[..len]);
+                        let (mut key, _header_key) = $cipher::new(secret);
+
+                        // switch to the platform-optimized implementation, if available
+                        if *optimize {
+                            key.update_pmtu(1500);
+                        }
+
+                        key
+                    };
+
+                    check_batch_round_trip(&new_key(), &new_key(), packets);
+                });
+        }
+    };
+}
+
+batch_test!(
+    aes128_gcm_batch_test,
+    TLS_AES_128_GCM_SHA256,
+    hkdf::HKDF_SHA256
+);
+batch_test!(
+    aes256_gcm_batch_test,
+    TLS_AES_256_GCM_SHA384,
+    hkdf::HKDF_SHA384
+);
+batch_test!(
+    chacha20_poly1305_batch_test,
+    TLS_CHACHA20_POLY1305_SHA256,
+    hkdf::HKDF_SHA256
+);
+
+/// Ensures sealing and opening a batch of packets matches the single packet operations
+fn check_batch_round_trip<K: Key>(sealer: &K, opener: &K, packets: &[Packet]) {
+    let packets: Vec<_> = packets
+        .iter()
+        .map(|packet| {
+            let header: Vec<u8> = Iterator::map(0..packet.header_len, |v| v as u8).collect();
+            let payload: Vec<u8> = Iterator::map(0..packet.payload_len, |v| v as u8).collect();
+            (packet.packet_number, header, payload)
+        })
+        .collect();
+
+    let mut expected = vec![];
+    let mut outputs = vec![];
+
+    for (packet_number, header, payload) in packets.iter() {
+        let mut output = payload.clone();
+        output.resize(payload.len() + sealer.tag_len(), 0);
+        outputs.push(output.clone());
+
+        sealer.encrypt(*packet_number, header, &mut output).unwrap();
+        expected.push(output);
+    }
+
+    let mut batch: Vec<_> = packets
+        .iter()
+        .zip(outputs.iter_mut())
+        .map(|((packet_number, header, _), output)| {
+            BatchPayload::new(*packet_number, header, output)
+        })
+        .collect();
+
+    sealer.encrypt_batch(&mut batch);
+    assert!(batch.iter().all(|entry| entry.result.is_ok()));
+    drop(batch);
+
+    assert_eq!(expected, outputs);
+
+    // corrupt the last packet to make sure failures are reported for each packet
+    if let Some(output) = outputs.last_mut() {
+        output[0] ^= 1;
+    }
+
+    let mut batch: Vec<_> = packets
+        .iter()
+        .zip(outputs.iter_mut())
+        .map(|((packet_number, header, _), output)| {
+            BatchPayload::new(*packet_number, header, output)
+        })
+        .collect();
+
+    opener.decrypt_batch(&mut batch);
+
+    let count = batch.len();
+    for (idx, entry) in batch.iter().enumerate() {
+        assert_eq!(entry.result.is_err(), idx + 1 == count);
+    }
+    drop(batch);
+
+    for ((_, _, payload), output) in packets.iter().zip(outputs.iter()).rev().skip(1) {
+        assert_eq!(payload[..], output[..payload.len()]);
+    }
+}
@@ -103,6 +103,28 @@ macro_rules! aesgcm {
                         Self::Ring(key) => key.decrypt(nonce, aad, input, tag),
                     }
                 }
+
+                #[inline]
+                fn encrypt_batch<F>(&self, payloads: &mut [aead::BatchPayload], nonce: F)
+                where
+                    F: FnMut(u64) -> [u8; NONCE_LEN],
+                {
+                    match self {
+                        Self::Precomputed(key) => key.encrypt_batch(payloads, nonce),
+                        Self::Ring(key) => key.encrypt_batch(payloads, nonce),
+                    }
+                }
+
+                #[inline]
+                fn decrypt_batch<F>(&self, payloads: &mut [aead::BatchPayload], nonce: F)
+                where
+                    F: FnMut(u64) -> [u8; NONCE_LEN],
+                {
+                    match self {
+                        Self::Precomputed(key) => key.decrypt_batch(payloads, nonce),
+                        Self::Ring(key) => key.decrypt_batch(payloads, nonce),
+                    }
+                }
             }
 
             type PrecomputedGHash = precomputed::Allocated<hkey::H>;
@@ -153,6 +175,30 @@ macro_rules! aesgcm {
                 ) -> aead::Result {
                     aead::Aead::decrypt(&self.key, nonce, aad, input, tag)
                 }
+
+                #[inline]
+                #[target_feature(enable = "aes,avx2,pclmulqdq")]
+                unsafe fn encrypt_batch_impl<F>(
+                    &self,
+                    payloads: &mut [aead::BatchPayload],
+                    nonce: F,
+                ) where
+                    F: FnMut(u64) -> [u8; NONCE_LEN],
+                {
+                    aead::Aead::encrypt_batch(&self.key, payloads, nonce)
+                }
+
+                #[inline]
+                #[target_feature(enable = "aes,avx2,pclmulqdq")]
+                unsafe fn decrypt_batch_impl<F>(
+                    &self,
+                    payloads: &mut [aead::BatchPayload],
+                    nonce: F,
+                ) where
+                    F: FnMut(u64) -> [u8; NONCE_LEN],
+                {
+                    aead::Aead::decrypt_batch(&self.key, payloads, nonce)
+                }
             }
 
             impl aead::Aead for PrecomputedKey {
@@ -186,6 +232,28 @@ macro_rules! aesgcm {
                         self.decrypt_impl(nonce, aad, input, tag)
                     }
                 }
+
+                #[inline]
+                fn encrypt_batch<F>(&self, payloads: &mut [aead::BatchPayload], nonce: F)
+                where
+                    F: FnMut(u64) -> [u8; NONCE_LEN],
+                {
+                    unsafe {
+                        debug_assert!(Avx2::is_supported());
+                        self.encrypt_batch_impl(payloads, nonce)
+                    }
+                }
+
+                #[inline]
+                fn decrypt_batch<F>(&self, payloads: &mut [aead::BatchPayload], nonce: F)
+                where
+                    F: FnMut(u64) -> [u8; NONCE_LEN],
+                {
+                    unsafe {
+                        debug_assert!(Avx2::is_supported());
+                        self.decrypt_batch_impl(payloads, nonce)
+                    }
+                }
             }
         }
     };
@@ -0,0 +1,43 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    aesgcm::NONCE_LEN,
+    arch::*,
+    block::{aarch64::Uint8x16Ext, Block},
+    ctr,
+};
+
+#[derive(Clone, Copy, Debug)]
+pub struct Ctr(uint8x16_t);
+
+impl ctr::Ctr for Ctr {
+    type Block = uint8x16_t;
+
+    #[inline(always)]
+    fn new(nonce: &[u8; NONCE_LEN]) -> Self {
+        // https://github.com/awslabs/aws-lc/blob/aed75eb04d322d101941e1377f274484f5e4f5b8/crypto/fipsmodule/modes/gcm.c#L249
+        //
+        // OPENSSL_memcpy(ctx->Yi.c, iv, 12);
+        // ctx->Yi.c[15] = 1;
+        let mut ctr = [0u8; 16];
+        ctr[..12].copy_from_slice(nonce);
+        ctr[15] = 1;
+        let ctr = uint8x16_t::from_array(ctr).reverse();
+        Self(ctr)
+    }
+
+    #[inline(always)]
+    fn block(&self) -> uint8x16_t {
+        self.0.reverse()
+    }
+
+    #[inline(always)]
+    fn increment(&mut self) {
+        unsafe {
+            debug_assert!(Neon::is_supported());
+            let one = vcombine_u64(vcreate_u64(1), vcreate_u64(0));
+            self.0 = vreinterpretq_u8_u64(vaddq_u64(vreinterpretq_u64_u8(self.0), one));
+        }
+    }
+}
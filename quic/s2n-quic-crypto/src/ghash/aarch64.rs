@@ -0,0 +1,57 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    arch::*,
+    block::{aarch64::Uint8x16Ext, Batch, Block, Zeroed},
+    ghash::KEY_LEN,
+};
+use zeroize::Zeroize;
+
+mod algo;
+pub mod hkey;
+pub mod precomputed;
+
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
+
+#[derive(Zeroize)]
+pub struct GHash(hkey::H);
+
+impl GHash {
+    #[allow(dead_code)] // this is currently used in testing only
+    #[inline(always)]
+    pub fn new(key: [u8; KEY_LEN]) -> Self {
+        use hkey::HKey;
+        Self(hkey::H::new(uint8x16_t::from_array(key)))
+    }
+}
+
+impl super::GHash for GHash {
+    type Block = uint8x16_t;
+    type State = uint8x16_t;
+
+    #[inline(always)]
+    fn start(&self, _required_blocks: usize) -> Self::State {
+        uint8x16_t::zeroed()
+    }
+
+    #[inline(always)]
+    fn update<B: Batch<Block = Self::Block>>(&self, state: &mut Self::State, block: &B) {
+        let mut y = *state;
+        block.for_each(
+            #[inline(always)]
+            |_idx, b| {
+                y = self.0.mul(b.reverse().xor(y));
+            },
+        );
+        *state = y;
+    }
+
+    #[inline(always)]
+    fn finish(&self, state: Self::State) -> Self::Block {
+        let y = state;
+
+        y.reverse()
+    }
+}
@@ -0,0 +1,116 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! A port of the x86 GHash algorithm to PMULL
+//!
+//! The x86 intrinsics used by the original implementation map onto NEON as follows:
+//!
+//! * `pclmulqdq` - `PMULL`/`PMULL2`, see [`clmul`]
+//! * `pshufd $78` - [`Uint8x16Ext::swap_halves`]
+//! * `pslldq $8` - [`Uint8x16Ext::shift_left_64`]
+//! * `psrldq $8` - [`Uint8x16Ext::shift_right_64`]
+
+use crate::{
+    arch::*,
+    block::{aarch64::Uint8x16Ext, Block},
+};
+use core::arch::asm;
+
+// https://github.com/awslabs/aws-lc/blob/5833176448d48aff0c2dc4c1ab745649c769a7a6/crypto/cipher_extra/asm/aes128gcmsiv-x86_64.pl#L58
+// poly:
+// .quad 0x1, 0xc200000000000000
+const POLYNOMIAL: u128 = 0xc200000000000000_0000000000000001;
+
+/// Performs a carry-less multiplication of two 64-bit halves of `a` and `b`
+///
+/// The halves are selected with `IMM` in the same way as `pclmulqdq`: bit 0 selects the half of
+/// `a` and bit 4 selects the half of `b`.
+#[inline]
+#[target_feature(enable = "neon,aes")]
+pub unsafe fn clmul<const IMM: u8>(a: uint8x16_t, b: uint8x16_t) -> uint8x16_t {
+    // `PMULL` multiplies the lower halves and `PMULL2` the upper halves so mixed selections
+    // move the selected half of one operand into place first
+    match (IMM & 0x01 != 0, IMM & 0x10 != 0) {
+        (false, false) => pmull(a, b),
+        (true, true) => pmull2(a, b),
+        (false, true) => pmull(a, b.swap_halves()),
+        (true, false) => pmull(a.swap_halves(), b),
+    }
+}
+
+// The PMULL intrinsics in `core::arch::aarch64` were not stable as of the crate's MSRV so
+// the instructions are emitted with inline assembly instead.
+
+/// Carry-less multiplication of the lower 64-bit halves
+#[inline]
+#[target_feature(enable = "neon,aes")]
+unsafe fn pmull(a: uint8x16_t, b: uint8x16_t) -> uint8x16_t {
+    let out: uint8x16_t;
+    asm!(
+        "pmull {out:v}.1q, {a:v}.1d, {b:v}.1d",
+        out = lateout(vreg) out,
+        a = in(vreg) a,
+        b = in(vreg) b,
+        options(pure, nomem, nostack, preserves_flags)
+    );
+    out
+}
+
+/// Carry-less multiplication of the upper 64-bit halves
+#[inline]
+#[target_feature(enable = "neon,aes")]
+unsafe fn pmull2(a: uint8x16_t, b: uint8x16_t) -> uint8x16_t {
+    let out: uint8x16_t;
+    asm!(
+        "pmull2 {out:v}.1q, {a:v}.2d, {b:v}.2d",
+        out = lateout(vreg) out,
+        a = in(vreg) a,
+        b = in(vreg) b,
+        options(pure, nomem, nostack, preserves_flags)
+    );
+    out
+}
+
+// From https://github.com/awslabs/aws-lc/blob/aed75eb04d322d101941e1377f274484f5e4f5b8/crypto/fipsmodule/modes/asm/ghash-x86_64.pl#L717
+#[inline(always)]
+pub unsafe fn init(h: uint8x16_t) -> uint8x16_t {
+    // this is only called once per key so it's simpler to operate on the full 128-bit value
+    let h: u128 = core::mem::transmute(h);
+
+    // # <<1 twist
+    let carry = h >> 127;
+    let h = h << 1;
+
+    // # magic reduction
+    // if(carry) H^=0x1c2_polynomial
+    let h = h ^ (POLYNOMIAL & carry.wrapping_neg());
+
+    core::mem::transmute(h)
+}
+
+// From https://github.com/awslabs/aws-lc/blob/5833176448d48aff0c2dc4c1ab745649c769a7a6/crypto/cipher_extra/asm/aes128gcmsiv-x86_64.pl#L93
+#[inline(always)]
+pub unsafe fn gfmul(a: uint8x16_t, b: uint8x16_t) -> uint8x16_t {
+    let mut tmp1 = clmul::<0x00>(a, b);
+    let mut tmp4 = clmul::<0x11>(a, b);
+    let mut tmp2 = clmul::<0x10>(a, b);
+    let tmp3 = clmul::<0x01>(a, b);
+
+    tmp2 = tmp2.xor(tmp3);
+    tmp1 = tmp1.xor(tmp2.shift_left_64());
+    tmp4 = tmp4.xor(tmp2.shift_right_64());
+
+    reduce(tmp1, tmp4)
+}
+
+/// Reduction phase of gfmul
+// From https://github.com/awslabs/aws-lc/blob/5833176448d48aff0c2dc4c1ab745649c769a7a6/crypto/cipher_extra/asm/aes128gcmsiv-x86_64.pl#L93
+#[inline(always)]
+pub unsafe fn reduce(mut tmp1: uint8x16_t, tmp4: uint8x16_t) -> uint8x16_t {
+    let poly: uint8x16_t = core::mem::transmute(POLYNOMIAL);
+
+    tmp1 = clmul::<0x10>(tmp1, poly).xor(tmp1.swap_halves());
+    tmp1 = clmul::<0x10>(tmp1, poly).xor(tmp1.swap_halves());
+
+    tmp1.xor(tmp4)
+}
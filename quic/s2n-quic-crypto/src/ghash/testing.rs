@@ -31,6 +31,9 @@ lazy_static! {
         #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
         let impls = super::x86::testing::implementations(impls);
 
+        #[cfg(target_arch = "aarch64")]
+        let impls = super::aarch64::testing::implementations(impls);
+
         #[cfg(test)]
         let impls = rust_crypto::implementations(impls);
 
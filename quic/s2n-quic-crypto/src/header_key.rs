@@ -1,8 +1,8 @@
 // Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::backend::{aead, hkdf};
 use core::fmt;
-use ring::{aead, hkdf};
 use s2n_quic_core::crypto::{self, HeaderProtectionMask};
 
 pub struct HeaderKey(pub(crate) aead::quic::HeaderProtectionKey);
@@ -94,6 +94,16 @@ impl crypto::HeaderKey for HeaderKeyPair {
     fn sealing_sample_len(&self) -> usize {
         self.sealer.sealing_sample_len()
     }
+
+    #[inline]
+    fn opening_header_protection_masks(&self, samples: &[u8], masks: &mut [HeaderProtectionMask]) {
+        self.opener.opening_header_protection_masks(samples, masks)
+    }
+
+    #[inline]
+    fn sealing_header_protection_masks(&self, samples: &[u8], masks: &mut [HeaderProtectionMask]) {
+        self.sealer.sealing_header_protection_masks(samples, masks)
+    }
 }
 
 macro_rules! header_key {
@@ -127,6 +137,24 @@ macro_rules! header_key {
             fn sealing_sample_len(&self) -> usize {
                 self.0.sealing_sample_len()
             }
+
+            #[inline]
+            fn opening_header_protection_masks(
+                &self,
+                samples: &[u8],
+                masks: &mut [s2n_quic_core::crypto::HeaderProtectionMask],
+            ) {
+                self.0.opening_header_protection_masks(samples, masks)
+            }
+
+            #[inline]
+            fn sealing_header_protection_masks(
+                &self,
+                samples: &[u8],
+                masks: &mut [s2n_quic_core::crypto::HeaderProtectionMask],
+            ) {
+                self.0.sealing_header_protection_masks(samples, masks)
+            }
         }
 
         impl From<crate::header_key::HeaderKeyPair> for $name {
@@ -1,8 +1,9 @@
 // Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{cipher_suite::TLS_AES_128_GCM_SHA256 as CipherSuite, header_key::HeaderKeyPair};
-use ring::hkdf;
+use crate::{
+    backend::hkdf, cipher_suite::TLS_AES_128_GCM_SHA256 as CipherSuite, header_key::HeaderKeyPair,
+};
 use s2n_quic_core::{
     crypto::{
         self,
@@ -101,6 +102,16 @@ impl Key for InitialKey {
         self.sealer.encrypt(packet_number, header, payload)
     }
 
+    #[inline]
+    fn decrypt_batch(&self, payloads: &mut [crypto::BatchPayload]) {
+        self.opener.decrypt_batch(payloads)
+    }
+
+    #[inline]
+    fn encrypt_batch(&self, payloads: &mut [crypto::BatchPayload]) {
+        self.sealer.encrypt_batch(payloads)
+    }
+
     #[inline]
     fn tag_len(&self) -> usize {
         self.sealer.tag_len()
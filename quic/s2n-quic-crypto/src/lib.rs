@@ -16,15 +16,29 @@ mod ctr;
 mod ghash;
 mod iv;
 
-#[doc(hidden)]
-pub use ring::{
-    self,
-    aead::{Algorithm, MAX_TAG_LEN},
-    hkdf::Prk,
+// The backend is only used internally so enabling `aws-lc-rs` anywhere in the dependency graph
+// doesn't change any public types. `aws-lc-rs` takes precedence if both features are enabled.
+cfg_if::cfg_if! {
+    if #[cfg(feature = "aws-lc-rs")] {
+        use aws_lc_rs as backend;
+    } else if #[cfg(feature = "ring")] {
+        use ring as backend;
+    } else {
+        compile_error!("either the `ring` or `aws-lc-rs` feature must be enabled");
+    }
+}
+
+use backend::{aead::Algorithm, hkdf::Prk};
+use s2n_quic_core::{
+    crypto::{provider, tls::CipherSuite},
+    endpoint,
 };
 
+/// The maximum length of an authentication tag
+pub const MAX_TAG_LEN: usize = backend::aead::MAX_TAG_LEN;
+
 #[derive(Clone)]
-pub struct SecretPair {
+pub(crate) struct SecretPair {
     pub server: Prk,
     pub client: Prk,
 }
@@ -50,5 +64,81 @@ impl s2n_quic_core::crypto::CryptoSuite for Suite {
     type RetryKey = retry::RetryKey;
 }
 
+impl s2n_quic_core::crypto::provider::Provider for Suite {
+    #[inline]
+    fn handshake_keys(
+        endpoint: endpoint::Type,
+        cipher_suite: CipherSuite,
+        secrets: provider::SecretPair,
+    ) -> Option<(Self::HandshakeKey, Self::HandshakeHeaderKey)> {
+        let (algorithm, secrets) = secret_pair(cipher_suite, secrets)?;
+        handshake::HandshakeKey::new(endpoint, algorithm, secrets)
+    }
+
+    #[inline]
+    fn one_rtt_keys(
+        endpoint: endpoint::Type,
+        cipher_suite: CipherSuite,
+        secrets: provider::SecretPair,
+    ) -> Option<(Self::OneRttKey, Self::OneRttHeaderKey)> {
+        let (algorithm, secrets) = secret_pair(cipher_suite, secrets)?;
+        one_rtt::OneRttKey::new(endpoint, algorithm, secrets)
+    }
+
+    #[inline]
+    fn zero_rtt_keys(
+        cipher_suite: CipherSuite,
+        secret: &[u8],
+    ) -> Option<(Self::ZeroRttKey, Self::ZeroRttHeaderKey)> {
+        // 0-RTT keys are currently only implemented for TLS_AES_128_GCM_SHA256
+        if cipher_suite != CipherSuite::TLS_AES_128_GCM_SHA256 {
+            return None;
+        }
+
+        let secret = Prk::new_less_safe(crate::backend::hkdf::HKDF_SHA256, secret);
+        Some(zero_rtt::ZeroRttKey::new(secret))
+    }
+}
+
+/// Maps a negotiated cipher suite to the algorithms used to derive its keys
+fn algorithms(
+    cipher_suite: CipherSuite,
+) -> Option<(crate::backend::hkdf::Algorithm, &'static Algorithm)> {
+    use crate::backend::{aead, hkdf};
+
+    match cipher_suite {
+        CipherSuite::TLS_AES_128_GCM_SHA256 => Some((hkdf::HKDF_SHA256, &aead::AES_128_GCM)),
+        CipherSuite::TLS_AES_256_GCM_SHA384 => Some((hkdf::HKDF_SHA384, &aead::AES_256_GCM)),
+        CipherSuite::TLS_CHACHA20_POLY1305_SHA256 => {
+            Some((hkdf::HKDF_SHA256, &aead::CHACHA20_POLY1305))
+        }
+        CipherSuite::Unknown => None,
+    }
+}
+
+fn secret_pair(
+    cipher_suite: CipherSuite,
+    secrets: provider::SecretPair,
+) -> Option<(&'static Algorithm, SecretPair)> {
+    let (digest, algorithm) = algorithms(cipher_suite)?;
+    let secrets = SecretPair {
+        client: Prk::new_less_safe(digest, secrets.client),
+        server: Prk::new_less_safe(digest, secrets.server),
+    };
+    Some((algorithm, secrets))
+}
+
 #[cfg(any(test, feature = "testing"))]
 pub mod testing;
+
+#[cfg(test)]
+mod tests {
+    use super::Suite;
+    use s2n_quic_core::crypto::provider::testing;
+
+    /// Runs the RFC 9001 test vectors against the compiled backend
+    #[test]
+    fn provider_conformance() {
+        testing::conformance::<Suite>();
+    }
+}
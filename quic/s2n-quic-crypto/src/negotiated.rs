@@ -6,7 +6,7 @@ use crate::{
     SecretPair,
 };
 use s2n_quic_core::{
-    crypto::{CryptoError, Key},
+    crypto::{BatchPayload, CryptoError, Key},
     endpoint,
 };
 
@@ -17,7 +17,7 @@ pub struct KeyPair {
 }
 
 impl KeyPair {
-    pub fn new(
+    pub(crate) fn new(
         endpoint: endpoint::Type,
         algorithm: &Algorithm,
         secrets: SecretPair,
@@ -71,6 +71,16 @@ impl Key for KeyPair {
         self.sealer.encrypt(packet_number, header, payload)
     }
 
+    #[inline]
+    fn decrypt_batch(&self, payloads: &mut [BatchPayload]) {
+        self.opener.decrypt_batch(payloads)
+    }
+
+    #[inline]
+    fn encrypt_batch(&self, payloads: &mut [BatchPayload]) {
+        self.sealer.encrypt_batch(payloads)
+    }
+
     #[inline]
     fn tag_len(&self) -> usize {
         self.sealer.tag_len()
@@ -98,24 +108,8 @@ macro_rules! negotiated_crypto {
         pub struct $name(crate::negotiated::KeyPair);
 
         impl $name {
-            /// Create a server cipher suite with a given negotiated algorithm and secret
-            pub fn new_server(
-                algorithm: &$crate::Algorithm,
-                secrets: $crate::SecretPair,
-            ) -> Option<(Self, $header_key)> {
-                Self::new(s2n_quic_core::endpoint::Type::Server, algorithm, secrets)
-            }
-
-            /// Create a client cipher suite with a given negotiated algorithm and secret
-            pub fn new_client(
-                algorithm: &$crate::Algorithm,
-                secrets: $crate::SecretPair,
-            ) -> Option<(Self, $header_key)> {
-                Self::new(s2n_quic_core::endpoint::Type::Client, algorithm, secrets)
-            }
-
             /// Create a cipher_suite for an endpoint type with a given negotiated algorithm and secret
-            pub fn new(
+            pub(crate) fn new(
                 endpoint: s2n_quic_core::endpoint::Type,
                 algorithm: &$crate::Algorithm,
                 secrets: $crate::SecretPair,
@@ -158,6 +152,16 @@ macro_rules! negotiated_crypto {
                 self.0.encrypt(packet_number, header, payload)
             }
 
+            #[inline]
+            fn decrypt_batch(&self, payloads: &mut [s2n_quic_core::crypto::BatchPayload]) {
+                self.0.decrypt_batch(payloads)
+            }
+
+            #[inline]
+            fn encrypt_batch(&self, payloads: &mut [s2n_quic_core::crypto::BatchPayload]) {
+                self.0.encrypt_batch(payloads)
+            }
+
             #[inline]
             fn tag_len(&self) -> usize {
                 self.0.tag_len()
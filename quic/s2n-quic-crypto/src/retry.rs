@@ -1,8 +1,8 @@
 // Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::backend::aead;
 use core::convert::TryInto;
-use ring::aead;
 use s2n_quic_core::crypto::{
     self,
     retry::{IntegrityTag, NONCE_BYTES, SECRET_KEY_BYTES},
@@ -34,7 +34,7 @@ impl crypto::RetryKey for RetryKey {
     fn validate(pseudo_packet: &[u8], tag: IntegrityTag) -> Result<(), CryptoError> {
         let expected = Self::generate_tag(pseudo_packet);
 
-        ring::constant_time::verify_slices_are_equal(&expected, &tag)
+        crate::backend::constant_time::verify_slices_are_equal(&expected, &tag)
             .map_err(|_| CryptoError::DECRYPT_ERROR)
     }
 }
@@ -2,14 +2,14 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{cipher_suite::TLS_AES_128_GCM_SHA256 as CipherSuite, header_key::HeaderKey};
-use s2n_quic_core::crypto::{self, CryptoError, HeaderProtectionMask, Key};
+use s2n_quic_core::crypto::{self, BatchPayload, CryptoError, HeaderProtectionMask, Key};
 
 #[derive(Debug)]
 pub struct ZeroRttKey(CipherSuite);
 
 impl ZeroRttKey {
     /// Create a ZeroRTT cipher suite with a given secret
-    pub fn new(secret: crate::Prk) -> (Self, ZeroRttHeaderKey) {
+    pub(crate) fn new(secret: crate::Prk) -> (Self, ZeroRttHeaderKey) {
         let (key, header_key) = CipherSuite::new(secret);
         let key = Self(key);
         let header_key = ZeroRttHeaderKey(header_key);
@@ -38,6 +38,14 @@ impl Key for ZeroRttKey {
         self.0.encrypt(packet_number, header, payload)
     }
 
+    fn decrypt_batch(&self, payloads: &mut [BatchPayload]) {
+        self.0.decrypt_batch(payloads)
+    }
+
+    fn encrypt_batch(&self, payloads: &mut [BatchPayload]) {
+        self.0.encrypt_batch(payloads)
+    }
+
     fn tag_len(&self) -> usize {
         self.0.tag_len()
     }
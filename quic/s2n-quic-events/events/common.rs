@@ -364,6 +364,12 @@ enum Frame {
     Datagram {
         len: u16,
     },
+    ResetStreamAt {
+        id: u64,
+        error_code: u64,
+        final_size: u64,
+        reliable_size: u64,
+    },
 }
 
 impl IntoEvent<builder::Frame> for &crate::frame::Padding {
@@ -400,6 +406,17 @@ impl IntoEvent<builder::Frame> for &crate::frame::ResetStream {
     }
 }
 
+impl IntoEvent<builder::Frame> for &crate::frame::ResetStreamAt {
+    fn into_event(self) -> builder::Frame {
+        builder::Frame::ResetStreamAt {
+            id: self.stream_id.as_u64(),
+            error_code: self.application_error_code.as_u64(),
+            final_size: self.final_size.as_u64(),
+            reliable_size: self.reliable_size.as_u64(),
+        }
+    }
+}
+
 impl IntoEvent<builder::Frame> for &crate::frame::StopSending {
     fn into_event(self) -> builder::Frame {
         builder::Frame::StopSending {
@@ -602,6 +619,24 @@ enum KeyType {
     OneRtt { generation: u16 },
 }
 
+/// The reason a key update occurred
+enum KeyUpdateCause {
+    /// The keys were installed as part of the handshake
+    Negotiated,
+    /// The peer initiated a key update
+    PeerInitiated,
+    /// The key approached the AEAD confidentiality limit
+    ConfidentialityLimit,
+    /// The key protected the packet count configured in the key update policy
+    PacketLimit,
+    /// The key protected the byte count configured in the key update policy
+    ByteLimit,
+    /// The key was in use for the interval configured in the key update policy
+    Interval,
+    /// The application requested a key update
+    Application,
+}
+
 /// A context from which the event is being emitted
 ///
 /// An event can occur in the context of an Endpoint or Connection
@@ -684,6 +719,29 @@ enum DatagramDropReason {
     InsufficientConnectionIds,
 }
 
+enum MemoryPressure {
+    /// The endpoint is not under memory pressure
+    None,
+    /// Flow control windows advertised to peers are no longer extended
+    ShrinkWindows,
+    /// The connection which buffers the most data is closed
+    CloseLargestConsumer,
+}
+
+impl IntoEvent<builder::MemoryPressure> for crate::endpoint::limits::MemoryPressure {
+    fn into_event(self) -> builder::MemoryPressure {
+        use crate::endpoint::limits::MemoryPressure;
+
+        match self {
+            MemoryPressure::None => builder::MemoryPressure::None {},
+            MemoryPressure::ShrinkWindows => builder::MemoryPressure::ShrinkWindows {},
+            MemoryPressure::CloseLargestConsumer { .. } => {
+                builder::MemoryPressure::CloseLargestConsumer {}
+            }
+        }
+    }
+}
+
 enum KeySpace {
     Initial {},
     Handshake {},
@@ -808,6 +866,16 @@ enum CongestionSource {
     PacketLoss,
 }
 
+/// The rate at which the application expects to send data
+enum ExpectedSendRate {
+    /// The application has not declared an expected send rate
+    Unspecified,
+    /// The application does not expect to send any data
+    Idle,
+    /// The application expects to send at most `bytes_per_second`
+    Limited { bytes_per_second: u64 },
+}
+
 #[allow(non_camel_case_types)] // we prefer to match the standard identifier
 enum CipherSuite {
     TLS_AES_128_GCM_SHA256,
@@ -840,6 +908,9 @@ enum SlowStartExitCause {
     Ecn,
     /// The round trip time estimate was updated
     Rtt,
+    /// HyStart++ completed Conservative Slow Start without the round trip time
+    /// returning to the baseline measured when Conservative Slow Start was entered
+    ConservativeSlowStart,
     /// Slow Start exited due to a reason other than those above
     ///
     /// With the Cubic congestion controller, this reason is used after the initial exiting of
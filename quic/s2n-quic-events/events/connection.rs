@@ -156,9 +156,14 @@ struct PacketDropped<'a> {
 #[event("security:key_update")]
 //= https://tools.ietf.org/id/draft-marx-qlog-event-definitions-quic-h3-02#5.2.1
 /// Crypto key updated
+///
+/// 1-RTT key updates are reported once they complete, which is when the first packet protected
+/// with the new keys is received from the peer. This includes locally initiated updates, which
+/// start when the first packet is sent with the new keys.
 struct KeyUpdate {
     key_type: KeyType,
     cipher_suite: CipherSuite,
+    cause: KeyUpdateCause,
 }
 
 #[event("security:key_space_discarded")]
@@ -268,6 +273,18 @@ struct TlsServerHello<'a> {
     payload: &'a [&'a [u8]],
 }
 
+#[event("tls:handshake_completed")]
+/// The TLS handshake completed with the given parameters
+struct TlsHandshakeCompleted<'a> {
+    cipher_suite: CipherSuite,
+    /// The IANA code point of the key exchange group, if it is a registered group
+    key_exchange_group: Option<u16>,
+    /// The IANA code point of the signature scheme used by the server
+    signature_scheme: Option<u16>,
+    /// The DER-encoded certificate chain presented by the peer
+    peer_cert_chain: &'a [&'a [u8]],
+}
+
 #[event("transport:rx_stream_progress")]
 struct RxStreamProgress {
     bytes: usize,
@@ -283,6 +300,15 @@ pub struct KeepAliveTimerExpired {
     timeout: Duration,
 }
 
+#[event("connectivity:keep_alive_ping_sent")]
+/// A PING frame was sent to keep the connection alive
+struct KeepAlivePingSent {
+    /// The interval after which the PING frame was sent
+    interval: Duration,
+    /// The number of consecutive keep-alive PING frames sent while the connection was idle
+    consecutive_pings: u32,
+}
+
 #[event("connectivity:mtu_updated")]
 /// The maximum transmission unit (MTU) for the path has changed
 struct MtuUpdated {
@@ -323,3 +349,42 @@ struct BbrStateChanged {
     path_id: u64,
     state: BbrState,
 }
+
+#[event("recovery:expected_send_rate_updated")]
+/// The application has declared the rate at which it expects to send data
+struct ExpectedSendRateUpdated {
+    path_id: u64,
+    rate: ExpectedSendRate,
+}
+
+#[event("connectivity:handshake_amplification_limited")]
+/// The server was blocked by the anti-amplification limit during the handshake and needed
+/// an additional flight from the peer before sending more data
+struct HandshakeAmplificationLimited {
+    path_id: u64,
+    /// The number of extra flights caused by the anti-amplification limit so far
+    extra_flights: u32,
+}
+
+#[event("connectivity:connection_close_received")]
+/// The peer closed the connection with a CONNECTION_CLOSE frame
+struct ConnectionCloseReceived<'a> {
+    /// The error the peer closed the connection with
+    error: crate::connection::Error,
+    /// The reason phrase sent by the peer, if any
+    reason: Option<&'a [u8]>,
+}
+
+#[event("security:packets_sealed")]
+/// A batch of 1-RTT packets was sealed together
+struct PacketsSealed {
+    /// The number of packets in the batch
+    count: usize,
+}
+
+#[event("security:packets_opened")]
+/// A batch of 1-RTT packets was opened together
+struct PacketsOpened {
+    /// The number of packets in the batch
+    count: usize,
+}
@@ -66,3 +66,12 @@ struct EndpointDatagramDropped {
 struct EndpointConnectionAttemptFailed {
     error: crate::connection::Error,
 }
+
+#[event("transport:memory_pressure_updated")]
+#[subject(endpoint)]
+/// The memory pressure the endpoint is under changed
+struct EndpointMemoryPressureUpdated {
+    /// The number of bytes buffered by the endpoint across all connections
+    buffered_bytes: u64,
+    pressure: MemoryPressure,
+}
@@ -18,7 +18,7 @@ mod model;
 pub mod network;
 pub mod time;
 
-pub use model::{Model, TxRecorder};
+pub use model::{Capacity, Link, Model, TxRecorder};
 pub use network::{Network, PathHandle};
 pub use time::now;
 
@@ -3,9 +3,10 @@
 
 use super::network::{Buffers, Network, Packet};
 use core::time::Duration;
-use s2n_quic_core::{havoc, path::MaxMtu};
+use s2n_quic_core::{havoc, inet::SocketAddress, path::MaxMtu, time::Timestamp};
 use std::{
     borrow::Cow,
+    collections::HashSet,
     sync::{
         atomic::{AtomicU16, AtomicU64, Ordering},
         Arc, Mutex,
@@ -156,6 +157,44 @@ impl Model {
         self
     }
 
+    /// Sets the number of bytes per second the network is able to deliver in both directions
+    ///
+    /// See [`Link::set_bandwidth`] for more details.
+    pub fn set_bandwidth(&self, value: u64) -> &Self {
+        self.0.uplink.set_bandwidth(value);
+        self.0.downlink.set_bandwidth(value);
+        self
+    }
+
+    /// The link used by packets sent from any address not registered with
+    /// [`Model::add_downlink_source`]
+    pub fn uplink(&self) -> &Link {
+        &self.0.uplink
+    }
+
+    /// The link used by packets sent from addresses registered with
+    /// [`Model::add_downlink_source`]
+    pub fn downlink(&self) -> &Link {
+        &self.0.downlink
+    }
+
+    /// Routes all of the packets sent from `address` over the downlink
+    ///
+    /// This is usually called with the address of each server so client and server traffic can be
+    /// shaped independently.
+    pub fn add_downlink_source(&self, address: SocketAddress) -> &Self {
+        self.0.downlink_sources.lock().unwrap().insert(address);
+        self
+    }
+
+    fn link(&self, source: &SocketAddress) -> &Link {
+        if self.0.downlink_sources.lock().unwrap().contains(source) {
+            &self.0.downlink
+        } else {
+            &self.0.uplink
+        }
+    }
+
     pub fn inflight_delay(&self) -> Duration {
         Duration::from_micros(self.0.inflight_delay.load(Ordering::SeqCst))
     }
@@ -181,6 +220,111 @@ impl Model {
     }
 }
 
+/// The capacity of a link which varies over time
+pub trait Capacity: 'static + Send + Sync {
+    /// Returns the time at which a packet of `len` bytes, which starts serializing onto the link
+    /// at `start`, has been fully transmitted
+    ///
+    /// Returning `None` drops the packet.
+    fn transmit(&self, start: Timestamp, len: u64) -> Option<Timestamp>;
+}
+
+/// A single direction of the network
+pub struct Link {
+    bandwidth: AtomicU64,
+    /// The one-way delay in microseconds, or `u64::MAX` to use the delay of the model
+    delay: AtomicU64,
+    capacity: Mutex<Option<Arc<dyn Capacity>>>,
+    /// The time at which the link finishes serializing the queued packets
+    idle_at: Mutex<Option<Timestamp>>,
+}
+
+impl Default for Link {
+    fn default() -> Self {
+        Self {
+            bandwidth: AtomicU64::new(u64::MAX),
+            delay: AtomicU64::new(u64::MAX),
+            capacity: Mutex::new(None),
+            idle_at: Mutex::new(None),
+        }
+    }
+}
+
+impl Link {
+    pub fn bandwidth(&self) -> u64 {
+        self.bandwidth.load(Ordering::SeqCst)
+    }
+
+    /// Sets the number of bytes per second the link is able to deliver
+    ///
+    /// Packets are serialized onto the link one at a time and queue behind each other when the
+    /// senders exceed this rate. Setting this value to `0` will drop all packets, which can be
+    /// used to model link outages. By default, the bandwidth is unlimited.
+    ///
+    /// The bandwidth is ignored while a [`Capacity`] is set.
+    pub fn set_bandwidth(&self, value: u64) -> &Self {
+        self.bandwidth.store(value, Ordering::SeqCst);
+        self
+    }
+
+    /// The one-way delay of the link, if it overrides the delay of the model
+    pub fn delay(&self) -> Option<Duration> {
+        match self.delay.load(Ordering::SeqCst) {
+            u64::MAX => None,
+            value => Some(Duration::from_micros(value)),
+        }
+    }
+
+    /// Overrides the amount of time a packet on this link is delayed before the receiver is able
+    /// to read it
+    pub fn set_delay(&self, value: Option<Duration>) -> &Self {
+        let value = value.map_or(u64::MAX, |value| value.as_micros() as _);
+        self.delay.store(value, Ordering::SeqCst);
+        self
+    }
+
+    /// Sets a time-varying capacity for the link
+    ///
+    /// Unlike [`Link::set_bandwidth`], the capacity is able to queue packets across periods where
+    /// the link is unable to deliver anything.
+    pub fn set_capacity<C: Capacity>(&self, capacity: C) -> &Self {
+        *self.capacity.lock().unwrap() = Some(Arc::new(capacity));
+        self
+    }
+
+    /// Queues a packet of `len` bytes onto the link and returns the time at which it is fully
+    /// serialized, or `None` if the packet should be dropped
+    fn serialize(&self, now: Timestamp, len: u64) -> Option<Timestamp> {
+        let capacity = self.capacity.lock().unwrap().clone();
+        let bandwidth = self.bandwidth();
+
+        if capacity.is_none() {
+            match bandwidth {
+                // the link is unlimited so there's nothing to queue behind
+                u64::MAX => return Some(now),
+                // the link is currently down
+                0 => return None,
+                _ => {}
+            }
+        }
+
+        let mut idle_at = self.idle_at.lock().unwrap();
+
+        // wait for any previously queued packets before serializing this one
+        let start = idle_at.map_or(now, |idle_at| idle_at.max(now));
+
+        let end = if let Some(capacity) = capacity {
+            capacity.transmit(start, len)?
+        } else {
+            start + Duration::from_micros(len.saturating_mul(1_000_000) / bandwidth)
+        };
+
+        *idle_at = Some(end);
+
+        Some(end)
+    }
+}
+
 fn rate_to_u64(rate: f64) -> u64 {
     let value = rate.max(0.0).min(1.0);
     let value = value * u64::MAX as f64;
@@ -200,6 +344,9 @@ struct State {
     inflight_delay: AtomicU64,
     inflight_delay_threshold: AtomicU64,
     current_inflight: AtomicU64,
+    uplink: Link,
+    downlink: Link,
+    downlink_sources: Mutex<HashSet<SocketAddress>>,
 }
 
 impl Default for State {
@@ -217,6 +364,9 @@ impl Default for State {
             inflight_delay: AtomicU64::new(0),
             inflight_delay_threshold: AtomicU64::new(u64::MAX),
             current_inflight: AtomicU64::new(0),
+            uplink: Link::default(),
+            downlink: Link::default(),
+            downlink_sources: Mutex::new(HashSet::new()),
         }
     }
 }
@@ -232,10 +382,11 @@ impl Network for Model {
         let max_udp_payload = self.max_udp_payload() as usize;
         let inflight_delay = self.inflight_delay();
         let inflight_delay_threshold = self.inflight_delay_threshold();
+        let delay = self.delay();
 
         let now = super::time::now();
-        let mut transmit_time = now + self.delay();
-        let transmit_time = &mut transmit_time;
+        let mut jitter_offset = Duration::ZERO;
+        let jitter_offset = &mut jitter_offset;
 
         #[inline]
         fn gen_rate(rate: u64) -> bool {
@@ -278,11 +429,19 @@ impl Network for Model {
 
             if !jitter.is_zero() {
                 // add a delay for the next packet to be transmitted
-                *transmit_time += gen_jitter(jitter);
+                *jitter_offset += gen_jitter(jitter);
             }
 
-            // copy the transmit time for this packet
-            let mut transmit_time = *transmit_time;
+            let link = self.link(&packet.path.local_address);
+
+            // serialize the packet onto the link, queueing behind any previous packets
+            let serialized = match link.serialize(now, packet.payload.len() as u64) {
+                Some(serialized) => serialized,
+                // drop the packet if the link is currently down
+                None => return 0,
+            };
+
+            let mut transmit_time = serialized + link.delay().unwrap_or(delay) + *jitter_offset;
 
             if !network_jitter.is_zero() {
                 transmit_time += gen_jitter(network_jitter);
@@ -301,6 +301,52 @@ mod tests {
 
     differential_test!(simple_differential_test, message::simple::Ring);
 
+    #[allow(unused_macros)]
+    macro_rules! gso_test {
+        ($name:ident, $module:ident) => {
+            /// Segments appended to a GSO payload are visible through `as_slice_mut`
+            #[test]
+            fn $name() {
+                use message::$module::{Handle, Ring};
+                use s2n_quic_core::{io::tx, path::Handle as _};
+
+                let capacity = 4;
+                let payloads = VecBuffer::new(capacity, MTU);
+                let ring = Ring::new(payloads, capacity);
+                let mut queue = Queue::new(ring);
+
+                let address: inet::SocketAddress =
+                    inet::SocketAddressV4::new([127, 0, 0, 1], 4433).into();
+                let handle = Handle::from_remote_address(address.into());
+
+                // keep a message in the occupied segment so the GSO payload is eventually
+                // accessed through the other half of the messages
+                for _ in 0..(capacity * 2) {
+                    {
+                        let mut free = queue.free_mut();
+                        tx::Queue::push(&mut free, (handle, [1u8; 32])).unwrap();
+                        tx::Queue::push(&mut free, (handle, [2u8; 32])).unwrap();
+
+                        let entries = tx::Queue::as_slice_mut(&mut free);
+                        let payload = tx::Entry::payload(entries.last().unwrap());
+                        assert_eq!(payload.len(), 64);
+                        assert!(payload[..32].iter().all(|v| *v == 1));
+                        assert!(payload[32..].iter().all(|v| *v == 2));
+                    }
+
+                    let occupied_len = queue.occupied_len();
+                    queue.occupied_mut().finish(occupied_len - 1);
+                }
+            }
+        };
+    }
+
+    #[cfg(all(s2n_quic_platform_socket_msg, s2n_quic_platform_gso))]
+    gso_test!(msg_gso_test, msg);
+
+    #[cfg(all(s2n_quic_platform_socket_mmsg, s2n_quic_platform_gso))]
+    gso_test!(mmsg_gso_test, mmsg);
+
     #[cfg(s2n_quic_platform_socket_msg)]
     differential_test!(msg_differential_test, message::msg::Ring);
 
@@ -104,22 +104,7 @@ impl<'a, Message: message::Message, B> Slice<'a, Message, B> {
         if let Some(gso) = self.gso_segment.take() {
             // only set the `msg_control` if there was more than one payload written to the message
             if gso.count > 1 {
-                // since messages are double the number of payloads, we need to calculate a primary
-                // and secondary index so we can accurately replicate the fields.
-                let mid = self.messages.len() / 2;
-                let (primary, secondary) = self.messages.split_at_mut(mid);
-                let index = gso.index;
-
-                // try to wrap around the midpoint
-                let (primary, secondary) = if let Some(index) = index.checked_sub(mid) {
-                    let primary = &mut primary[index];
-                    let secondary = &mut secondary[index];
-                    (secondary, primary)
-                } else {
-                    let primary = &mut primary[index];
-                    let secondary = &mut secondary[index];
-                    (primary, secondary)
-                };
+                let (primary, secondary) = counterparts(self.messages, gso.index);
 
                 // let the primary message know that we sent multiple payloads in a single message
                 primary.set_segment_size(gso.size);
@@ -206,6 +191,12 @@ impl<'a, Message: message::Message, B> Slice<'a, Message, B> {
                     // set the len to the actual amount written to the payload
                     prev_message.set_payload_len(payload_len + size.min(gso.size));
                 }
+
+                // replicate the len to the secondary message so the payload can be accessed
+                // through `as_slice_mut` while the GSO payload is still being built
+                let (primary, secondary) = counterparts(self.messages, gso.index);
+                secondary.replicate_fields_from(primary);
+
                 // increment the number of segments that we've written
                 gso.count += 1;
 
@@ -238,6 +229,26 @@ impl<'a, Message: message::Message, B> Slice<'a, Message, B> {
     }
 }
 
+/// Returns the message at `index` along with its counterpart in the other half of `messages`
+#[inline]
+fn counterparts<Message>(messages: &mut [Message], index: usize) -> (&mut Message, &mut Message) {
+    // since messages are double the number of payloads, we need to calculate a primary
+    // and secondary index so we can accurately replicate the fields.
+    let mid = messages.len() / 2;
+    let (primary, secondary) = messages.split_at_mut(mid);
+
+    // try to wrap around the midpoint
+    if let Some(index) = index.checked_sub(mid) {
+        let primary = &mut primary[index];
+        let secondary = &mut secondary[index];
+        (secondary, primary)
+    } else {
+        let primary = &mut primary[index];
+        let secondary = &mut secondary[index];
+        (primary, secondary)
+    }
+}
+
 impl<'a, Message: message::Message, R> Drop for Slice<'a, Message, R> {
     #[inline]
     fn drop(&mut self) {
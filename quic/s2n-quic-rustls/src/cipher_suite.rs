@@ -72,6 +72,18 @@ impl crypto::Key for PacketKey {
 
 impl crypto::ZeroRttKey for PacketKey {}
 
+/// Maps the rustls cipher suite to the s2n-quic-core cipher suite
+pub(crate) fn into_cipher_suite(cipher_suite: CipherSuite) -> tls::CipherSuite {
+    match cipher_suite {
+        CipherSuite::TLS13_AES_128_GCM_SHA256 => tls::CipherSuite::TLS_AES_128_GCM_SHA256,
+        CipherSuite::TLS13_AES_256_GCM_SHA384 => tls::CipherSuite::TLS_AES_256_GCM_SHA384,
+        CipherSuite::TLS13_CHACHA20_POLY1305_SHA256 => {
+            tls::CipherSuite::TLS_CHACHA20_POLY1305_SHA256
+        }
+        _ => tls::CipherSuite::Unknown,
+    }
+}
+
 pub struct PacketKeys {
     sealer: PacketKey,
     opener: PacketKey,
@@ -81,14 +93,7 @@ impl PacketKeys {
     pub(crate) fn new(keys: quic::Keys, cipher_suite: CipherSuite) -> (Self, HeaderProtectionKeys) {
         let quic::Keys { local, remote } = keys;
 
-        let cipher_suite = match cipher_suite {
-            CipherSuite::TLS13_AES_128_GCM_SHA256 => tls::CipherSuite::TLS_AES_128_GCM_SHA256,
-            CipherSuite::TLS13_AES_256_GCM_SHA384 => tls::CipherSuite::TLS_AES_256_GCM_SHA384,
-            CipherSuite::TLS13_CHACHA20_POLY1305_SHA256 => {
-                tls::CipherSuite::TLS_CHACHA20_POLY1305_SHA256
-            }
-            _ => tls::CipherSuite::Unknown,
-        };
+        let cipher_suite = into_cipher_suite(cipher_suite);
 
         let (sealer_packet, sealer_header) = PacketKey::new(local, cipher_suite);
         let (opener_packet, opener_header) = PacketKey::new(remote, cipher_suite);
@@ -57,3 +57,43 @@ fn client_server_test() {
 
     pair.finish();
 }
+
+#[test]
+fn reloadable_certificate_test() {
+    use s2n_quic_core::crypto::tls::{self, testing::certificates::*};
+
+    let mut client = client::Builder::new()
+        .with_certificate(CERT_PEM)
+        .unwrap()
+        .build()
+        .unwrap();
+
+    let certificate = server::ReloadableCertificate::new(CERT_PEM, KEY_PEM).unwrap();
+    let mut server = server::Builder::new()
+        .with_reloadable_certificate(certificate.clone())
+        .unwrap()
+        .build()
+        .unwrap();
+
+    let mut pair = tls::testing::Pair::new(&mut server, &mut client, "localhost".into());
+
+    while pair.is_handshaking() {
+        pair.poll(None).unwrap();
+    }
+
+    pair.finish();
+
+    // new handshakes use the updated certificate, which the client doesn't trust
+    certificate
+        .update(UNTRUSTED_CERT_PEM, UNTRUSTED_KEY_PEM)
+        .unwrap();
+
+    let mut pair = tls::testing::Pair::new(&mut server, &mut client, "localhost".into());
+    let mut result = Ok(());
+
+    while result.is_ok() && pair.is_handshaking() {
+        result = pair.poll(None);
+    }
+
+    assert!(result.is_err());
+}
@@ -5,7 +5,7 @@ use crate::{certificate, encode_transport_parameters, session::Session};
 use rustls::{quic, ServerConfig};
 use s2n_codec::EncoderValue;
 use s2n_quic_core::{application::ServerName, crypto::tls};
-use std::sync::Arc;
+use std::sync::{Arc, RwLock};
 
 #[derive(Clone)]
 pub struct Server {
@@ -113,6 +113,14 @@ impl Builder {
         self.with_cert_resolver(resolver)
     }
 
+    /// Configures the server with a certificate which can be replaced while it is running
+    pub fn with_reloadable_certificate(
+        self,
+        certificate: ReloadableCertificate,
+    ) -> Result<Self, rustls::Error> {
+        self.with_cert_resolver(Arc::new(certificate))
+    }
+
     pub fn with_cert_resolver(
         mut self,
         cert_resolver: Arc<dyn rustls::server::ResolvesServerCert>,
@@ -161,6 +169,15 @@ impl Builder {
     }
 }
 
+fn certified_key(
+    chain: certificate::Certificate,
+    priv_key: certificate::PrivateKey,
+) -> Result<Arc<rustls::sign::CertifiedKey>, rustls::Error> {
+    let key = rustls::sign::any_supported_type(&priv_key.0)
+        .map_err(|_| rustls::Error::General("invalid private key".into()))?;
+    Ok(Arc::new(rustls::sign::CertifiedKey::new(chain.0, key)))
+}
+
 struct AlwaysResolvesChain(Arc<rustls::sign::CertifiedKey>);
 
 impl AlwaysResolvesChain {
@@ -168,11 +185,7 @@ impl AlwaysResolvesChain {
         chain: certificate::Certificate,
         priv_key: certificate::PrivateKey,
     ) -> Result<Self, rustls::Error> {
-        let key = rustls::sign::any_supported_type(&priv_key.0)
-            .map_err(|_| rustls::Error::General("invalid private key".into()))?;
-        Ok(Self(Arc::new(rustls::sign::CertifiedKey::new(
-            chain.0, key,
-        ))))
+        Ok(Self(certified_key(chain, priv_key)?))
     }
 }
 
@@ -184,3 +197,57 @@ impl rustls::server::ResolvesServerCert for AlwaysResolvesChain {
         Some(Arc::clone(&self.0))
     }
 }
+
+/// A certificate chain and private key which can be replaced while the server is running
+///
+/// This can be used to rotate certificates without restarting the endpoint. Each new handshake
+/// resolves the most recently stored certificate. Handshakes which have already started and
+/// established connections are unaffected by updates.
+#[derive(Clone)]
+pub struct ReloadableCertificate(Arc<RwLock<Arc<rustls::sign::CertifiedKey>>>);
+
+impl ReloadableCertificate {
+    pub fn new<C: certificate::IntoCertificate, PK: certificate::IntoPrivateKey>(
+        certificate: C,
+        private_key: PK,
+    ) -> Result<Self, rustls::Error> {
+        let key = certified_key(
+            certificate.into_certificate()?,
+            private_key.into_private_key()?,
+        )?;
+        Ok(Self(Arc::new(RwLock::new(key))))
+    }
+
+    /// Replaces the certificate chain and private key used for subsequent handshakes
+    ///
+    /// Files can be reloaded by passing their [`Path`](std::path::Path)s. If the new
+    /// certificate fails to load, the current one is kept.
+    pub fn update<C: certificate::IntoCertificate, PK: certificate::IntoPrivateKey>(
+        &self,
+        certificate: C,
+        private_key: PK,
+    ) -> Result<(), rustls::Error> {
+        let key = certified_key(
+            certificate.into_certificate()?,
+            private_key.into_private_key()?,
+        )?;
+        *self
+            .0
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner()) = key;
+        Ok(())
+    }
+}
+
+impl rustls::server::ResolvesServerCert for ReloadableCertificate {
+    fn resolve(
+        &self,
+        _client_hello: rustls::server::ClientHello,
+    ) -> Option<Arc<rustls::sign::CertifiedKey>> {
+        let key = self
+            .0
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        Some(Arc::clone(&key))
+    }
+}
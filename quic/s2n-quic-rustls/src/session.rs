@@ -2,7 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::cipher_suite::{
-    HeaderProtectionKey, HeaderProtectionKeys, OneRttKey, PacketKey, PacketKeys,
+    into_cipher_suite, HeaderProtectionKey, HeaderProtectionKeys, OneRttKey, PacketKey, PacketKeys,
 };
 use bytes::Bytes;
 use core::{fmt, fmt::Debug, task::Poll};
@@ -310,6 +310,39 @@ impl tls::Session for Session {
         self.emit_events(context)?;
         result
     }
+
+    fn handshake_details(&self) -> Option<tls::HandshakeDetails> {
+        let cipher_suite = into_cipher_suite(self.connection.negotiated_cipher_suite()?.suite());
+
+        let peer_cert_chain = self
+            .connection
+            .peer_certificates()
+            .unwrap_or_default()
+            .iter()
+            .map(|cert| Bytes::copy_from_slice(&cert.0))
+            .collect();
+
+        Some(tls::HandshakeDetails {
+            peer_cert_chain,
+            cipher_suite,
+            // rustls 0.20 only exposes the negotiated cipher suite; the key exchange group and
+            // signature scheme aren't available from either the connection or the suite
+            key_exchange_group: None,
+            signature_scheme: None,
+        })
+    }
+
+    fn export_keying_material(
+        &self,
+        label: &[u8],
+        context: &[u8],
+        output: &mut [u8],
+    ) -> Result<(), CryptoError> {
+        self.connection
+            .export_keying_material(output, label, Some(context))
+            .map_err(crate::error::reason)
+            .map_err(|reason| CryptoError::INTERNAL_ERROR.with_reason(reason))
+    }
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
@@ -61,7 +61,11 @@ struct Plan {
 
     sim: Sim,
 
+    #[serde(default)]
     report: BTreeMap<String, Report>,
+
+    #[serde(default)]
+    timeline: BTreeMap<String, Timeline>,
 }
 
 impl Plan {
@@ -98,6 +102,12 @@ impl Plan {
             reports.push(res);
         }
 
+        for (timeline_name, timeline) in self.timeline.iter() {
+            let mut res = timeline.run(&out, command, &db, timeline_name)?;
+            res.0 = format!("{} - {}", name, res.0);
+            reports.push(res);
+        }
+
         Ok(())
     }
 }
@@ -163,3 +173,30 @@ impl Report {
         Ok((title, output))
     }
 }
+
+#[derive(Clone, Debug, Deserialize)]
+struct Timeline {
+    #[serde(default)]
+    title: Option<String>,
+}
+
+impl Timeline {
+    fn run(&self, out: &Path, command: &str, db: &Path, name: &str) -> Result<(String, PathBuf)> {
+        let title = self.title.as_deref().unwrap_or(name).to_owned();
+        let output = out.join(format!("{name}.json"));
+
+        let status = Command::new(command)
+            .arg("timeline")
+            .arg(db)
+            .arg("--title")
+            .arg(&title)
+            .stdout(fs::File::create(&output)?)
+            .status()?;
+
+        if !status.success() {
+            return Err(anyhow!("{} timeline did not succeed", name));
+        }
+
+        Ok((title, output))
+    }
+}
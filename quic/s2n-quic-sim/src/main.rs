@@ -11,6 +11,7 @@ mod query;
 mod report;
 mod run;
 mod stats;
+mod timeline;
 
 #[derive(Debug, StructOpt)]
 enum Args {
@@ -18,6 +19,7 @@ enum Args {
     Run(Box<run::Run>),
     Report(report::Report),
     Batch(batch::Batch),
+    Timeline(timeline::Timeline),
 }
 
 fn main() -> Result {
@@ -37,6 +39,7 @@ fn main() -> Result {
         Args::Run(args) => args.run(),
         Args::Report(args) => args.run(),
         Args::Batch(args) => args.run(),
+        Args::Timeline(args) => args.run(),
     }
 }
 
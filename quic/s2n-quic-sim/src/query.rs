@@ -107,6 +107,9 @@ impl Query {
                 Stats::Setup(_) => {
                     // unused
                 }
+                Stats::Trace(_) | Stats::Timeline(_) => {
+                    // unused
+                }
                 Stats::Parameters(p) => {
                     if let Some(connections) = acc.remove(&p.seed) {
                         for conn in &connections {
@@ -94,6 +94,9 @@ impl Report {
                 Stats::Setup(s) => {
                     args = s.args;
                 }
+                Stats::Trace(_) | Stats::Timeline(_) => {
+                    // unused
+                }
                 Stats::Parameters(p) => {
                     let id = seed_id(p.seed);
                     if let Some(connections) = acc.remove(&id) {
@@ -8,7 +8,7 @@ use s2n_quic::provider::io::testing::{test_seed, Model};
 use structopt::StructOpt;
 
 mod config;
-pub use config::Config;
+pub use config::{Config, CongestionController};
 
 mod endpoint;
 mod events;
@@ -16,6 +16,9 @@ mod events;
 mod range;
 use range::CliRange;
 
+mod trace;
+use trace::Direction;
+
 #[derive(Debug, StructOpt)]
 pub struct Run {
     #[structopt(flatten)]
@@ -42,6 +45,15 @@ impl Run {
         assert_ne!(self.clients.start, 0);
         assert_ne!(self.connections.start, 0);
 
+        let bin = self.trace_bin;
+        let uplink_trace = self.uplink_trace.or(&self.trace).load(bin)?;
+        let downlink_trace = self.downlink_trace.or(&self.trace).load(bin)?;
+        let traces = [
+            (Direction::Uplink, uplink_trace),
+            (Direction::Downlink, downlink_trace),
+        ];
+        let traces = &traces;
+
         let test = |seed: u64| {
             let network = Model::default();
 
@@ -49,11 +61,23 @@ impl Run {
                 let server_len = self.servers.gen();
                 let client_len = self.clients.gen();
 
-                let events = self.gen_network(seed, server_len, client_len, &network);
+                let mut events = self.gen_network(seed, server_len, client_len, &network);
+
+                for (direction, trace) in traces.iter() {
+                    if let Some(trace) = trace.as_ref() {
+                        trace.drive(&network, *direction);
+                        events = events.with_timeline(bin.duration());
+                    }
+                }
+
+                let congestion_controller = self.congestion_controller;
 
                 let mut servers = vec![];
                 for _ in 0..server_len {
-                    servers.push(endpoint::server(handle, events.clone())?);
+                    let server = endpoint::server(handle, events.clone(), congestion_controller)?;
+                    // packets sent by the servers are shaped by the downlink trace
+                    network.add_downlink_source(server.into());
+                    servers.push(server);
                 }
 
                 for _ in 0..client_len {
@@ -64,6 +88,7 @@ impl Run {
                     endpoint::client(
                         handle,
                         events.clone(),
+                        congestion_controller,
                         &servers,
                         count,
                         delay,
@@ -82,7 +107,15 @@ impl Run {
                 stats::Setup {
                     args: self.config.args(),
                 }
-                .write(stdout)
+                .write(&mut *stdout)?;
+
+                for (direction, trace) in traces.iter() {
+                    if let Some(trace) = trace.as_ref() {
+                        trace.write(&mut *stdout, *direction, bin)?;
+                    }
+                }
+
+                Ok(())
             });
 
             let pb = if self.progress {
@@ -1,7 +1,11 @@
 // Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
 // SPDX-License-Identifier: Apache-2.0
 
-use super::CliRange;
+use super::{
+    trace::{Bin, TraceFile},
+    CliRange,
+};
+use core::{fmt, str::FromStr};
 use humantime::Duration;
 use serde::Deserialize;
 use structopt::StructOpt;
@@ -118,5 +122,54 @@ config!(
         #[name = "iterations"]
         #[default = "10000"]
         iterations: u64,
+
+        #[name = "trace"]
+        #[default = ""]
+        trace: TraceFile,
+
+        #[name = "uplink_trace"]
+        #[default = ""]
+        uplink_trace: TraceFile,
+
+        #[name = "downlink_trace"]
+        #[default = ""]
+        downlink_trace: TraceFile,
+
+        #[name = "trace_bin"]
+        #[default = "100ms"]
+        trace_bin: Bin,
+
+        #[name = "congestion_controller"]
+        #[default = "cubic"]
+        congestion_controller: CongestionController,
     }
 );
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CongestionController {
+    Cubic,
+    Bbr,
+}
+
+impl FromStr for CongestionController {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "cubic" => Ok(Self::Cubic),
+            "bbr" => Ok(Self::Bbr),
+            _ => Err(anyhow::anyhow!("invalid congestion controller: {}", s)),
+        }
+    }
+}
+
+impl fmt::Display for CongestionController {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Cubic => "cubic",
+            Self::Bbr => "bbr",
+        }
+        .fmt(f)
+    }
+}
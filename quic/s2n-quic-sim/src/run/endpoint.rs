@@ -1,10 +1,11 @@
 // Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
 // SPDX-License-Identifier: Apache-2.0
 
-use super::{events, CliRange};
+use super::{events, CliRange, CongestionController};
 use s2n_quic::{
     client::Connect,
     provider::{
+        congestion_controller::{Bbr, Cubic},
         event::tracing::Subscriber as Tracing,
         io::testing::{primary, rand, spawn, time, Handle, Result},
     },
@@ -13,12 +14,22 @@ use s2n_quic::{
 use s2n_quic_core::{crypto::tls::testing::certificates, stream::testing::Data};
 use std::net::SocketAddr;
 
-pub fn server(handle: &Handle, events: events::Events) -> Result<SocketAddr> {
-    let mut server = Server::builder()
+pub fn server(
+    handle: &Handle,
+    events: events::Events,
+    congestion_controller: CongestionController,
+) -> Result<SocketAddr> {
+    let server = Server::builder()
         .with_io(handle.builder().build().unwrap())?
         .with_tls((certificates::CERT_PEM, certificates::KEY_PEM))?
-        .with_event((events, Tracing::default()))?
-        .start()?;
+        .with_event((events, Tracing::default()))?;
+
+    let mut server = match congestion_controller {
+        CongestionController::Cubic => server
+            .with_congestion_controller(Cubic::default())?
+            .start()?,
+        CongestionController::Bbr => server.with_congestion_controller(Bbr::default())?.start()?,
+    };
     let server_addr = server.local_addr()?;
 
     // accept connections and echo back
@@ -39,9 +50,11 @@ pub fn server(handle: &Handle, events: events::Events) -> Result<SocketAddr> {
     Ok(server_addr)
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn client(
     handle: &Handle,
     events: events::Events,
+    congestion_controller: CongestionController,
     servers: &[SocketAddr],
     count: usize,
     delay: CliRange<humantime::Duration>,
@@ -51,8 +64,14 @@ pub fn client(
     let client = Client::builder()
         .with_io(handle.builder().build().unwrap())?
         .with_tls(certificates::CERT_PEM)?
-        .with_event((events, Tracing::default()))?
-        .start()?;
+        .with_event((events, Tracing::default()))?;
+
+    let client = match congestion_controller {
+        CongestionController::Cubic => client
+            .with_congestion_controller(Cubic::default())?
+            .start()?,
+        CongestionController::Bbr => client.with_congestion_controller(Bbr::default())?.start()?,
+    };
 
     for _ in 0..count {
         let delay = delay.gen_duration();
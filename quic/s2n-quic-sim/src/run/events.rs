@@ -1,7 +1,7 @@
 // Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::stats::{Connection, Parameters};
+use crate::stats::{Connection, Parameters, Timeline};
 use core::time::Duration;
 use once_cell::sync::Lazy;
 use s2n_quic::{
@@ -31,6 +31,15 @@ pub fn close() {
 #[derive(Clone, Debug)]
 pub struct Events {
     params: Arc<DumpOnDrop<Parameters>>,
+    timeline: Option<Duration>,
+}
+
+impl Events {
+    /// Records the progress of each connection at the given interval
+    pub fn with_timeline(mut self, interval: Duration) -> Self {
+        self.timeline = Some(interval);
+        self
+    }
 }
 
 fn now() -> Duration {
@@ -57,6 +66,56 @@ impl<Inner> core::ops::DerefMut for PrimaryContext<Inner> {
     }
 }
 
+pub struct ConnectionState {
+    stats: DumpOnDrop<Connection>,
+    timeline: Option<TimelineSampler>,
+}
+
+impl ConnectionState {
+    #[inline]
+    fn timeline<F: FnOnce(&mut Timeline)>(&mut self, now: Duration, f: F) {
+        if let Some(timeline) = self.timeline.as_mut() {
+            f(&mut timeline.sample);
+            timeline.on_update(now);
+        }
+    }
+}
+
+impl core::ops::Deref for ConnectionState {
+    type Target = Connection;
+
+    fn deref(&self) -> &Self::Target {
+        &self.stats
+    }
+}
+
+impl core::ops::DerefMut for ConnectionState {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.stats
+    }
+}
+
+struct TimelineSampler {
+    sample: Timeline,
+    interval: Duration,
+    next_sample: Duration,
+}
+
+impl TimelineSampler {
+    #[inline]
+    fn on_update(&mut self, now: Duration) {
+        if now < self.next_sample {
+            return;
+        }
+
+        self.sample.time = Some(now.into());
+        self.next_sample = now + self.interval;
+
+        let sample = &self.sample;
+        dump(|io| sample.write(io));
+    }
+}
+
 trait OrDefault<T> {
     fn or_default(&mut self) -> &mut T;
 }
@@ -72,7 +131,7 @@ impl<T: Default> OrDefault<T> for Option<T> {
 }
 
 impl event::Subscriber for Events {
-    type ConnectionContext = PrimaryContext<DumpOnDrop<Connection>>;
+    type ConnectionContext = PrimaryContext<ConnectionState>;
 
     fn create_connection_context(
         &mut self,
@@ -97,7 +156,24 @@ impl event::Subscriber for Events {
             conn.client_id = Some(id);
         }
 
-        let inner = DumpOnDrop(conn);
+        let timeline = self
+            .timeline
+            .filter(|_| seed != 0)
+            .map(|interval| TimelineSampler {
+                sample: Timeline {
+                    seed,
+                    id,
+                    is_client: conn.client_id.is_some(),
+                    ..Default::default()
+                },
+                interval,
+                next_sample: Duration::ZERO,
+            });
+
+        let inner = ConnectionState {
+            stats: DumpOnDrop(conn),
+            timeline,
+        };
         PrimaryContext {
             guard: primary::guard(),
             inner,
@@ -148,7 +224,7 @@ impl event::Subscriber for Events {
     fn on_recovery_metrics(
         &mut self,
         context: &mut Self::ConnectionContext,
-        _meta: &event::ConnectionMeta,
+        meta: &event::ConnectionMeta,
         event: &event::events::RecoveryMetrics,
     ) {
         context.max_cwin = context.max_cwin.max(event.congestion_window as _);
@@ -168,6 +244,12 @@ impl event::Subscriber for Events {
                 .into(),
         );
         context.smoothed_rtt = Some(event.smoothed_rtt.into());
+
+        context.timeline(meta.timestamp.duration_since_start(), |sample| {
+            sample.smoothed_rtt = Some(event.smoothed_rtt.into());
+            sample.latest_rtt = Some(event.latest_rtt.into());
+            sample.cwin = event.congestion_window as _;
+        });
     }
 
     #[inline]
@@ -219,6 +301,10 @@ impl event::Subscriber for Events {
             .rx
             .or_default()
             .stream_progress(meta.timestamp, event.bytes);
+
+        context.timeline(meta.timestamp.duration_since_start(), |sample| {
+            sample.rx_stream_progress += event.bytes as u64;
+        });
     }
 
     #[inline]
@@ -232,6 +318,10 @@ impl event::Subscriber for Events {
             .tx
             .or_default()
             .stream_progress(meta.timestamp, event.bytes);
+
+        context.timeline(meta.timestamp.duration_since_start(), |sample| {
+            sample.tx_stream_progress += event.bytes as u64;
+        });
     }
 
     #[inline]
@@ -282,6 +372,7 @@ impl From<Parameters> for Events {
     fn from(s: Parameters) -> Self {
         Self {
             params: Arc::new(DumpOnDrop(s)),
+            timeline: None,
         }
     }
 }
@@ -0,0 +1,534 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{stats, Result};
+use anyhow::anyhow;
+use core::{fmt, str::FromStr, time::Duration};
+use s2n_quic::provider::io::testing::{spawn, time, Capacity, Link, Model};
+use s2n_quic_core::time::Timestamp;
+use serde::Deserialize;
+use std::{fs, path::PathBuf, sync::Arc};
+
+/// The size of each delivery opportunity in a Mahimahi trace
+const MAHIMAHI_PACKET_SIZE: u64 = 1500;
+
+/// The minimum amount of time the last row of a CSV trace is held before repeating
+const MIN_HOLD: Duration = Duration::from_millis(100);
+
+const NANOS_PER_SEC: u128 = 1_000_000_000;
+
+/// The interval over which link parameters are held constant
+///
+/// Mahimahi traces are binned into intervals of this size and the connection timelines are
+/// sampled at the same rate.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize)]
+#[serde(try_from = "String")]
+pub struct Bin(Duration);
+
+impl Bin {
+    pub fn duration(self) -> Duration {
+        self.0
+    }
+}
+
+impl FromStr for Bin {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let value = humantime::parse_duration(s)?;
+
+        if value.is_zero() {
+            return Err(anyhow!("trace bin must be greater than 0"));
+        }
+
+        Ok(Self(value))
+    }
+}
+
+impl TryFrom<String> for Bin {
+    type Error = anyhow::Error;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl fmt::Display for Bin {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        humantime::format_duration(self.0).fmt(f)
+    }
+}
+
+/// The direction of the network a trace is applied to
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Direction {
+    /// Packets sent by the clients
+    Uplink,
+    /// Packets sent by the servers
+    Downlink,
+}
+
+impl Direction {
+    fn link(self, model: &Model) -> &Link {
+        match self {
+            Self::Uplink => model.uplink(),
+            Self::Downlink => model.downlink(),
+        }
+    }
+}
+
+impl fmt::Display for Direction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Uplink => "uplink",
+            Self::Downlink => "downlink",
+        }
+        .fmt(f)
+    }
+}
+
+/// An optional path to a network trace
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(transparent)]
+pub struct TraceFile(Option<PathBuf>);
+
+impl TraceFile {
+    /// Returns `self` if a path was specified, otherwise `fallback`
+    pub fn or<'a>(&'a self, fallback: &'a Self) -> &'a Self {
+        if self.0.is_some() {
+            self
+        } else {
+            fallback
+        }
+    }
+
+    pub fn load(&self, bin: Bin) -> Result<Option<Arc<Trace>>> {
+        let path = if let Some(path) = self.0.as_ref() {
+            path
+        } else {
+            return Ok(None);
+        };
+
+        let contents = fs::read_to_string(path)
+            .map_err(|err| anyhow!("could not read trace {}: {}", path.display(), err))?;
+
+        let trace = if path.extension().map_or(false, |ext| ext == "csv") {
+            Trace::from_csv(&contents)?
+        } else {
+            Trace::from_mahimahi(&contents, bin)?
+        };
+
+        Ok(Some(Arc::new(trace)))
+    }
+}
+
+impl FromStr for TraceFile {
+    type Err = core::convert::Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.is_empty() {
+            Ok(Self(None))
+        } else {
+            Ok(Self(Some(s.into())))
+        }
+    }
+}
+
+impl fmt::Display for TraceFile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(path) = self.0.as_ref() {
+            path.display().fmt(f)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Sample {
+    /// The offset from the start of the trace
+    pub start: Duration,
+    /// The number of bytes per second the link can deliver
+    pub bandwidth: u64,
+    /// The one-way delay of the link, if specified by the trace
+    pub delay: Option<Duration>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Trace {
+    samples: Vec<Sample>,
+    /// The amount of time before the trace repeats
+    period: Duration,
+}
+
+impl Trace {
+    /// Parses a Mahimahi trace
+    ///
+    /// Each line contains a millisecond timestamp at which a single MTU-sized packet can be
+    /// delivered. Multiple lines with the same timestamp represent multiple delivery
+    /// opportunities. The opportunities are grouped into bins of the given size. Once the last
+    /// timestamp is reached, the trace repeats.
+    pub fn from_mahimahi(contents: &str, bin: Bin) -> Result<Self> {
+        let bin = bin.duration();
+        let mut opportunities = vec![];
+
+        for (line_number, line) in lines(contents) {
+            let ms: u64 = line
+                .parse()
+                .map_err(|err| anyhow!("invalid timestamp on line {}: {}", line_number, err))?;
+            opportunities.push(Duration::from_millis(ms));
+        }
+
+        let period = opportunities
+            .iter()
+            .copied()
+            .max()
+            .ok_or_else(|| anyhow!("trace is empty"))?
+            .max(bin);
+
+        let period_nanos = period.as_nanos();
+        let bin_nanos = bin.as_nanos();
+
+        let bins = ((period_nanos + bin_nanos - 1) / bin_nanos) as usize;
+        let mut counts = vec![0u64; bins];
+        for opportunity in opportunities {
+            // the last timestamp is the start of the next repetition
+            let offset = opportunity.as_nanos() % period_nanos;
+            counts[(offset / bin_nanos) as usize] += 1;
+        }
+
+        let samples = counts
+            .into_iter()
+            .enumerate()
+            .map(|(idx, count)| {
+                let start = bin_nanos * idx as u128;
+                // the last bin may be cut short by the end of the trace
+                let width = bin_nanos.min(period_nanos - start);
+
+                Sample {
+                    start: bin * idx as u32,
+                    bandwidth: (count as u128 * MAHIMAHI_PACKET_SIZE as u128 * NANOS_PER_SEC
+                        / width) as u64,
+                    delay: None,
+                }
+            })
+            .collect();
+
+        Ok(Self { samples, period })
+    }
+
+    /// Parses a CSV trace
+    ///
+    /// Each row contains `time_ms,bandwidth_kbps[,delay_ms]` and the parameters are held until the
+    /// next row. A header row is allowed. Once the last row is reached, the trace repeats.
+    pub fn from_csv(contents: &str) -> Result<Self> {
+        let mut samples: Vec<Sample> = vec![];
+        let mut has_header = false;
+
+        for (line_number, line) in lines(contents) {
+            let mut columns = line.split(',').map(str::trim);
+
+            let start = columns.next().unwrap_or_default();
+            let start: f64 = match start.parse() {
+                Ok(start) => start,
+                // skip the header
+                Err(_) if samples.is_empty() && !has_header => {
+                    has_header = true;
+                    continue;
+                }
+                Err(err) => return Err(anyhow!("invalid time on line {}: {}", line_number, err)),
+            };
+
+            let bandwidth: f64 = columns
+                .next()
+                .ok_or_else(|| anyhow!("missing bandwidth on line {}", line_number))?
+                .parse()
+                .map_err(|err| anyhow!("invalid bandwidth on line {}: {}", line_number, err))?;
+
+            let delay = columns
+                .next()
+                .filter(|v| !v.is_empty())
+                .map(|v| v.parse::<f64>())
+                .transpose()
+                .map_err(|err| anyhow!("invalid delay on line {}: {}", line_number, err))?;
+
+            let sample = Sample {
+                start: Duration::from_secs_f64(start.max(0.0) / 1000.0),
+                // convert kbit/s to bytes/s
+                bandwidth: (bandwidth.max(0.0) * 1000.0 / 8.0) as u64,
+                delay: delay.map(|v| Duration::from_secs_f64(v.max(0.0) / 1000.0)),
+            };
+
+            if let Some(prev) = samples.last() {
+                if prev.start > sample.start {
+                    return Err(anyhow!("trace times must increase on line {}", line_number));
+                }
+            }
+
+            samples.push(sample);
+        }
+
+        let last = samples.last().ok_or_else(|| anyhow!("trace is empty"))?;
+
+        // hold the last row for as long as the one before it
+        let hold = samples
+            .iter()
+            .rev()
+            .nth(1)
+            .map_or(MIN_HOLD, |prev| last.start - prev.start)
+            .max(MIN_HOLD);
+        let period = last.start + hold;
+
+        Ok(Self { samples, period })
+    }
+
+    /// Returns the offset from the start of the trace at which `len` bytes, which start
+    /// serializing at `offset`, have been fully delivered
+    ///
+    /// Bytes are queued across any periods where the link has no capacity. `None` is returned if
+    /// the trace is never able to deliver anything.
+    pub fn transmit(&self, offset: Duration, len: u64) -> Option<Duration> {
+        if !self
+            .segments()
+            .any(|(start, end, bandwidth)| start < end && bandwidth > 0)
+        {
+            return None;
+        }
+
+        let period = self.period.as_nanos();
+        let mut now = offset.as_nanos();
+        // the start of the current repetition of the trace
+        let mut base = now / period * period;
+        // track the remaining bytes scaled by nanoseconds to avoid losing fractional bytes
+        let mut remaining = len as u128 * NANOS_PER_SEC;
+
+        loop {
+            for (start, end, bandwidth) in self.segments() {
+                let end = base + end;
+
+                // skip the bins that have already passed, including those without any capacity
+                if end <= now || bandwidth == 0 {
+                    now = now.max(end);
+                    continue;
+                }
+
+                let start = now.max(base + start);
+                let bandwidth = bandwidth as u128;
+                let available = (end - start) * bandwidth;
+
+                if available >= remaining {
+                    // round up to the next nanosecond so the bytes are fully delivered
+                    let end = start + (remaining + bandwidth - 1) / bandwidth;
+                    return Some(Duration::from_nanos(end as u64));
+                }
+
+                remaining -= available;
+                now = end;
+            }
+
+            base += period;
+        }
+    }
+
+    /// Returns the `(start, end, bandwidth)` of each sample within a single repetition of the
+    /// trace, in nanoseconds
+    fn segments(&self) -> impl Iterator<Item = (u128, u128, u64)> + '_ {
+        let period = self.period.as_nanos();
+
+        self.samples.iter().enumerate().map(move |(idx, sample)| {
+            // the first sample is held from the start of each repetition
+            let start = if idx == 0 { 0 } else { sample.start.as_nanos() };
+            let end = self
+                .samples
+                .get(idx + 1)
+                .map_or(period, |next| next.start.as_nanos());
+            (start, end, sample.bandwidth)
+        })
+    }
+
+    /// Replays the trace on the link for the given direction in simulated time
+    pub fn drive(self: &Arc<Self>, model: &Model, direction: Direction) {
+        let start = time::now();
+
+        direction.link(model).set_capacity(Schedule {
+            trace: self.clone(),
+            start,
+        });
+
+        // the capacity is computed from the trace so only the delay needs to be updated
+        if self.samples.iter().all(|sample| sample.delay.is_none()) {
+            return;
+        }
+
+        let trace = self.clone();
+        let model = model.clone();
+
+        spawn(async move {
+            let mut offset = Duration::ZERO;
+
+            loop {
+                for sample in trace.samples.iter() {
+                    time::delay_until(start + offset + sample.start).await;
+
+                    if let Some(delay) = sample.delay {
+                        direction.link(&model).set_delay(Some(delay));
+                    }
+                }
+
+                offset += trace.period;
+            }
+        });
+    }
+
+    /// Records the trace in the stats output
+    pub fn write<W: std::io::Write>(
+        &self,
+        mut w: W,
+        direction: Direction,
+        bin: Bin,
+    ) -> std::io::Result<()> {
+        // split the trace into chunks to stay under the maximum message size
+        for chunk in self.samples.chunks(1000) {
+            stats::Trace {
+                period: Some(self.period.into()),
+                direction: direction.to_string(),
+                bin: Some(bin.duration().into()),
+                samples: chunk
+                    .iter()
+                    .map(|sample| stats::TraceSample {
+                        start: Some(sample.start.into()),
+                        bandwidth: sample.bandwidth,
+                        delay: sample.delay.map(|v| v.into()),
+                    })
+                    .collect(),
+            }
+            .write(&mut w)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// The capacity of a link which replays a trace from the given start time
+struct Schedule {
+    trace: Arc<Trace>,
+    start: Timestamp,
+}
+
+impl Capacity for Schedule {
+    fn transmit(&self, start: Timestamp, len: u64) -> Option<Timestamp> {
+        let offset = start.saturating_duration_since(self.start);
+        let end = self.trace.transmit(offset, len)?;
+        Some(self.start + end)
+    }
+}
+
+fn lines(contents: &str) -> impl Iterator<Item = (usize, &str)> {
+    contents
+        .lines()
+        .enumerate()
+        .map(|(idx, line)| (idx + 1, line.trim()))
+        .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_MAHIMAHI: &str = "50\n100\n150\n250\n300\n";
+
+    #[test]
+    fn mahimahi_test() {
+        let trace = Trace::from_mahimahi(TEST_MAHIMAHI, "100ms".parse().unwrap()).unwrap();
+
+        assert_eq!(trace.period, Duration::from_millis(300));
+        assert_eq!(
+            trace
+                .samples
+                .iter()
+                .map(|sample| sample.bandwidth)
+                .collect::<Vec<_>>(),
+            vec![30_000, 30_000, 15_000]
+        );
+    }
+
+    #[test]
+    fn mahimahi_bin_test() {
+        let trace = Trace::from_mahimahi(TEST_MAHIMAHI, "50ms".parse().unwrap()).unwrap();
+
+        assert_eq!(trace.period, Duration::from_millis(300));
+        assert_eq!(
+            trace
+                .samples
+                .iter()
+                .map(|sample| sample.bandwidth)
+                .collect::<Vec<_>>(),
+            vec![30_000, 30_000, 30_000, 30_000, 0, 30_000]
+        );
+        assert!("0ms".parse::<Bin>().is_err());
+    }
+
+    #[test]
+    fn csv_test() {
+        let trace = Trace::from_csv("time,bandwidth,delay\n0,8000,20\n1000,800\n").unwrap();
+
+        assert_eq!(trace.period, Duration::from_secs(2));
+        assert_eq!(
+            &trace.samples,
+            &[
+                Sample {
+                    start: Duration::ZERO,
+                    bandwidth: 1_000_000,
+                    delay: Some(Duration::from_millis(20)),
+                },
+                Sample {
+                    start: Duration::from_secs(1),
+                    bandwidth: 100_000,
+                    delay: None,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn csv_ordering_test() {
+        assert!(Trace::from_csv("1000,800\n0,800\n").is_err());
+    }
+
+    #[test]
+    fn transmit_test() {
+        // 1000 bytes/s for a second, then an outage for a second, then 1000 bytes/s again
+        let trace = Trace::from_csv("0,8\n1000,0\n2000,8\n").unwrap();
+        assert_eq!(trace.period, Duration::from_secs(3));
+
+        let ms = Duration::from_millis;
+        let transmit = |offset, len| trace.transmit(ms(offset), len).map(|v| v.as_millis());
+
+        assert_eq!(transmit(0, 500), Some(500));
+        // the packet is queued across the outage instead of being dropped
+        assert_eq!(transmit(800, 500), Some(2300));
+        assert_eq!(transmit(1500, 100), Some(2100));
+        // the trace wraps around to the next repetition
+        assert_eq!(transmit(2900, 200), Some(3100));
+        assert_eq!(transmit(3000 * 4 + 100, 100), Some(3000 * 4 + 200));
+
+        let outage = Trace::from_csv("0,0\n1000,0\n").unwrap();
+        assert_eq!(outage.transmit(Duration::ZERO, 1), None);
+    }
+
+    #[test]
+    fn schedule_test() {
+        let trace = Trace::from_mahimahi("100\n200\n400\n", "100ms".parse().unwrap()).unwrap();
+        let trace = Arc::new(trace);
+        let start = unsafe { Timestamp::from_duration(Duration::from_secs(1)) };
+        let schedule = Schedule { trace, start };
+
+        let ms = |v| start + Duration::from_millis(v);
+
+        // each bin delivers a single packet, except for the empty bin at 300ms
+        assert_eq!(schedule.transmit(ms(0), 1500), Some(ms(100)));
+        assert_eq!(schedule.transmit(ms(100), 1500), Some(ms(200)));
+        assert_eq!(schedule.transmit(ms(250), 1500), Some(ms(450)));
+        assert_eq!(schedule.transmit(ms(300), 1500), Some(ms(500)));
+    }
+}
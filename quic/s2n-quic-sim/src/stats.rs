@@ -22,6 +22,8 @@ pub enum Stats {
     Setup(Setup),
     Parameters(Parameters),
     Connection(Connection),
+    Trace(Trace),
+    Timeline(Timeline),
 }
 
 impl Stats {
@@ -84,6 +86,14 @@ impl<R: io::Read> Reader<R> {
                     let msg = Setup::decode(buffer)?;
                     Ok(msg.into())
                 }
+                3 => {
+                    let msg = Trace::decode(buffer)?;
+                    Ok(msg.into())
+                }
+                4 => {
+                    let msg = Timeline::decode(buffer)?;
+                    Ok(msg.into())
+                }
                 _ => Err(io::Error::new(
                     io::ErrorKind::InvalidData,
                     "invalid stat tag",
@@ -123,6 +133,75 @@ impl Setup {
     }
 }
 
+#[derive(Clone, Message)]
+pub struct Trace {
+    #[prost(message, tag = "1")]
+    pub period: Option<Duration>,
+    #[prost(message, repeated, tag = "2")]
+    pub samples: Vec<TraceSample>,
+    #[prost(string, tag = "3")]
+    pub direction: String,
+    #[prost(message, tag = "4")]
+    pub bin: Option<Duration>,
+}
+
+impl From<Trace> for Stats {
+    fn from(t: Trace) -> Self {
+        Self::Trace(t)
+    }
+}
+
+impl Trace {
+    pub fn write<W: io::Write>(&self, w: W) -> io::Result<()> {
+        Stats::write(w, 3, self)
+    }
+}
+
+#[derive(Clone, Copy, Message, PartialEq, Eq)]
+pub struct TraceSample {
+    #[prost(message, tag = "1")]
+    pub start: Option<Duration>,
+    #[prost(uint64, tag = "2")]
+    pub bandwidth: u64,
+    #[prost(message, tag = "3")]
+    pub delay: Option<Duration>,
+}
+
+/// A point-in-time sample of a connection's progress
+#[derive(Clone, Copy, Message)]
+pub struct Timeline {
+    #[prost(uint64, tag = "1")]
+    pub seed: u64,
+    #[prost(uint64, tag = "2")]
+    pub id: u64,
+    #[prost(message, tag = "3")]
+    pub time: Option<Duration>,
+    #[prost(uint64, tag = "4")]
+    pub rx_stream_progress: u64,
+    #[prost(uint64, tag = "5")]
+    pub tx_stream_progress: u64,
+    #[prost(message, tag = "6")]
+    pub smoothed_rtt: Option<Duration>,
+    #[prost(message, tag = "7")]
+    pub latest_rtt: Option<Duration>,
+    #[prost(uint64, tag = "8")]
+    pub cwin: u64,
+    #[prost(bool, tag = "9")]
+    pub is_client: bool,
+}
+
+impl From<Timeline> for Stats {
+    fn from(t: Timeline) -> Self {
+        Self::Timeline(t)
+    }
+}
+
+impl Timeline {
+    pub fn write<W: io::Write>(&self, w: W) -> io::Result<()> {
+        Stats::write(w, 4, self)
+    }
+}
+
 #[derive(Clone, Copy, Message)]
 pub struct Parameters {
     #[prost(uint64, tag = "1")]
@@ -787,3 +866,59 @@ impl<'de> serde::Deserialize<'de> for Filter {
         deserializer.deserialize_str(StrVisitor::<Self>(Default::default()))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn trace_round_trip_test() {
+        let sample = TraceSample {
+            start: Some(core::time::Duration::from_millis(100).into()),
+            bandwidth: 15_000,
+            delay: None,
+        };
+
+        let mut buffer = vec![];
+        Trace {
+            period: Some(core::time::Duration::from_millis(300).into()),
+            samples: vec![sample],
+            direction: "uplink".to_owned(),
+            bin: Some(core::time::Duration::from_millis(50).into()),
+        }
+        .write(&mut buffer)
+        .unwrap();
+        Timeline {
+            seed: 1,
+            rx_stream_progress: 1000,
+            is_client: true,
+            ..Default::default()
+        }
+        .write(&mut buffer)
+        .unwrap();
+
+        let mut reader = Stats::reader(&buffer[..]);
+
+        match reader.next().unwrap().unwrap() {
+            Stats::Trace(trace) => {
+                assert_eq!(trace.samples, vec![sample]);
+                assert_eq!(trace.direction, "uplink");
+                assert_eq!(
+                    trace.bin.unwrap().as_duration(),
+                    core::time::Duration::from_millis(50)
+                );
+            }
+            other => panic!("unexpected stat: {other:?}"),
+        }
+
+        match reader.next().unwrap().unwrap() {
+            Stats::Timeline(timeline) => {
+                assert_eq!(timeline.rx_stream_progress, 1000);
+                assert!(timeline.is_client);
+            }
+            other => panic!("unexpected stat: {other:?}"),
+        }
+
+        assert!(reader.next().is_none());
+    }
+}
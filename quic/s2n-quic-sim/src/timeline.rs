@@ -0,0 +1,364 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    stats::{Stats, Timeline as Sample, TraceSample},
+    Result,
+};
+use core::time::Duration;
+use serde_json::json;
+use std::{
+    collections::{BTreeMap, HashSet},
+    fs, io,
+    path::PathBuf,
+};
+use structopt::StructOpt;
+
+/// Plots connection throughput and RTT against the network trace over simulated time
+#[derive(Debug, StructOpt)]
+pub struct Timeline {
+    #[structopt(long)]
+    title: Option<String>,
+
+    input: PathBuf,
+}
+
+/// The interval used when the input doesn't record one
+const DEFAULT_INTERVAL: Duration = Duration::from_millis(100);
+
+#[derive(Debug, Default, PartialEq)]
+struct Bin {
+    /// Bytes received by the clients
+    downlink_bytes: u64,
+    /// Bytes received by the servers
+    uplink_bytes: u64,
+    rtt_sum: f64,
+    rtt_count: u64,
+}
+
+/// A trace applied to one direction of the network
+#[derive(Debug, Default)]
+struct Trace {
+    period: Duration,
+    samples: Vec<TraceSample>,
+}
+
+/// Groups the connection samples into bins of the given interval
+///
+/// Returns the bins, keyed by their index, along with the time of the last sample.
+fn bin_samples(
+    connections: &mut BTreeMap<(u64, u64), Vec<Sample>>,
+    interval: Duration,
+) -> (BTreeMap<u64, Bin>, Duration) {
+    let mut bins: BTreeMap<u64, Bin> = BTreeMap::new();
+    let mut end = Duration::ZERO;
+
+    for samples in connections.values_mut() {
+        samples.sort_by_key(|sample| sample.time.unwrap_or_default().as_duration());
+
+        let mut prev_rx = 0;
+        for sample in samples.iter() {
+            let time = sample.time.unwrap_or_default().as_duration();
+            end = end.max(time);
+
+            let bin = bins
+                .entry((time.as_nanos() / interval.as_nanos()) as u64)
+                .or_default();
+
+            let rx_bytes = sample.rx_stream_progress - prev_rx;
+            prev_rx = sample.rx_stream_progress;
+
+            if sample.is_client {
+                bin.downlink_bytes += rx_bytes;
+            } else {
+                bin.uplink_bytes += rx_bytes;
+            }
+
+            if let Some(rtt) = sample.smoothed_rtt {
+                bin.rtt_sum += rtt.as_duration().as_secs_f64();
+                bin.rtt_count += 1;
+            }
+        }
+    }
+
+    (bins, end)
+}
+
+impl Timeline {
+    pub fn run(&self) -> Result {
+        let reader = fs::File::open(&self.input)?;
+        let reader = io::BufReader::new(reader);
+        let reader = Stats::reader(reader);
+
+        let mut args = vec![];
+        let mut interval = None;
+        let mut traces: BTreeMap<String, Trace> = BTreeMap::new();
+        let mut connections: BTreeMap<(u64, u64), Vec<Sample>> = BTreeMap::new();
+        let mut seeds = HashSet::new();
+
+        for stat in reader {
+            match stat? {
+                Stats::Setup(s) => {
+                    args = s.args;
+                }
+                Stats::Trace(t) => {
+                    if let Some(bin) = t.bin {
+                        interval = Some(bin.as_duration());
+                    }
+
+                    let trace = traces.entry(t.direction).or_default();
+                    trace.period = t.period.unwrap_or_default().as_duration();
+                    trace.samples.extend(t.samples);
+                }
+                Stats::Timeline(sample) => {
+                    seeds.insert(sample.seed);
+                    connections
+                        .entry((sample.seed, sample.id))
+                        .or_default()
+                        .push(sample);
+                }
+                Stats::Parameters(_) | Stats::Connection(_) => {
+                    // unused
+                }
+            }
+        }
+
+        let interval = interval.unwrap_or(DEFAULT_INTERVAL);
+        let (bins, end) = bin_samples(&mut connections, interval);
+
+        let interval = interval.as_secs_f64();
+        let seeds = seeds.len().max(1) as f64;
+
+        let mut throughput = vec![];
+        let mut rtt = vec![];
+
+        for (idx, bin) in bins.iter() {
+            let time = *idx as f64 * interval;
+
+            // average the goodput across all of the seeds in megabits per second
+            for (bytes, series) in [
+                (bin.downlink_bytes, "downlink goodput"),
+                (bin.uplink_bytes, "uplink goodput"),
+            ] {
+                let mbps = bytes as f64 * 8.0 / interval / seeds / 1_000_000.0;
+                throughput.push(json!({ "time": time, "value": mbps, "series": series }));
+            }
+
+            if bin.rtt_count > 0 {
+                let ms = bin.rtt_sum / bin.rtt_count as f64 * 1000.0;
+                rtt.push(json!({ "time": time, "value": ms, "series": "smoothed_rtt" }));
+            }
+        }
+
+        // repeat each trace until the last sample
+        for (direction, trace) in traces.iter() {
+            if trace.period.is_zero() {
+                continue;
+            }
+
+            let series = format!("{direction} trace");
+            let delay_series = format!("{direction} delay");
+
+            let mut offset = Duration::ZERO;
+            while offset <= end {
+                for sample in trace.samples.iter() {
+                    let time = offset + sample.start.unwrap_or_default().as_duration();
+                    let time = time.as_secs_f64();
+
+                    let mbps = sample.bandwidth as f64 * 8.0 / 1_000_000.0;
+                    throughput.push(json!({ "time": time, "value": mbps, "series": series }));
+
+                    if let Some(delay) = sample.delay {
+                        let ms = delay.as_duration().as_secs_f64() * 1000.0;
+                        rtt.push(json!({ "time": time, "value": ms, "series": delay_series }));
+                    }
+                }
+                offset += trace.period;
+            }
+        }
+
+        let title = self
+            .title
+            .clone()
+            .unwrap_or_else(|| "throughput and rtt vs trace".to_owned());
+
+        let width = 1000;
+        let height = 300;
+        let padding = 60;
+
+        let chart = |name: &str, data: &str, offset: u32, label: &str| {
+            json!({
+                "type": "group",
+                "name": name,
+                "encode": {
+                    "enter": {
+                        "y": { "value": offset },
+                        "width": { "value": width },
+                        "height": { "value": height }
+                    }
+                },
+                "scales": [
+                    {
+                        "name": "scale$x",
+                        "type": "linear",
+                        "domain": { "data": data, "field": "time" },
+                        "range": "width",
+                        "zero": true
+                    },
+                    {
+                        "name": "scale$y",
+                        "type": "linear",
+                        "domain": { "data": data, "field": "value" },
+                        "range": "height",
+                        "nice": true,
+                        "zero": true
+                    }
+                ],
+                "axes": [
+                    { "orient": "bottom", "scale": "scale$x", "title": "time (s)" },
+                    { "orient": "left", "scale": "scale$y", "title": label }
+                ],
+                "marks": [
+                    {
+                        "type": "group",
+                        "from": {
+                            "facet": { "name": "series", "data": data, "groupby": "series" }
+                        },
+                        "marks": [
+                            {
+                                "type": "line",
+                                "from": { "data": "series" },
+                                "encode": {
+                                    "enter": {
+                                        "x": { "scale": "scale$x", "field": "time" },
+                                        "y": { "scale": "scale$y", "field": "value" },
+                                        "stroke": { "scale": "scale$color", "field": "series" },
+                                        "strokeWidth": { "value": 1.5 },
+                                        "interpolate": { "value": "step-after" }
+                                    }
+                                }
+                            }
+                        ]
+                    }
+                ]
+            })
+        };
+
+        let vega = json!({
+            "$schema": "https://vega.github.io/schema/vega/v5.json",
+            "width": width,
+            "height": height * 2 + padding,
+            "padding": 5,
+            "background": "white",
+
+            "title": {
+                "text": title,
+                "anchor": "middle",
+                "fontSize": 16,
+                "frame": "group",
+                "offset": 4
+            },
+
+            "signals": [
+                {
+                    "name": "sig$command",
+                    "value": args.join(" "),
+                }
+            ],
+
+            "data": [
+                { "name": "data$throughput", "values": throughput },
+                { "name": "data$rtt", "values": rtt }
+            ],
+
+            "scales": [
+                {
+                    "name": "scale$color",
+                    "type": "ordinal",
+                    "domain": [
+                        "downlink trace",
+                        "uplink trace",
+                        "downlink goodput",
+                        "uplink goodput",
+                        "smoothed_rtt",
+                        "downlink delay",
+                        "uplink delay"
+                    ],
+                    "range": "category"
+                }
+            ],
+
+            "legends": [
+                { "stroke": "scale$color", "orient": "top-right" }
+            ],
+
+            "marks": [
+                chart("throughput", "data$throughput", 0, "Mbps"),
+                chart("rtt", "data$rtt", height + padding, "rtt (ms)"),
+            ],
+        });
+
+        println!("{vega}");
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(id: u64, is_client: bool, time_ms: u64, rx: u64, rtt_ms: u64) -> Sample {
+        Sample {
+            seed: 1,
+            id,
+            time: Some(Duration::from_millis(time_ms).into()),
+            rx_stream_progress: rx,
+            smoothed_rtt: Some(Duration::from_millis(rtt_ms).into()),
+            is_client,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn bin_samples_test() {
+        let mut connections = BTreeMap::new();
+        // insert the samples out of order to make sure they're sorted before computing deltas
+        connections.insert(
+            (1, 0),
+            vec![
+                sample(0, true, 150, 3000, 30),
+                sample(0, true, 0, 1000, 10),
+                sample(0, true, 50, 1500, 20),
+            ],
+        );
+        connections.insert((1, 1), vec![sample(1, false, 120, 400, 40)]);
+
+        let (bins, end) = bin_samples(&mut connections, Duration::from_millis(100));
+
+        assert_eq!(end, Duration::from_millis(150));
+        assert_eq!(
+            bins.into_iter().collect::<Vec<_>>(),
+            vec![
+                (
+                    0,
+                    Bin {
+                        downlink_bytes: 1500,
+                        uplink_bytes: 0,
+                        rtt_sum: 0.030,
+                        rtt_count: 2,
+                    }
+                ),
+                (
+                    1,
+                    Bin {
+                        downlink_bytes: 1500,
+                        uplink_bytes: 400,
+                        rtt_sum: 0.070,
+                        rtt_count: 2,
+                    }
+                ),
+            ]
+        );
+    }
+}
@@ -5,15 +5,12 @@ use bytes::{Bytes, BytesMut};
 use core::{ffi::c_void, marker::PhantomData};
 use s2n_quic_core::{
     application::ServerName,
-    crypto::{tls, CryptoError, CryptoSuite},
+    crypto::{
+        provider::{Provider, SecretPair},
+        tls, CryptoError,
+    },
     endpoint, transport,
 };
-use s2n_quic_crypto::{
-    handshake::HandshakeKey,
-    one_rtt::OneRttKey,
-    ring::{aead, hkdf},
-    Prk, SecretPair, Suite,
-};
 use s2n_tls::{connection::Connection, error::Fallible, ffi::*};
 
 /// The preallocated size of the outgoing buffer
@@ -37,17 +34,7 @@ pub struct Callback<'a, T, C> {
 impl<'a, T, C> Callback<'a, T, C>
 where
     T: 'a + tls::Context<C>,
-    C: CryptoSuite<
-        HandshakeKey = <Suite as CryptoSuite>::HandshakeKey,
-        HandshakeHeaderKey = <Suite as CryptoSuite>::HandshakeHeaderKey,
-        InitialKey = <Suite as CryptoSuite>::InitialKey,
-        InitialHeaderKey = <Suite as CryptoSuite>::InitialHeaderKey,
-        OneRttKey = <Suite as CryptoSuite>::OneRttKey,
-        OneRttHeaderKey = <Suite as CryptoSuite>::OneRttHeaderKey,
-        ZeroRttKey = <Suite as CryptoSuite>::ZeroRttKey,
-        ZeroRttHeaderKey = <Suite as CryptoSuite>::ZeroRttHeaderKey,
-        RetryKey = <Suite as CryptoSuite>::RetryKey,
-    >,
+    C: Provider,
 {
     /// Initializes the s2n-tls connection with all of the contexts and callbacks
     ///
@@ -169,8 +156,7 @@ where
                     return Ok(());
                 }
 
-                let (prk_algo, _aead) = get_algo_type(conn).ok_or(CryptoError::INTERNAL_ERROR)?;
-                let secret = Prk::new_less_safe(prk_algo, secret);
+                let secret = Secret::new(secret).ok_or(CryptoError::INTERNAL_ERROR)?;
                 self.state.secrets = Secrets::Half { secret, id };
 
                 Ok(())
@@ -179,9 +165,7 @@ where
                 id: other_id,
                 secret: other_secret,
             } => {
-                let (prk_algo, aead_algo) =
-                    get_algo_type(conn).ok_or(CryptoError::INTERNAL_ERROR)?;
-                let secret = Prk::new_less_safe(prk_algo, secret);
+                let cipher_suite = get_cipher_suite(conn).ok_or(CryptoError::INTERNAL_ERROR)?;
                 let pair = match (id, other_id) {
                     (
                         s2n_secret_type_t::CLIENT_HANDSHAKE_TRAFFIC_SECRET,
@@ -192,7 +176,7 @@ where
                         s2n_secret_type_t::SERVER_APPLICATION_TRAFFIC_SECRET,
                     ) => SecretPair {
                         client: secret,
-                        server: other_secret,
+                        server: other_secret.as_bytes(),
                     },
                     (
                         s2n_secret_type_t::SERVER_HANDSHAKE_TRAFFIC_SECRET,
@@ -203,7 +187,7 @@ where
                         s2n_secret_type_t::CLIENT_APPLICATION_TRAFFIC_SECRET,
                     ) => SecretPair {
                         server: secret,
-                        client: other_secret,
+                        client: other_secret.as_bytes(),
                     },
                     _ => {
                         debug_assert!(false, "invalid key phase");
@@ -216,16 +200,17 @@ where
 
                 match self.state.tx_phase {
                     HandshakePhase::Initial => {
-                        let (key, header_key) = HandshakeKey::new(self.endpoint, aead_algo, pair)
-                            .expect("invalid cipher");
+                        let (key, header_key) =
+                            C::handshake_keys(self.endpoint, cipher_suite, pair)
+                                .expect("invalid cipher");
 
                         self.context.on_handshake_keys(key, header_key)?;
                         self.state.tx_phase.transition();
                         self.state.rx_phase.transition();
                     }
                     _ => {
-                        let (key, header_key) =
-                            OneRttKey::new(self.endpoint, aead_algo, pair).expect("invalid cipher");
+                        let (key, header_key) = C::one_rtt_keys(self.endpoint, cipher_suite, pair)
+                            .expect("invalid cipher");
 
                         let params = unsafe {
                             // Safety: conn needs to outlive params
@@ -387,7 +372,7 @@ impl Default for HandshakePhase {
 enum Secrets {
     Waiting,
     Half {
-        secret: Prk,
+        secret: Secret,
         id: s2n_secret_type_t::Type,
     },
 }
@@ -398,9 +383,48 @@ impl Default for Secrets {
     }
 }
 
-fn get_algo_type(
-    connection: *mut s2n_connection,
-) -> Option<(hkdf::Algorithm, &'static aead::Algorithm)> {
+/// The largest secret emitted by s2n-tls, which is the output length of SHA384
+const MAX_SECRET_LEN: usize = 48;
+
+/// Holds a copy of the first secret emitted for a key phase until its pair is available
+struct Secret {
+    bytes: [u8; MAX_SECRET_LEN],
+    len: usize,
+}
+
+impl Secret {
+    fn new(secret: &[u8]) -> Option<Self> {
+        let mut bytes = [0; MAX_SECRET_LEN];
+        bytes.get_mut(..secret.len())?.copy_from_slice(secret);
+        Some(Self {
+            bytes,
+            len: secret.len(),
+        })
+    }
+
+    fn as_bytes(&self) -> &[u8] {
+        &self.bytes[..self.len]
+    }
+}
+
+impl core::fmt::Debug for Secret {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        // don't print the actual secret
+        f.debug_struct("Secret").field("len", &self.len).finish()
+    }
+}
+
+impl Drop for Secret {
+    fn drop(&mut self) {
+        for byte in self.bytes.iter_mut() {
+            // Safety: the pointer is valid and aligned since it comes from a reference. The
+            //         volatile write keeps the compiler from eliding the store.
+            unsafe { core::ptr::write_volatile(byte, 0) };
+        }
+    }
+}
+
+fn get_cipher_suite(connection: *mut s2n_connection) -> Option<tls::CipherSuite> {
     let mut cipher = [0, 0];
     unsafe {
         s2n_connection_get_cipher_iana_value(connection, &mut cipher[0], &mut cipher[1])
@@ -437,9 +461,9 @@ fn get_algo_type(
     //# exception of TLS_AES_128_CCM_8_SHA256.
 
     match cipher {
-        TLS_AES_128_GCM_SHA256 => Some((hkdf::HKDF_SHA256, &aead::AES_128_GCM)),
-        TLS_AES_256_GCM_SHA384 => Some((hkdf::HKDF_SHA384, &aead::AES_256_GCM)),
-        TLS_CHACHA20_POLY1305_SHA256 => Some((hkdf::HKDF_SHA256, &aead::CHACHA20_POLY1305)),
+        TLS_AES_128_GCM_SHA256 => Some(tls::CipherSuite::TLS_AES_128_GCM_SHA256),
+        TLS_AES_256_GCM_SHA384 => Some(tls::CipherSuite::TLS_AES_256_GCM_SHA384),
+        TLS_CHACHA20_POLY1305_SHA256 => Some(tls::CipherSuite::TLS_CHACHA20_POLY1305_SHA256),
         _ => None,
     }
 }
@@ -8,8 +8,14 @@ use crate::{
     session::Session,
     ConfigLoader,
 };
+use core::marker::PhantomData;
 use s2n_codec::EncoderValue;
-use s2n_quic_core::{application::ServerName, crypto::tls, endpoint};
+use s2n_quic_core::{
+    application::ServerName,
+    crypto::{provider::Provider, tls},
+    endpoint,
+};
+use s2n_quic_crypto::Suite;
 use s2n_tls::{
     callbacks::VerifyHostNameCallback,
     config::{self, Config},
@@ -18,11 +24,12 @@ use s2n_tls::{
 };
 use std::sync::Arc;
 
-pub struct Client<L: ConfigLoader = Config> {
+pub struct Client<L: ConfigLoader = Config, C: Provider = Suite> {
     loader: L,
     #[allow(dead_code)] // we need to hold on to the handle to ensure it is cleaned up correctly
     keylog: Option<KeyLogHandle>,
     params: Params,
+    crypto: PhantomData<fn() -> C>,
 }
 
 impl Client {
@@ -44,6 +51,7 @@ impl<L: ConfigLoader> Client<L> {
             loader,
             keylog: None,
             params: Default::default(),
+            crypto: PhantomData,
         }
     }
 }
@@ -56,16 +64,17 @@ impl Default for Client {
     }
 }
 
-impl<L: ConfigLoader> ConfigLoader for Client<L> {
+impl<L: ConfigLoader, C: 'static + Provider> ConfigLoader for Client<L, C> {
     #[inline]
     fn load(&mut self, cx: crate::ConnectionContext) -> s2n_tls::config::Config {
         self.loader.load(cx)
     }
 }
 
-pub struct Builder {
+pub struct Builder<C: Provider = Suite> {
     config: config::Builder,
     keylog: Option<KeyLogHandle>,
+    crypto: PhantomData<fn() -> C>,
 }
 
 impl Default for Builder {
@@ -79,11 +88,23 @@ impl Default for Builder {
         Self {
             config,
             keylog: None,
+            crypto: PhantomData,
         }
     }
 }
 
-impl Builder {
+impl<Crypto: Provider> Builder<Crypto> {
+    /// Derives the packet protection keys for the handshake through the crypto provider `P`
+    ///
+    /// By default, keys are derived with [`s2n_quic_crypto::Suite`].
+    pub fn with_crypto_provider<P: Provider>(self) -> Result<Builder<P>, Error> {
+        Ok(Builder {
+            config: self.config,
+            keylog: self.keylog,
+            crypto: PhantomData,
+        })
+    }
+
     pub fn with_application_protocols<P: IntoIterator<Item = I>, I: AsRef<[u8]>>(
         mut self,
         protocols: P,
@@ -181,17 +202,18 @@ impl Builder {
         Ok(self)
     }
 
-    pub fn build(self) -> Result<Client, Error> {
+    pub fn build(self) -> Result<Client<Config, Crypto>, Error> {
         Ok(Client {
             loader: self.config.build()?,
             keylog: self.keylog,
             params: Default::default(),
+            crypto: PhantomData,
         })
     }
 }
 
-impl<L: ConfigLoader> tls::Endpoint for Client<L> {
-    type Session = Session;
+impl<L: ConfigLoader, C: 'static + Provider> tls::Endpoint for Client<L, C> {
+    type Session = Session<C>;
 
     fn new_server_session<Params: EncoderValue>(&mut self, _params: &Params) -> Self::Session {
         panic!("cannot create a server session from a client config");
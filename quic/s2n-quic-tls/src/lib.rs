@@ -3,6 +3,7 @@
 
 use s2n_quic_core::application::ServerName;
 use s2n_tls::config::Config;
+use std::sync::{Arc, Mutex};
 
 /// Ensure memory is correctly managed in tests
 #[cfg(test)]
@@ -47,9 +48,57 @@ impl ConfigLoader for Box<dyn ConfigLoader> {
     }
 }
 
+/// A [`ConfigLoader`] which can be replaced while the endpoint is running
+///
+/// This can be used to rotate certificates without restarting the endpoint. Each new handshake
+/// loads its config from the most recently stored loader. Handshakes which have already started
+/// and established connections keep using the config they were created with.
+///
+/// ```rust,ignore
+/// let reloadable = ReloadableConfig::new(Server::builder().with_certificate(cert, key)?.build()?);
+/// let server = Server::from_loader(reloadable.clone());
+///
+/// // later on, after the certificate has been renewed
+/// reloadable.update(Server::builder().with_certificate(new_cert, new_key)?.build()?);
+/// ```
+#[derive(Clone)]
+pub struct ReloadableConfig {
+    loader: Arc<Mutex<Box<dyn ConfigLoader>>>,
+}
+
+impl ReloadableConfig {
+    pub fn new<L: ConfigLoader>(loader: L) -> Self {
+        Self {
+            loader: Arc::new(Mutex::new(Box::new(loader))),
+        }
+    }
+
+    /// Replaces the loader used for subsequent handshakes
+    pub fn update<L: ConfigLoader>(&self, loader: L) {
+        let loader: Box<dyn ConfigLoader> = Box::new(loader);
+        // drop the previous loader after releasing the lock
+        let _previous = core::mem::replace(&mut *self.lock(), loader);
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<Box<dyn ConfigLoader>> {
+        // a panicking loader doesn't leave the stored value in an inconsistent state
+        self.loader
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl ConfigLoader for ReloadableConfig {
+    #[inline]
+    fn load(&mut self, cx: ConnectionContext) -> Config {
+        self.lock().load(cx)
+    }
+}
+
 mod callback;
 mod keylog;
 mod params;
+mod server_name;
 mod session;
 
 pub mod certificate;
@@ -2,31 +2,38 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    certificate::{Format, IntoCertificate, IntoPrivateKey},
+    certificate::{Certificate, Format, IntoCertificate, IntoPrivateKey, PrivateKey},
     keylog::KeyLogHandle,
     params::Params,
+    server_name::{Pattern, ServerNameConfigs},
     session::Session,
     ConfigLoader,
 };
+use core::marker::PhantomData;
 use s2n_codec::EncoderValue;
-use s2n_quic_core::{application::ServerName, crypto::tls, endpoint};
-#[cfg(any(test, all(s2n_quic_unstable, feature = "unstable_client_hello")))]
-use s2n_tls::callbacks::ClientHelloCallback;
+use s2n_quic_core::{
+    application::ServerName,
+    crypto::{provider::Provider, tls},
+    endpoint,
+};
+use s2n_quic_crypto::Suite;
 #[cfg(any(test, all(s2n_quic_unstable, feature = "unstable_private_key")))]
-use s2n_tls::callbacks::PrivateKeyCallback;
+use s2n_tls::callbacks::{PrivateKeyCallback, PrivateKeyOperation};
 use s2n_tls::{
-    callbacks::VerifyHostNameCallback,
+    callbacks::{ClientHelloCallback, ConnectionFuture, VerifyHostNameCallback},
     config::{self, Config},
+    connection::Connection,
     enums::ClientAuthType,
     error::Error,
 };
-use std::sync::Arc;
+use std::{pin::Pin, sync::Arc};
 
-pub struct Server<L: ConfigLoader = Config> {
+pub struct Server<L: ConfigLoader = Config, C: Provider = Suite> {
     loader: L,
     #[allow(dead_code)] // we need to hold on to the handle to ensure it is cleaned up correctly
     keylog: Option<KeyLogHandle>,
     params: Params,
+    crypto: PhantomData<fn() -> C>,
 }
 
 impl Server {
@@ -48,6 +55,7 @@ impl<L: ConfigLoader> Server<L> {
             loader,
             keylog: None,
             params: Default::default(),
+            crypto: PhantomData,
         }
     }
 }
@@ -60,64 +68,122 @@ impl Default for Server {
     }
 }
 
-impl<L: ConfigLoader> ConfigLoader for Server<L> {
+impl<L: ConfigLoader, C: 'static + Provider> ConfigLoader for Server<L, C> {
     #[inline]
     fn load(&mut self, cx: crate::ConnectionContext) -> s2n_tls::config::Config {
         self.loader.load(cx)
     }
 }
 
-impl<L: ConfigLoader> From<Server<L>> for Config {
-    fn from(mut server: Server<L>) -> Self {
+impl<L: ConfigLoader, C: 'static + Provider> From<Server<L, C>> for Config {
+    fn from(mut server: Server<L, C>) -> Self {
         server.load(crate::ConnectionContext { server_name: None })
     }
 }
 
-pub struct Builder {
+/// A setting which is applied to the default config and each config selected by server name
+type Setting = Box<dyn Fn(&mut config::Builder) -> Result<(), Error> + Send + Sync>;
+
+pub struct Builder<C: Provider = Suite> {
     config: config::Builder,
     keylog: Option<KeyLogHandle>,
+    settings: Vec<Setting>,
+    server_names: Vec<(Pattern, Certificate, PrivateKey)>,
+    client_hello_handler: Option<Box<dyn ClientHelloCallback>>,
+    crypto: PhantomData<fn() -> C>,
 }
 
 impl Default for Builder {
     fn default() -> Self {
-        let mut config = config::Builder::default();
-        config.enable_quic().unwrap();
-        // https://github.com/aws/s2n-tls/blob/main/docs/USAGE-GUIDE.md#s2n_config_set_cipher_preferences
-        config.set_security_policy(crate::DEFAULT_POLICY).unwrap();
-        config.set_application_protocol_preference([b"h3"]).unwrap();
-
         Self {
-            config,
+            config: default_config(),
             keylog: None,
+            settings: Vec::new(),
+            server_names: Vec::new(),
+            client_hello_handler: None,
+            crypto: PhantomData,
         }
     }
 }
 
-impl Builder {
+fn default_config() -> config::Builder {
+    let mut config = config::Builder::default();
+    config.enable_quic().unwrap();
+    // https://github.com/aws/s2n-tls/blob/main/docs/USAGE-GUIDE.md#s2n_config_set_cipher_preferences
+    config.set_security_policy(crate::DEFAULT_POLICY).unwrap();
+    config.set_application_protocol_preference([b"h3"]).unwrap();
+    config
+}
+
+fn load_certificate(
+    config: &mut config::Builder,
+    certificate: Certificate,
+    private_key: PrivateKey,
+) -> Result<(), Error> {
+    let certificate = certificate
+        .0
+        .as_pem()
+        .expect("pem is currently the only certificate format supported");
+    match private_key.0 {
+        Format::Pem(bytes) => config.load_pem(certificate, bytes.as_ref())?,
+        Format::None => config.load_public_pem(certificate)?,
+        Format::Der(_) => panic!("der private keys not supported"),
+    };
+    Ok(())
+}
+
+impl<Crypto: Provider> Builder<Crypto> {
+    /// Derives the packet protection keys for the handshake through the crypto provider `P`
+    ///
+    /// By default, keys are derived with [`s2n_quic_crypto::Suite`].
+    pub fn with_crypto_provider<P: Provider>(self) -> Result<Builder<P>, Error> {
+        Ok(Builder {
+            config: self.config,
+            keylog: self.keylog,
+            settings: self.settings,
+            server_names: self.server_names,
+            client_hello_handler: self.client_hello_handler,
+            crypto: PhantomData,
+        })
+    }
+
+    /// Sets the handler invoked for each client hello
+    ///
+    /// If certificates are registered with [`Self::with_server_name_certificate`], the handler
+    /// is invoked after the config for the requested server name has been selected.
     #[cfg(any(test, all(s2n_quic_unstable, feature = "unstable_client_hello")))]
     pub fn with_client_hello_handler<T: 'static + ClientHelloCallback>(
         mut self,
         handler: T,
     ) -> Result<Self, Error> {
-        self.config.set_client_hello_callback(handler)?;
+        self.client_hello_handler = Some(Box::new(handler));
         Ok(self)
     }
 
     #[cfg(any(test, all(s2n_quic_unstable, feature = "unstable_private_key")))]
     pub fn with_private_key_handler<T: 'static + PrivateKeyCallback>(
-        mut self,
+        self,
         handler: T,
     ) -> Result<Self, Error> {
-        self.config.set_private_key_callback(handler)?;
-        Ok(self)
+        let handler = Arc::new(handler);
+        self.with_setting(move |config| {
+            config.set_private_key_callback(SharedCallback(handler.clone()))?;
+            Ok(())
+        })
     }
 
     pub fn with_application_protocols<P: IntoIterator<Item = I>, I: AsRef<[u8]>>(
-        mut self,
+        self,
         protocols: P,
     ) -> Result<Self, Error> {
-        self.config.set_application_protocol_preference(protocols)?;
-        Ok(self)
+        let protocols: Vec<Vec<u8>> = protocols
+            .into_iter()
+            .map(|protocol| protocol.as_ref().to_vec())
+            .collect();
+        self.with_setting(move |config| {
+            config.set_application_protocol_preference(&protocols)?;
+            Ok(())
+        })
     }
 
     pub fn with_certificate<C: IntoCertificate, PK: IntoPrivateKey>(
@@ -125,30 +191,58 @@ impl Builder {
         certificate: C,
         private_key: PK,
     ) -> Result<Self, Error> {
-        let private_key = private_key.into_private_key()?.0;
-        let certificate = certificate.into_certificate()?.0;
-        let certificate = certificate
-            .as_pem()
-            .expect("pem is currently the only certificate format supported");
-        match private_key {
-            Format::Pem(bytes) => self.config.load_pem(certificate, bytes.as_ref())?,
-            Format::None => self.config.load_public_pem(certificate)?,
-            Format::Der(_) => panic!("der private keys not supported"),
-        };
+        load_certificate(
+            &mut self.config,
+            certificate.into_certificate()?,
+            private_key.into_private_key()?,
+        )?;
         Ok(self)
     }
 
-    pub fn with_trusted_certificate<C: IntoCertificate>(
+    /// Presents the certificate to clients requesting `server_name`
+    ///
+    /// This allows a single endpoint to host many domains. The name may start with a `*.`
+    /// wildcard, which matches exactly one label, and exact names take precedence over
+    /// wildcards. Clients which don't request a registered name are presented the certificate
+    /// configured with [`Self::with_certificate`].
+    ///
+    /// The config for each server name is created with all of the other settings of this
+    /// builder, regardless of the order in which they are configured.
+    ///
+    /// ```rust,ignore
+    /// let server = Server::builder()
+    ///     .with_certificate(default_cert, default_key)?
+    ///     .with_server_name_certificate("*.example.com", example_cert, example_key)?
+    ///     .build()?;
+    /// ```
+    pub fn with_server_name_certificate<N: AsRef<str>, C: IntoCertificate, PK: IntoPrivateKey>(
         mut self,
+        server_name: N,
+        certificate: C,
+        private_key: PK,
+    ) -> Result<Self, Error> {
+        self.server_names.push((
+            Pattern::new(server_name.as_ref())?,
+            certificate.into_certificate()?,
+            private_key.into_private_key()?,
+        ));
+        Ok(self)
+    }
+
+    pub fn with_trusted_certificate<C: IntoCertificate>(
+        self,
         certificate: C,
     ) -> Result<Self, Error> {
         let certificate = certificate.into_certificate()?;
         let certificate = certificate
             .0
             .as_pem()
-            .expect("pem is currently the only certificate format supported");
-        self.config.trust_pem(certificate)?;
-        Ok(self)
+            .expect("pem is currently the only certificate format supported")
+            .to_vec();
+        self.with_setting(move |config| {
+            config.trust_pem(&certificate)?;
+            Ok(())
+        })
     }
 
     /// Clears the default trust store for this client.
@@ -160,26 +254,29 @@ impl Builder {
     /// Note that call ordering matters. The caller should call this
     /// method before making any calls to `with_trusted_certificate()`.
     /// Calling this method after a method that modifies the trust store will clear it.
-    pub fn with_empty_trust_store(mut self) -> Result<Self, Error> {
-        self.config.wipe_trust_store()?;
-        Ok(self)
+    pub fn with_empty_trust_store(self) -> Result<Self, Error> {
+        self.with_setting(|config| {
+            config.wipe_trust_store()?;
+            Ok(())
+        })
     }
 
     /// Configures this server instance to require client authentication (mutual TLS).
-    pub fn with_client_authentication(mut self) -> Result<Self, Error> {
-        self.config.set_client_auth_type(ClientAuthType::Required)?;
-        Ok(self)
+    pub fn with_client_authentication(self) -> Result<Self, Error> {
+        self.with_setting(|config| {
+            config.set_client_auth_type(ClientAuthType::Required)?;
+            Ok(())
+        })
     }
 
     /// Set the application level certificate verification handler which will be invoked on this
     /// server instance when a client certificate is presented during the mutual TLS handshake.
     #[deprecated(note = "use `with_verify_host_name_callback` instead")]
     pub fn with_verify_client_certificate_handler<T: 'static + VerifyHostNameCallback>(
-        mut self,
+        self,
         handler: T,
     ) -> Result<Self, Error> {
-        self.config.set_verify_host_callback(handler)?;
-        Ok(self)
+        self.with_verify_host_name_callback(handler)
     }
 
     /// Set the host name verification callback.
@@ -187,44 +284,80 @@ impl Builder {
     /// This will be invoked when a client certificate is presented during a mutual TLS
     /// handshake.
     pub fn with_verify_host_name_callback<T: 'static + VerifyHostNameCallback>(
-        mut self,
+        self,
         handler: T,
     ) -> Result<Self, Error> {
-        self.config.set_verify_host_callback(handler)?;
-        Ok(self)
+        let handler = Arc::new(handler);
+        self.with_setting(move |config| {
+            config.set_verify_host_callback(SharedCallback(handler.clone()))?;
+            Ok(())
+        })
     }
 
     pub fn with_key_logging(mut self) -> Result<Self, Error> {
         use crate::keylog::KeyLog;
 
         self.keylog = KeyLog::try_open();
+        let keylog = self.keylog.clone();
 
-        unsafe {
-            // Safety: the KeyLog is stored on `self` to ensure it outlives `config`
-            if let Some(keylog) = self.keylog.as_ref() {
-                self.config
-                    .set_key_log_callback(Some(KeyLog::callback), Arc::as_ptr(keylog) as *mut _)?;
-            } else {
-                // disable key logging if it failed to create a file
-                self.config
-                    .set_key_log_callback(None, core::ptr::null_mut())?;
+        self.with_setting(move |config| {
+            unsafe {
+                // Safety: the KeyLog is stored on the server to ensure it outlives `config`
+                if let Some(keylog) = keylog.as_ref() {
+                    config.set_key_log_callback(
+                        Some(KeyLog::callback),
+                        Arc::as_ptr(keylog) as *mut _,
+                    )?;
+                } else {
+                    // disable key logging if it failed to create a file
+                    config.set_key_log_callback(None, core::ptr::null_mut())?;
+                }
             }
-        }
 
+            Ok(())
+        })
+    }
+
+    /// Applies the setting to the default config and records it for the server name configs
+    fn with_setting<F>(mut self, setting: F) -> Result<Self, Error>
+    where
+        F: 'static + Fn(&mut config::Builder) -> Result<(), Error> + Send + Sync,
+    {
+        setting(&mut self.config)?;
+        self.settings.push(Box::new(setting));
         Ok(self)
     }
 
-    pub fn build(self) -> Result<Server, Error> {
+    pub fn build(mut self) -> Result<Server<Config, Crypto>, Error> {
+        if !self.server_names.is_empty() {
+            let mut configs = ServerNameConfigs::new(self.client_hello_handler.take());
+
+            for (pattern, certificate, private_key) in self.server_names {
+                let mut config = default_config();
+                for setting in &self.settings {
+                    setting(&mut config)?;
+                }
+                load_certificate(&mut config, certificate, private_key)?;
+                configs.insert(pattern, config.build()?);
+            }
+
+            self.config.set_client_hello_callback(configs)?;
+        } else if let Some(handler) = self.client_hello_handler {
+            self.config
+                .set_client_hello_callback(SharedCallback(Arc::from(handler)))?;
+        }
+
         Ok(Server {
             loader: self.config.build()?,
             keylog: self.keylog,
             params: Default::default(),
+            crypto: PhantomData,
         })
     }
 }
 
-impl<L: ConfigLoader> tls::Endpoint for Server<L> {
-    type Session = Session;
+impl<L: ConfigLoader, C: 'static + Provider> tls::Endpoint for Server<L, C> {
+    type Session = Session<C>;
 
     fn new_server_session<Params: EncoderValue>(&mut self, params: &Params) -> Self::Session {
         let config = self
@@ -247,3 +380,32 @@ impl<L: ConfigLoader> tls::Endpoint for Server<L> {
         s2n_quic_crypto::MAX_TAG_LEN
     }
 }
+
+/// Shares a callback between the default config and the configs selected by server name
+struct SharedCallback<T: ?Sized>(Arc<T>);
+
+impl<T: ?Sized + ClientHelloCallback> ClientHelloCallback for SharedCallback<T> {
+    fn on_client_hello(
+        &self,
+        connection: &mut Connection,
+    ) -> Result<Option<Pin<Box<dyn ConnectionFuture>>>, Error> {
+        self.0.on_client_hello(connection)
+    }
+}
+
+impl<T: ?Sized + VerifyHostNameCallback> VerifyHostNameCallback for SharedCallback<T> {
+    fn verify_host_name(&self, host_name: &str) -> bool {
+        self.0.verify_host_name(host_name)
+    }
+}
+
+#[cfg(any(test, all(s2n_quic_unstable, feature = "unstable_private_key")))]
+impl<T: ?Sized + PrivateKeyCallback> PrivateKeyCallback for SharedCallback<T> {
+    fn handle_operation(
+        &self,
+        connection: &mut Connection,
+        operation: PrivateKeyOperation,
+    ) -> Result<Option<Pin<Box<dyn ConnectionFuture>>>, Error> {
+        self.0.handle_operation(connection, operation)
+    }
+}
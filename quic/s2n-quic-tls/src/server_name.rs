@@ -0,0 +1,111 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use core::pin::Pin;
+use s2n_tls::{
+    callbacks::{ClientHelloCallback, ConnectionFuture},
+    config::Config,
+    connection::Connection,
+    error::Error,
+};
+use std::collections::HashMap;
+
+/// A server name which may start with a `*.` wildcard label
+pub(crate) struct Pattern {
+    name: String,
+    is_wildcard: bool,
+}
+
+impl Pattern {
+    /// Parses the server name
+    ///
+    /// A leading `*.` label matches exactly one label, so `*.example.com` matches
+    /// `www.example.com` but not `example.com` or `a.b.example.com`.
+    pub(crate) fn new(server_name: &str) -> Result<Self, Error> {
+        // DNS names are case-insensitive
+        let server_name = server_name.to_ascii_lowercase();
+
+        let (name, is_wildcard) = if let Some(domain) = server_name.strip_prefix("*.") {
+            (domain, true)
+        } else {
+            (server_name.as_str(), false)
+        };
+
+        if name.is_empty() || name.contains('*') {
+            return Err(Error::io_error(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "invalid server name",
+            )));
+        }
+
+        Ok(Self {
+            name: name.to_owned(),
+            is_wildcard,
+        })
+    }
+}
+
+/// Server configs selected by the server name requested by the client
+#[derive(Default)]
+pub(crate) struct ServerNameConfigs {
+    exact: HashMap<String, Config>,
+    /// Wildcard configs, keyed by the domain following the `*.` prefix
+    wildcard: HashMap<String, Config>,
+    /// The application's client hello handler, which is invoked after the config is selected
+    client_hello_handler: Option<Box<dyn ClientHelloCallback>>,
+}
+
+impl ServerNameConfigs {
+    pub(crate) fn new(client_hello_handler: Option<Box<dyn ClientHelloCallback>>) -> Self {
+        Self {
+            client_hello_handler,
+            ..Default::default()
+        }
+    }
+
+    /// Registers a config for the server name
+    pub(crate) fn insert(&mut self, pattern: Pattern, config: Config) {
+        let map = if pattern.is_wildcard {
+            &mut self.wildcard
+        } else {
+            &mut self.exact
+        };
+
+        map.insert(pattern.name, config);
+    }
+
+    /// Returns the config registered for the name, preferring exact matches over wildcards
+    pub(crate) fn get(&self, server_name: &str) -> Option<&Config> {
+        let server_name = server_name.to_ascii_lowercase();
+
+        if let Some(config) = self.exact.get(&server_name) {
+            return Some(config);
+        }
+
+        let (_label, domain) = server_name.split_once('.')?;
+        self.wildcard.get(domain)
+    }
+}
+
+impl ClientHelloCallback for ServerNameConfigs {
+    fn on_client_hello(
+        &self,
+        connection: &mut Connection,
+    ) -> Result<Option<Pin<Box<dyn ConnectionFuture>>>, Error> {
+        let config = connection
+            .server_name()
+            .and_then(|server_name| self.get(server_name))
+            .cloned();
+
+        // fall back to the default config if the client didn't request a registered name
+        if let Some(config) = config {
+            connection.set_config(config)?;
+        }
+
+        if let Some(handler) = self.client_hello_handler.as_ref() {
+            return handler.on_client_hello(connection);
+        }
+
+        Ok(None)
+    }
+}
@@ -2,23 +2,26 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::callback::{self, Callback};
-use bytes::BytesMut;
-use core::{marker::PhantomData, task::Poll};
+use bytes::{Bytes, BytesMut};
+use core::{fmt, marker::PhantomData, task::Poll};
 use s2n_quic_core::{
     application::ServerName,
-    crypto::{tls, CryptoError, CryptoSuite},
+    crypto::{
+        provider::{Provider, SecretPair},
+        tls, CryptoError, CryptoSuite,
+    },
     endpoint, transport,
 };
 use s2n_quic_crypto::Suite;
 use s2n_tls::{
     config::Config,
     connection::Connection,
-    enums::{Blinding, Mode},
+    enums::{Blinding, HashAlgorithm, Mode, SignatureAlgorithm},
     error::Error,
 };
 
-#[derive(Debug)]
-pub struct Session {
+/// A TLS session which derives its packet protection keys through the crypto provider `C`
+pub struct Session<C: Provider = Suite> {
     endpoint: endpoint::Type,
     pub(crate) connection: Connection,
     state: callback::State,
@@ -27,9 +30,24 @@ pub struct Session {
     emitted_server_name: bool,
     // This is only set for the client to avoid an extra allocation
     server_name: Option<ServerName>,
+    crypto: PhantomData<fn() -> C>,
 }
 
-impl Session {
+impl<C: Provider> fmt::Debug for Session<C> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Session")
+            .field("endpoint", &self.endpoint)
+            .field("connection", &self.connection)
+            .field("state", &self.state)
+            .field("handshake_complete", &self.handshake_complete)
+            .field("send_buffer", &self.send_buffer)
+            .field("emitted_server_name", &self.emitted_server_name)
+            .field("server_name", &self.server_name)
+            .finish()
+    }
+}
+
+impl<C: Provider> Session<C> {
     pub fn new(
         endpoint: endpoint::Type,
         config: Config,
@@ -61,23 +79,52 @@ impl Session {
             send_buffer: BytesMut::new(),
             emitted_server_name: false,
             server_name,
+            crypto: PhantomData,
         })
     }
 }
 
-impl CryptoSuite for Session {
-    type HandshakeKey = <Suite as CryptoSuite>::HandshakeKey;
-    type HandshakeHeaderKey = <Suite as CryptoSuite>::HandshakeHeaderKey;
-    type InitialKey = <Suite as CryptoSuite>::InitialKey;
-    type InitialHeaderKey = <Suite as CryptoSuite>::InitialHeaderKey;
-    type OneRttKey = <Suite as CryptoSuite>::OneRttKey;
-    type OneRttHeaderKey = <Suite as CryptoSuite>::OneRttHeaderKey;
-    type ZeroRttKey = <Suite as CryptoSuite>::ZeroRttKey;
-    type ZeroRttHeaderKey = <Suite as CryptoSuite>::ZeroRttHeaderKey;
-    type RetryKey = <Suite as CryptoSuite>::RetryKey;
+impl<C: Provider> CryptoSuite for Session<C> {
+    type HandshakeKey = <C as CryptoSuite>::HandshakeKey;
+    type HandshakeHeaderKey = <C as CryptoSuite>::HandshakeHeaderKey;
+    type InitialKey = <C as CryptoSuite>::InitialKey;
+    type InitialHeaderKey = <C as CryptoSuite>::InitialHeaderKey;
+    type OneRttKey = <C as CryptoSuite>::OneRttKey;
+    type OneRttHeaderKey = <C as CryptoSuite>::OneRttHeaderKey;
+    type ZeroRttKey = <C as CryptoSuite>::ZeroRttKey;
+    type ZeroRttHeaderKey = <C as CryptoSuite>::ZeroRttHeaderKey;
+    type RetryKey = <C as CryptoSuite>::RetryKey;
+}
+
+impl<C: Provider> Provider for Session<C> {
+    #[inline]
+    fn handshake_keys(
+        endpoint: endpoint::Type,
+        cipher_suite: tls::CipherSuite,
+        secrets: SecretPair,
+    ) -> Option<(Self::HandshakeKey, Self::HandshakeHeaderKey)> {
+        C::handshake_keys(endpoint, cipher_suite, secrets)
+    }
+
+    #[inline]
+    fn one_rtt_keys(
+        endpoint: endpoint::Type,
+        cipher_suite: tls::CipherSuite,
+        secrets: SecretPair,
+    ) -> Option<(Self::OneRttKey, Self::OneRttHeaderKey)> {
+        C::one_rtt_keys(endpoint, cipher_suite, secrets)
+    }
+
+    #[inline]
+    fn zero_rtt_keys(
+        cipher_suite: tls::CipherSuite,
+        secret: &[u8],
+    ) -> Option<(Self::ZeroRttKey, Self::ZeroRttHeaderKey)> {
+        C::zero_rtt_keys(cipher_suite, secret)
+    }
 }
 
-impl tls::Session for Session {
+impl<C: Provider> tls::Session for Session<C> {
     fn poll<W>(&mut self, context: &mut W) -> Poll<Result<(), transport::Error>>
     where
         W: tls::Context<Self>,
@@ -121,4 +168,115 @@ impl tls::Session for Session {
             Poll::Pending => Poll::Pending,
         }
     }
+
+    fn handshake_details(&self) -> Option<tls::HandshakeDetails> {
+        if !self.handshake_complete {
+            return None;
+        }
+
+        let cipher_suite = match self.connection.cipher_suite().ok()? {
+            "TLS_AES_128_GCM_SHA256" => tls::CipherSuite::TLS_AES_128_GCM_SHA256,
+            "TLS_AES_256_GCM_SHA384" => tls::CipherSuite::TLS_AES_256_GCM_SHA384,
+            "TLS_CHACHA20_POLY1305_SHA256" => tls::CipherSuite::TLS_CHACHA20_POLY1305_SHA256,
+            _ => tls::CipherSuite::Unknown,
+        };
+
+        // hybrid post-quantum groups are reported as `None` since they don't have a
+        // registered code point
+        let key_exchange_group = match self.connection.selected_curve().ok()? {
+            "secp256r1" => Some(tls::NamedGroup::SECP256R1),
+            "secp384r1" => Some(tls::NamedGroup::SECP384R1),
+            "secp521r1" => Some(tls::NamedGroup::SECP521R1),
+            "x25519" => Some(tls::NamedGroup::X25519),
+            _ => None,
+        };
+
+        let signature_scheme = self.signature_scheme();
+
+        // The peer may not have sent a certificate chain (e.g. the client without mTLS)
+        let peer_cert_chain = self
+            .connection
+            .peer_cert_chain()
+            .map(|chain| {
+                chain
+                    .iter()
+                    .filter_map(|cert| cert.ok()?.der().ok().map(Bytes::copy_from_slice))
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        Some(tls::HandshakeDetails {
+            peer_cert_chain,
+            cipher_suite,
+            key_exchange_group,
+            signature_scheme,
+        })
+    }
+
+    fn export_keying_material(
+        &self,
+        label: &[u8],
+        context: &[u8],
+        output: &mut [u8],
+    ) -> Result<(), CryptoError> {
+        if !self.handshake_complete {
+            return Err(CryptoError::INTERNAL_ERROR.with_reason("handshake not complete"));
+        }
+
+        self.connection
+            .tls_exporter(label, context, output)
+            .map_err(|_| {
+                CryptoError::INTERNAL_ERROR.with_reason("failed to export keying material")
+            })
+    }
+}
+
+impl<C: Provider> Session<C> {
+    /// Maps the signature and hash algorithms selected by s2n-tls to the TLS 1.3 signature scheme
+    fn signature_scheme(&self) -> Option<tls::SignatureScheme> {
+        let signature = self.connection.selected_signature_algorithm().ok()?;
+        let hash = self.connection.selected_hash_algorithm().ok()?;
+
+        let scheme = match (signature, hash) {
+            (SignatureAlgorithm::RSA_PKCS1, HashAlgorithm::SHA256) => {
+                tls::SignatureScheme::RSA_PKCS1_SHA256
+            }
+            (SignatureAlgorithm::RSA_PKCS1, HashAlgorithm::SHA384) => {
+                tls::SignatureScheme::RSA_PKCS1_SHA384
+            }
+            (SignatureAlgorithm::RSA_PKCS1, HashAlgorithm::SHA512) => {
+                tls::SignatureScheme::RSA_PKCS1_SHA512
+            }
+            (SignatureAlgorithm::ECDSA, HashAlgorithm::SHA256) => {
+                tls::SignatureScheme::ECDSA_SECP256R1_SHA256
+            }
+            (SignatureAlgorithm::ECDSA, HashAlgorithm::SHA384) => {
+                tls::SignatureScheme::ECDSA_SECP384R1_SHA384
+            }
+            (SignatureAlgorithm::ECDSA, HashAlgorithm::SHA512) => {
+                tls::SignatureScheme::ECDSA_SECP521R1_SHA512
+            }
+            (SignatureAlgorithm::RSA_PSS_RSAE, HashAlgorithm::SHA256) => {
+                tls::SignatureScheme::RSA_PSS_RSAE_SHA256
+            }
+            (SignatureAlgorithm::RSA_PSS_RSAE, HashAlgorithm::SHA384) => {
+                tls::SignatureScheme::RSA_PSS_RSAE_SHA384
+            }
+            (SignatureAlgorithm::RSA_PSS_RSAE, HashAlgorithm::SHA512) => {
+                tls::SignatureScheme::RSA_PSS_RSAE_SHA512
+            }
+            (SignatureAlgorithm::RSA_PSS_PSS, HashAlgorithm::SHA256) => {
+                tls::SignatureScheme::RSA_PSS_PSS_SHA256
+            }
+            (SignatureAlgorithm::RSA_PSS_PSS, HashAlgorithm::SHA384) => {
+                tls::SignatureScheme::RSA_PSS_PSS_SHA384
+            }
+            (SignatureAlgorithm::RSA_PSS_PSS, HashAlgorithm::SHA512) => {
+                tls::SignatureScheme::RSA_PSS_PSS_SHA512
+            }
+            _ => return None,
+        };
+
+        Some(scheme)
+    }
 }
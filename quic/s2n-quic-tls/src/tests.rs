@@ -3,19 +3,24 @@
 
 use crate::{certificate, client, server};
 use core::{
-    sync::atomic::{AtomicBool, AtomicU8, Ordering},
+    sync::atomic::{AtomicBool, AtomicU8, AtomicUsize, Ordering},
     task::Poll,
 };
 use openssl::{ec::EcKey, ecdsa::EcdsaSig};
 use pin_project::pin_project;
 use s2n_quic_core::{
-    crypto::tls::{
-        self,
-        testing::certificates::{CERT_PEM, KEY_PEM, UNTRUSTED_CERT_PEM, UNTRUSTED_KEY_PEM},
-        Endpoint,
+    crypto::{
+        provider::{Provider, SecretPair},
+        tls::{
+            self,
+            testing::certificates::{CERT_PEM, KEY_PEM, UNTRUSTED_CERT_PEM, UNTRUSTED_KEY_PEM},
+            Endpoint,
+        },
+        CryptoSuite,
     },
-    transport,
+    endpoint, transport,
 };
+use s2n_quic_crypto::Suite;
 #[cfg(any(test, all(s2n_quic_unstable, feature = "unstable_client_hello")))]
 use s2n_tls::callbacks::ClientHelloCallback;
 #[cfg(any(test, all(s2n_quic_unstable, feature = "unstable_client_hello")))]
@@ -439,3 +444,227 @@ fn config_loader() {
     // make sure the server can actually create a session
     let _ = server.new_server_session(&1);
 }
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn reloadable_config() {
+    use crate::{ReloadableConfig, Server};
+
+    let reloadable = ReloadableConfig::new(s2n_server());
+    let mut server = Server::from_loader(reloadable.clone());
+    let mut client = s2n_client();
+
+    run(&mut server, &mut client, None);
+
+    // handshakes which already started keep the config they were created with
+    let mut pair = tls::testing::Pair::new(&mut server, &mut client, "localhost".into());
+
+    reloadable.update(
+        server::Builder::default()
+            .with_certificate(UNTRUSTED_CERT_PEM, UNTRUSTED_KEY_PEM)
+            .unwrap()
+            .build()
+            .unwrap(),
+    );
+
+    while pair.is_handshaking() {
+        pair.poll(None).unwrap();
+    }
+    pair.finish();
+
+    // new handshakes use the updated config, which the client doesn't trust
+    assert!(run_result(&mut server, &mut client, None).is_err());
+}
+
+#[test]
+fn server_name_configs() {
+    use crate::server_name::{Pattern, ServerNameConfigs};
+    use s2n_tls::config::Config;
+
+    let config = || Config::from(s2n_server());
+    let pattern = |name| Pattern::new(name).unwrap();
+
+    let mut configs = ServerNameConfigs::default();
+    configs.insert(pattern("Example.com"), config());
+    configs.insert(pattern("*.example.com"), config());
+    configs.insert(pattern("*.qlaws.example.com"), config());
+
+    for name in [
+        "example.com",
+        "EXAMPLE.COM",
+        "www.example.com",
+        "www.qlaws.example.com",
+    ] {
+        assert!(configs.get(name).is_some(), "{name} should match");
+    }
+
+    for name in [
+        "",
+        "com",
+        "other.com",
+        "a.b.example.com",
+        "example.com.evil",
+    ] {
+        assert!(configs.get(name).is_none(), "{name} should not match");
+    }
+
+    // exact names take precedence over wildcards
+    let wildcard: *const Config = configs.get("www.example.com").unwrap();
+    configs.insert(pattern("www.example.com"), config());
+    assert!(!core::ptr::eq(
+        wildcard,
+        configs.get("www.example.com").unwrap()
+    ));
+    assert!(core::ptr::eq(
+        wildcard,
+        configs.get("api.example.com").unwrap()
+    ));
+
+    for name in ["", "*.", "*", "www.*.example.com", "*.*.example.com"] {
+        assert!(Pattern::new(name).is_err(), "{name} should be invalid");
+    }
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn s2n_client_s2n_server_name_certificate_test() {
+    // the default certificate isn't trusted by the client, so the handshake only succeeds if
+    // the certificate registered for the name is selected
+    let mut server = server::Builder::default()
+        .with_certificate(UNTRUSTED_CERT_PEM, UNTRUSTED_KEY_PEM)
+        .unwrap()
+        .with_server_name_certificate("localhost", CERT_PEM, KEY_PEM)
+        .unwrap()
+        .build()
+        .unwrap();
+    let mut client = s2n_client();
+
+    run(&mut server, &mut client, None);
+
+    let mut server = server::Builder::default()
+        .with_certificate(UNTRUSTED_CERT_PEM, UNTRUSTED_KEY_PEM)
+        .unwrap()
+        .with_server_name_certificate("*.localhost", CERT_PEM, KEY_PEM)
+        .unwrap()
+        .build()
+        .unwrap();
+
+    assert!(run_result(&mut server, &mut client, None).is_err());
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn s2n_client_s2n_server_name_certificate_settings_test() {
+    // settings configured after the server name are also applied to its config
+    let mut server = server::Builder::default()
+        .with_certificate(UNTRUSTED_CERT_PEM, UNTRUSTED_KEY_PEM)
+        .unwrap()
+        .with_server_name_certificate("localhost", CERT_PEM, KEY_PEM)
+        .unwrap()
+        .with_application_protocols(["custom"])
+        .unwrap()
+        .build()
+        .unwrap();
+    let mut client = client::Builder::default()
+        .with_certificate(CERT_PEM)
+        .unwrap()
+        .with_application_protocols(["custom"])
+        .unwrap()
+        .build()
+        .unwrap();
+
+    run(&mut server, &mut client, None);
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn s2n_client_s2n_server_name_certificate_ch_callback_test() {
+    // the client hello handler is still invoked when certificates are registered by name
+    for wait_counter in 0..=3 {
+        let handle = MyCallbackHandler::new(wait_counter);
+        let done = handle.done.clone();
+        let mut server = server::Builder::default()
+            .with_certificate(UNTRUSTED_CERT_PEM, UNTRUSTED_KEY_PEM)
+            .unwrap()
+            .with_server_name_certificate("localhost", CERT_PEM, KEY_PEM)
+            .unwrap()
+            .with_client_hello_handler(handle)
+            .unwrap()
+            .build()
+            .unwrap();
+        let mut client = s2n_client();
+
+        run(&mut server, &mut client, Some(done));
+    }
+}
+
+/// The number of key pairs derived through [`CountingProvider`]
+static DERIVED_KEYS: AtomicUsize = AtomicUsize::new(0);
+
+/// Derives keys with [`Suite`] while counting the number of derivations
+#[derive(Debug)]
+struct CountingProvider;
+
+impl CryptoSuite for CountingProvider {
+    type HandshakeKey = <Suite as CryptoSuite>::HandshakeKey;
+    type HandshakeHeaderKey = <Suite as CryptoSuite>::HandshakeHeaderKey;
+    type InitialKey = <Suite as CryptoSuite>::InitialKey;
+    type InitialHeaderKey = <Suite as CryptoSuite>::InitialHeaderKey;
+    type OneRttKey = <Suite as CryptoSuite>::OneRttKey;
+    type OneRttHeaderKey = <Suite as CryptoSuite>::OneRttHeaderKey;
+    type ZeroRttKey = <Suite as CryptoSuite>::ZeroRttKey;
+    type ZeroRttHeaderKey = <Suite as CryptoSuite>::ZeroRttHeaderKey;
+    type RetryKey = <Suite as CryptoSuite>::RetryKey;
+}
+
+impl Provider for CountingProvider {
+    fn handshake_keys(
+        endpoint: endpoint::Type,
+        cipher_suite: tls::CipherSuite,
+        secrets: SecretPair,
+    ) -> Option<(Self::HandshakeKey, Self::HandshakeHeaderKey)> {
+        DERIVED_KEYS.fetch_add(1, Ordering::SeqCst);
+        Suite::handshake_keys(endpoint, cipher_suite, secrets)
+    }
+
+    fn one_rtt_keys(
+        endpoint: endpoint::Type,
+        cipher_suite: tls::CipherSuite,
+        secrets: SecretPair,
+    ) -> Option<(Self::OneRttKey, Self::OneRttHeaderKey)> {
+        DERIVED_KEYS.fetch_add(1, Ordering::SeqCst);
+        Suite::one_rtt_keys(endpoint, cipher_suite, secrets)
+    }
+
+    fn zero_rtt_keys(
+        cipher_suite: tls::CipherSuite,
+        secret: &[u8],
+    ) -> Option<(Self::ZeroRttKey, Self::ZeroRttHeaderKey)> {
+        DERIVED_KEYS.fetch_add(1, Ordering::SeqCst);
+        Suite::zero_rtt_keys(cipher_suite, secret)
+    }
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn s2n_client_s2n_server_crypto_provider_test() {
+    let mut client = client::Builder::default()
+        .with_crypto_provider::<CountingProvider>()
+        .unwrap()
+        .with_certificate(CERT_PEM)
+        .unwrap()
+        .build()
+        .unwrap();
+    let mut server = server::Builder::default()
+        .with_crypto_provider::<CountingProvider>()
+        .unwrap()
+        .with_certificate(CERT_PEM, KEY_PEM)
+        .unwrap()
+        .build()
+        .unwrap();
+
+    run(&mut server, &mut client, None);
+
+    // both endpoints derive their handshake and 1-RTT keys through the provider
+    assert_eq!(DERIVED_KEYS.load(Ordering::SeqCst), 4);
+}
@@ -7,17 +7,20 @@ use crate::{
     connection::{self, ConnectionApi, OpenToken},
     stream::{ops, Stream, StreamError, StreamId},
 };
-use bytes::Bytes;
+use bytes::{Bytes, BytesMut};
 use core::{
     fmt,
     sync::atomic::{self, Ordering},
     task::{Context, Poll},
+    time::Duration,
 };
 use s2n_quic_core::{
     application,
     application::ServerName,
+    crypto::tls::HandshakeDetails,
     inet::SocketAddress,
     query::{Query, QueryMut},
+    recovery::ExpectedSendRate,
     stream::StreamType,
 };
 
@@ -101,6 +104,11 @@ impl Clone for Connection {
     }
 }
 
+/// The maximum length of a reason phrase passed to [`Connection::close_with_reason`]
+///
+/// This ensures the CONNECTION_CLOSE frame always fits in a single packet.
+pub const MAX_CLOSE_REASON_LEN: usize = 1024;
+
 impl Connection {
     pub(crate) fn new(api: ConnectionApi) -> Self {
         // Safety
@@ -157,12 +165,52 @@ impl Connection {
         self.api.poll_request(stream_id, request, context)
     }
 
+    /// Polls for the Connection to complete the handshake
+    ///
+    /// The method will return
+    /// - `Poll::Ready(Ok(()))` if the handshake has completed
+    /// - `Poll::Ready(Err(connection_error))` if the connection was closed before or after the
+    ///   handshake completed
+    /// - `Poll::Pending` if the handshake is still in progress
+    #[inline]
+    pub fn poll_handshake_complete(
+        &self,
+        context: &Context,
+    ) -> Poll<Result<(), connection::Error>> {
+        self.api.poll_handshake_complete(context)
+    }
+
+    /// Polls for the Connection to close
+    ///
+    /// Once closed, the error which closed the Connection is returned.
+    #[inline]
+    pub fn poll_closed(&self, context: &Context) -> Poll<connection::Error> {
+        self.api.poll_closed(context)
+    }
+
+    /// Returns `true` if the Connection has not been closed
+    #[inline]
+    pub fn is_open(&self) -> bool {
+        self.api.is_open()
+    }
+
     /// Closes the Connection with the provided error code
     ///
     /// This will immediately terminate all outstanding streams.
     #[inline]
     pub fn close(&self, error_code: application::Error) {
-        self.api.close_connection(Some(error_code));
+        self.api.close_connection(Some((error_code, Bytes::new())));
+    }
+
+    /// Closes the Connection with the provided error code and reason phrase
+    ///
+    /// This will immediately terminate all outstanding streams. Reasons longer than
+    /// [`MAX_CLOSE_REASON_LEN`] bytes are truncated.
+    #[inline]
+    pub fn close_with_reason(&self, error_code: application::Error, reason: &[u8]) {
+        let reason = &reason[..reason.len().min(MAX_CLOSE_REASON_LEN)];
+        self.api
+            .close_connection(Some((error_code, Bytes::copy_from_slice(reason))));
     }
 
     #[inline]
@@ -175,6 +223,24 @@ impl Connection {
         self.api.application_protocol()
     }
 
+    #[inline]
+    pub fn handshake_details(&self) -> Result<Option<HandshakeDetails>, connection::Error> {
+        self.api.handshake_details()
+    }
+
+    /// Fills `output` with keying material exported from the TLS session
+    ///
+    /// See [RFC 8446 Section 7.5](https://www.rfc-editor.org/rfc/rfc8446#section-7.5)
+    #[inline]
+    pub fn export_keying_material(
+        &self,
+        label: &[u8],
+        context: &[u8],
+        output: &mut [u8],
+    ) -> Result<(), connection::Error> {
+        self.api.export_keying_material(label, context, output)
+    }
+
     #[inline]
     pub fn id(&self) -> u64 {
         self.api.id()
@@ -185,10 +251,44 @@ impl Connection {
         self.api.ping()
     }
 
+    #[inline]
+    pub fn initiate_key_update(&self) -> Result<(), connection::Error> {
+        self.api.initiate_key_update()
+    }
+
     pub fn keep_alive(&self, enabled: bool) -> Result<(), connection::Error> {
         self.api.keep_alive(enabled)
     }
 
+    pub fn keep_alive_with(
+        &self,
+        settings: connection::KeepAlive,
+    ) -> Result<(), connection::Error> {
+        self.api.keep_alive_with(settings)
+    }
+
+    pub fn set_expected_send_rate(
+        &self,
+        expected_send_rate: ExpectedSendRate,
+    ) -> Result<(), connection::Error> {
+        self.api.set_expected_send_rate(expected_send_rate)
+    }
+
+    pub fn update_limit(
+        &self,
+        update: connection::limits::Update,
+    ) -> Result<(), connection::Error> {
+        self.api.update_limit(update)
+    }
+
+    pub fn register_receive_buffer(&self, buffer: BytesMut) -> Result<(), connection::Error> {
+        self.api.register_receive_buffer(buffer)
+    }
+
+    pub fn drain(&self, timeout: Duration) -> Result<(), connection::Error> {
+        self.api.drain(timeout)
+    }
+
     #[inline]
     pub fn local_address(&self) -> Result<SocketAddress, connection::Error> {
         self.api.local_address()
@@ -9,16 +9,19 @@ use crate::{
     stream::{Stream, StreamError},
 };
 use alloc::sync::Arc;
-use bytes::Bytes;
+use bytes::{Bytes, BytesMut};
 use core::{
     sync::atomic::AtomicUsize,
     task::{Context, Poll},
+    time::Duration,
 };
 use s2n_quic_core::{
     application,
     application::ServerName,
+    crypto::tls::HandshakeDetails,
     inet::SocketAddress,
     query::{Query, QueryMut},
+    recovery::ExpectedSendRate,
     stream::{ops, StreamId, StreamType},
 };
 
@@ -52,18 +55,48 @@ pub(crate) trait ConnectionApiProvider: Sync + Send {
         context: &Context,
     ) -> Poll<Result<Stream, connection::Error>>;
 
-    fn close_connection(&self, code: Option<application::Error>);
+    fn poll_handshake_complete(&self, context: &Context) -> Poll<Result<(), connection::Error>>;
+
+    fn poll_closed(&self, context: &Context) -> Poll<connection::Error>;
+
+    fn is_open(&self) -> bool;
+
+    fn close_connection(&self, code: Option<(application::Error, Bytes)>);
 
     fn server_name(&self) -> Result<Option<ServerName>, connection::Error>;
 
     fn application_protocol(&self) -> Result<Bytes, connection::Error>;
 
+    fn handshake_details(&self) -> Result<Option<HandshakeDetails>, connection::Error>;
+
+    fn export_keying_material(
+        &self,
+        label: &[u8],
+        context: &[u8],
+        output: &mut [u8],
+    ) -> Result<(), connection::Error>;
+
     fn id(&self) -> u64;
 
     fn ping(&self) -> Result<(), connection::Error>;
 
+    fn initiate_key_update(&self) -> Result<(), connection::Error>;
+
     fn keep_alive(&self, enabled: bool) -> Result<(), connection::Error>;
 
+    fn keep_alive_with(&self, settings: connection::KeepAlive) -> Result<(), connection::Error>;
+
+    fn set_expected_send_rate(
+        &self,
+        expected_send_rate: ExpectedSendRate,
+    ) -> Result<(), connection::Error>;
+
+    fn update_limit(&self, update: connection::limits::Update) -> Result<(), connection::Error>;
+
+    fn register_receive_buffer(&self, buffer: BytesMut) -> Result<(), connection::Error>;
+
+    fn drain(&self, timeout: Duration) -> Result<(), connection::Error>;
+
     fn local_address(&self) -> Result<SocketAddress, connection::Error>;
 
     fn remote_address(&self) -> Result<SocketAddress, connection::Error>;
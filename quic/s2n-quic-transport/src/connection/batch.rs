@@ -0,0 +1,242 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Stages 1-RTT packets so they can be sealed and opened in batches
+
+use crate::connection::{InternalConnectionId, ProcessingError};
+use s2n_quic_core::{
+    inet::DatagramInfo,
+    io::tx,
+    packet::{
+        encoding::UnsealedPacket,
+        number::{PacketNumber, PacketNumberLen},
+        short::{CleartextShort, ProtectedShort},
+        KeyPhase,
+    },
+    path,
+    recovery::MAX_BURST_PACKETS,
+    stateless_reset,
+};
+use smallvec::SmallVec;
+
+/// The maximum number of 1-RTT packets which are sealed or opened together
+pub const MAX_BATCH_SIZE: usize = MAX_BURST_PACKETS as usize;
+
+/// A 1-RTT packet which was encoded at the end of a datagram but not sealed yet
+#[derive(Clone, Copy, Debug)]
+pub struct UnsealedRecord {
+    pub packet_number: PacketNumber,
+    pub packet_number_len: PacketNumberLen,
+    pub header_len: usize,
+    /// The length of the packet, including the space reserved for the crypto tag
+    pub len: usize,
+    pub key_phase: KeyPhase,
+}
+
+/// Tracks the 1-RTT packet written into the datagram which is currently being pushed
+#[derive(Debug, Default)]
+pub struct Stage {
+    /// The number of encoded packets which have not been sealed yet
+    pub pending: usize,
+    /// The packet written into the current datagram, if any
+    pub packet: Option<UnsealedRecord>,
+}
+
+/// A 1-RTT packet which was written into an entry of the transmission queue
+#[derive(Clone, Copy, Debug)]
+struct Record {
+    /// The index of the entry in [`tx::Queue::as_slice_mut`]
+    entry: usize,
+    /// The offset of the packet in the payload of the entry
+    offset: usize,
+    packet: UnsealedRecord,
+}
+
+/// 1-RTT packets which were written into the transmission queue and are sealed together
+///
+/// The packets are sealed in place, before the queue is flushed to the socket.
+#[derive(Debug, Default)]
+pub struct Tx {
+    packets: SmallVec<[Record; MAX_BATCH_SIZE]>,
+}
+
+impl Tx {
+    /// Returns the number of packets in the batch
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.packets.len()
+    }
+
+    /// Returns `true` if there are no packets in the batch
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.packets.is_empty()
+    }
+
+    /// Adds the packet at the end of the datagram which was last pushed onto `queue`
+    #[inline]
+    pub fn on_push<Q: tx::Queue>(&mut self, queue: &mut Q, packet: UnsealedRecord) {
+        debug_assert!(!queue.is_empty());
+        debug_assert!(self.packets.len() < MAX_BATCH_SIZE);
+
+        // The last entry holds the datagram which was just pushed. If the datagram was
+        // segmented with GSO, it was appended to the payload of the entry.
+        let entry = queue.len() - 1;
+        let payload_len = tx::Entry::payload(&queue.as_slice_mut()[entry]).len();
+
+        // 1-RTT packets are always the last packet in a datagram
+        let offset = payload_len - packet.len;
+
+        self.packets.push(Record {
+            entry,
+            offset,
+            packet,
+        });
+    }
+
+    /// Takes the packets out of the batch so they can be sealed
+    ///
+    /// `entries` are the entries of the queue the packets were pushed onto. The packets are
+    /// returned in the order they were written, along with the key phase they were encoded with.
+    pub fn take<'a, E: tx::Entry>(
+        &mut self,
+        entries: &'a mut [E],
+    ) -> SmallVec<[(KeyPhase, UnsealedPacket<'a>); MAX_BATCH_SIZE]> {
+        let mut packets = SmallVec::new();
+        let mut records = self.packets.drain(..).peekable();
+
+        let first = match records.peek() {
+            Some(record) => record.entry,
+            None => return packets,
+        };
+
+        for (index, entry) in entries.iter_mut().enumerate().skip(first) {
+            let mut payload = entry.payload_mut();
+            // the offset of `payload` in the payload of the entry
+            let mut start = 0;
+
+            // split the packets out of the entry, which holds several packets if the
+            // datagrams were segmented with GSO
+            while let Some(record) = records.next_if(|record| record.entry == index) {
+                let (_, remaining) =
+                    core::mem::take(&mut payload).split_at_mut(record.offset - start);
+                let (buffer, remaining) = remaining.split_at_mut(record.packet.len);
+                payload = remaining;
+                start = record.offset + record.packet.len;
+
+                let packet = UnsealedPacket {
+                    packet_number: record.packet.packet_number,
+                    packet_number_len: record.packet.packet_number_len,
+                    header_len: record.packet.header_len,
+                    buffer,
+                };
+                packets.push((record.packet.key_phase, packet));
+            }
+
+            if records.peek().is_none() {
+                break;
+            }
+        }
+
+        debug_assert!(
+            records.next().is_none(),
+            "every packet should be written into an entry of the queue"
+        );
+
+        packets
+    }
+}
+
+/// A 1-RTT packet which is opened as part of a batch
+#[derive(Debug)]
+pub struct RxPacket<'a> {
+    pub datagram: DatagramInfo,
+    /// The packet, which is taken by the connection when it's opened or processed
+    pub packet: Option<ProtectedShort<'a>>,
+    /// The packet once it was opened with the rest of the batch
+    pub cleartext: Option<CleartextShort<'a>>,
+    /// The last bytes of the datagram, which are compared against the known stateless reset
+    /// tokens if the packet can't be decrypted
+    pub stateless_reset_token: Option<stateless_reset::Token>,
+    /// The outcome of processing the packet
+    pub result: Result<(), ProcessingError>,
+}
+
+/// 1-RTT packets which were received for the same connection on the same path
+///
+/// The packets are opened together and then each datagram is received and its packet processed
+/// in the order they were received.
+#[derive(Debug)]
+pub struct Rx<'a, Handle> {
+    target: Option<(InternalConnectionId, Handle)>,
+    packets: SmallVec<[RxPacket<'a>; MAX_BATCH_SIZE]>,
+}
+
+impl<'a, Handle> Default for Rx<'a, Handle> {
+    fn default() -> Self {
+        Self {
+            target: None,
+            packets: SmallVec::new(),
+        }
+    }
+}
+
+impl<'a, Handle: path::Handle> Rx<'a, Handle> {
+    /// Returns `true` if a packet for the connection on the given path can be added to the batch
+    #[inline]
+    pub fn can_push(&self, internal_connection_id: InternalConnectionId, handle: &Handle) -> bool {
+        match &self.target {
+            Some((id, target)) => {
+                *id == internal_connection_id
+                    && target.strict_eq(handle)
+                    && self.packets.len() < MAX_BATCH_SIZE
+            }
+            None => true,
+        }
+    }
+
+    /// Adds a packet to the batch
+    ///
+    /// [`Self::can_push`] should be checked before adding the packet.
+    #[inline]
+    pub fn push(
+        &mut self,
+        internal_connection_id: InternalConnectionId,
+        handle: Handle,
+        datagram: DatagramInfo,
+        packet: ProtectedShort<'a>,
+        stateless_reset_token: Option<stateless_reset::Token>,
+    ) {
+        debug_assert!(self.can_push(internal_connection_id, &handle));
+
+        self.target = Some((internal_connection_id, handle));
+        self.packets.push(RxPacket {
+            datagram,
+            packet: Some(packet),
+            cleartext: None,
+            stateless_reset_token,
+            result: Ok(()),
+        });
+    }
+
+    /// Returns `true` if there are no pending packets in the batch
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.packets.is_empty()
+    }
+
+    /// Takes the pending packets out of the batch, along with the connection and path they were
+    /// received for
+    #[inline]
+    pub fn take(
+        &mut self,
+    ) -> Option<(
+        InternalConnectionId,
+        Handle,
+        SmallVec<[RxPacket<'a>; MAX_BATCH_SIZE]>,
+    )> {
+        let (internal_connection_id, handle) = self.target.take()?;
+        let packets = core::mem::take(&mut self.packets);
+        Some((internal_connection_id, handle, packets))
+    }
+}
@@ -18,7 +18,7 @@ use crate::{
     stream,
 };
 use alloc::{collections::BTreeMap, sync::Arc};
-use bytes::Bytes;
+use bytes::{Bytes, BytesMut};
 use core::{
     cell::Cell,
     marker::PhantomData,
@@ -26,6 +26,7 @@ use core::{
     pin::Pin,
     sync::atomic::AtomicUsize,
     task::{Context, Poll},
+    time::Duration,
 };
 use intrusive_collections::{
     intrusive_adapter, KeyAdapter, LinkedList, LinkedListLink, RBTree, RBTreeLink,
@@ -33,10 +34,11 @@ use intrusive_collections::{
 use s2n_quic_core::{
     application,
     application::ServerName,
+    crypto::tls::HandshakeDetails,
     event::supervisor,
     inet::SocketAddress,
     query::{Query, QueryMut},
-    recovery::K_GRANULARITY,
+    recovery::{ExpectedSendRate, K_GRANULARITY},
     time::Timestamp,
     transport,
 };
@@ -58,6 +60,12 @@ intrusive_adapter!(WaitingForConnectionIdAdapter<C, L> = Arc<ConnectionNode<C, L
     waiting_for_connection_id_link: LinkedListLink
 } where C: connection::Trait, L: connection::Lock<C>);
 
+// Intrusive list adapter for managing the list of
+// `waiting_for_send_buffer` connections
+intrusive_adapter!(WaitingForSendBufferAdapter<C, L> = Arc<ConnectionNode<C, L>>: ConnectionNode<C, L> {
+    waiting_for_send_buffer_link: LinkedListLink
+} where C: connection::Trait, L: connection::Lock<C>);
+
 // Intrusive red black tree adapter for managing a list of `waiting_for_timeout` connections
 intrusive_adapter!(WaitingForTimeoutAdapter<C, L> = Arc<ConnectionNode<C, L>>: ConnectionNode<C, L> {
     waiting_for_timeout_link: RBTreeLink
@@ -85,10 +93,20 @@ struct ConnectionNode<C: connection::Trait, L: connection::Lock<C>> {
     waiting_for_transmission_link: LinkedListLink,
     /// Allows the Connection to be part of the `waiting_for_connection_id` collection
     waiting_for_connection_id_link: LinkedListLink,
+    /// Allows the Connection to be part of the `waiting_for_send_buffer` collection
+    waiting_for_send_buffer_link: LinkedListLink,
     /// Allows the Connection to be part of the `waiting_for_timeout` collection
     waiting_for_timeout_link: RBTreeLink,
     /// The cached time at which the connection will timeout next
     timeout: Cell<Option<Timestamp>>,
+    /// The cached number of bytes buffered by the connection
+    buffered_bytes: Cell<u64>,
+    /// The cached number of bytes buffered for sending by the connection
+    send_buffered_bytes: Cell<u64>,
+    /// The cached time at which the connection last made progress on its streams
+    last_stream_progress: Cell<Option<Timestamp>>,
+    /// Is `true` if the connection has entered the closing state
+    is_closing: Cell<bool>,
     /// The count of outstanding application handles
     application_handle_count: AtomicUsize,
     /// The inner connection type
@@ -108,8 +126,13 @@ impl<C: connection::Trait, L: connection::Lock<C>> ConnectionNode<C, L> {
             done_connections_link: LinkedListLink::new(),
             waiting_for_transmission_link: LinkedListLink::new(),
             waiting_for_connection_id_link: LinkedListLink::new(),
+            waiting_for_send_buffer_link: LinkedListLink::new(),
             waiting_for_timeout_link: RBTreeLink::new(),
             timeout: Cell::new(None),
+            buffered_bytes: Cell::new(0),
+            send_buffered_bytes: Cell::new(0),
+            last_stream_progress: Cell::new(None),
+            is_closing: Cell::new(false),
             application_handle_count: AtomicUsize::new(0),
             _connection: PhantomData,
         }
@@ -185,6 +208,10 @@ impl<C: connection::Trait, L: connection::Lock<C>> ConnectionNode<C, L> {
                 !self.waiting_for_transmission_link.is_linked(),
                 "A done connection should not be waiting for transmission"
             );
+            assert!(
+                !self.waiting_for_send_buffer_link.is_linked(),
+                "A done connection should not be waiting for the send buffer budget"
+            );
 
             return;
         }
@@ -214,7 +241,7 @@ impl<'a, C: connection::Trait, L: connection::Lock<C>> KeyAdapter<'a>
                 // to ensure the timeout value is properly updated.
                 //
                 // Assuming everything is tested properly, this should never be reached
-                Timestamp::from_duration(core::time::Duration::from_secs(0))
+                Timestamp::from_duration(Duration::from_secs(0))
             }
         }
     }
@@ -294,7 +321,25 @@ impl<C: connection::Trait, L: connection::Lock<C>> ConnectionApiProvider for Con
         }
     }
 
-    fn close_connection(&self, error: Option<application::Error>) {
+    fn poll_handshake_complete(&self, context: &Context) -> Poll<Result<(), connection::Error>> {
+        self.api_poll_call(|conn| conn.poll_handshake_complete(context))
+    }
+
+    fn poll_closed(&self, context: &Context) -> Poll<connection::Error> {
+        let response = self.api_poll_call(|conn| conn.poll_closed(context).map(Ok));
+
+        match response {
+            Poll::Pending => Poll::Pending,
+            // a poisoned connection lock is also considered closed
+            Poll::Ready(Ok(error)) | Poll::Ready(Err(error)) => error.into(),
+        }
+    }
+
+    fn is_open(&self) -> bool {
+        matches!(self.inner.read(|conn| conn.error()), Ok(None))
+    }
+
+    fn close_connection(&self, error: Option<(application::Error, Bytes)>) {
         let _: Result<(), connection::Error> = self.api_write_call(|conn| {
             conn.application_close(error);
             Ok(())
@@ -309,6 +354,19 @@ impl<C: connection::Trait, L: connection::Lock<C>> ConnectionApiProvider for Con
         self.api_read_call(|conn| Ok(conn.application_protocol()))
     }
 
+    fn handshake_details(&self) -> Result<Option<HandshakeDetails>, connection::Error> {
+        self.api_read_call(|conn| Ok(conn.handshake_details()))
+    }
+
+    fn export_keying_material(
+        &self,
+        label: &[u8],
+        context: &[u8],
+        output: &mut [u8],
+    ) -> Result<(), connection::Error> {
+        self.api_read_call(|conn| conn.export_keying_material(label, context, output))
+    }
+
     fn id(&self) -> u64 {
         self.internal_connection_id.into()
     }
@@ -317,10 +375,37 @@ impl<C: connection::Trait, L: connection::Lock<C>> ConnectionApiProvider for Con
         self.api_write_call(|conn| conn.ping())
     }
 
+    fn initiate_key_update(&self) -> Result<(), connection::Error> {
+        self.api_write_call(|conn| conn.initiate_key_update())
+    }
+
     fn keep_alive(&self, enabled: bool) -> Result<(), connection::Error> {
         self.api_write_call(|conn| conn.keep_alive(enabled))
     }
 
+    fn keep_alive_with(&self, settings: connection::KeepAlive) -> Result<(), connection::Error> {
+        self.api_write_call(|conn| conn.keep_alive_with(settings))
+    }
+
+    fn set_expected_send_rate(
+        &self,
+        expected_send_rate: ExpectedSendRate,
+    ) -> Result<(), connection::Error> {
+        self.api_write_call(|conn| conn.set_expected_send_rate(expected_send_rate))
+    }
+
+    fn update_limit(&self, update: connection::limits::Update) -> Result<(), connection::Error> {
+        self.api_write_call(|conn| conn.update_limit(update))
+    }
+
+    fn register_receive_buffer(&self, buffer: BytesMut) -> Result<(), connection::Error> {
+        self.api_write_call(|conn| conn.register_receive_buffer(buffer))
+    }
+
+    fn drain(&self, timeout: Duration) -> Result<(), connection::Error> {
+        self.api_write_call(|conn| conn.drain(timeout))
+    }
+
     fn local_address(&self) -> Result<SocketAddress, connection::Error> {
         self.api_read_call(|conn| conn.local_address())
     }
@@ -365,6 +450,8 @@ struct InterestLists<C: connection::Trait, L: connection::Lock<C>> {
     waiting_for_transmission: LinkedList<WaitingForTransmissionAdapter<C, L>>,
     /// Connections which need a new connection ID
     waiting_for_connection_id: LinkedList<WaitingForConnectionIdAdapter<C, L>>,
+    /// Connections which are waiting for the send buffer budget of the endpoint
+    waiting_for_send_buffer: LinkedList<WaitingForSendBufferAdapter<C, L>>,
     /// Connections which are waiting for a timeout to occur
     waiting_for_timeout: RBTree<WaitingForTimeoutAdapter<C, L>>,
     waiting_for_open: BTreeMap<InternalConnectionId, ConnectionSender>,
@@ -372,6 +459,10 @@ struct InterestLists<C: connection::Trait, L: connection::Lock<C>> {
     handshake_connections: usize,
     /// Total connection count
     connection_count: usize,
+    /// Total number of bytes buffered across all connections
+    buffered_bytes: u64,
+    /// Total number of bytes buffered for sending across all connections
+    send_buffered_bytes: u64,
 }
 
 impl<C: connection::Trait, L: connection::Lock<C>> InterestLists<C, L> {
@@ -380,10 +471,13 @@ impl<C: connection::Trait, L: connection::Lock<C>> InterestLists<C, L> {
             done_connections: LinkedList::new(DoneConnectionsAdapter::new()),
             waiting_for_transmission: LinkedList::new(WaitingForTransmissionAdapter::new()),
             waiting_for_connection_id: LinkedList::new(WaitingForConnectionIdAdapter::new()),
+            waiting_for_send_buffer: LinkedList::new(WaitingForSendBufferAdapter::new()),
             waiting_for_timeout: RBTree::new(WaitingForTimeoutAdapter::new()),
             waiting_for_open: BTreeMap::new(),
             handshake_connections: 0,
             connection_count: 0,
+            buffered_bytes: 0,
+            send_buffered_bytes: 0,
         }
     }
 
@@ -461,6 +555,12 @@ impl<C: connection::Trait, L: connection::Lock<C>> InterestLists<C, L> {
             waiting_for_connection_id
         );
 
+        sync_interests_list!(
+            interests.send_buffer_blocked,
+            waiting_for_send_buffer_link,
+            waiting_for_send_buffer
+        );
+
         // Check if the timeout has changed since last time we queried the interests
         if node.timeout.get() != interests.timeout {
             // remove the connection if it's currently linked
@@ -552,6 +652,17 @@ impl<C: connection::Trait, L: connection::Lock<C>> InterestLists<C, L> {
             }
         }
 
+        // Update the memory accounting with the latest value reported by the connection
+        self.buffered_bytes -= node.buffered_bytes.get();
+        self.buffered_bytes += interests.buffered_bytes;
+        node.buffered_bytes.set(interests.buffered_bytes);
+        self.send_buffered_bytes -= node.send_buffered_bytes.get();
+        self.send_buffered_bytes += interests.send_buffered_bytes;
+        node.send_buffered_bytes.set(interests.send_buffered_bytes);
+        node.last_stream_progress
+            .set(interests.last_stream_progress);
+        node.is_closing.set(interests.closing);
+
         node.ensure_consistency();
 
         Ok(())
@@ -579,9 +690,12 @@ impl<C: connection::Trait, L: connection::Lock<C>> InterestLists<C, L> {
 
         remove_connection_from_list!(waiting_for_transmission, waiting_for_transmission_link);
         remove_connection_from_list!(waiting_for_connection_id, waiting_for_connection_id_link);
+        remove_connection_from_list!(waiting_for_send_buffer, waiting_for_send_buffer_link);
         remove_connection_from_list!(waiting_for_timeout, waiting_for_timeout_link);
 
         self.connection_count -= 1;
+        self.buffered_bytes -= connection.buffered_bytes.get();
+        self.send_buffered_bytes -= connection.send_buffered_bytes.get();
     }
 }
 
@@ -609,6 +723,10 @@ pub struct ConnectionContainer<C: connection::Trait, L: connection::Lock<C>> {
     ///
     /// This is only used by clients
     connector_receiver: ConnectorReceiver,
+    /// Set once the application requested the active connections to be drained
+    ///
+    /// No new connections are accepted while draining
+    is_draining: bool,
 }
 
 macro_rules! iterate_interruptible {
@@ -668,6 +786,7 @@ impl<C: connection::Trait, L: connection::Lock<C>> ConnectionContainer<C, L> {
             interest_lists: InterestLists::new(),
             accept_queue,
             connector_receiver,
+            is_draining: false,
         }
     }
 
@@ -675,7 +794,7 @@ impl<C: connection::Trait, L: connection::Lock<C>> ConnectionContainer<C, L> {
     pub fn can_accept(&self) -> bool {
         debug_assert!(<C::Config as endpoint::Config>::ENDPOINT_TYPE.is_server());
 
-        !self.accept_queue.is_closed()
+        !self.is_draining && !self.accept_queue.is_closed()
     }
 
     /// Returns `true` if the endpoint can make connection attempts
@@ -692,6 +811,18 @@ impl<C: connection::Trait, L: connection::Lock<C>> ConnectionContainer<C, L> {
         self.connection_map.is_empty()
     }
 
+    /// Stop accepting new connections and drain all of the active connections
+    ///
+    /// Each connection is closed once its streams have finished or the `timeout` has expired.
+    pub fn drain(&mut self, timeout: Duration) {
+        self.is_draining = true;
+
+        for node in self.connection_map.iter() {
+            // errors are ignored since closed or panicked connections have nothing to drain
+            let _ = node.inner.write(|conn| conn.drain(timeout));
+        }
+    }
+
     /// Stop accepting new connection attempts and close pending connection requests
     ///
     /// Drains any requests in the connector_receiver queue and notifies the application
@@ -725,7 +856,7 @@ impl<C: connection::Trait, L: connection::Lock<C>> ConnectionContainer<C, L> {
     pub fn is_open(&self) -> bool {
         !self.connection_map.is_empty()
             || match <C::Config as endpoint::Config>::ENDPOINT_TYPE {
-                endpoint::Type::Server => self.can_accept(),
+                endpoint::Type::Server => !self.accept_queue.is_closed(),
                 endpoint::Type::Client => self.can_connect(),
             }
     }
@@ -815,6 +946,58 @@ impl<C: connection::Trait, L: connection::Lock<C>> ConnectionContainer<C, L> {
         self.interest_lists.connection_count
     }
 
+    /// Returns the total number of bytes buffered across all connections
+    pub fn buffered_bytes(&self) -> u64 {
+        self.interest_lists.buffered_bytes
+    }
+
+    /// Returns the total number of bytes buffered for sending across all connections
+    pub fn send_buffered_bytes(&self) -> u64 {
+        self.interest_lists.send_buffered_bytes
+    }
+
+    /// Notifies all connections that the endpoint entered or left memory pressure
+    pub fn on_memory_pressure(&mut self, enabled: bool) {
+        for node in self.connection_map.iter() {
+            // errors are ignored since panicked connections are removed on the next interaction
+            let _ = node.inner.write(|conn| conn.on_memory_pressure(enabled));
+        }
+    }
+
+    /// Executes the given function on the connection which buffers the most data
+    ///
+    /// Connections which are already closing or which made progress on their streams
+    /// within the last `min_idle_time` are not considered.
+    pub fn with_largest_consumer<F, R>(
+        &mut self,
+        now: Timestamp,
+        min_idle_time: Duration,
+        func: F,
+    ) -> Option<(R, ConnectionInterests)>
+    where
+        F: FnOnce(&mut C) -> R,
+    {
+        let is_idle = |node: &ConnectionNode<C, L>| {
+            node.last_stream_progress.get().map_or(true, |last| {
+                now.saturating_duration_since(last) >= min_idle_time
+            })
+        };
+
+        let connection_id = self
+            .connection_map
+            .iter()
+            .filter(|node| {
+                !node.is_closing.get()
+                    && !node.done_connections_link.is_linked()
+                    && node.buffered_bytes.get() > 0
+                    && is_idle(node)
+            })
+            .max_by_key(|node| node.buffered_bytes.get())?
+            .internal_connection_id;
+
+        self.with_connection(connection_id, func)
+    }
+
     /// Looks up the `Connection` with the given ID and executes the provided function
     /// on it.
     ///
@@ -915,6 +1098,20 @@ impl<C: connection::Trait, L: connection::Lock<C>> ConnectionContainer<C, L> {
             let expected = self.count_handshaking_connections();
             assert_eq!(expected, self.interest_lists.handshake_connections);
             assert_eq!(self.len(), self.connection_map.iter().count());
+            assert_eq!(
+                self.buffered_bytes(),
+                self.connection_map
+                    .iter()
+                    .map(|node| node.buffered_bytes.get())
+                    .sum::<u64>()
+            );
+            assert_eq!(
+                self.send_buffered_bytes(),
+                self.connection_map
+                    .iter()
+                    .map(|node| node.send_buffered_bytes.get())
+                    .sum::<u64>()
+            );
         }
     }
 
@@ -932,6 +1129,20 @@ impl<C: connection::Trait, L: connection::Lock<C>> ConnectionContainer<C, L> {
         );
     }
 
+    /// Iterates over all `Connection`s which are waiting for the send buffer budget
+    /// of the endpoint, and executes the given function on each `Connection`
+    pub fn iterate_send_buffer_blocked_list<F>(&mut self, mut func: F)
+    where
+        F: FnMut(&mut C) -> ConnectionContainerIterationResult,
+    {
+        iterate_interruptible!(
+            self,
+            waiting_for_send_buffer,
+            waiting_for_send_buffer_link,
+            func
+        );
+    }
+
     /// Iterates over all `Connection`s which are waiting for new connection Ids,
     /// and executes the given function on each `Connection`
     pub fn iterate_new_connection_id_list<F>(&mut self, mut func: F)
@@ -11,13 +11,15 @@ use crate::{
     endpoint, path, stream,
 };
 use bolero::{check, generator::*};
-use bytes::Bytes;
+use bytes::{Bytes, BytesMut};
 use core::{
     task::{Context, Poll},
     time::Duration,
 };
 use s2n_quic_core::{
-    application, event,
+    application,
+    crypto::tls::HandshakeDetails,
+    event,
     event::builder::DatagramDropReason,
     inet::{DatagramInfo, SocketAddress},
     io::tx,
@@ -31,6 +33,7 @@ use s2n_quic_core::{
     },
     path::MaxMtu,
     query,
+    recovery::ExpectedSendRate,
     time::{Timer, Timestamp},
 };
 use std::sync::Mutex;
@@ -189,6 +192,27 @@ impl connection::Trait for TestConnection {
         Ok(())
     }
 
+    /// Opens a batch of short packets which were received on the same path
+    fn open_short_packet_batch(
+        &mut self,
+        _path_id: path::Id,
+        _packets: &mut [connection::batch::RxPacket],
+        _subscriber: &mut <Self::Config as endpoint::Config>::EventSubscriber,
+    ) {
+    }
+
+    /// Is called when the datagram of a short packet in an opened batch had been received
+    fn handle_batched_short_packet(
+        &mut self,
+        _path_id: path::Id,
+        _packet: &mut connection::batch::RxPacket,
+        _random_generator: &mut <Self::Config as endpoint::Config>::RandomGenerator,
+        _subscriber: &mut <Self::Config as endpoint::Config>::EventSubscriber,
+        _packet_interceptor: &mut <Self::Config as endpoint::Config>::PacketInterceptor,
+    ) -> Result<(), ProcessingError> {
+        Ok(())
+    }
+
     /// Is called when a version negotiation packet had been received
     fn handle_version_negotiation_packet(
         &mut self,
@@ -274,7 +298,7 @@ impl connection::Trait for TestConnection {
         todo!()
     }
 
-    fn application_close(&mut self, _error: Option<application::Error>) {
+    fn application_close(&mut self, _error: Option<(application::Error, Bytes)>) {
         // no-op
     }
 
@@ -286,14 +310,75 @@ impl connection::Trait for TestConnection {
         todo!()
     }
 
+    fn handshake_details(&self) -> Option<HandshakeDetails> {
+        todo!()
+    }
+
+    fn export_keying_material(
+        &self,
+        _label: &[u8],
+        _context: &[u8],
+        _output: &mut [u8],
+    ) -> Result<(), connection::Error> {
+        todo!()
+    }
+
     fn ping(&mut self) -> Result<(), connection::Error> {
         todo!()
     }
 
+    fn initiate_key_update(&mut self) -> Result<(), connection::Error> {
+        todo!()
+    }
+
     fn keep_alive(&mut self, _enabled: bool) -> Result<(), connection::Error> {
         todo!()
     }
 
+    fn poll_handshake_complete(
+        &mut self,
+        _context: &Context,
+    ) -> Poll<Result<(), connection::Error>> {
+        todo!()
+    }
+
+    fn poll_closed(&mut self, _context: &Context) -> Poll<connection::Error> {
+        todo!()
+    }
+
+    fn keep_alive_with(
+        &mut self,
+        _settings: connection::KeepAlive,
+    ) -> Result<(), connection::Error> {
+        todo!()
+    }
+
+    fn set_expected_send_rate(
+        &mut self,
+        _expected_send_rate: ExpectedSendRate,
+    ) -> Result<(), connection::Error> {
+        todo!()
+    }
+
+    fn update_limit(
+        &mut self,
+        _update: connection::limits::Update,
+    ) -> Result<(), connection::Error> {
+        todo!()
+    }
+
+    fn register_receive_buffer(&mut self, _buffer: BytesMut) -> Result<(), connection::Error> {
+        todo!()
+    }
+
+    fn drain(&mut self, _timeout: Duration) -> Result<(), connection::Error> {
+        todo!()
+    }
+
+    fn on_memory_pressure(&mut self, _enabled: bool) {}
+
+    fn on_send_buffer_available(&mut self) {}
+
     fn local_address(&self) -> Result<SocketAddress, connection::Error> {
         todo!()
     }
@@ -8,6 +8,7 @@ use crate::{
         self,
         close_sender::CloseSender,
         id::{ConnectionInfo, Interest},
+        lifecycle,
         limits::Limits,
         local_id_registry::LocalIdRegistrationError,
         ConnectionIdMapper, ConnectionInterests, ConnectionTimers, ConnectionTransmission,
@@ -25,7 +26,7 @@ use crate::{
     wakeup_queue::WakeupHandle,
 };
 use alloc::sync::Arc;
-use bytes::Bytes;
+use bytes::{Bytes, BytesMut};
 use core::{
     fmt,
     task::{Context, Poll, Waker},
@@ -47,15 +48,16 @@ use s2n_quic_core::{
     packet::{
         handshake::ProtectedHandshake,
         initial::{CleartextInitial, ProtectedInitial},
+        interceptor::Interceptor as _,
         number::PacketNumberSpace,
         retry::ProtectedRetry,
-        short::ProtectedShort,
+        short::{CleartextShort, ProtectedShort},
         version_negotiation::ProtectedVersionNegotiation,
         zero_rtt::ProtectedZeroRtt,
     },
     path::{Handle as _, MaxMtu},
     query,
-    recovery::CongestionController,
+    recovery::{CongestionController, ExpectedSendRate},
     stateless_reset::token::Generator as _,
     time::{timer, Timestamp},
     transport,
@@ -168,6 +170,10 @@ pub struct ConnectionImpl<Config: endpoint::Config> {
     ///
     /// This is stored so future calls from the application return the same error
     error: Result<(), connection::Error>,
+    /// The reason phrase provided by the application when closing the connection
+    ///
+    /// This is stored outside of the error to keep [`connection::Error`] small.
+    close_reason: Bytes,
     /// Sends CONNECTION_CLOSE close frames after the connection is closed
     close_sender: CloseSender,
     /// Manages all of the different packet spaces and their respective components
@@ -176,6 +182,30 @@ pub struct ConnectionImpl<Config: endpoint::Config> {
     wakeup_handle: Arc<WakeupHandle<InternalConnectionId>>,
     /// A Waker to the connection.
     waker: Waker,
+    /// The expected send rate declared by the application that has not yet been applied
+    /// to the paths of the connection
+    pending_expected_send_rate: Option<ExpectedSendRate>,
+    /// The timeout for draining the connection, if the application requested it
+    ///
+    /// This is kept until the application space is available and the streams start draining.
+    pending_drain_timeout: Option<Duration>,
+    /// The number of extra flights the handshake needed due to the anti-amplification limit
+    amplification_limited_flights: u32,
+    /// The send buffer budget of the endpoint, which is shared across all of its connections
+    ///
+    /// This is applied to the streams once the application space is available.
+    endpoint_send_buffer: stream::EndpointSendBuffer,
+    /// Is `true` if the endpoint is under memory pressure
+    ///
+    /// This is applied to the streams once the application space is available.
+    memory_pressure: bool,
+    /// The last time stream data was received or transmitted on the connection
+    ///
+    /// This is used by the endpoint to avoid closing connections which are still making
+    /// progress when under memory pressure.
+    last_stream_progress: Timestamp,
+    /// Tasks which are waiting on the connection to complete the handshake or close
+    lifecycle_wakers: lifecycle::Wakers,
     event_context: EventContext<Config>,
 }
 
@@ -258,6 +288,7 @@ macro_rules! transmission_context {
             transmission_mode: $transmission_mode,
             publisher: &mut $self.event_context.publisher($timestamp, $subscriber),
             packet_interceptor: $packet_interceptor,
+            stage: None,
         }
     }};
 }
@@ -285,6 +316,18 @@ impl<Config: endpoint::Config> ConnectionImpl<Config> {
             Poll::Pending => return Ok(()),
         }
 
+        // the application space may have just become available
+        self.start_pending_drain(timestamp);
+        if let Some((space, _)) = self.space_manager.application_mut() {
+            space
+                .stream_manager
+                .set_endpoint_send_buffer(&self.endpoint_send_buffer);
+            space
+                .stream_manager
+                .on_memory_pressure(self.memory_pressure);
+        }
+        let space_manager = &mut self.space_manager;
+
         //= https://www.rfc-editor.org/rfc/rfc9000#section-7.1
         //#
         //#   Client                                                  Server
@@ -315,6 +358,8 @@ impl<Config: endpoint::Config> ConnectionImpl<Config> {
             self.accept_state = AcceptState::HandshakeCompleted;
             // Move the connection into the active state.
             self.state = ConnectionState::Active;
+            // Notify any handles waiting on the handshake to complete
+            self.lifecycle_wakers.on_handshake_complete();
 
             // Cancel the max handshake duration timer as the handshake has completed in time
             self.timers.max_handshake_duration_timer.cancel();
@@ -332,6 +377,28 @@ impl<Config: endpoint::Config> ConnectionImpl<Config> {
         Ok(())
     }
 
+    /// Starts draining the streams if the application requested it
+    ///
+    /// A drain requested during the handshake is deferred until the application space is
+    /// available. The drain deadline is armed right away so it still applies to connections
+    /// which never finish the handshake.
+    fn start_pending_drain(&mut self, timestamp: Timestamp) {
+        let timeout = if let Some(timeout) = self.pending_drain_timeout {
+            timeout
+        } else {
+            return;
+        };
+
+        if !self.timers.drain_timer.is_armed() {
+            self.timers.drain_timer.set(timestamp + timeout);
+        }
+
+        if let Some((space, _)) = self.space_manager.application_mut() {
+            space.stream_manager.drain();
+            self.pending_drain_timeout = None;
+        }
+    }
+
     /// Returns the idle timeout based on transport parameters of both peers
     fn get_idle_timer_duration(&self) -> Option<Duration> {
         //= https://www.rfc-editor.org/rfc/rfc9000#section-10.1
@@ -377,7 +444,8 @@ impl<Config: endpoint::Config> ConnectionImpl<Config> {
         if packet.bytes_progressed > 0 {
             publisher.on_rx_stream_progress(RxStreamProgress {
                 bytes: packet.bytes_progressed,
-            })
+            });
+            self.last_stream_progress = packet.datagram.timestamp;
         }
 
         // check to see if we're flushing and should now close the connection
@@ -410,6 +478,56 @@ impl<Config: endpoint::Config> ConnectionImpl<Config> {
         })
     }
 
+    /// Processes a short packet which was successfully decrypted
+    fn handle_cleartext_short_packet(
+        &mut self,
+        datagram: &DatagramInfo,
+        path_id: path::Id,
+        packet: CleartextShort,
+        random_generator: &mut Config::RandomGenerator,
+        subscriber: &mut Config::EventSubscriber,
+        packet_interceptor: &mut Config::PacketInterceptor,
+    ) -> Result<(), ProcessingError> {
+        let mut publisher = self.event_context.publisher(datagram.timestamp, subscriber);
+
+        if let Some((space, handshake_status)) = self.space_manager.application_mut() {
+            publisher.on_packet_received(event::builder::PacketReceived {
+                packet_header: event::builder::PacketHeader::new(
+                    packet.packet_number,
+                    publisher.quic_version(),
+                ),
+            });
+
+            // Connection Ids are issued to the peer after the handshake is
+            // confirmed and the handshake space is discarded. Therefore only
+            // short packets need to be processed for local_connection_id changes.
+            self.path_manager[path_id].on_process_local_connection_id(
+                path_id,
+                &packet,
+                &datagram.destination_connection_id,
+                &mut publisher,
+            );
+
+            let processed_packet = space.handle_cleartext_payload(
+                packet.packet_number,
+                packet.payload,
+                datagram,
+                path_id,
+                &mut self.path_manager,
+                handshake_status,
+                &mut self.local_id_registry,
+                random_generator,
+                &mut publisher,
+                packet_interceptor,
+            )?;
+
+            // notify the connection a packet was processed
+            self.on_processed_packet(&processed_packet, subscriber)?;
+        }
+
+        Ok(())
+    }
+
     /// Send path validation frames for the non-active path.
     ///
     /// Since non-probing frames can only be sent on the active path, a separate
@@ -454,6 +572,7 @@ impl<Config: endpoint::Config> ConnectionImpl<Config> {
                         transmission_mode,
                         publisher: &mut self.event_context.publisher(timestamp, subscriber),
                         packet_interceptor,
+                        stage: None,
                     },
                     space_manager: &mut self.space_manager,
                 })
@@ -466,6 +585,85 @@ impl<Config: endpoint::Config> ConnectionImpl<Config> {
         count
     }
 
+    /// Transmits datagrams on the active path and seals their 1-RTT packets in batches
+    ///
+    /// The datagrams are written straight into the transmission queue. Their 1-RTT packets are
+    /// sealed in place once the batch is full or the connection stops transmitting.
+    fn batch_transmission<Tx: tx::Queue<Handle = Config::PathHandle>>(
+        &mut self,
+        queue: &mut Tx,
+        timestamp: Timestamp,
+        outcome: &mut transmission::Outcome,
+        subscriber: &mut Config::EventSubscriber,
+        packet_interceptor: &mut Config::PacketInterceptor,
+    ) -> usize {
+        let path_id = self.path_manager.active_path_id();
+        let mut tx_batch = connection::batch::Tx::default();
+        let mut stage = connection::batch::Stage::default();
+        let mut count = 0;
+
+        while self.path_manager.active_path().can_transmit(timestamp) {
+            let mut transmission = ConnectionTransmission {
+                context: transmission_context!(
+                    self,
+                    outcome,
+                    path_id,
+                    timestamp,
+                    transmission::Mode::Normal,
+                    subscriber,
+                    packet_interceptor,
+                ),
+                space_manager: &mut self.space_manager,
+            };
+            transmission.context.stage = Some(&mut stage);
+
+            let result = queue.push(transmission);
+            let packet = stage.packet.take();
+
+            if result.is_err() {
+                break;
+            }
+
+            count += 1;
+
+            if let Some(packet) = packet {
+                tx_batch.on_push(queue, packet);
+
+                if tx_batch.len() == connection::batch::MAX_BATCH_SIZE {
+                    self.seal_batch(queue, &mut tx_batch, timestamp, subscriber);
+                    stage.pending = 0;
+                }
+            }
+        }
+
+        self.seal_batch(queue, &mut tx_batch, timestamp, subscriber);
+
+        count
+    }
+
+    /// Seals the 1-RTT packets which were written into the transmission queue for a batch
+    fn seal_batch<Tx: tx::Queue<Handle = Config::PathHandle>>(
+        &mut self,
+        queue: &mut Tx,
+        tx_batch: &mut connection::batch::Tx,
+        timestamp: Timestamp,
+        subscriber: &mut Config::EventSubscriber,
+    ) {
+        if tx_batch.is_empty() {
+            return;
+        }
+
+        if let Some((space, _handshake_status)) = self.space_manager.application_mut() {
+            let mut publisher = self.event_context.publisher(timestamp, subscriber);
+            space.seal_batch(tx_batch, queue.as_slice_mut(), &mut publisher);
+        } else {
+            debug_assert!(
+                false,
+                "packets can only be staged while the application space is available"
+            );
+        }
+    }
+
     fn on_supervisor_timeout(
         &mut self,
         timestamp: Timestamp,
@@ -520,26 +718,38 @@ impl<Config: endpoint::Config> ConnectionImpl<Config> {
 
     /// Polls for the connection to flush all of the outstanding streams
     ///
+    /// This is the case if the application dropped the connection or if it is draining.
+    ///
     /// Once all of the streams are finished, `Poll::Ready` will be returned
     fn poll_flush(&mut self) -> Poll<()> {
-        if matches!(self.state, ConnectionState::Flushing) {
-            let is_finished = if let Some((space, _)) = self.space_manager.application_mut() {
-                space
-                    .stream_manager
-                    .flush(transport::Error::NO_ERROR.into())
-                    .is_ready()
-            } else {
-                debug_assert!(
-                    false,
-                    "connection should only be flushing with application space"
-                );
-                true
-            };
-
-            if is_finished {
-                self.error = Err(transport::Error::NO_ERROR.into());
-                return Poll::Ready(());
+        let is_finished = match self.state {
+            ConnectionState::Flushing => {
+                if let Some((space, _)) = self.space_manager.application_mut() {
+                    space
+                        .stream_manager
+                        .flush(transport::Error::NO_ERROR.into())
+                        .is_ready()
+                } else {
+                    debug_assert!(
+                        false,
+                        "connection should only be flushing with application space"
+                    );
+                    true
+                }
+            }
+            ConnectionState::Handshaking | ConnectionState::Active => {
+                if let Some((space, _)) = self.space_manager.application_mut() {
+                    space.stream_manager.poll_drain().is_ready()
+                } else {
+                    false
+                }
             }
+            _ => false,
+        };
+
+        if is_finished {
+            self.error = Err(transport::Error::NO_ERROR.into());
+            return Poll::Ready(());
         }
 
         Poll::Pending
@@ -610,10 +820,18 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
             path_manager,
             limits: parameters.limits,
             error: Ok(()),
+            close_reason: Bytes::new(),
             close_sender: CloseSender::default(),
             space_manager: parameters.space_manager,
             wakeup_handle,
             waker,
+            pending_expected_send_rate: None,
+            pending_drain_timeout: None,
+            endpoint_send_buffer: parameters.endpoint_send_buffer,
+            memory_pressure: parameters.memory_pressure,
+            last_stream_progress: parameters.timestamp,
+            amplification_limited_flights: 0,
+            lifecycle_wakers: Default::default(),
             event_context,
         };
 
@@ -703,6 +921,8 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
         // Update the connection state based on the type of error
         self.state = error.into();
         self.error = Err(error);
+        // Notify any handles waiting on the connection to close
+        self.lifecycle_wakers.on_close();
 
         //= https://www.rfc-editor.org/rfc/rfc9000#section-10.3
         //# An endpoint that wishes to communicate a fatal
@@ -712,9 +932,20 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
         let close_context = s2n_quic_core::connection::close::Context::new(&remote_address);
         let active_path_id = self.path_manager.active_path_id();
 
-        if let Some((early_connection_close, connection_close)) =
+        if let Some((early_connection_close, mut connection_close)) =
             s2n_quic_core::connection::error::as_frame(error, close_formatter, &close_context)
         {
+            // The application explicitly provided a reason so include it in the 1-RTT frame.
+            // Early frames never include the reason, since the application state could be
+            // exposed to an unauthenticated peer.
+            let close_reason = self.close_reason.clone();
+            if matches!(error, connection::Error::Application { .. })
+                && !close_reason.is_empty()
+                && connection_close.frame_type.is_none()
+            {
+                connection_close.reason = Some(&close_reason);
+            }
+
             let mut outcome = transmission::Outcome::default();
             let mut context = transmission_context!(
                 self,
@@ -869,23 +1100,36 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
                 }
 
                 // Send all other data for the active path
-                while self.path_manager.active_path().can_transmit(timestamp)
-                    && queue
-                        .push(ConnectionTransmission {
-                            context: transmission_context!(
-                                self,
-                                &mut outcome,
-                                path_id,
-                                timestamp,
-                                transmission::Mode::Normal,
-                                subscriber,
-                                packet_interceptor,
-                            ),
-                            space_manager: &mut self.space_manager,
-                        })
-                        .is_ok()
-                {
-                    count += 1;
+                //
+                // The 1-RTT packets are sealed in batches unless the packet interceptor needs to
+                // observe each datagram as it's written.
+                if !packet_interceptor.intercepts_tx_datagram() {
+                    count += self.batch_transmission(
+                        queue,
+                        timestamp,
+                        &mut outcome,
+                        subscriber,
+                        packet_interceptor,
+                    );
+                } else {
+                    while self.path_manager.active_path().can_transmit(timestamp)
+                        && queue
+                            .push(ConnectionTransmission {
+                                context: transmission_context!(
+                                    self,
+                                    &mut outcome,
+                                    path_id,
+                                    timestamp,
+                                    transmission::Mode::Normal,
+                                    subscriber,
+                                    packet_interceptor,
+                                ),
+                                space_manager: &mut self.space_manager,
+                            })
+                            .is_ok()
+                    {
+                        count += 1;
+                    }
                 }
 
                 if outcome.ack_elicitation.is_ack_eliciting() {
@@ -939,7 +1183,8 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
                 if outcome.bytes_progressed > 0 {
                     publisher.on_tx_stream_progress(TxStreamProgress {
                         bytes: outcome.bytes_progressed,
-                    })
+                    });
+                    self.last_stream_progress = timestamp;
                 }
 
                 // check to see if we are flushing and should close
@@ -1047,6 +1292,16 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
             self.on_supervisor_timeout(timestamp, subscriber, supervisor_context)?;
         }
 
+        // close the connection if the streams didn't finish draining in time
+        if self
+            .timers
+            .drain_timer
+            .poll_expiration(timestamp)
+            .is_ready()
+        {
+            return Err(transport::Error::NO_ERROR.into());
+        }
+
         // check to see if we're flushing the connection
         if self.poll_flush().is_ready() {
             return self.error;
@@ -1091,6 +1346,27 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
         // return an error if the application set one
         self.error?;
 
+        // apply the expected send rate if the application declared one
+        if let Some(expected_send_rate) = self.pending_expected_send_rate.take() {
+            let mut publisher = self.event_context.publisher(timestamp, subscriber);
+            self.path_manager
+                .on_expected_send_rate(expected_send_rate, &mut publisher);
+        }
+
+        // start draining the connection if the application requested it
+        self.start_pending_drain(timestamp);
+
+        // Streams may have started waiting for the send buffer budget of the endpoint right
+        // before it became available again, in which case the endpoint didn't notify us
+        if let Some((space, _)) = self.space_manager.application_mut() {
+            space.stream_manager.on_send_buffer_available();
+        }
+
+        // check to see if the streams have finished draining
+        if self.poll_flush().is_ready() {
+            return self.error;
+        }
+
         Ok(())
     }
 
@@ -1147,6 +1423,18 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
                     .on_datagram_received(rtt, datagram.timestamp);
             }
         } else if unblocked {
+            if !handshake_confirmed {
+                // The server had to wait for another datagram from the peer before it could
+                // continue sending the handshake
+                self.amplification_limited_flights += 1;
+                publisher.on_handshake_amplification_limited(
+                    event::builder::HandshakeAmplificationLimited {
+                        path_id: id.into_event(),
+                        extra_flights: self.amplification_limited_flights,
+                    },
+                );
+            }
+
             //= https://www.rfc-editor.org/rfc/rfc9002#appendix-A.6
             //# When a server is blocked by anti-amplification limits, receiving a
             //# datagram unblocks it, even if none of the packets in the datagram are
@@ -1406,7 +1694,7 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
             return Ok(());
         }
 
-        if let Some((space, handshake_status)) = self.space_manager.application_mut() {
+        if let Some((space, _handshake_status)) = self.space_manager.application_mut() {
             let packet = space.validate_and_decrypt_packet(
                 packet,
                 datagram,
@@ -1415,41 +1703,101 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
                 &mut publisher,
             )?;
 
-            publisher.on_packet_received(event::builder::PacketReceived {
-                packet_header: event::builder::PacketHeader::new(
-                    packet.packet_number,
-                    publisher.quic_version(),
-                ),
-            });
+            self.handle_cleartext_short_packet(
+                datagram,
+                path_id,
+                packet,
+                random_generator,
+                subscriber,
+                packet_interceptor,
+            )?;
+        }
 
-            // Connection Ids are issued to the peer after the handshake is
-            // confirmed and the handshake space is discarded. Therefore only
-            // short packets need to be processed for local_connection_id changes.
-            self.path_manager[path_id].on_process_local_connection_id(
+        Ok(())
+    }
+
+    /// Opens a batch of short packets which were received on the same path
+    fn open_short_packet_batch(
+        &mut self,
+        path_id: path::Id,
+        packets: &mut [connection::batch::RxPacket],
+        subscriber: &mut Config::EventSubscriber,
+    ) {
+        let timestamp = match packets.first() {
+            Some(packet) => packet.datagram.timestamp,
+            None => return,
+        };
+
+        // Packets are only opened together once the handshake is complete, otherwise they are
+        // handled individually
+        if !self.space_manager.is_handshake_complete() {
+            return;
+        }
+
+        if let Some((space, _handshake_status)) = self.space_manager.application_mut() {
+            let mut publisher = self.event_context.publisher(timestamp, subscriber);
+            let decrypted = space.validate_and_decrypt_batch(
+                packets,
                 path_id,
-                &packet,
-                &datagram.destination_connection_id,
+                &self.path_manager[path_id],
                 &mut publisher,
             );
 
-            let processed_packet = space.handle_cleartext_payload(
-                packet.packet_number,
-                packet.payload,
+            for (entry, packet) in packets.iter_mut().zip(decrypted) {
+                entry.cleartext = packet;
+            }
+        }
+    }
+
+    /// Is called when the datagram of a short packet in an opened batch had been received
+    fn handle_batched_short_packet(
+        &mut self,
+        path_id: path::Id,
+        packet: &mut connection::batch::RxPacket,
+        random_generator: &mut Config::RandomGenerator,
+        subscriber: &mut Config::EventSubscriber,
+        packet_interceptor: &mut Config::PacketInterceptor,
+    ) -> Result<(), ProcessingError> {
+        let datagram = &packet.datagram;
+
+        if let Some(protected) = packet.packet.take() {
+            return self.handle_short_packet(
                 datagram,
                 path_id,
-                &mut self.path_manager,
-                handshake_status,
-                &mut self.local_id_registry,
+                protected,
                 random_generator,
-                &mut publisher,
+                subscriber,
                 packet_interceptor,
-            )?;
+            );
+        }
 
-            // notify the connection a packet was processed
-            self.on_processed_packet(&processed_packet, subscriber)?;
+        // the packet couldn't be opened
+        let cleartext = match packet.cleartext.take() {
+            Some(cleartext) => cleartext,
+            None => return packet.result,
+        };
+
+        if let Some((space, _handshake_status)) = self.space_manager.application_mut() {
+            let mut publisher = self.event_context.publisher(datagram.timestamp, subscriber);
+            space.on_batch_packet_decrypted(
+                cleartext.packet_number,
+                datagram,
+                path_id,
+                &self.path_manager[path_id],
+                &mut publisher,
+            )?;
+        } else {
+            return Ok(());
         }
 
-        Ok(())
+        self.handle_cleartext_short_packet(
+            datagram,
+            path_id,
+            cleartext,
+            random_generator,
+            subscriber,
+            packet_interceptor,
+        )
     }
 
     /// Is called when a version negotiation packet had been received
@@ -1708,6 +2056,13 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
             };
         } else {
             interests.timeout = self.next_expiration();
+            if let Some(space) = self.space_manager.application() {
+                interests.buffered_bytes = space.buffered_bytes();
+                interests.send_buffered_bytes = space.stream_manager.send_buffered_bytes();
+                interests.send_buffer_blocked =
+                    space.stream_manager.is_blocked_on_endpoint_send_buffer();
+                interests.last_stream_progress = Some(self.last_stream_progress);
+            }
         }
 
         interests
@@ -1770,13 +2125,38 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
             .poll_open_local_stream(stream_type, open_token, context)
     }
 
-    fn application_close(&mut self, error: Option<application::Error>) {
+    fn poll_handshake_complete(
+        &mut self,
+        context: &Context,
+    ) -> Poll<Result<(), connection::Error>> {
+        self.error?;
+
+        if self.is_handshaking() {
+            self.lifecycle_wakers.register_handshake(context.waker());
+            return Poll::Pending;
+        }
+
+        Ok(()).into()
+    }
+
+    fn poll_closed(&mut self, context: &Context) -> Poll<connection::Error> {
+        if let Err(error) = self.error {
+            return error.into();
+        }
+
+        self.lifecycle_wakers.register_closed(context.waker());
+        Poll::Pending
+    }
+
+    fn application_close(&mut self, error: Option<(application::Error, Bytes)>) {
         if self.error.is_err() {
             return;
         }
 
-        if let Some(error) = error {
+        if let Some((error, reason)) = error {
             self.error = Err(connection::Error::application(error));
+            self.close_reason = reason;
+            self.lifecycle_wakers.on_close();
         } else {
             // give the connection some time to flush all outstanding streams
             self.state = ConnectionState::Flushing;
@@ -1795,6 +2175,24 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
         self.space_manager.application_protocol.clone()
     }
 
+    fn handshake_details(&self) -> Option<tls::HandshakeDetails> {
+        self.space_manager.handshake_details.clone()
+    }
+
+    fn export_keying_material(
+        &self,
+        label: &[u8],
+        context: &[u8],
+        output: &mut [u8],
+    ) -> Result<(), connection::Error> {
+        self.error?;
+
+        self.space_manager
+            .export_keying_material(label, context, output)?;
+
+        Ok(())
+    }
+
     fn ping(&mut self) -> Result<(), connection::Error> {
         self.error?;
 
@@ -1813,6 +2211,24 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
         Ok(())
     }
 
+    fn initiate_key_update(&mut self) -> Result<(), connection::Error> {
+        self.error?;
+
+        if let Some((space, _)) = self.space_manager.application_mut() {
+            space.initiate_key_update();
+
+            self.wakeup_handle.wakeup();
+        } else {
+            debug_assert!(
+                false,
+                "applications can't interact with the connection until the application space is available"
+            );
+            return Err(connection::Error::unspecified());
+        }
+
+        Ok(())
+    }
+
     fn keep_alive(&mut self, enabled: bool) -> Result<(), connection::Error> {
         self.error?;
 
@@ -1831,6 +2247,105 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
         Ok(())
     }
 
+    fn keep_alive_with(
+        &mut self,
+        settings: connection::KeepAlive,
+    ) -> Result<(), connection::Error> {
+        self.error?;
+
+        if let Some((space, _)) = self.space_manager.application_mut() {
+            space.keep_alive_with(settings);
+
+            self.wakeup_handle.wakeup();
+        } else {
+            debug_assert!(
+                false,
+                "applications can't interact with the connection until the application space is available"
+            );
+            return Err(connection::Error::unspecified());
+        }
+
+        Ok(())
+    }
+
+    fn set_expected_send_rate(
+        &mut self,
+        expected_send_rate: ExpectedSendRate,
+    ) -> Result<(), connection::Error> {
+        self.error?;
+
+        self.pending_expected_send_rate = Some(expected_send_rate);
+        self.wakeup_handle.wakeup();
+
+        Ok(())
+    }
+
+    fn drain(&mut self, timeout: Duration) -> Result<(), connection::Error> {
+        self.error?;
+
+        self.pending_drain_timeout = Some(timeout);
+        self.wakeup_handle.wakeup();
+
+        Ok(())
+    }
+
+    fn on_memory_pressure(&mut self, enabled: bool) {
+        self.memory_pressure = enabled;
+
+        if let Some((space, _)) = self.space_manager.application_mut() {
+            space.stream_manager.on_memory_pressure(enabled);
+
+            // wake up the connection so any withheld credit is transmitted
+            self.wakeup_handle.wakeup();
+        }
+    }
+
+    fn on_send_buffer_available(&mut self) {
+        if let Some((space, _)) = self.space_manager.application_mut() {
+            space.stream_manager.on_send_buffer_available();
+
+            // wake up the connection so any unblocked streams can make progress
+            self.wakeup_handle.wakeup();
+        }
+    }
+
+    fn update_limit(
+        &mut self,
+        update: connection::limits::Update,
+    ) -> Result<(), connection::Error> {
+        self.error?;
+
+        if let Some((space, _)) = self.space_manager.application_mut() {
+            space.stream_manager.update_limit(update);
+
+            self.wakeup_handle.wakeup();
+        } else {
+            debug_assert!(
+                false,
+                "applications can't interact with the connection until the application space is available"
+            );
+            return Err(connection::Error::unspecified());
+        }
+
+        Ok(())
+    }
+
+    fn register_receive_buffer(&mut self, buffer: BytesMut) -> Result<(), connection::Error> {
+        self.error?;
+
+        if let Some((space, _)) = self.space_manager.application_mut() {
+            space.stream_manager.register_receive_buffer(buffer);
+        } else {
+            debug_assert!(
+                false,
+                "applications can't interact with the connection until the application space is available"
+            );
+            return Err(connection::Error::unspecified());
+        }
+
+        Ok(())
+    }
+
     fn local_address(&self) -> Result<SocketAddress, connection::Error> {
         Ok(*self.path_manager.active_path().handle.local_address())
     }
@@ -25,6 +25,17 @@ pub struct ConnectionInterests {
     pub ack: bool,
     /// Is `Some(Timestamp)` if the connection needs to be woken up at the specified time
     pub timeout: Option<Timestamp>,
+    /// The number of bytes which are currently buffered by the `Connection`
+    pub buffered_bytes: u64,
+    /// The number of bytes which are currently buffered for sending by the `Connection`
+    ///
+    /// This is included in `buffered_bytes`.
+    pub send_buffered_bytes: u64,
+    /// Is `true` if streams of the `Connection` are waiting for the send buffer budget
+    /// of the endpoint to become available
+    pub send_buffer_blocked: bool,
+    /// The last time the `Connection` received or transmitted stream data
+    pub last_stream_progress: Option<Timestamp>,
 }
 
 impl ConnectionInterests {
@@ -39,6 +50,9 @@ impl ConnectionInterests {
     ///
     /// The `finalization` interest is the exception. A `Connection` can only
     /// be finalized if all parts are interested in finalization.
+    ///
+    /// The buffered bytes of both collections are added together and the latest stream
+    /// progress is kept.
     pub fn merge(self, other: ConnectionInterests) -> ConnectionInterests {
         ConnectionInterests {
             finalization: self.finalization && other.finalization,
@@ -53,6 +67,12 @@ impl ConnectionInterests {
                 (None, Some(b)) => Some(b),
                 (None, None) => None,
             },
+            buffered_bytes: self.buffered_bytes.saturating_add(other.buffered_bytes),
+            send_buffered_bytes: self
+                .send_buffered_bytes
+                .saturating_add(other.send_buffered_bytes),
+            send_buffer_blocked: self.send_buffer_blocked || other.send_buffer_blocked,
+            last_stream_progress: self.last_stream_progress.max(other.last_stream_progress),
         }
     }
 }
@@ -89,6 +109,10 @@ mod tests {
             new_connection_id: false,
             ack: false,
             timeout: None,
+            buffered_bytes: 0,
+            send_buffered_bytes: 0,
+            send_buffer_blocked: false,
+            last_stream_progress: None,
         };
 
         let b_time = unsafe { Timestamp::from_duration(Duration::from_secs(123)) };
@@ -100,6 +124,10 @@ mod tests {
             new_connection_id: true,
             ack: true,
             timeout: Some(b_time),
+            buffered_bytes: 10,
+            send_buffered_bytes: 5,
+            send_buffer_blocked: true,
+            last_stream_progress: Some(b_time),
         };
 
         let c_time = unsafe { Timestamp::from_duration(Duration::from_secs(456)) };
@@ -111,6 +139,10 @@ mod tests {
             new_connection_id: false,
             ack: false,
             timeout: Some(c_time),
+            buffered_bytes: 20,
+            send_buffered_bytes: 10,
+            send_buffer_blocked: false,
+            last_stream_progress: Some(c_time),
         };
 
         assert_eq!(
@@ -122,6 +154,10 @@ mod tests {
                 new_connection_id: true,
                 ack: true,
                 timeout: Some(b_time),
+                buffered_bytes: 10,
+                send_buffered_bytes: 5,
+                send_buffer_blocked: true,
+                last_stream_progress: Some(b_time),
             },
             a + b
         );
@@ -135,6 +171,10 @@ mod tests {
                 new_connection_id: false,
                 ack: false,
                 timeout: Some(c_time),
+                buffered_bytes: 20,
+                send_buffered_bytes: 10,
+                send_buffer_blocked: false,
+                last_stream_progress: Some(c_time),
             },
             a + c
         );
@@ -148,6 +188,10 @@ mod tests {
                 new_connection_id: true,
                 ack: true,
                 timeout: Some(b_time),
+                buffered_bytes: 30,
+                send_buffered_bytes: 15,
+                send_buffer_blocked: true,
+                last_stream_progress: Some(c_time),
             },
             b + c
         );
@@ -14,12 +14,16 @@ use crate::{
     path::{self, path_event},
     stream,
 };
-use bytes::Bytes;
-use core::task::{Context, Poll};
+use bytes::{Bytes, BytesMut};
+use core::{
+    task::{Context, Poll},
+    time::Duration,
+};
 use s2n_codec::DecoderBufferMut;
 use s2n_quic_core::{
     application,
     application::ServerName,
+    crypto::tls::HandshakeDetails,
     event::{self, builder::DatagramDropReason, supervisor, ConnectionPublisher, IntoEvent},
     inet::{DatagramInfo, SocketAddress},
     io::tx,
@@ -34,6 +38,7 @@ use s2n_quic_core::{
     },
     path::{Handle as _, MaxMtu},
     query,
+    recovery::ExpectedSendRate,
     time::Timestamp,
 };
 
@@ -160,6 +165,30 @@ pub trait ConnectionTrait: 'static + Send + Sized {
         packet_interceptor: &mut <Self::Config as endpoint::Config>::PacketInterceptor,
     ) -> Result<(), ProcessingError>;
 
+    /// Opens a batch of short packets which were received on the same path
+    ///
+    /// This is called once the datagram of the first packet in `packets` was received on the
+    /// path. The opened packets are stored in their `cleartext` and the error for each packet
+    /// which couldn't be opened is stored in its `result`.
+    fn open_short_packet_batch(
+        &mut self,
+        path_id: path::Id,
+        packets: &mut [connection::batch::RxPacket],
+        subscriber: &mut <Self::Config as endpoint::Config>::EventSubscriber,
+    );
+
+    /// Is called when the datagram of a short packet in an opened batch had been received
+    ///
+    /// Packets which weren't opened with the batch are handled individually.
+    fn handle_batched_short_packet(
+        &mut self,
+        path_id: path::Id,
+        packet: &mut connection::batch::RxPacket,
+        random_generator: &mut <Self::Config as endpoint::Config>::RandomGenerator,
+        subscriber: &mut <Self::Config as endpoint::Config>::EventSubscriber,
+        packet_interceptor: &mut <Self::Config as endpoint::Config>::PacketInterceptor,
+    ) -> Result<(), ProcessingError>;
+
     /// Is called when a version negotiation packet had been received
     fn handle_version_negotiation_packet(
         &mut self,
@@ -421,6 +450,13 @@ pub trait ConnectionTrait: 'static + Send + Sized {
         context: &Context,
     ) -> Poll<Result<Option<stream::StreamId>, connection::Error>>;
 
+    /// Polls for the connection to complete the handshake
+    fn poll_handshake_complete(&mut self, context: &Context)
+        -> Poll<Result<(), connection::Error>>;
+
+    /// Polls for the connection to close, returning the error which closed the connection
+    fn poll_closed(&mut self, context: &Context) -> Poll<connection::Error>;
+
     fn poll_open_stream(
         &mut self,
         stream_type: stream::StreamType,
@@ -428,16 +464,50 @@ pub trait ConnectionTrait: 'static + Send + Sized {
         context: &Context,
     ) -> Poll<Result<stream::StreamId, connection::Error>>;
 
-    fn application_close(&mut self, error: Option<application::Error>);
+    fn application_close(&mut self, error: Option<(application::Error, Bytes)>);
 
     fn server_name(&self) -> Option<ServerName>;
 
     fn application_protocol(&self) -> Bytes;
 
+    fn handshake_details(&self) -> Option<HandshakeDetails>;
+
+    fn export_keying_material(
+        &self,
+        label: &[u8],
+        context: &[u8],
+        output: &mut [u8],
+    ) -> Result<(), connection::Error>;
+
     fn ping(&mut self) -> Result<(), connection::Error>;
 
+    fn initiate_key_update(&mut self) -> Result<(), connection::Error>;
+
     fn keep_alive(&mut self, enabled: bool) -> Result<(), connection::Error>;
 
+    fn keep_alive_with(&mut self, settings: connection::KeepAlive)
+        -> Result<(), connection::Error>;
+
+    fn set_expected_send_rate(
+        &mut self,
+        expected_send_rate: ExpectedSendRate,
+    ) -> Result<(), connection::Error>;
+
+    fn update_limit(&mut self, update: connection::limits::Update)
+        -> Result<(), connection::Error>;
+
+    fn register_receive_buffer(&mut self, buffer: BytesMut) -> Result<(), connection::Error>;
+
+    fn drain(&mut self, timeout: Duration) -> Result<(), connection::Error>;
+
+    /// Notifies the connection that the endpoint entered or left memory pressure
+    fn on_memory_pressure(&mut self, enabled: bool);
+
+    /// Notifies the connection that the send buffer budget of the endpoint is available again
+    ///
+    /// This is only called for connections which expressed interest in the send buffer budget.
+    fn on_send_buffer_available(&mut self);
+
     fn local_address(&self) -> Result<SocketAddress, connection::Error>;
 
     fn remote_address(&self) -> Result<SocketAddress, connection::Error>;
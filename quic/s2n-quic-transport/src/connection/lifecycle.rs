@@ -0,0 +1,95 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Tracks the tasks which are waiting on a connection to transition between lifecycle stages
+
+use alloc::vec::Vec;
+use core::task::Waker;
+
+/// Wakers for tasks which observe the lifecycle of a connection through a handle
+///
+/// Multiple handles can observe the same connection, so a list of wakers is stored for each
+/// stage rather than a single waker.
+#[derive(Debug, Default)]
+pub struct Wakers {
+    /// Tasks waiting for the handshake to complete
+    handshake: Vec<Waker>,
+    /// Tasks waiting for the connection to close
+    closed: Vec<Waker>,
+}
+
+impl Wakers {
+    /// Registers a task to be woken when the handshake completes or the connection closes
+    #[inline]
+    pub fn register_handshake(&mut self, waker: &Waker) {
+        register(&mut self.handshake, waker);
+    }
+
+    /// Registers a task to be woken when the connection closes
+    #[inline]
+    pub fn register_closed(&mut self, waker: &Waker) {
+        register(&mut self.closed, waker);
+    }
+
+    /// Called when the connection completes the handshake
+    #[inline]
+    pub fn on_handshake_complete(&mut self) {
+        wake_all(&mut self.handshake);
+    }
+
+    /// Called when the connection is closed
+    ///
+    /// Tasks waiting for the handshake are also woken, since it will never complete.
+    #[inline]
+    pub fn on_close(&mut self) {
+        wake_all(&mut self.handshake);
+        wake_all(&mut self.closed);
+    }
+}
+
+#[inline]
+fn register(wakers: &mut Vec<Waker>, waker: &Waker) {
+    // avoid storing the same task multiple times if it polls more than once
+    if !wakers.iter().any(|w| w.will_wake(waker)) {
+        wakers.push(waker.clone());
+    }
+}
+
+#[inline]
+fn wake_all(wakers: &mut Vec<Waker>) {
+    for waker in wakers.drain(..) {
+        waker.wake();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures_test::task::new_count_waker;
+
+    #[test]
+    fn wake_test() {
+        let mut wakers = Wakers::default();
+        let (handshake_waker, handshake_count) = new_count_waker();
+        let (closed_waker, closed_count) = new_count_waker();
+
+        // registering the same task twice should only wake it once
+        wakers.register_handshake(&handshake_waker);
+        wakers.register_handshake(&handshake_waker);
+        wakers.register_closed(&closed_waker);
+
+        wakers.on_handshake_complete();
+        assert_eq!(handshake_count, 1);
+        assert_eq!(closed_count, 0);
+
+        wakers.register_handshake(&handshake_waker);
+        wakers.on_close();
+        assert_eq!(handshake_count, 2);
+        assert_eq!(closed_count, 1);
+
+        // wakers are only notified once
+        wakers.on_close();
+        assert_eq!(handshake_count, 2);
+        assert_eq!(closed_count, 1);
+    }
+}
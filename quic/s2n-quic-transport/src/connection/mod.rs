@@ -4,13 +4,14 @@
 //! This module contains the implementation of QUIC `Connections` and their management
 
 use crate::{
-    endpoint, path::MaxMtu, recovery::congestion_controller, space::PacketSpaceManager,
+    endpoint, path::MaxMtu, recovery::congestion_controller, space::PacketSpaceManager, stream,
     wakeup_queue::WakeupHandle,
 };
 use s2n_quic_core::{connection, event, event::supervisor, time::Timestamp};
 
 mod api;
 mod api_provider;
+pub(crate) mod batch;
 mod close_sender;
 mod connection_container;
 mod connection_id_mapper;
@@ -21,6 +22,7 @@ mod connection_trait;
 mod errors;
 pub(crate) mod finalization;
 mod internal_connection_id;
+mod lifecycle;
 pub(crate) mod local_id_registry;
 pub(crate) mod open_token;
 pub(crate) mod peer_id_registry;
@@ -37,7 +39,7 @@ pub(crate) use local_id_registry::LocalIdRegistry;
 pub(crate) use peer_id_registry::PeerIdRegistry;
 pub(crate) use transmission::{ConnectionTransmission, ConnectionTransmissionContext};
 
-pub use api::Connection;
+pub use api::{Connection, MAX_CLOSE_REASON_LEN};
 pub use connection_impl::ConnectionImpl as Implementation;
 pub use connection_trait::Lock;
 pub use open_token::Pair as OpenToken;
@@ -75,6 +77,10 @@ pub struct Parameters<'a, Cfg: endpoint::Config> {
     pub limits: connection::Limits,
     /// The largest maximum transmission unit (MTU) that can be sent on a path
     pub max_mtu: MaxMtu,
+    /// The send buffer budget of the endpoint, which is shared across all of its connections
+    pub endpoint_send_buffer: stream::EndpointSendBuffer,
+    /// Is `true` if the endpoint is under memory pressure
+    pub memory_pressure: bool,
     /// The context that should be passed to all related connection events
     pub event_context: <Cfg::EventSubscriber as event::Subscriber>::ConnectionContext,
     /// The context passed to the connection supervisor
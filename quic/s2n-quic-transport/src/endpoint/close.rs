@@ -7,13 +7,25 @@ use alloc::sync::Arc;
 use core::{
     sync::atomic::{AtomicBool, Ordering},
     task::{Context, Poll, Waker},
+    time::Duration,
 };
 
+/// A request from the application to close the endpoint
+#[derive(Debug)]
+pub(crate) struct Request {
+    /// Woken once the endpoint has closed
+    pub waker: Waker,
+    /// If set, all of the active connections are drained with the given timeout
+    pub drain_timeout: Option<Duration>,
+}
+
 /// Held by library. Used to receive close attempts and track close state.
 #[derive(Debug)]
 pub(crate) struct CloseHandle {
     /// Used to determine if the application has interest in closing the endpoint
     first_waker: Option<Waker>,
+    /// Set if the application requested the active connections to be drained
+    drain_timeout: Option<Duration>,
     /// A channel which is used to receive connection close attempts
     close_receiver: CloseReceiver,
     /// Track the endpoint open state
@@ -24,6 +36,7 @@ impl CloseHandle {
     pub fn new(close_receiver: CloseReceiver, endpoint_state: EndpointState) -> Self {
         Self {
             first_waker: None,
+            drain_timeout: None,
             close_receiver,
             endpoint_state,
         }
@@ -35,8 +48,9 @@ impl CloseHandle {
             Poll::Ready(())
         } else {
             match self.close_receiver.try_next() {
-                Ok(Some(waker)) => {
-                    self.first_waker = Some(waker);
+                Ok(Some(request)) => {
+                    self.first_waker = Some(request.waker);
+                    self.drain_timeout = request.drain_timeout;
                     Poll::Ready(())
                 }
                 _ => Poll::Pending,
@@ -44,6 +58,14 @@ impl CloseHandle {
         }
     }
 
+    /// Returns the drain timeout if the application requested the active connections to be
+    /// drained
+    ///
+    /// The timeout is only returned once.
+    pub fn take_drain_timeout(&mut self) -> Option<Duration> {
+        self.drain_timeout.take()
+    }
+
     /// Marks that the endpoint has finished processing and accepting connections and is
     /// ready to be closed.
     pub fn close(&mut self) {
@@ -52,8 +74,8 @@ impl CloseHandle {
         if let Some(waker) = self.first_waker.take() {
             waker.wake();
         }
-        while let Ok(Some(waker)) = self.close_receiver.try_next() {
-            waker.wake_by_ref();
+        while let Ok(Some(request)) = self.close_receiver.try_next() {
+            request.waker.wake_by_ref();
         }
     }
 }
@@ -101,6 +123,23 @@ impl Closer {
     pub(crate) fn poll_close(
         &mut self,
         context: &mut Context,
+    ) -> Poll<Result<(), connection::Error>> {
+        self.poll_request(None, context)
+    }
+
+    /// Drains all of the active connections and closes the endpoint once they have finished
+    pub(crate) fn poll_drain(
+        &mut self,
+        timeout: Duration,
+        context: &mut Context,
+    ) -> Poll<Result<(), connection::Error>> {
+        self.poll_request(Some(timeout), context)
+    }
+
+    fn poll_request(
+        &mut self,
+        drain_timeout: Option<Duration>,
+        context: &mut Context,
     ) -> Poll<Result<(), connection::Error>> {
         if !self.endpoint_state.is_open() {
             return Poll::Ready(Ok(()));
@@ -110,7 +149,11 @@ impl Closer {
             match self.close_sender.poll_ready(context) {
                 Poll::Ready(Ok(())) => {
                     // send a waker to the endpoint, which is woken once the endpoint has closed
-                    match self.close_sender.try_send(context.waker().clone()) {
+                    let request = Request {
+                        waker: context.waker().clone(),
+                        drain_timeout,
+                    };
+                    match self.close_sender.try_send(request) {
                         Ok(_) => {
                             self.request_sent = true;
                         }
@@ -272,6 +272,8 @@ impl<Config: endpoint::Config> endpoint::Endpoint<Config> {
             quic_version,
             limits,
             max_mtu,
+            endpoint_send_buffer: self.send_buffer.clone(),
+            memory_pressure: self.memory_pressure.shrinks_windows(),
             event_context,
             supervisor_context: &supervisor_context,
             event_subscriber: endpoint_context.event_subscriber,
@@ -14,6 +14,7 @@ use crate::{
     endpoint::close::CloseHandle,
     recovery::congestion_controller::{self, Endpoint as _},
     space::PacketSpaceManager,
+    stream,
     wakeup_queue::WakeupQueue,
 };
 use alloc::collections::VecDeque;
@@ -29,7 +30,10 @@ use s2n_quic_core::{
     },
     crypto::{tls, tls::Endpoint as _, CryptoSuite, InitialKey},
     datagram::{Endpoint as DatagramEndpoint, PreConnectionInfo},
-    endpoint::{limits::Outcome, Limiter as _},
+    endpoint::{
+        limits::{MemoryPressure, MemoryUsage, Outcome},
+        Limiter as _,
+    },
     event::{
         self, supervisor, ConnectionPublisher, EndpointPublisher as _, IntoEvent, Subscriber as _,
     },
@@ -39,10 +43,13 @@ use s2n_quic_core::{
     path,
     path::{Handle as _, MaxMtu},
     random::Generator as _,
-    stateless_reset::token::{Generator as _, LEN as StatelessResetTokenLen},
+    stateless_reset::{
+        token::{Generator as _, LEN as StatelessResetTokenLen},
+        Token as StatelessResetToken,
+    },
     time::{Clock, Timestamp},
     token::{self, Format},
-    transport::parameters::ClientTransportParameters,
+    transport::{self, parameters::ClientTransportParameters},
 };
 
 pub mod close;
@@ -87,6 +94,10 @@ pub struct Endpoint<Cfg: Config> {
     close_packet_buffer: packet_buffer::Buffer,
     /// The largest maximum transmission unit (MTU) that can be sent on a path
     max_mtu: MaxMtu,
+    /// The memory pressure the endpoint is currently under
+    memory_pressure: MemoryPressure,
+    /// The send buffer budget of the endpoint, which is shared across all of its connections
+    send_buffer: stream::EndpointSendBuffer,
 }
 
 impl<Cfg: Config> s2n_quic_core::endpoint::Endpoint for Endpoint<Cfg> {
@@ -104,23 +115,30 @@ impl<Cfg: Config> s2n_quic_core::endpoint::Endpoint for Endpoint<Cfg> {
 
         let local_address = queue.local_address();
         let entries = queue.as_slice_mut();
+        let len = entries.len();
         let mut now: Option<Timestamp> = None;
 
-        for entry in entries.iter_mut() {
-            let timestamp = match now {
-                Some(time) => time,
-                None => {
-                    now = Some(clock.get_time());
-                    now.expect("value should be set")
-                }
-            };
+        {
+            // 1-RTT packets for the same connection are opened together
+            let mut rx_batch = connection::batch::Rx::default();
+
+            for entry in entries.iter_mut() {
+                let timestamp = match now {
+                    Some(time) => time,
+                    None => {
+                        now = Some(clock.get_time());
+                        now.expect("value should be set")
+                    }
+                };
 
-            if let Some((header, payload)) = entry.read(&local_address) {
-                self.receive_datagram(&header, payload, timestamp)
+                if let Some((header, payload)) = entry.read(&local_address) {
+                    self.receive_datagram(&header, payload, timestamp, &mut rx_batch)
+                }
             }
+
+            self.flush_rx_batch(&mut rx_batch);
         }
 
-        let len = entries.len();
         queue.finish(len);
     }
 
@@ -180,15 +198,21 @@ impl<Cfg: Config> s2n_quic_core::endpoint::Endpoint for Endpoint<Cfg> {
         cx: &mut task::Context<'_>,
         clock: &C,
     ) -> Poll<Result<usize, s2n_quic_core::endpoint::CloseError>> {
-        if self.close_handle.poll_interest().is_ready() // poll for close interest
-            && self.connections.is_empty() // wait for all connections to close gracefully
-            && self.connections.is_open()
-        {
-            // transition to close state
-            self.close_handle.close();
+        if self.close_handle.poll_interest().is_ready() {
+            // poll for close interest
+            if let Some(timeout) = self.close_handle.take_drain_timeout() {
+                // stop accepting new connections and drain the active ones
+                self.connections.drain(timeout);
+            }
 
-            // stop accepting new connections and prepare to close the endpoint
-            self.connections.close();
+            // wait for all connections to close gracefully
+            if self.connections.is_empty() && self.connections.is_open() {
+                // transition to close state
+                self.close_handle.close();
+
+                // stop accepting new connections and prepare to close the endpoint
+                self.connections.close();
+            }
         }
 
         // Drop the endpoint if there is no more progress to be made.
@@ -321,6 +345,8 @@ impl<Cfg: Config> Endpoint<Cfg> {
             stateless_reset_dispatch: stateless_reset::Dispatch::default(),
             close_packet_buffer: Default::default(),
             max_mtu: Default::default(),
+            memory_pressure: Default::default(),
+            send_buffer: Default::default(),
         };
 
         (endpoint, handle)
@@ -345,7 +371,8 @@ impl<Cfg: Config> Endpoint<Cfg> {
             self.connections.len(),
             &remote_address,
             timestamp.into_event(),
-        );
+        )
+        .with_buffered_bytes(self.buffered_bytes());
 
         let context = self.config.context();
         let outcome = context.endpoint_limits.on_connection_attempt(&attempt);
@@ -421,16 +448,23 @@ impl<Cfg: Config> Endpoint<Cfg> {
     }
 
     /// Ingests a single datagram
-    fn receive_datagram(
+    ///
+    /// Datagrams which only contain a 1-RTT packet for a known connection are added to
+    /// `rx_batch` instead of being processed immediately.
+    fn receive_datagram<'a>(
         &mut self,
         header: &datagram::Header<Cfg::PathHandle>,
-        payload: &mut [u8],
+        payload: &'a mut [u8],
         timestamp: Timestamp,
+        rx_batch: &mut connection::batch::Rx<'a, Cfg::PathHandle>,
     ) {
         let endpoint_context = self.config.context();
 
         let remote_address = header.path.remote_address();
 
+        // The token is copied out before decoding since the payload is borrowed by the packet
+        let stateless_reset_token = Self::stateless_reset_token(payload);
+
         // Try to decode the first packet in the datagram
         let payload_len = payload.len();
         let buffer = DecoderBufferMut::new(payload);
@@ -468,7 +502,8 @@ impl<Cfg: Config> Endpoint<Cfg> {
             //# versions might allow the use of a long header.
 
             // The packet may be a stateless reset, check before returning.
-            let internal_connection_id = self.close_on_matching_stateless_reset(payload, timestamp);
+            let internal_connection_id =
+                self.close_on_matching_stateless_reset(stateless_reset_token, timestamp);
 
             if internal_connection_id.is_none() {
                 // The packet didn't contain a valid stateless token
@@ -542,7 +577,6 @@ impl<Cfg: Config> Endpoint<Cfg> {
         };
 
         // TODO validate the connection ID before looking up the connection in the map
-        let close_packet_buffer = &mut self.close_packet_buffer;
 
         // Try to lookup the internal connection ID and dispatch the packet
         // to the Connection
@@ -550,6 +584,30 @@ impl<Cfg: Config> Endpoint<Cfg> {
             .connection_id_mapper
             .lookup_internal_connection_id(&datagram.destination_connection_id)
         {
+            let packet = match packet {
+                ProtectedPacket::Short(packet) if remaining.is_empty() => {
+                    if !rx_batch.can_push(internal_id, &header.path) {
+                        self.flush_rx_batch(rx_batch);
+                    }
+
+                    rx_batch.push(
+                        internal_id,
+                        header.path,
+                        *datagram,
+                        packet,
+                        stateless_reset_token,
+                    );
+                    return;
+                }
+                packet => packet,
+            };
+
+            // Packets for a connection are processed in the order they were received so any
+            // pending batch is processed first
+            self.flush_rx_batch(rx_batch);
+
+            let endpoint_context = self.config.context();
+            let close_packet_buffer = &mut self.close_packet_buffer;
             let mut check_for_stateless_reset = false;
             let max_mtu = self.max_mtu;
 
@@ -676,7 +734,7 @@ impl<Cfg: Config> Endpoint<Cfg> {
             });
 
             if check_for_stateless_reset {
-                self.close_on_matching_stateless_reset(payload, timestamp);
+                self.close_on_matching_stateless_reset(stateless_reset_token, timestamp);
             }
 
             return;
@@ -809,7 +867,7 @@ impl<Cfg: Config> Endpoint<Cfg> {
                 //# valid stateless reset token as a Stateless Reset, as other QUIC
                 //# versions might allow the use of a long header.
                 let is_stateless_reset = self
-                    .close_on_matching_stateless_reset(payload, timestamp)
+                    .close_on_matching_stateless_reset(stateless_reset_token, timestamp)
                     .is_some();
 
                 //= https://www.rfc-editor.org/rfc/rfc9000#section-9.3.2
@@ -864,14 +922,107 @@ impl<Cfg: Config> Endpoint<Cfg> {
         );
     }
 
-    /// Checks if the given payload contains a stateless reset token matching a known token.
-    /// If there is a match, the matching connection will be closed and the `InternalConnectionId`
-    /// will be returned.
-    fn close_on_matching_stateless_reset(
-        &mut self,
-        payload: &[u8],
-        timestamp: Timestamp,
-    ) -> Option<InternalConnectionId> {
+    /// Processes the 1-RTT packets which were batched for a single connection
+    fn flush_rx_batch(&mut self, rx_batch: &mut connection::batch::Rx<Cfg::PathHandle>) {
+        let (internal_id, handle, mut packets) = match rx_batch.take() {
+            Some(batch) => batch,
+            None => return,
+        };
+
+        let endpoint_context = self.config.context();
+        let close_packet_buffer = &mut self.close_packet_buffer;
+        let max_mtu = self.max_mtu;
+
+        let _ = self.connections.with_connection(internal_id, |conn| {
+            let mut is_open = false;
+
+            // Each datagram is received right before its packet is processed, like datagrams
+            // which aren't batched. The packets are opened together once the connection accepts
+            // the first datagram on the path.
+            for index in 0..packets.len() {
+                let entry = &mut packets[index];
+
+                let path_id = match conn.on_datagram_received(
+                    &handle,
+                    &entry.datagram,
+                    endpoint_context.congestion_controller,
+                    endpoint_context.path_migration,
+                    max_mtu,
+                    endpoint_context.event_subscriber,
+                ) {
+                    Ok(path_id) => path_id,
+                    Err(datagram_drop_reason) => {
+                        // An error received at this point was caused by a datagram that has not
+                        // been authenticated yet, and thus the connection should not be closed.
+                        conn.with_event_publisher(
+                            entry.datagram.timestamp,
+                            None,
+                            endpoint_context.event_subscriber,
+                            |publisher, _path| {
+                                publisher.on_datagram_dropped(event::builder::DatagramDropped {
+                                    len: entry.datagram.payload_len as u16,
+                                    reason: datagram_drop_reason,
+                                });
+                            },
+                        );
+                        entry.packet = None;
+                        entry.cleartext = None;
+                        entry.result = Ok(());
+                        continue;
+                    }
+                };
+
+                if !is_open {
+                    is_open = true;
+                    conn.open_short_packet_batch(
+                        path_id,
+                        &mut packets[index..],
+                        endpoint_context.event_subscriber,
+                    );
+                }
+
+                let entry = &mut packets[index];
+                entry.result = conn.handle_batched_short_packet(
+                    path_id,
+                    entry,
+                    endpoint_context.random_generator,
+                    endpoint_context.event_subscriber,
+                    endpoint_context.packet_interceptor,
+                );
+
+                if let Err(ProcessingError::ConnectionError(err)) = entry.result {
+                    conn.close(
+                        err,
+                        endpoint_context.connection_close_formatter,
+                        close_packet_buffer,
+                        entry.datagram.timestamp,
+                        endpoint_context.event_subscriber,
+                        endpoint_context.packet_interceptor,
+                    );
+                    return;
+                }
+            }
+        });
+
+        // Packets which failed decryption are a potential indication of a stateless reset from
+        // the peer. See `receive_datagram` for the requirements.
+        for entry in packets.iter() {
+            if let Err(ProcessingError::CryptoError(_)) = entry.result {
+                if self
+                    .close_on_matching_stateless_reset(
+                        entry.stateless_reset_token,
+                        entry.datagram.timestamp,
+                    )
+                    .is_some()
+                {
+                    break;
+                }
+            }
+        }
+    }
+
+    /// Returns the last bytes of the given payload as a potential stateless reset token
+    fn stateless_reset_token(payload: &[u8]) -> Option<StatelessResetToken> {
         let buffer = DecoderBuffer::new(payload);
 
         //= https://www.rfc-editor.org/rfc/rfc9000#section-10.3.1
@@ -883,6 +1034,18 @@ impl<Cfg: Config> Endpoint<Cfg> {
         let token_index = payload.len().checked_sub(StatelessResetTokenLen)?;
         let buffer = buffer.skip(token_index).ok()?;
         let (token, _) = buffer.decode().ok()?;
+        Some(token)
+    }
+
+    /// Checks if the given token from the end of a datagram matches a known stateless reset
+    /// token. If there is a match, the matching connection will be closed and the
+    /// `InternalConnectionId` will be returned.
+    fn close_on_matching_stateless_reset(
+        &mut self,
+        token: Option<StatelessResetToken>,
+        timestamp: Timestamp,
+    ) -> Option<InternalConnectionId> {
+        let token = token?;
         let endpoint_context = self.config.context();
         let internal_id = self
             .connection_id_mapper
@@ -962,6 +1125,95 @@ impl<Cfg: Config> Endpoint<Cfg> {
                     panic!("Generated connection ID was already in use");
                 }
             });
+
+        self.on_memory_usage(timestamp);
+        self.on_send_buffer_usage();
+    }
+
+    /// Blocks or unblocks the streams of all connections depending on the send buffer budget
+    /// of the endpoint limits
+    ///
+    /// Connections observe the shared budget directly, so blocking it doesn't require notifying
+    /// them. Once the budget is available again, only the connections with streams waiting for
+    /// it are notified.
+    fn on_send_buffer_usage(&mut self) {
+        let limit = self.config.context().endpoint_limits.max_send_buffer_size();
+        let blocked = limit.map_or(false, |limit| {
+            self.connections.send_buffered_bytes() >= limit
+        });
+
+        if blocked == self.send_buffer.is_blocked() {
+            return;
+        }
+
+        self.send_buffer.set_blocked(blocked);
+
+        if !blocked {
+            self.connections
+                .iterate_send_buffer_blocked_list(|connection| {
+                    connection.on_send_buffer_available();
+                    ConnectionContainerIterationResult::Continue
+                });
+        }
+    }
+
+    /// Returns the number of bytes buffered by the endpoint across all connections
+    fn buffered_bytes(&self) -> u64 {
+        self.connections.buffered_bytes() + self.close_packet_buffer.buffered_bytes()
+    }
+
+    /// Applies the memory pressure policy of the endpoint limits to the current memory usage
+    fn on_memory_usage(&mut self, timestamp: Timestamp) {
+        let buffered_bytes = self.buffered_bytes();
+        let usage = MemoryUsage::new(
+            buffered_bytes,
+            self.connections.len(),
+            timestamp.into_event(),
+        );
+
+        let close_packet_buffer = &mut self.close_packet_buffer;
+        let endpoint_context = self.config.context();
+        let pressure = endpoint_context.endpoint_limits.on_memory_usage(&usage);
+
+        if pressure != self.memory_pressure {
+            let mut publisher = event::EndpointPublisherSubscriber::new(
+                event::builder::EndpointMeta {
+                    endpoint_type: Cfg::ENDPOINT_TYPE,
+                    timestamp,
+                },
+                None,
+                endpoint_context.event_subscriber,
+            );
+            publisher.on_endpoint_memory_pressure_updated(
+                event::builder::EndpointMemoryPressureUpdated {
+                    buffered_bytes,
+                    pressure: pressure.into_event(),
+                },
+            );
+
+            if pressure.shrinks_windows() != self.memory_pressure.shrinks_windows() {
+                self.connections
+                    .on_memory_pressure(pressure.shrinks_windows());
+            }
+
+            self.memory_pressure = pressure;
+        }
+
+        if let Some(min_idle_time) = pressure.closes_largest_consumer() {
+            self.connections
+                .with_largest_consumer(timestamp, min_idle_time, |conn| {
+                    conn.close(
+                        transport::Error::INTERNAL_ERROR
+                            .with_reason("memory pressure")
+                            .into(),
+                        endpoint_context.connection_close_formatter,
+                        close_packet_buffer,
+                        timestamp,
+                        endpoint_context.event_subscriber,
+                        endpoint_context.packet_interceptor,
+                    );
+                });
+        }
     }
 
     fn create_client_connection(
@@ -1142,6 +1394,8 @@ impl<Cfg: Config> Endpoint<Cfg> {
             quic_version,
             limits,
             max_mtu: self.max_mtu,
+            endpoint_send_buffer: self.send_buffer.clone(),
+            memory_pressure: self.memory_pressure.shrinks_windows(),
             event_context,
             supervisor_context: &supervisor_context,
             event_subscriber: endpoint_context.event_subscriber,
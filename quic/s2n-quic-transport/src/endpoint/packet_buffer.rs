@@ -67,6 +67,11 @@ impl Buffer {
 
         Some(self.buffer.split_to(len).freeze())
     }
+
+    /// Returns the number of bytes which are currently allocated for future packets
+    pub fn buffered_bytes(&self) -> u64 {
+        self.buffer.capacity() as u64
+    }
 }
 
 #[cfg(test)]
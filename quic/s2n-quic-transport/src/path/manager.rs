@@ -24,7 +24,7 @@ use s2n_quic_core::{
     random,
     recovery::{
         congestion_controller::{self, Endpoint as _},
-        RttEstimator,
+        CongestionController as _, ExpectedSendRate, RttEstimator,
     },
     stateless_reset,
     time::{timer, Timestamp},
@@ -68,6 +68,12 @@ pub struct Manager<Config: endpoint::Config> {
     /// The `paths` data structure will need to be enhanced to include garbage collection
     /// of old paths to overcome this limitation.
     pending_packet_authentication: Option<u8>,
+
+    /// The rate at which the application has declared it expects to send
+    ///
+    /// This is applied to the congestion controller of each path, including paths
+    /// created after the rate was declared.
+    expected_send_rate: ExpectedSendRate,
 }
 
 impl<Config: endpoint::Config> Manager<Config> {
@@ -78,6 +84,7 @@ impl<Config: endpoint::Config> Manager<Config> {
             active: 0,
             last_known_active_validated_path: None,
             pending_packet_authentication: None,
+            expected_send_rate: ExpectedSendRate::Unspecified,
         };
         manager.paths[0].activated = true;
         manager.paths[0].is_active = true;
@@ -388,7 +395,8 @@ impl<Config: endpoint::Config> Manager<Config> {
         // again on confirming the peer's ownership of its new address.
         let rtt = RttEstimator::new(self.active_path().rtt_estimator.max_ack_delay());
         let path_info = congestion_controller::PathInfo::new(&remote_address);
-        let cc = congestion_controller_endpoint.new_congestion_controller(path_info);
+        let mut cc = congestion_controller_endpoint.new_congestion_controller(path_info);
+        cc.on_expected_send_rate(self.expected_send_rate);
 
         let peer_connection_id = {
             if self.active_path().local_connection_id != datagram.destination_connection_id {
@@ -762,6 +770,25 @@ impl<Config: endpoint::Config> Manager<Config> {
         Ok(())
     }
 
+    /// Applies the application's expected send rate to the congestion controller of each path
+    pub fn on_expected_send_rate<Pub: event::ConnectionPublisher>(
+        &mut self,
+        expected_send_rate: ExpectedSendRate,
+        publisher: &mut Pub,
+    ) {
+        self.expected_send_rate = expected_send_rate;
+
+        for (id, path) in self.paths.iter_mut().enumerate() {
+            path.congestion_controller
+                .on_expected_send_rate(expected_send_rate);
+
+            publisher.on_expected_send_rate_updated(event::builder::ExpectedSendRateUpdated {
+                path_id: path_id(id as u8).into_event(),
+                rate: expected_send_rate.into_event(),
+            });
+        }
+    }
+
     /// Notifies the path manager of the connection closing event
     pub fn on_closing(&mut self) {
         self.active_path_mut().on_closing();
@@ -18,7 +18,7 @@ use s2n_quic_core::{
     inet::{DatagramInfo, ExplicitCongestionNotification, SocketAddress},
     path::{migration, RemoteAddress},
     random::{self, Generator},
-    recovery::RttEstimator,
+    recovery::{ExpectedSendRate, RttEstimator},
     stateless_reset::token::testing::*,
     time::{Clock, NoopClock},
 };
@@ -731,6 +731,73 @@ fn test_adding_new_path() {
     assert_eq!(manager.paths.len(), 2);
 }
 
+#[test]
+// apply the expected send rate to existing and new paths
+// Setup:
+// - create path manger with one path
+// - set the expected send rate
+//
+// Trigger:
+// - call on_datagram_received with new remote address
+//
+// Expectation:
+// - assert both paths use the expected send rate
+fn expected_send_rate_applies_to_new_paths() {
+    // Setup:
+    let mut publisher = Publisher::snapshot();
+    let first_conn_id = connection::PeerId::try_from_bytes(&[1]).unwrap();
+    let first_addr: SocketAddr = "127.0.0.1:8001".parse().unwrap();
+    let first_addr = RemoteAddress::from(SocketAddress::from(first_addr));
+    let first_path = ServerPath::new(
+        first_addr,
+        first_conn_id,
+        connection::LocalId::TEST_ID,
+        RttEstimator::default(),
+        Default::default(),
+        false,
+        DEFAULT_MAX_MTU,
+    );
+    let mut manager = manager_server(first_path);
+    let expected_send_rate = ExpectedSendRate::Limited {
+        bytes_per_second: 1000,
+    };
+    manager.on_expected_send_rate(expected_send_rate, &mut publisher);
+    assert_eq!(
+        manager[path_id(0)].congestion_controller.expected_send_rate,
+        expected_send_rate
+    );
+
+    // Trigger:
+    let new_addr: SocketAddr = "127.0.0.2:8001".parse().unwrap();
+    let new_addr = RemoteAddress::from(SocketAddress::from(new_addr));
+    let datagram = DatagramInfo {
+        timestamp: NoopClock {}.get_time(),
+        payload_len: 0,
+        ecn: ExplicitCongestionNotification::default(),
+        destination_connection_id: connection::LocalId::TEST_ID,
+        source_connection_id: None,
+    };
+    let (new_path_id, _unblocked) = manager
+        .on_datagram_received(
+            &new_addr,
+            &datagram,
+            true,
+            &mut Default::default(),
+            &mut migration::default::Validator::default(),
+            DEFAULT_MAX_MTU,
+            &mut publisher,
+        )
+        .unwrap();
+
+    // Expectation:
+    assert_eq!(
+        manager[new_path_id]
+            .congestion_controller
+            .expected_send_rate,
+        expected_send_rate
+    );
+}
+
 #[test]
 // do NOT add new path if handshake is not confirmed
 // Setup:
@@ -3,7 +3,7 @@
 
 use crate::{
     ack::AckManager,
-    connection::{self, ConnectionTransmissionContext, ProcessingError},
+    connection::{self, batch, ConnectionTransmissionContext, ProcessingError},
     endpoint, path,
     path::{path_event, Path},
     processed_packet::ProcessedPacket,
@@ -18,24 +18,30 @@ use core::{convert::TryInto, fmt, marker::PhantomData};
 use once_cell::sync::OnceCell;
 use s2n_codec::EncoderBuffer;
 use s2n_quic_core::{
-    crypto::{application::KeySet, limited, tls, CryptoSuite},
+    crypto::{
+        application::{key_update, KeySet},
+        limited, tls, BatchPayload, CryptoError, CryptoSuite, HeaderKey as _, HeaderProtectionMask,
+        Key as _,
+    },
     event::{self, ConnectionPublisher as _, IntoEvent},
     frame::{
         ack::AckRanges, crypto::CryptoRef, datagram::DatagramRef, stream::StreamRef, Ack,
         ConnectionClose, DataBlocked, HandshakeDone, MaxData, MaxStreamData, MaxStreams,
-        NewConnectionId, NewToken, PathChallenge, PathResponse, ResetStream, RetireConnectionId,
-        StopSending, StreamDataBlocked, StreamsBlocked,
+        NewConnectionId, NewToken, PathChallenge, PathResponse, ResetStream, ResetStreamAt,
+        RetireConnectionId, StopSending, StreamDataBlocked, StreamsBlocked,
     },
     inet::DatagramInfo,
+    io::tx,
     packet::{
         encoding::{PacketEncoder, PacketEncodingError},
         number::{PacketNumber, PacketNumberRange, PacketNumberSpace, SlidingWindow},
-        short::{CleartextShort, ProtectedShort, Short, SpinBit},
+        short::{BatchShort, CleartextShort, EncryptedShort, ProtectedShort, Short, SpinBit},
     },
     path::MaxMtu,
     time::{timer, Timestamp},
     transport,
 };
+use smallvec::SmallVec;
 
 pub struct ApplicationSpace<Config: endpoint::Config> {
     /// Transmission Packet numbers
@@ -89,9 +95,10 @@ impl<Config: endpoint::Config> ApplicationSpace<Config> {
         ack_manager: AckManager,
         keep_alive: KeepAlive,
         max_mtu: MaxMtu,
+        key_update_policy: key_update::Policy,
         datagram_manager: datagram::Manager<Config>,
     ) -> Self {
-        let key_set = KeySet::new(key, Self::key_limits(max_mtu));
+        let key_set = KeySet::new(key, Self::key_limits(max_mtu, key_update_policy));
 
         Self {
             tx_packet_numbers: TxPacketNumbers::new(PacketNumberSpace::ApplicationData, now),
@@ -189,24 +196,68 @@ impl<Config: endpoint::Config> ApplicationSpace<Config> {
 
         let spin_bit = self.spin_bit;
         let header_key = &self.header_key;
-        let (_protected_packet, buffer) =
-            self.key_set
-                .encrypt_packet(buffer, |buffer, key, key_phase| {
-                    let packet = Short {
-                        spin_bit,
-                        key_phase,
-                        destination_connection_id,
-                        packet_number,
-                        payload,
-                    };
-                    packet.encode_packet(
-                        key,
-                        header_key,
-                        packet_number_encoder,
-                        min_packet_len,
-                        buffer,
-                    )
-                })?;
+
+        // If the connection is staging a batch, encode the packet now and seal it later with
+        // the rest of the batch
+        let batch_phase = context
+            .stage
+            .as_ref()
+            .and_then(|stage| self.key_set.batch_encryption_phase(stage.pending));
+
+        let (key_phase, buffer) = if let Some(key_phase) = batch_phase {
+            let packet = Short {
+                spin_bit,
+                key_phase,
+                destination_connection_id,
+                packet_number,
+                payload,
+            };
+            let (packet, buffer) = packet.encode_unsealed_packet(
+                self.key_set.key(key_phase),
+                header_key,
+                packet_number_encoder,
+                min_packet_len,
+                buffer,
+            )?;
+
+            if let Some(stage) = context.stage.as_mut() {
+                stage.pending += 1;
+                stage.packet = Some(batch::UnsealedRecord {
+                    packet_number: packet.packet_number,
+                    packet_number_len: packet.packet_number_len,
+                    header_len: packet.header_len,
+                    len: packet.len(),
+                    key_phase,
+                });
+            }
+
+            (key_phase, buffer)
+        } else {
+            let mut sent_phase = self.key_set.key_phase();
+            let (_protected_packet, buffer) =
+                self.key_set
+                    .encrypt_packet(buffer, |buffer, key, key_phase| {
+                        sent_phase = key_phase;
+                        let packet = Short {
+                            spin_bit,
+                            key_phase,
+                            destination_connection_id,
+                            packet_number,
+                            payload,
+                        };
+                        packet.encode_packet(
+                            key,
+                            header_key,
+                            packet_number_encoder,
+                            min_packet_len,
+                            buffer,
+                        )
+                    })?;
+
+            (sent_phase, buffer)
+        };
+
+        self.key_set.on_packet_sent(packet_number, key_phase);
 
         outcome.bytes_progressed +=
             (self.stream_manager.outgoing_bytes_progressed() - bytes_progressed).as_u64() as usize;
@@ -232,7 +283,16 @@ impl<Config: endpoint::Config> ApplicationSpace<Config> {
 
         // reset the keep alive timer after sending an ack-eliciting packet
         if outcome.ack_elicitation.is_ack_eliciting() {
-            self.keep_alive.reset(timestamp);
+            if let Some((interval, consecutive_pings)) =
+                self.keep_alive.on_ack_eliciting_packet_sent(timestamp)
+            {
+                context
+                    .publisher
+                    .on_keep_alive_ping_sent(event::builder::KeepAlivePingSent {
+                        interval,
+                        consecutive_pings,
+                    });
+            }
         }
 
         context
@@ -313,6 +373,115 @@ impl<Config: endpoint::Config> ApplicationSpace<Config> {
         Ok((outcome, buffer))
     }
 
+    /// Seals the 1-RTT packets which were written into the transmission queue for a batch
+    ///
+    /// The payloads are sealed together and the header protection masks are derived together
+    /// for all of the sealed packets. If the key reaches its confidentiality limit, the rest of
+    /// the packets are sealed with the next key. Packets which fail to seal are cleared, so their
+    /// cleartext isn't transmitted, and are recovered as lost packets.
+    pub fn seal_batch<E: tx::Entry, Pub: event::ConnectionPublisher>(
+        &mut self,
+        batch: &mut batch::Tx,
+        entries: &mut [E],
+        publisher: &mut Pub,
+    ) {
+        let sample_len = self.header_key.sealing_sample_len();
+        let mut packets = batch.take(entries);
+        let mut results =
+            SmallVec::<[bool; batch::MAX_BATCH_SIZE]>::from_elem(false, packets.len());
+        let mut attempted =
+            SmallVec::<[bool; batch::MAX_BATCH_SIZE]>::from_elem(false, packets.len());
+        let mut can_update = true;
+
+        loop {
+            // seal the packets in the phase of the first packet which wasn't sealed yet
+            let key_phase = match packets.iter().zip(&attempted).find(|(_, done)| !**done) {
+                Some(((key_phase, _), _)) => *key_phase,
+                None => break,
+            };
+
+            let mut indices = SmallVec::<[usize; batch::MAX_BATCH_SIZE]>::new();
+            let len = {
+                let mut payloads = SmallVec::<[BatchPayload; batch::MAX_BATCH_SIZE]>::new();
+
+                for (index, (phase, packet)) in packets.iter_mut().enumerate() {
+                    if !attempted[index] && *phase == key_phase {
+                        indices.push(index);
+                        payloads.push(packet.batch_payload());
+                    }
+                }
+
+                let len = self.key_set.encrypt_batch(key_phase, &mut payloads);
+
+                for (index, payload) in indices.iter().zip(&payloads[..len]) {
+                    results[*index] = payload.result.is_ok();
+                    attempted[*index] = true;
+                }
+
+                len
+            };
+
+            let remaining = &indices[len..];
+            if remaining.is_empty() {
+                continue;
+            }
+
+            let next_phase = self.key_set.encryption_phase();
+
+            if can_update && next_phase != key_phase {
+                can_update = false;
+
+                // The key reached its confidentiality limit so the rest of the packets are sealed
+                // with the next key. The key phase bit is authenticated and is rewritten before
+                // the packets are sealed.
+                for index in remaining {
+                    let (phase, packet) = &mut packets[*index];
+                    *phase = next_phase;
+                    next_phase.set_packet_tag(&mut packet.buffer[0]);
+                    self.key_set
+                        .on_packet_sent(packet.packet_number, next_phase);
+                }
+            } else {
+                // the packets can't be sealed until the key update completes
+                for index in remaining {
+                    attempted[*index] = true;
+                }
+            }
+        }
+
+        // derive the header protection masks for all of the sealed packets together
+        // samples are 16 bytes for all of the current cipher suites
+        let mut samples = SmallVec::<[u8; 256]>::new();
+        let mut masks = SmallVec::<[HeaderProtectionMask; batch::MAX_BATCH_SIZE]>::new();
+        for ((_, packet), _) in packets.iter().zip(&results).filter(|(_, ok)| **ok) {
+            let sample = packet
+                .header_protection_sample(sample_len)
+                .expect("header protection should always work");
+            samples.extend_from_slice(sample);
+            masks.push(Default::default());
+        }
+        self.header_key
+            .sealing_header_protection_masks(&samples, &mut masks);
+
+        let mut masks = masks.into_iter();
+        let mut count = 0;
+        for ((_, packet), ok) in packets.into_iter().zip(results) {
+            if ok {
+                let mask = masks.next().expect("a mask is derived for each packet");
+                packet.protect(mask);
+                count += 1;
+            } else {
+                // The datagram is already in the transmission queue so the packet is cleared
+                // instead. It was recorded as sent and will be declared lost.
+                packet.buffer.fill(0);
+            }
+        }
+
+        if count > 0 {
+            publisher.on_packets_sealed(event::builder::PacketsSealed { count });
+        }
+    }
+
     /// Signals the connection was previously blocked by anti-amplification limits
     /// but is now no longer limited.
     pub fn on_amplification_unblocked(
@@ -345,6 +514,8 @@ impl<Config: endpoint::Config> ApplicationSpace<Config> {
         // Retire the local connection ID used during the handshake to reduce linkability
         local_id_registry.retire_handshake_connection_id();
 
+        self.key_set.on_handshake_confirmed(timestamp);
+
         //= https://www.rfc-editor.org/rfc/rfc9002#section-6.2.1
         //# A sender SHOULD restart its PTO timer every time an ack-eliciting
         //# packet is sent or acknowledged, or when Initial or Handshake keys are
@@ -386,7 +557,11 @@ impl<Config: endpoint::Config> ApplicationSpace<Config> {
 
         self.stream_manager.on_timeout(timestamp);
 
-        if self.keep_alive.on_timeout(timestamp).is_ready() {
+        if self
+            .keep_alive
+            .on_timeout(timestamp, random_generator)
+            .is_ready()
+        {
             publisher.on_keep_alive_timer_expired(event::builder::KeepAliveTimerExpired {
                 timeout: self.keep_alive.period(),
             });
@@ -406,10 +581,31 @@ impl<Config: endpoint::Config> ApplicationSpace<Config> {
         self.ping.send()
     }
 
+    /// Initiates a 1-RTT key update as soon as it is permitted
+    pub fn initiate_key_update(&mut self) {
+        self.key_set.request_update(key_update::Cause::Application);
+
+        // send a packet so the update is initiated promptly
+        self.ping();
+    }
+
     pub fn keep_alive(&mut self, enabled: bool) {
         self.keep_alive.update(enabled);
     }
 
+    pub fn keep_alive_with(&mut self, settings: s2n_quic_core::connection::KeepAlive) {
+        self.keep_alive.configure(settings);
+    }
+
+    /// Returns the number of bytes which are buffered by streams and datagrams
+    pub fn buffered_bytes(&self) -> u64 {
+        use s2n_quic_core::datagram::{Receiver as _, Sender as _};
+
+        self.stream_manager.buffered_bytes()
+            + self.datagram_manager.sender.buffered_bytes()
+            + self.datagram_manager.receiver.buffered_bytes()
+    }
+
     /// Returns the Packet Number to be used when encoding outgoing packets
     fn packet_number_encoder(&self) -> PacketNumber {
         self.tx_packet_numbers.largest_sent_packet_number_acked()
@@ -436,6 +632,7 @@ impl<Config: endpoint::Config> ApplicationSpace<Config> {
                 path_id,
                 path_manager,
                 tx_packet_numbers: &mut self.tx_packet_numbers,
+                key_set: &mut self.key_set,
             },
         )
     }
@@ -476,6 +673,7 @@ impl<Config: endpoint::Config> ApplicationSpace<Config> {
         let decrypted = self.key_set.decrypt_packet(
             packet,
             largest_acked,
+            datagram.timestamp,
             //= https://www.rfc-editor.org/rfc/rfc9001#section-6.3
             //# For a short period after a key
             //# update completes, up to the PTO, endpoints MAY defer generation of
@@ -493,10 +691,11 @@ impl<Config: endpoint::Config> ApplicationSpace<Config> {
                     .pto_period(1, PacketNumberSpace::ApplicationData),
         );
         match decrypted {
-            Ok((_, Some(generation))) => {
+            Ok((_, Some((generation, cause)))) => {
                 publisher.on_key_update(event::builder::KeyUpdate {
                     key_type: event::builder::KeyType::OneRtt { generation },
                     cipher_suite: self.key_set.cipher_suite().into_event(),
+                    cause: cause.into_event(),
                 });
             }
             Ok(_) => {}
@@ -529,10 +728,227 @@ impl<Config: endpoint::Config> ApplicationSpace<Config> {
         decrypted.map(|x| x.0)
     }
 
-    fn key_limits(max_mtu: MaxMtu) -> limited::Limits {
+    /// Validates and decrypts a batch of packets in the Application packet space
+    ///
+    /// The header protection masks for all of the packets are derived together and runs of
+    /// packets in the current key phase are decrypted together. Packets which signal a different
+    /// key phase are decrypted individually, as they may rotate the keys. The decrypted packets
+    /// are returned in the same order as `packets`, and the error for each packet which couldn't
+    /// be decrypted is stored in its `result`.
+    ///
+    /// Each decrypted packet must be checked with [`Self::on_batch_packet_decrypted`] before it
+    /// is processed.
+    pub fn validate_and_decrypt_batch<'a, Pub: event::ConnectionPublisher>(
+        &mut self,
+        packets: &mut [batch::RxPacket<'a>],
+        path_id: path::Id,
+        path: &path::Path<Config>,
+        publisher: &mut Pub,
+    ) -> SmallVec<[Option<CleartextShort<'a>>; batch::MAX_BATCH_SIZE]> {
+        // The largest acknowledged packet number only changes when an ACK frame is transmitted,
+        // so processing the packets in the batch doesn't change how the packet numbers expand.
+        let largest_acked = self.ack_manager.largest_received_packet_number_acked();
+        let sample_len = self.header_key.opening_sample_len();
+
+        // derive the header protection masks for all of the packets together
+        let mut samples = SmallVec::<[u8; 256]>::new();
+        let mut masks = SmallVec::<[HeaderProtectionMask; batch::MAX_BATCH_SIZE]>::new();
+        let has_sample: SmallVec<[bool; batch::MAX_BATCH_SIZE]> = packets
+            .iter()
+            .map(|entry| {
+                let sample = entry
+                    .packet
+                    .as_ref()
+                    .and_then(|packet| packet.header_protection_sample(sample_len).ok());
+
+                if let Some(sample) = sample {
+                    samples.extend_from_slice(sample);
+                    masks.push(Default::default());
+                }
+
+                sample.is_some()
+            })
+            .collect();
+        self.header_key
+            .opening_header_protection_masks(&samples, &mut masks);
+        let mut masks = masks.into_iter();
+
+        let mut encrypted = SmallVec::<[Option<EncryptedShort<'a>>; batch::MAX_BATCH_SIZE]>::new();
+        for (entry, has_sample) in packets.iter_mut().zip(has_sample) {
+            let packet = match entry.packet.take() {
+                Some(packet) => packet,
+                None => {
+                    encrypted.push(None);
+                    continue;
+                }
+            };
+
+            let packet = if has_sample {
+                let mask = masks.next().expect("a mask is derived for each sample");
+                packet.unprotect_with_mask(mask, largest_acked)
+            } else {
+                Err(CryptoError::DECODE_ERROR)
+            };
+
+            match packet {
+                Ok(packet) => encrypted.push(Some(packet)),
+                Err(err) => {
+                    publisher.on_packet_dropped(event::builder::PacketDropped {
+                        reason: event::builder::PacketDropReason::UnprotectFailed {
+                            space: event::builder::KeySpace::OneRtt,
+                            path: path_event!(path, path_id),
+                        },
+                    });
+                    entry.result = Err(err.into());
+                    encrypted.push(None);
+                }
+            }
+        }
+
+        let mut decrypted: SmallVec<[Option<CleartextShort<'a>>; batch::MAX_BATCH_SIZE]> =
+            packets.iter().map(|_| None).collect();
+        let mut run = SmallVec::<[(usize, BatchShort<'a>); batch::MAX_BATCH_SIZE]>::new();
+
+        for (index, packet) in encrypted.into_iter().enumerate() {
+            let packet = match packet {
+                Some(packet) => packet,
+                None => continue,
+            };
+
+            if packet.key_phase() == self.key_set.key_phase() {
+                run.push((index, packet.into_batch()));
+                continue;
+            }
+
+            // open the packets in the current phase before the packet rotates the keys
+            self.decrypt_batch_run(&mut run, packets, &mut decrypted, path_id, path, publisher);
+
+            let entry = &mut packets[index];
+            let packet_header =
+                event::builder::PacketHeader::new(packet.packet_number, publisher.quic_version());
+            let timestamp = entry.datagram.timestamp;
+            let pto = timestamp
+                + path
+                    .rtt_estimator
+                    .pto_period(1, PacketNumberSpace::ApplicationData);
+
+            match self
+                .key_set
+                .decrypt_packet(packet, largest_acked, timestamp, pto)
+            {
+                Ok((packet, update)) => {
+                    if let Some((generation, cause)) = update {
+                        publisher.on_key_update(event::builder::KeyUpdate {
+                            key_type: event::builder::KeyType::OneRtt { generation },
+                            cipher_suite: self.key_set.cipher_suite().into_event(),
+                            cause: cause.into_event(),
+                        });
+                    }
+                    decrypted[index] = Some(packet);
+                }
+                Err(err) => {
+                    publisher.on_packet_dropped(event::builder::PacketDropped {
+                        reason: event::builder::PacketDropReason::DecryptionFailed {
+                            packet_header,
+                            path: path_event!(path, path_id),
+                        },
+                    });
+                    entry.result = Err(err);
+                }
+            }
+        }
+
+        self.decrypt_batch_run(&mut run, packets, &mut decrypted, path_id, path, publisher);
+
+        decrypted
+    }
+
+    /// Decrypts a run of packets in the current key phase together
+    fn decrypt_batch_run<'a, Pub: event::ConnectionPublisher>(
+        &mut self,
+        run: &mut SmallVec<[(usize, BatchShort<'a>); batch::MAX_BATCH_SIZE]>,
+        packets: &mut [batch::RxPacket<'a>],
+        decrypted: &mut [Option<CleartextShort<'a>>],
+        path_id: path::Id,
+        path: &path::Path<Config>,
+        publisher: &mut Pub,
+    ) {
+        if run.is_empty() {
+            return;
+        }
+
+        let (results, limit) = {
+            let mut payloads: SmallVec<[BatchPayload; batch::MAX_BATCH_SIZE]> = run
+                .iter_mut()
+                .map(|(_, packet)| packet.batch_payload())
+                .collect();
+
+            let limit = self.key_set.decrypt_batch(&mut payloads);
+
+            let results: SmallVec<[Result<(), CryptoError>; batch::MAX_BATCH_SIZE]> =
+                payloads.iter().map(|payload| payload.result).collect();
+
+            (results, limit)
+        };
+
+        if run.len() > 1 {
+            publisher.on_packets_opened(event::builder::PacketsOpened { count: run.len() });
+        }
+
+        let tag_len = self.key_set.key(self.key_set.key_phase()).tag_len();
+
+        for ((index, packet), result) in run.drain(..).zip(results) {
+            let packet_header =
+                event::builder::PacketHeader::new(packet.packet_number(), publisher.quic_version());
+
+            let result = result
+                .map_err(transport::Error::from)
+                .and_then(|_| packet.into_decrypted(tag_len));
+
+            match result {
+                Ok(packet) => decrypted[index] = Some(packet),
+                Err(err) => {
+                    publisher.on_packet_dropped(event::builder::PacketDropped {
+                        reason: event::builder::PacketDropReason::DecryptionFailed {
+                            packet_header,
+                            path: path_event!(path, path_id),
+                        },
+                    });
+
+                    // the integrity limit closes the connection at the first packet which failed
+                    packets[index].result = Err(limit.err().unwrap_or_else(|| err.into()));
+                }
+            }
+        }
+    }
+
+    /// Checks a packet which was decrypted as part of a batch before it is processed
+    ///
+    /// This is called in the order the packets were received, after the previous packets in the
+    /// batch were processed, so duplicates within the batch are detected.
+    pub fn on_batch_packet_decrypted<Pub: event::ConnectionPublisher>(
+        &mut self,
+        packet_number: PacketNumber,
+        datagram: &DatagramInfo,
+        path_id: path::Id,
+        path: &path::Path<Config>,
+        publisher: &mut Pub,
+    ) -> Result<(), ProcessingError> {
+        if self.is_duplicate(packet_number, path_id, path, publisher) {
+            return Err(ProcessingError::DuplicatePacket);
+        }
+
+        // reset the keep alive timer after receiving a packet
+        self.keep_alive.reset(datagram.timestamp);
+
+        Ok(())
+    }
+
+    fn key_limits(max_mtu: MaxMtu, key_update_policy: key_update::Policy) -> limited::Limits {
         let mut limits = limited::Limits::default();
 
         limits.max_mtu = max_mtu;
+        limits.key_update_policy = key_update_policy;
 
         // AEAD optimizations are currently in the testing phase so make them opt-in at runtime
         limits.sealer_optimization_threshold = {
@@ -604,6 +1020,8 @@ struct RecoveryContext<'a, Config: endpoint::Config> {
     path_id: path::Id,
     path_manager: &'a mut path::Manager<Config>,
     tx_packet_numbers: &'a mut TxPacketNumbers,
+    key_set:
+        &'a mut KeySet<<<Config::TLSEndpoint as tls::Endpoint>::Session as CryptoSuite>::OneRttKey>,
 }
 
 impl<'a, Config: endpoint::Config> recovery::Context<Config> for RecoveryContext<'a, Config> {
@@ -653,6 +1071,7 @@ impl<'a, Config: endpoint::Config> recovery::Context<Config> for RecoveryContext
         self.stream_manager.on_packet_ack(packet_number_range);
         self.local_id_registry.on_packet_ack(packet_number_range);
         self.path_manager.on_packet_ack(packet_number_range);
+        self.key_set.on_packet_ack(packet_number_range);
     }
 
     fn on_packet_ack(&mut self, timestamp: Timestamp, packet_number_range: &PacketNumberRange) {
@@ -786,6 +1205,13 @@ impl<Config: endpoint::Config> PacketSpace<Config> for ApplicationSpace<Config>
         self.stream_manager.on_reset_stream(&frame)
     }
 
+    fn handle_reset_stream_at_frame(
+        &mut self,
+        frame: ResetStreamAt,
+    ) -> Result<(), transport::Error> {
+        self.stream_manager.on_reset_stream_at(&frame)
+    }
+
     fn handle_stop_sending_frame(&mut self, frame: StopSending) -> Result<(), transport::Error> {
         self.stream_manager.on_stop_sending(&frame)
     }
@@ -955,6 +1381,11 @@ impl<Config: endpoint::Config> PacketSpace<Config> for ApplicationSpace<Config>
             path_event!(path, path_id),
             publisher,
         );
+
+        // the peer is still active so the keep alive doesn't need to back off
+        if processed_packet.ack_elicitation.is_ack_eliciting() {
+            self.keep_alive.on_peer_activity();
+        }
         self.processed_packet_numbers
             .insert(processed_packet.packet_number)
             .expect("packet number was already checked");
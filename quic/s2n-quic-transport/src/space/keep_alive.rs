@@ -2,20 +2,39 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use core::{task::Poll, time::Duration};
-use s2n_quic_core::time::{timer, Timer, Timestamp};
+use s2n_quic_core::{
+    connection, random,
+    time::{timer, Timer, Timestamp},
+};
 
 #[derive(Debug)]
 pub struct KeepAlive {
     enabled: bool,
+    /// The period derived from the idle timeout and the maximum keep-alive period
+    default_period: Duration,
+    /// The period which must not be exceeded to keep the connection from timing out
+    max_period: Option<Duration>,
+    /// The application-provided keep-alive settings
+    settings: connection::KeepAlive,
+    /// The current period, including any backoff
     period: Duration,
+    /// The jitter which is subtracted from the current period
+    jitter: Duration,
+    /// The interval after which the last PING was requested
+    ping_interval: Duration,
+    /// The number of consecutive PINGs which were sent while the connection was idle
+    consecutive_pings: u32,
+    /// Is `true` if a PING was requested and is waiting to be transmitted
+    ping_pending: bool,
     timer: Timer,
 }
 
 impl KeepAlive {
     pub fn new(max_idle_timeout: Option<Duration>, max_period: Duration) -> Self {
-        let period = if let Some(max_idle_timeout) = max_idle_timeout {
+        let (period, max_idle_period) = if let Some(max_idle_timeout) = max_idle_timeout {
             // send a ping frame at 3/4 max idle timeout to ensure it is delivered in time
-            (max_idle_timeout * 3 / 4).min(max_period)
+            let max_idle_period = max_idle_timeout * 3 / 4;
+            (max_idle_period.min(max_period), Some(max_idle_period))
         } else {
             //= https://www.rfc-editor.org/rfc/rfc9000#section-10.1.2
             //# A connection will time out if no packets are sent or received for a
@@ -28,12 +47,19 @@ impl KeepAlive {
 
             // Even though we don't have an idle timeout, we should still have a default
             // keep-alive period to ensure middleboxes don't drop their UDP flow
-            max_period
+            (max_period, None)
         };
 
         Self {
             enabled: false,
+            default_period: period,
+            max_period: max_idle_period,
+            settings: Default::default(),
             period,
+            jitter: Duration::ZERO,
+            ping_interval: Duration::ZERO,
+            consecutive_pings: 0,
+            ping_pending: false,
             timer: Timer::default(),
         }
     }
@@ -43,13 +69,46 @@ impl KeepAlive {
         self.enabled = enabled;
     }
 
+    /// Enables the keep-alive with the provided settings
+    #[inline]
+    pub fn configure(&mut self, settings: connection::KeepAlive) {
+        self.enabled = true;
+        self.settings = settings;
+        self.jitter = Duration::ZERO;
+        self.reset_backoff();
+    }
+
     #[inline]
     pub fn reset(&mut self, now: Timestamp) {
-        self.timer.set(now + self.period)
+        self.timer.set(now + (self.period - self.jitter))
     }
 
+    /// Called when an ack-eliciting packet was sent
     #[inline]
-    pub fn on_timeout(&mut self, now: Timestamp) -> Poll<()> {
+    pub fn on_ack_eliciting_packet_sent(&mut self, now: Timestamp) -> Option<(Duration, u32)> {
+        self.reset(now);
+
+        if core::mem::take(&mut self.ping_pending) {
+            return Some((self.ping_interval, self.consecutive_pings));
+        }
+
+        // the application sent data so the connection is no longer idle
+        self.reset_backoff();
+        None
+    }
+
+    /// Called when the peer sent an ack-eliciting packet
+    #[inline]
+    pub fn on_peer_activity(&mut self) {
+        self.reset_backoff();
+    }
+
+    #[inline]
+    pub fn on_timeout(
+        &mut self,
+        now: Timestamp,
+        random_generator: &mut dyn random::Generator,
+    ) -> Poll<()> {
         if !self.enabled {
             return Poll::Pending;
         }
@@ -57,6 +116,18 @@ impl KeepAlive {
         let res = self.timer.poll_expiration(now);
 
         if res.is_ready() {
+            self.ping_pending = true;
+            self.ping_interval = self.period - self.jitter;
+            self.consecutive_pings = self.consecutive_pings.saturating_add(1);
+
+            if let Some(max_backoff_period) = self.settings.max_backoff_interval() {
+                let max_backoff_period = self.cap(max_backoff_period);
+                self.period = (self.period * 2)
+                    .min(max_backoff_period)
+                    .max(self.base_period());
+            }
+
+            self.jitter = self.gen_jitter(random_generator);
             self.reset(now);
         }
 
@@ -67,6 +138,46 @@ impl KeepAlive {
     pub fn period(&self) -> Duration {
         self.period
     }
+
+    /// Returns the period which is used while the connection is not backing off
+    #[inline]
+    fn base_period(&self) -> Duration {
+        self.settings
+            .interval()
+            .map_or(self.default_period, |interval| self.cap(interval))
+    }
+
+    /// Ensures the period does not exceed the idle timeout of the connection
+    #[inline]
+    fn cap(&self, period: Duration) -> Duration {
+        if let Some(max_period) = self.max_period {
+            period.min(max_period)
+        } else {
+            period
+        }
+    }
+
+    #[inline]
+    fn reset_backoff(&mut self) {
+        self.consecutive_pings = 0;
+        self.period = self.base_period();
+        self.jitter = self.jitter.min(self.period / 2);
+    }
+
+    #[inline]
+    fn gen_jitter(&self, random_generator: &mut dyn random::Generator) -> Duration {
+        let max_jitter = self.settings.jitter().min(self.period / 2).as_nanos() as u64;
+
+        if max_jitter == 0 {
+            return Duration::ZERO;
+        }
+
+        let mut bytes = [0; 8];
+        random_generator.public_random_fill(&mut bytes);
+        let nanos = u64::from_le_bytes(bytes) % (max_jitter + 1);
+
+        Duration::from_nanos(nanos)
+    }
 }
 
 impl timer::Provider for KeepAlive {
@@ -78,3 +189,75 @@ impl timer::Provider for KeepAlive {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use s2n_quic_core::time::{Clock, NoopClock};
+
+    fn settings() -> connection::KeepAlive {
+        connection::KeepAlive::default()
+            .with_interval(Duration::from_secs(10))
+            .unwrap()
+    }
+
+    #[test]
+    fn interval_is_capped_by_idle_timeout() {
+        let mut keep_alive = KeepAlive::new(Some(Duration::from_secs(8)), Duration::from_secs(30));
+        assert_eq!(keep_alive.period(), Duration::from_secs(6));
+
+        keep_alive.configure(settings());
+        assert_eq!(keep_alive.period(), Duration::from_secs(6));
+
+        let mut keep_alive = KeepAlive::new(None, Duration::from_secs(30));
+        keep_alive.configure(settings());
+        assert_eq!(keep_alive.period(), Duration::from_secs(10));
+    }
+
+    #[test]
+    fn backoff_test() {
+        let mut random = random::testing::Generator::default();
+        let mut keep_alive = KeepAlive::new(None, Duration::from_secs(30));
+        keep_alive.configure(settings().with_backoff(Duration::from_secs(35)).unwrap());
+
+        let mut now = NoopClock.get_time();
+        keep_alive.reset(now);
+
+        for (consecutive_pings, interval) in [10, 20, 35, 35].iter().enumerate() {
+            now += keep_alive.period();
+            assert!(keep_alive.on_timeout(now, &mut random).is_ready());
+            assert_eq!(
+                keep_alive.on_ack_eliciting_packet_sent(now),
+                Some((Duration::from_secs(*interval), consecutive_pings as u32 + 1))
+            );
+        }
+
+        // any other activity resets the backoff
+        assert_eq!(keep_alive.on_ack_eliciting_packet_sent(now), None);
+        assert_eq!(keep_alive.period(), Duration::from_secs(10));
+    }
+
+    #[test]
+    fn jitter_test() {
+        let mut random = random::testing::Generator::default();
+        let mut keep_alive = KeepAlive::new(None, Duration::from_secs(30));
+        keep_alive.configure(settings().with_jitter(Duration::from_secs(60)).unwrap());
+
+        let mut now = NoopClock.get_time();
+        keep_alive.reset(now);
+
+        let mut jittered = false;
+        for _ in 0..10 {
+            now += keep_alive.period();
+            assert!(keep_alive.on_timeout(now, &mut random).is_ready());
+
+            // at most half of the period is used as jitter
+            assert!(keep_alive.jitter <= Duration::from_secs(5));
+            assert!(keep_alive
+                .timer
+                .is_expired(now + keep_alive.period() - keep_alive.jitter));
+            jittered |= !keep_alive.jitter.is_zero();
+        }
+        assert!(jittered);
+    }
+}
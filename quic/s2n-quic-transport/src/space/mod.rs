@@ -23,8 +23,8 @@ use s2n_quic_core::{
     frame::{
         ack::AckRanges, crypto::CryptoRef, datagram::DatagramRef, stream::StreamRef, Ack,
         ConnectionClose, DataBlocked, HandshakeDone, MaxData, MaxStreamData, MaxStreams,
-        NewConnectionId, NewToken, PathChallenge, PathResponse, ResetStream, RetireConnectionId,
-        StopSending, StreamDataBlocked, StreamsBlocked,
+        NewConnectionId, NewToken, PathChallenge, PathResponse, ResetStream, ResetStreamAt,
+        RetireConnectionId, StopSending, StreamDataBlocked, StreamsBlocked,
     },
     inet::DatagramInfo,
     packet::number::{PacketNumber, PacketNumberSpace},
@@ -57,6 +57,10 @@ struct SessionInfo<Config: endpoint::Config> {
 
 pub struct PacketSpaceManager<Config: endpoint::Config> {
     session_info: Option<SessionInfo<Config>>,
+    /// The TLS session, which is retained after the handshake to export keying material
+    ///
+    /// This is only set if the application enabled exporters in the connection limits.
+    completed_session: Option<<Config::TLSEndpoint as tls::Endpoint>::Session>,
     retry_cid: Option<Box<PeerId>>,
     initial: Option<Box<InitialSpace<Config>>>,
     handshake: Option<Box<HandshakeSpace<Config>>>,
@@ -74,6 +78,8 @@ pub struct PacketSpaceManager<Config: endpoint::Config> {
     //# another mechanism is used for agreeing on an application protocol,
     //# endpoints MUST use ALPN for this purpose.
     pub application_protocol: Bytes,
+    /// The parameters negotiated by the TLS handshake
+    pub handshake_details: Option<tls::HandshakeDetails>,
 }
 
 impl<Config: endpoint::Config> fmt::Debug for PacketSpaceManager<Config> {
@@ -149,12 +155,14 @@ impl<Config: endpoint::Config> PacketSpaceManager<Config> {
         publisher.on_key_update(event::builder::KeyUpdate {
             key_type: event::builder::KeyType::Initial,
             cipher_suite: initial_key.cipher_suite().into_event(),
+            cause: event::builder::KeyUpdateCause::Negotiated,
         });
         Self {
             session_info: Some(SessionInfo {
                 session,
                 initial_cid,
             }),
+            completed_session: None,
             retry_cid: None,
             initial: Some(Box::new(InitialSpace::new(
                 initial_key,
@@ -168,6 +176,7 @@ impl<Config: endpoint::Config> PacketSpaceManager<Config> {
             handshake_status: HandshakeStatus::default(),
             server_name: None,
             application_protocol: Bytes::new(),
+            handshake_details: None,
         }
     }
 
@@ -227,8 +236,30 @@ impl<Config: endpoint::Config> PacketSpaceManager<Config> {
 
             match session_info.session.poll(&mut context)? {
                 Poll::Ready(_success) => {
-                    // The TLS session and retry_cid is no longer needed
-                    self.session_info = None;
+                    // Capture the negotiated parameters before the session is discarded
+                    if let Some(details) = session_info.session.handshake_details() {
+                        let peer_cert_chain: Vec<&[u8]> = details
+                            .peer_cert_chain
+                            .iter()
+                            .map(|cert| &cert[..])
+                            .collect();
+                        publisher.on_tls_handshake_completed(
+                            event::builder::TlsHandshakeCompleted {
+                                cipher_suite: details.cipher_suite.into_event(),
+                                key_exchange_group: details.key_exchange_group.map(|group| group.0),
+                                signature_scheme: details.signature_scheme.map(|scheme| scheme.0),
+                                peer_cert_chain: &peer_cert_chain,
+                            },
+                        );
+                        self.handshake_details = Some(details);
+                    }
+
+                    // The retry_cid is no longer needed and the session is only kept around
+                    // if the application wants to export keying material
+                    let session_info = self.session_info.take();
+                    if limits.keying_material_exporter() {
+                        self.completed_session = session_info.map(|info| info.session);
+                    }
                     self.retry_cid = None;
                 }
                 Poll::Pending => return Poll::Pending,
@@ -238,6 +269,26 @@ impl<Config: endpoint::Config> PacketSpaceManager<Config> {
         Poll::Ready(Ok(()))
     }
 
+    /// Fills `output` with keying material exported from the TLS session
+    pub fn export_keying_material(
+        &self,
+        label: &[u8],
+        context: &[u8],
+        output: &mut [u8],
+    ) -> Result<(), connection::Error> {
+        let session = match self.completed_session.as_ref() {
+            Some(session) => session,
+            None if self.session_info.is_some() => {
+                return Err(connection::Error::handshake_not_complete())
+            }
+            None => return Err(connection::Error::keying_material_exporter_disabled()),
+        };
+
+        session.export_keying_material(label, context, output)?;
+
+        Ok(())
+    }
+
     /// Called when the connection timer expired
     pub fn on_timeout<Pub: event::ConnectionPublisher>(
         &mut self,
@@ -421,6 +472,7 @@ impl<Config: endpoint::Config> PacketSpaceManager<Config> {
         publisher: &mut Pub,
     ) {
         self.session_info = None;
+        self.completed_session = None;
         self.retry_cid = None;
         self.discard_initial(path, path_id, publisher);
         self.discard_handshake(path, path_id, publisher);
@@ -638,6 +690,7 @@ pub trait PacketSpace<Config: endpoint::Config> {
     default_frame_handler!(handle_max_stream_data_frame, MaxStreamData);
     default_frame_handler!(handle_max_streams_frame, MaxStreams);
     default_frame_handler!(handle_reset_stream_frame, ResetStream);
+    default_frame_handler!(handle_reset_stream_at_frame, ResetStreamAt);
     default_frame_handler!(handle_stop_sending_frame, StopSending);
     default_frame_handler!(handle_stream_data_blocked_frame, StreamDataBlocked);
     default_frame_handler!(handle_streams_blocked_frame, StreamsBlocked);
@@ -766,8 +819,17 @@ pub trait PacketSpace<Config: endpoint::Config> {
                     )
                     .map_err(on_error)?;
 
+                    let error: connection::Error = frame.into();
+
+                    publisher.on_connection_close_received(
+                        event::builder::ConnectionCloseReceived {
+                            error,
+                            reason: frame.reason,
+                        },
+                    );
+
                     // skip processing any other frames and return an error
-                    return Err(frame.into());
+                    return Err(error);
                 }
                 Frame::Stream(frame) => {
                     let on_error = on_frame_processed!(frame);
@@ -802,6 +864,10 @@ pub trait PacketSpace<Config: endpoint::Config> {
                     let on_error = on_frame_processed!(frame);
                     self.handle_reset_stream_frame(frame).map_err(on_error)?;
                 }
+                Frame::ResetStreamAt(frame) => {
+                    let on_error = on_frame_processed!(frame);
+                    self.handle_reset_stream_at_frame(frame).map_err(on_error)?;
+                }
                 Frame::StopSending(frame) => {
                     let on_error = on_frame_processed!(frame);
                     self.handle_stop_sending_frame(frame).map_err(on_error)?;
@@ -325,6 +325,7 @@ impl<'a, Config: endpoint::Config, Pub: event::ConnectionPublisher>
         self.publisher.on_key_update(event::builder::KeyUpdate {
             key_type: event::builder::KeyType::Handshake,
             cipher_suite,
+            cause: event::builder::KeyUpdateCause::Negotiated,
         });
         Ok(())
     }
@@ -348,6 +349,7 @@ impl<'a, Config: endpoint::Config, Pub: event::ConnectionPublisher>
         self.publisher.on_key_update(event::builder::KeyUpdate {
             key_type: event::builder::KeyType::ZeroRtt,
             cipher_suite,
+            cause: event::builder::KeyUpdateCause::Negotiated,
         });
         Ok(())
     }
@@ -423,11 +425,13 @@ impl<'a, Config: endpoint::Config, Pub: event::ConnectionPublisher>
             ack_manager,
             keep_alive,
             max_mtu,
+            self.limits.key_update_policy(),
             datagram_manager,
         )));
         self.publisher.on_key_update(event::builder::KeyUpdate {
             key_type: event::builder::KeyType::OneRtt { generation: 0 },
             cipher_suite,
+            cause: event::builder::KeyUpdateCause::Negotiated,
         });
 
         Ok(())
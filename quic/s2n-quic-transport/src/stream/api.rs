@@ -4,12 +4,13 @@
 //! Defines the Stream objects that applications are interacting with
 
 use crate::connection::Connection;
-use bytes::Bytes;
+use bytes::{Bytes, BytesMut};
 use core::{
     fmt,
     future::Future,
     pin::Pin,
     task::{Context, Poll},
+    time::Duration,
 };
 pub use s2n_quic_core::{
     application,
@@ -182,6 +183,40 @@ macro_rules! tx_stream_apis {
             }
         }
 
+        /// Enqueues a chunk of data with a deadline for sending it towards the peer.
+        ///
+        /// The `lifetime` starts once the data is first transmitted. If the data is lost after
+        /// the lifetime has elapsed, it is not retransmitted and the stream is reset with the
+        /// provided `error_code` instead.
+        ///
+        /// This method should only be called after calling `poll_send_ready` first, as the stream
+        /// may not have available send buffer capacity.
+        ///
+        /// The method will return:
+        /// - `Ok(())` if the data was enqueued for sending.
+        /// - `Err(stream_error)` if the data could not be sent, because the stream
+        ///   had previously entered an error state, or the stream was not ready to send data.
+        pub fn send_data_with_deadline(
+            &mut self,
+            chunk: Bytes,
+            lifetime: Duration,
+            error_code: application::Error,
+        ) -> Result<(), StreamError> {
+            if chunk.is_empty() {
+                return Ok(());
+            }
+
+            match self
+                .tx_request()?
+                .send(&mut [chunk])
+                .with_deadline(lifetime, error_code)
+                .poll(None)?
+            {
+                response if response.tx().expect("invalid response").chunks.consumed == 1 => Ok(()),
+                _ => Err(StreamError::sending_blocked()),
+            }
+        }
+
         /// Flushes the send buffer and waits for acknowledgement from the peer.
         ///
         /// The method will return:
@@ -224,6 +259,22 @@ macro_rules! tx_stream_apis {
             self.tx_request()?.reset(error_code).poll(None)?;
             Ok(())
         }
+
+        /// Initiates a reliable `RESET` on the stream.
+        ///
+        /// The first `reliable_size` bytes of the stream are still delivered to the peer before
+        /// it is notified of the provided `error_code`. This requires both peers to enable
+        /// reliable stream resets.
+        pub fn reset_at(
+            &mut self,
+            error_code: application::Error,
+            reliable_size: u64,
+        ) -> Result<(), StreamError> {
+            self.tx_request()?
+                .reset_at(error_code, reliable_size)
+                .poll(None)?;
+            Ok(())
+        }
     };
 }
 
@@ -288,6 +339,29 @@ macro_rules! rx_stream_apis {
             Poll::Ready(Ok((consumed, is_open)))
         }
 
+        /// Registers a buffer which received data is directly written into
+        ///
+        /// The method will return:
+        /// - `Ok(true)` if the buffer was registered. In this case the buffer is replaced with
+        ///   an empty buffer and chunks which are received afterwards are views into it.
+        /// - `Ok(false)` if the buffer could not be registered, because data is already
+        ///   buffered at the current offset of the stream.
+        /// - `Err(stream_error)` if the stream could not be read, because the stream
+        ///   had previously entered an error state.
+        pub fn register_receive_buffer(
+            &mut self,
+            buffer: &mut BytesMut,
+        ) -> Result<bool, StreamError> {
+            if buffer.capacity() == 0 {
+                return Ok(false);
+            }
+
+            self.rx_request()?.receive_into(buffer).poll(None)?;
+
+            // registered buffers are replaced with an empty buffer
+            Ok(buffer.capacity() == 0)
+        }
+
         /// Sends a `STOP_SENDING` message to the peer. This requests the peer to
         /// finish the `Stream` as soon as possible by issuing a `RESET` with the
         /// provided `error_code`.
@@ -485,11 +559,29 @@ macro_rules! tx_request_apis {
             self
         }
 
+        pub fn with_deadline(
+            &mut self,
+            lifetime: Duration,
+            error_code: application::Error,
+        ) -> &mut Self {
+            self.request.with_deadline(lifetime, error_code);
+            self
+        }
+
         pub fn reset(&mut self, error_code: application::Error) -> &mut Self {
             self.request.reset(error_code);
             self
         }
 
+        pub fn reset_at(
+            &mut self,
+            error_code: application::Error,
+            reliable_size: u64,
+        ) -> &mut Self {
+            self.request.reset_at(error_code, reliable_size);
+            self
+        }
+
         pub fn flush(&mut self) -> &mut Self {
             self.request.flush();
             self
@@ -504,6 +596,11 @@ macro_rules! rx_request_apis {
             self
         }
 
+        pub fn receive_into(&mut self, buffer: &'chunks mut BytesMut) -> &mut Self {
+            self.request.receive_into(buffer);
+            self
+        }
+
         pub fn with_watermark(&mut self, low: usize, high: usize) -> &mut Self {
             self.request.with_watermark(low, high);
             self
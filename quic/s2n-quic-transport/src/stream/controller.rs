@@ -95,6 +95,18 @@ impl Controller {
         }
     }
 
+    /// Updates the number of streams of the given type the peer is allowed to
+    /// have open concurrently.
+    ///
+    /// Additional credit is advertised with a `MAX_STREAMS` frame. Lowering the
+    /// limit does not revoke credit which was already granted to the peer.
+    pub fn set_max_open_remote_streams(&mut self, stream_type: StreamType, limit: VarInt) {
+        match stream_type {
+            StreamType::Bidirectional => self.remote_bidi_controller.set_max_local_limit(limit),
+            StreamType::Unidirectional => self.remote_uni_controller.set_max_local_limit(limit),
+        }
+    }
+
     /// This method is called when the local application wishes to open the next stream
     /// of a type (Bidirectional/Unidirectional).
     ///
@@ -210,6 +222,13 @@ impl Controller {
         self.remote_uni_controller.close();
     }
 
+    /// This method is called when the stream manager starts draining. No additional
+    /// stream credit will be issued to the peer.
+    pub fn drain(&mut self) {
+        self.remote_bidi_controller.close();
+        self.remote_uni_controller.close();
+    }
+
     /// This method is called when a packet delivery got acknowledged
     pub fn on_packet_ack<A: ack::Set>(&mut self, ack_set: &A) {
         self.local_bidi_controller.on_packet_ack(ack_set);
@@ -246,14 +265,17 @@ impl Controller {
 
         macro_rules! on_transmit {
             ($controller:ident, $endpoint:expr, $ty:expr) => {
-                if let Some(nth) = self
+                // The stream limit may have been updated before any streams were
+                // opened, in which case the initial stream ID is used.
+                let stream_id = self
                     .$controller
                     .total_open_stream_count()
                     .checked_sub(VarInt::from_u32(1))
-                {
-                    if let Some(stream_id) = StreamId::nth($endpoint, $ty, nth.as_u64()) {
-                        self.$controller.on_transmit(stream_id, context)?;
-                    }
+                    .map_or(Some(StreamId::initial($endpoint, $ty)), |nth| {
+                        StreamId::nth($endpoint, $ty, nth.as_u64())
+                    });
+                if let Some(stream_id) = stream_id {
+                    self.$controller.on_transmit(stream_id, context)?;
                 }
             };
         }
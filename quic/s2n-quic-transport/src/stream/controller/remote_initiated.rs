@@ -95,15 +95,42 @@ impl RemoteInitiated {
     pub fn on_close_stream(&mut self) {
         self.closed_streams += 1;
 
-        let max_streams = self
-            .closed_streams
-            .saturating_add(self.max_local_limit)
-            .min(MAX_STREAMS_MAX_VALUE);
-        self.max_streams_sync.update_latest_value(max_streams);
+        let max_streams = self.max_streams();
+        // The limit may have been lowered, in which case no additional credit is
+        // granted until enough streams have been closed.
+        if max_streams > self.max_streams_sync.latest_value() {
+            self.max_streams_sync.update_latest_value(max_streams);
+        }
+
+        self.check_integrity();
+    }
+
+    /// Updates the number of streams the peer is allowed to have open concurrently
+    ///
+    /// Raising the limit immediately advertises the additional credit to the peer.
+    /// Since the cumulative stream limit can never decrease, lowering the limit only
+    /// reduces the credit which is granted as the peer closes its streams.
+    pub fn set_max_local_limit(&mut self, max_local_limit: VarInt) {
+        self.max_local_limit = max_local_limit;
+        self.max_streams_sync
+            .set_threshold(max_local_limit / MAX_STREAMS_SYNC_FRACTION);
+
+        let max_streams = self.max_streams();
+        if max_streams > self.max_streams_sync.latest_value() {
+            self.max_streams_sync.force_update_latest_value(max_streams);
+        }
 
         self.check_integrity();
     }
 
+    /// Returns the cumulative number of streams the peer should be allowed to open
+    #[inline]
+    fn max_streams(&self) -> VarInt {
+        self.closed_streams
+            .saturating_add(self.max_local_limit)
+            .min(MAX_STREAMS_MAX_VALUE)
+    }
+
     /// Returns the number of streams currently open
     #[inline]
     pub fn open_stream_count(&self) -> VarInt {
@@ -146,9 +173,9 @@ impl RemoteInitiated {
                 "Cannot close more streams than previously opened"
             );
             assert!(
-                self.open_stream_count() <= self.max_local_limit,
-                "Cannot have more incoming streams open concurrently than
-                the max_local_limit"
+                self.open_stream_count()
+                    <= self.max_streams_sync.latest_value() - self.closed_streams,
+                "Cannot have more incoming streams open concurrently than the advertised limit"
             );
         }
     }
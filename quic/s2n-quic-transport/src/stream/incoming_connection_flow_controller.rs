@@ -46,6 +46,9 @@ struct IncomingConnectionFlowControllerImpl {
     /// The amount of flow control credits which had been acquired and where the
     /// data had already been consumed by the application
     pub(super) consumed_window: VarInt,
+    /// Is `true` if the endpoint is under memory pressure. No additional
+    /// credit is granted to the peer while this is set.
+    pub(super) memory_pressure: bool,
 }
 
 impl IncomingConnectionFlowControllerImpl {
@@ -59,6 +62,7 @@ impl IncomingConnectionFlowControllerImpl {
             desired_flow_control_window,
             acquired_window: VarInt::from_u32(0),
             consumed_window: VarInt::from_u32(0),
+            memory_pressure: false,
         }
     }
 
@@ -78,10 +82,51 @@ impl IncomingConnectionFlowControllerImpl {
             "Can not consume more window than previously acquired"
         );
 
-        self.read_window_sync.update_latest_value(
-            self.consumed_window
-                .saturating_add(VarInt::from_u32(self.desired_flow_control_window)),
-        );
+        if self.memory_pressure {
+            return;
+        }
+
+        let window = self
+            .consumed_window
+            .saturating_add(VarInt::from_u32(self.desired_flow_control_window));
+        // The desired window may have been lowered, in which case no additional
+        // credit is granted until enough data has been consumed.
+        if window > self.read_window_sync.latest_value() {
+            self.read_window_sync.update_latest_value(window);
+        }
+    }
+
+    pub fn set_desired_flow_control_window(&mut self, desired_flow_control_window: u32) {
+        self.desired_flow_control_window = desired_flow_control_window;
+        self.read_window_sync
+            .set_threshold(VarInt::from_u32(desired_flow_control_window / 10));
+
+        if self.memory_pressure {
+            return;
+        }
+
+        let window = self
+            .consumed_window
+            .saturating_add(VarInt::from_u32(desired_flow_control_window));
+        if window > self.read_window_sync.latest_value() {
+            self.read_window_sync.force_update_latest_value(window);
+        }
+    }
+
+    pub fn set_memory_pressure(&mut self, enabled: bool) {
+        self.memory_pressure = enabled;
+
+        if enabled {
+            return;
+        }
+
+        // Grant the credit which was withheld while under memory pressure
+        let window = self
+            .consumed_window
+            .saturating_add(VarInt::from_u32(self.desired_flow_control_window));
+        if window > self.read_window_sync.latest_value() {
+            self.read_window_sync.update_latest_value(window);
+        }
     }
 
     pub fn acquire_window(&mut self, desired: VarInt) -> Result<(), transport::Error> {
@@ -157,6 +202,33 @@ impl IncomingConnectionFlowController {
         self.inner.borrow_mut().release_window(amount)
     }
 
+    /// Updates the flow control window the flow controller tries to maintain
+    ///
+    /// Growing the window immediately advertises the additional credit to the peer
+    /// with a `MAX_DATA` frame. Since the advertised limit can never decrease,
+    /// shrinking the window only reduces the credit which is granted as data
+    /// is consumed.
+    pub fn set_desired_flow_control_window(&mut self, desired_flow_control_window: u32) {
+        self.inner
+            .borrow_mut()
+            .set_desired_flow_control_window(desired_flow_control_window)
+    }
+
+    /// Stops or resumes extending the flow control window
+    ///
+    /// While the endpoint is under memory pressure no additional credit is
+    /// granted to the peer, even if the application consumes data.
+    pub fn set_memory_pressure(&mut self, enabled: bool) {
+        self.inner.borrow_mut().set_memory_pressure(enabled)
+    }
+
+    /// Returns `true` if the endpoint is under memory pressure
+    ///
+    /// Streams sharing this flow controller don't extend their own windows while this is set.
+    pub fn is_under_memory_pressure(&self) -> bool {
+        self.inner.borrow().memory_pressure
+    }
+
     /// This method gets called when a packet delivery got acknowledged
     pub fn on_packet_ack<A: ack::Set>(&mut self, ack_set: &A) {
         self.inner.borrow_mut().on_packet_ack(ack_set)
@@ -177,6 +249,13 @@ impl IncomingConnectionFlowController {
         self.inner.borrow().acquired_window
     }
 
+    /// Returns the amount of data which was received but not yet consumed by
+    /// the application
+    pub fn unconsumed_window(&self) -> VarInt {
+        let inner = self.inner.borrow();
+        inner.acquired_window - inner.consumed_window
+    }
+
     #[cfg(test)]
     pub fn remaining_window(&self) -> VarInt {
         self.inner.borrow_mut().remaining_window()
@@ -11,6 +11,7 @@ use crate::{
         self,
         incoming_connection_flow_controller::IncomingConnectionFlowController,
         outgoing_connection_flow_controller::OutgoingConnectionFlowController,
+        send_buffer_budget::{EndpointSendBuffer, SendBufferBudget},
         stream_container::{StreamContainer, StreamContainerIterationResult},
         stream_events::StreamEvents,
         stream_impl::StreamConfig,
@@ -18,21 +19,28 @@ use crate::{
     },
     transmission::{self, interest::Provider as _},
 };
+use alloc::collections::VecDeque;
+use bytes::BytesMut;
 use core::{
     task::{Context, Poll, Waker},
     time::Duration,
 };
 use futures_core::ready;
 use s2n_quic_core::{
-    ack, endpoint,
+    ack, application,
+    connection::limits,
+    endpoint,
     frame::{
         stream::StreamRef, DataBlocked, MaxData, MaxStreamData, MaxStreams, ResetStream,
-        StopSending, StreamDataBlocked, StreamsBlocked,
+        ResetStreamAt, StopSending, StreamDataBlocked, StreamsBlocked,
     },
     packet::number::PacketNumberSpace,
     stream::{iter::StreamIter, ops, StreamId, StreamType},
     time::{timer, Timestamp},
-    transport::{self, parameters::InitialFlowControlLimits},
+    transport::{
+        self,
+        parameters::{InitialFlowControlLimits, InitialStreamLimits},
+    },
     varint::VarInt,
 };
 
@@ -109,6 +117,10 @@ pub(super) struct AcceptState {
     /// The `Waker` for the task which needs to get woken when the next
     /// unidirectional stream was accepted
     uni_waker: Option<Waker>,
+    /// Set once the connection starts draining. Contains the IDs of the first
+    /// bidirectional and unidirectional Streams which are refused, since they were
+    /// opened by the peer after draining started.
+    drain_boundary: Option<(Option<StreamId>, Option<StreamId>)>,
 }
 
 impl AcceptState {
@@ -126,9 +138,32 @@ impl AcceptState {
             )),
             bidi_waker: None,
             uni_waker: None,
+            drain_boundary: None,
         }
     }
 
+    /// Returns `true` if the connection is draining
+    pub fn is_draining(&self) -> bool {
+        self.drain_boundary.is_some()
+    }
+
+    /// Returns `true` if the Stream was opened by the peer after the connection
+    /// started draining
+    pub fn is_refused(&self, stream_id: StreamId) -> bool {
+        let (bidi, uni) = match self.drain_boundary {
+            Some(boundary) => boundary,
+            None => return false,
+        };
+
+        let boundary = match stream_id.stream_type() {
+            StreamType::Bidirectional => bidi,
+            StreamType::Unidirectional => uni,
+        };
+
+        // If the boundary is not set all of the Stream IDs were already used
+        boundary.map_or(false, |boundary| stream_id >= boundary)
+    }
+
     /// Returns a mutable reference to the `Waker` for the given Stream type
     pub fn waker_mut(&mut self, stream_type: StreamType) -> &mut Option<Waker> {
         match stream_type {
@@ -163,6 +198,8 @@ pub struct StreamManagerState<S> {
     pub(super) incoming_connection_flow_controller: IncomingConnectionFlowController,
     /// Flow control credit manager for sending data
     pub(super) outgoing_connection_flow_controller: OutgoingConnectionFlowController,
+    /// Budget for the data buffered for sending across all streams
+    pub(super) send_buffer_budget: SendBufferBudget,
     /// Controller for managing streams concurrency limits
     stream_controller: stream::Controller,
     /// A container which contains all Streams
@@ -175,6 +212,10 @@ pub struct StreamManagerState<S> {
     /// The initial flow control limits which we advertised towards the peer
     /// via transport parameters
     initial_local_limits: InitialFlowControlLimits,
+    /// The receive windows which are maintained for each type of Stream.
+    ///
+    /// These start out as the initial limits, but can be updated by the application.
+    desired_stream_limits: InitialStreamLimits,
     /// The initial flow control limits we received from the peer via transport
     /// parameters
     initial_peer_limits: InitialFlowControlLimits,
@@ -186,6 +227,15 @@ pub struct StreamManagerState<S> {
     /// Limits for the Stream manager. Since only Stream limits are utilized at
     /// the moment we only store those
     stream_limits: stream::Limits,
+    /// Whether both peers negotiated support for reliable stream resets
+    reliable_stream_reset: bool,
+    /// Whether the local endpoint advertised support for reliable stream resets
+    local_reliable_stream_reset: bool,
+    /// The error code used to refuse streams opened by the peer while draining
+    drain_refusal_error: application::Error,
+    /// Buffers registered by the application, which are handed to streams
+    /// receiving data at their current offset
+    receive_buffers: VecDeque<BytesMut>,
 }
 
 impl<S: StreamTrait> StreamManagerState<S> {
@@ -202,6 +252,21 @@ impl<S: StreamTrait> StreamManagerState<S> {
         result
     }
 
+    /// Wakes up `Stream`s which are blocked on the send buffer budget if it
+    /// is available again
+    fn on_send_buffer_budget_available(&mut self) {
+        if !self.send_buffer_budget.has_capacity() {
+            return;
+        }
+
+        self.streams
+            .iterate_send_buffer_budget_list(&mut self.stream_controller, |stream| {
+                let mut events = StreamEvents::new();
+                stream.on_send_buffer_budget_available(&mut events);
+                events.wake_all();
+            });
+    }
+
     /// Inserts the `Stream` into the StreamContainer.
     ///
     /// This method does not perform any validation whether it is allowed to
@@ -218,17 +283,20 @@ impl<S: StreamTrait> StreamManagerState<S> {
             .stream_limits
             .max_data(self.local_endpoint_type.peer_type(), stream_id);
 
-        // We pass the initial_receive_window also as the desired flow control
-        // window. Thereby we will maintain the same flow control window over
-        // the lifetime of the Stream.
-        // If we would want to have another limit, we would need to have various
-        // limits for the various combinations of unidirectional/bidirectional
-        // Streams. Those would bloat up the config, and essentially just
-        // duplicate the transport parameters.
+        // The desired flow control window starts out as the initial_receive_window
+        // and is maintained over the lifetime of the Stream, unless the application
+        // updates the limits.
+        let desired_flow_control_window = self
+            .desired_stream_limits
+            .max_data(self.local_endpoint_type, stream_id);
         debug_assert!(
             initial_receive_window <= VarInt::from_u32(core::u32::MAX),
             "Receive window must not exceed 32bit range"
         );
+        debug_assert!(
+            desired_flow_control_window <= VarInt::from_u32(core::u32::MAX),
+            "Receive window must not exceed 32bit range"
+        );
 
         self.streams.insert_stream(S::new(StreamConfig {
             incoming_connection_flow_controller: self.incoming_connection_flow_controller.clone(),
@@ -236,9 +304,10 @@ impl<S: StreamTrait> StreamManagerState<S> {
             local_endpoint_type: self.local_endpoint_type,
             stream_id,
             initial_receive_window,
-            desired_flow_control_window: initial_receive_window.as_u64() as u32,
+            desired_flow_control_window: desired_flow_control_window.as_u64() as u32,
             initial_send_window,
             max_send_buffer_size: self.stream_limits.max_send_buffer_size.as_u32(),
+            send_buffer_budget: self.send_buffer_budget.clone(),
         }));
     }
 
@@ -290,6 +359,10 @@ impl<S: StreamTrait> StreamManagerState<S> {
                 //# order for streams is consistent on both endpoints.
                 for stream_id in stream_iter {
                     self.insert_stream(stream_id);
+
+                    if self.accept_state.is_refused(stream_id) {
+                        self.refuse_stream(stream_id);
+                    }
                 }
 
                 //= https://www.rfc-editor.org/rfc/rfc9000#section-2.1
@@ -325,6 +398,25 @@ impl<S: StreamTrait> StreamManagerState<S> {
         Ok(())
     }
 
+    /// Refuses a Stream which was opened by the peer while the connection is draining
+    ///
+    /// The Stream is reset and detached, since it will never be handed to the application.
+    fn refuse_stream(&mut self, stream_id: StreamId) {
+        let mut request = ops::Request::default();
+        let error = self.drain_refusal_error;
+
+        if stream_id.stream_type().is_bidirectional() {
+            request.reset(error).detach_tx();
+        }
+
+        request.stop_sending(error).detach_rx();
+
+        self.streams
+            .with_stream(stream_id, &mut self.stream_controller, |stream| {
+                let _ = stream.poll_request(&mut request, None);
+            });
+    }
+
     fn poll_open_local_stream(
         &mut self,
         stream_type: StreamType,
@@ -359,6 +451,9 @@ impl<S: StreamTrait> StreamManagerState<S> {
         }
         self.close_reason = Some(error);
 
+        // Buffers which weren't handed to any stream are no longer needed
+        self.receive_buffers.clear();
+
         self.streams
             .iterate_streams(&mut self.stream_controller, |stream| {
                 // We have to wake inside the lock, since `StreamEvent`s has no capacity
@@ -440,6 +535,9 @@ impl<S: StreamTrait> AbstractStreamManager<S> {
                 outgoing_connection_flow_controller: OutgoingConnectionFlowController::new(
                     initial_peer_limits.max_data,
                 ),
+                send_buffer_budget: SendBufferBudget::new(
+                    connection_limits.max_connection_send_buffer_size(),
+                ),
                 stream_controller: stream::Controller::new(
                     local_endpoint_type,
                     initial_peer_limits,
@@ -450,10 +548,15 @@ impl<S: StreamTrait> AbstractStreamManager<S> {
                 next_stream_ids: StreamIdSet::initial(),
                 local_endpoint_type,
                 initial_local_limits,
+                desired_stream_limits: initial_local_limits.stream_limits,
                 initial_peer_limits,
                 close_reason: None,
                 accept_state: AcceptState::new(local_endpoint_type),
                 stream_limits: connection_limits.stream_limits(),
+                reliable_stream_reset: connection_limits.reliable_stream_reset(),
+                local_reliable_stream_reset: connection_limits.local_reliable_stream_reset(),
+                drain_refusal_error: connection_limits.drain_refusal_error(),
+                receive_buffers: VecDeque::new(),
             },
             last_blocked_sync_period: Duration::ZERO,
         }
@@ -511,6 +614,11 @@ impl<S: StreamTrait> AbstractStreamManager<S> {
             return Ok(Some(stream_id)).into();
         });
 
+        // Draining connections don't accept any new Streams
+        if self.inner.accept_state.is_draining() {
+            return Ok(None).into();
+        }
+
         match self.inner.close_reason {
             // The connection closed without an error
             Some(connection::Error::Closed { .. }) => return Ok(None).into(),
@@ -546,6 +654,10 @@ impl<S: StreamTrait> AbstractStreamManager<S> {
             .next_stream_id(stream_type)
             .ok_or_else(connection::Error::stream_id_exhausted)?;
 
+        if self.inner.accept_state.is_refused(next_id_to_accept) {
+            return Ok(None);
+        }
+
         if self.inner.streams.contains(next_id_to_accept) {
             *self.inner.accept_state.next_stream_mut(stream_type) =
                 next_id_to_accept.next_of_type();
@@ -602,6 +714,9 @@ impl<S: StreamTrait> AbstractStreamManager<S> {
                 events.wake_all();
             },
         );
+
+        // Acknowledged data is released from the send buffer budget
+        self.inner.on_send_buffer_budget_available();
     }
 
     /// This method gets called when a packet loss is reported
@@ -664,6 +779,179 @@ impl<S: StreamTrait> AbstractStreamManager<S> {
         );
     }
 
+    /// Starts draining the connection
+    ///
+    /// The peer will not be issued any additional stream credit and all Streams
+    /// which it opens from now on are refused. Streams which are already open
+    /// are unaffected.
+    pub fn drain(&mut self) {
+        if self.inner.close_reason.is_some() || self.inner.accept_state.is_draining() {
+            return;
+        }
+
+        let peer_type = self.inner.local_endpoint_type.peer_type();
+        let bidi = *self
+            .inner
+            .next_stream_ids
+            .get_mut(peer_type, StreamType::Bidirectional);
+        let uni = *self
+            .inner
+            .next_stream_ids
+            .get_mut(peer_type, StreamType::Unidirectional);
+        self.inner.accept_state.drain_boundary = Some((bidi, uni));
+
+        self.inner.stream_controller.drain();
+
+        // Notify tasks which are blocked on `accept()`
+        for stream_type in [StreamType::Bidirectional, StreamType::Unidirectional] {
+            if let Some(waker) = self.inner.accept_state.waker_mut(stream_type).take() {
+                waker.wake();
+            }
+        }
+    }
+
+    /// Polls for all of the Streams to finish after the connection started draining
+    pub fn poll_drain(&self) -> Poll<()> {
+        if self.inner.accept_state.is_draining() && self.inner.streams.nr_active_streams() == 0 {
+            Poll::Ready(())
+        } else {
+            Poll::Pending
+        }
+    }
+
+    /// Updates a flow control limit of the connection
+    ///
+    /// Raised limits are advertised to the peer immediately. Since QUIC limits can
+    /// never decrease, lowered limits only reduce the credit which is granted to
+    /// the peer in the future.
+    ///
+    /// The update is expected to be validated with [`limits::Update::validate`].
+    pub fn update_limit(&mut self, update: limits::Update) {
+        debug_assert!(
+            update.validate().is_ok(),
+            "limit updates should be validated by the caller"
+        );
+
+        /// Receive windows are tracked as 32 bit values
+        fn window(value: u64) -> VarInt {
+            VarInt::from_u32(value.min(core::u32::MAX as u64) as u32)
+        }
+
+        fn stream_limit(value: u64) -> VarInt {
+            VarInt::new(value).unwrap_or(VarInt::MAX)
+        }
+
+        let stream_limits = &mut self.inner.desired_stream_limits;
+
+        match update {
+            limits::Update::DataWindow(value) => {
+                self.inner
+                    .incoming_connection_flow_controller
+                    .set_desired_flow_control_window(window(value).as_u64() as u32);
+                return;
+            }
+            limits::Update::BidirectionalLocalDataWindow(value) => {
+                stream_limits.max_data_bidi_local = window(value);
+            }
+            limits::Update::BidirectionalRemoteDataWindow(value) => {
+                stream_limits.max_data_bidi_remote = window(value);
+            }
+            limits::Update::UnidirectionalDataWindow(value) => {
+                stream_limits.max_data_uni = window(value);
+            }
+            limits::Update::MaxOpenRemoteBidirectionalStreams(value) => {
+                self.inner
+                    .stream_controller
+                    .set_max_open_remote_streams(StreamType::Bidirectional, stream_limit(value));
+                return;
+            }
+            limits::Update::MaxOpenRemoteUnidirectionalStreams(value) => {
+                self.inner
+                    .stream_controller
+                    .set_max_open_remote_streams(StreamType::Unidirectional, stream_limit(value));
+                return;
+            }
+            _ => {
+                debug_assert!(false, "unsupported limit update: {update:?}");
+                return;
+            }
+        }
+
+        // Apply the updated receive windows to all of the open streams
+        let stream_limits = self.inner.desired_stream_limits;
+        let local_endpoint_type = self.inner.local_endpoint_type;
+        self.inner
+            .streams
+            .iterate_streams(&mut self.inner.stream_controller, |stream| {
+                let window = stream_limits.max_data(local_endpoint_type, stream.stream_id());
+                stream.update_receive_window(window.as_u64() as u32);
+            });
+    }
+
+    /// Returns the number of bytes which are buffered by all streams
+    ///
+    /// This includes data which was enqueued for sending but not yet acknowledged
+    /// and data which was received but not yet consumed by the application.
+    pub fn buffered_bytes(&self) -> u64 {
+        self.send_buffered_bytes()
+            + self
+                .inner
+                .incoming_connection_flow_controller
+                .unconsumed_window()
+                .as_u64()
+    }
+
+    /// Returns the number of bytes which were enqueued for sending by all streams but not yet
+    /// acknowledged
+    pub fn send_buffered_bytes(&self) -> u64 {
+        self.inner.send_buffer_budget.buffered()
+    }
+
+    /// Applies the send buffer budget of the endpoint, which blocks streams from buffering
+    /// data for sending while it is exhausted
+    pub fn set_endpoint_send_buffer(&mut self, endpoint_send_buffer: &EndpointSendBuffer) {
+        self.inner
+            .send_buffer_budget
+            .set_endpoint(endpoint_send_buffer);
+    }
+
+    /// Returns `true` if streams are waiting for the send buffer budget of the endpoint
+    /// to become available again
+    pub fn is_blocked_on_endpoint_send_buffer(&self) -> bool {
+        self.inner.send_buffer_budget.is_blocked()
+            && self.inner.streams.has_send_buffer_budget_interest()
+    }
+
+    /// Notifies the streams which are waiting for the send buffer budget that it may be
+    /// available again
+    pub fn on_send_buffer_available(&mut self) {
+        self.inner.on_send_buffer_budget_available();
+    }
+
+    /// Stops or resumes extending the connection and stream flow control windows
+    pub fn on_memory_pressure(&mut self, enabled: bool) {
+        if self
+            .inner
+            .incoming_connection_flow_controller
+            .is_under_memory_pressure()
+            == enabled
+        {
+            return;
+        }
+
+        self.inner
+            .incoming_connection_flow_controller
+            .set_memory_pressure(enabled);
+
+        if !enabled {
+            self.inner
+                .streams
+                .iterate_streams(&mut self.inner.stream_controller, |stream| {
+                    stream.on_memory_pressure_released();
+                });
+        }
+    }
+
     /// Called when the connection timer expires
     pub fn on_timeout(&mut self, now: Timestamp) {
         self.inner.stream_controller.on_timeout(now);
@@ -760,6 +1048,10 @@ impl<S: StreamTrait> AbstractStreamManager<S> {
         // There is no `finalize_done_streams` here, since we do not expect to
         // perform an operation which brings us in a finalization state
 
+        // Streams with expired data are reset during transmission, which releases
+        // their data from the send buffer budget
+        self.inner.on_send_buffer_budget_available();
+
         transmit_result
     }
 
@@ -830,7 +1122,46 @@ impl<S: StreamTrait> AbstractStreamManager<S> {
     /// a stream
     pub fn on_data(&mut self, frame: &StreamRef) -> Result<(), transport::Error> {
         let stream_id = StreamId::from_varint(frame.stream_id);
-        self.handle_stream_frame(stream_id, |stream, events| stream.on_data(frame, events))
+        let mut events = StreamEvents::new();
+
+        let result = self.inner.reset_streams_on_error(|state| {
+            // Open streams if necessary
+            state.open_stream_if_necessary(stream_id)?;
+
+            let receive_buffers = &mut state.receive_buffers;
+
+            // Pass the frame to the Stream.
+            // If the Stream does not exist it is no error.
+            state
+                .streams
+                .with_stream(stream_id, &mut state.stream_controller, |stream| {
+                    // Hand the next registered buffer to the stream, so the data is written
+                    // directly into it
+                    if let Some(buffer) = receive_buffers.pop_front() {
+                        if let Err(buffer) = stream.offer_receive_buffer(frame, buffer) {
+                            receive_buffers.push_front(buffer);
+                        }
+                    }
+
+                    stream.on_data(frame, &mut events)
+                })
+                .unwrap_or(Ok(()))
+        });
+
+        events.wake_all();
+        result
+    }
+
+    /// Registers a buffer which is handed to the next stream that receives data
+    /// at its current offset without having a buffer allocated for it
+    ///
+    /// Buffers are handed out in the order they were registered.
+    pub fn register_receive_buffer(&mut self, buffer: BytesMut) {
+        if self.inner.close_reason.is_some() || buffer.capacity() == 0 {
+            return;
+        }
+
+        self.inner.receive_buffers.push_back(buffer);
     }
 
     /// This is called when a `DATA_BLOCKED` frame had been received
@@ -857,6 +1188,23 @@ impl<S: StreamTrait> AbstractStreamManager<S> {
         self.handle_stream_frame(stream_id, |stream, events| stream.on_reset(frame, events))
     }
 
+    /// This is called when a `RESET_STREAM_AT` frame had been received for
+    /// a stream
+    pub fn on_reset_stream_at(&mut self, frame: &ResetStreamAt) -> Result<(), transport::Error> {
+        // The peer may only send the frame if we advertised support for the extension, even
+        // if it didn't advertise support itself
+        if !self.inner.local_reliable_stream_reset {
+            return Err(transport::Error::PROTOCOL_VIOLATION
+                .with_reason("RESET_STREAM_AT received without negotiating support")
+                .with_frame_type(frame.tag().into()));
+        }
+
+        let stream_id = StreamId::from_varint(frame.stream_id);
+        self.handle_stream_frame(stream_id, |stream, events| {
+            stream.on_reset_at(frame, events)
+        })
+    }
+
     /// This is called when a `MAX_STREAM_DATA` frame had been received for
     /// a stream
     pub fn on_max_stream_data(&mut self, frame: &MaxStreamData) -> Result<(), transport::Error> {
@@ -872,7 +1220,12 @@ impl<S: StreamTrait> AbstractStreamManager<S> {
         let stream_id = StreamId::from_varint(frame.stream_id);
         self.handle_stream_frame(stream_id, |stream, events| {
             stream.on_stop_sending(frame, events)
-        })
+        })?;
+
+        // The stream is reset, which releases its data from the send buffer budget
+        self.inner.on_send_buffer_budget_available();
+
+        Ok(())
     }
 
     /// This is called when a `MAX_DATA` frame had been received
@@ -947,6 +1300,7 @@ impl<S: StreamTrait> AbstractStreamManager<S> {
         F: FnOnce(&mut S) -> R,
     {
         let had_transmission_interest = self.inner.streams.has_transmission_interest();
+        let was_send_buffer_blocked = self.is_blocked_on_endpoint_send_buffer();
 
         let result = self
             .inner
@@ -962,6 +1316,11 @@ impl<S: StreamTrait> AbstractStreamManager<S> {
         let require_wakeup =
             !had_transmission_interest && self.inner.streams.has_transmission_interest();
 
+        // The connection also needs to be woken up if streams started waiting for the send
+        // buffer budget of the endpoint, so the endpoint notifies it once it is available again
+        let require_wakeup = require_wakeup
+            || (!was_send_buffer_blocked && self.is_blocked_on_endpoint_send_buffer());
+
         // TODO: This currently wakes the connection task while inside the connection Mutex.
         // It will be better if we return the `Waker` instead and perform the wakeup afterwards.
         if require_wakeup {
@@ -978,12 +1337,27 @@ impl<S: StreamTrait> AbstractStreamManager<S> {
         request: &mut ops::Request,
         context: Option<&Context>,
     ) -> Result<ops::Response, StreamError> {
-        self.perform_api_call(
+        let is_reliable_reset = request
+            .tx
+            .as_ref()
+            .map_or(false, |tx| tx.reset.is_some() && tx.reliable_size > 0);
+        if is_reliable_reset && !self.inner.reliable_stream_reset {
+            return Err(StreamError::reliable_reset_unsupported());
+        }
+
+        let response = self.perform_api_call(
             stream_id,
             Err(StreamError::invalid_stream()),
             api_call_context,
             |stream| stream.poll_request(request, context),
-        )
+        );
+
+        // Resetting a stream releases its data from the send buffer budget
+        if request.tx.as_ref().map_or(false, |tx| tx.reset.is_some()) {
+            self.inner.on_send_buffer_budget_available();
+        }
+
+        response
     }
 
     /// Returns whether or not streams have data to send
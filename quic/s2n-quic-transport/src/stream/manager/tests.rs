@@ -17,7 +17,7 @@ use crate::{
         stream_impl::StreamConfig,
         stream_interests::{StreamInterestProvider, StreamInterests},
         testing::*,
-        AbstractStreamManager, StreamError, StreamEvents, StreamTrait,
+        AbstractStreamManager, EndpointSendBuffer, StreamError, StreamEvents, StreamTrait,
     },
     sync::DEFAULT_SYNC_PERIOD,
     transmission,
@@ -25,7 +25,7 @@ use crate::{
     wakeup_queue::{WakeupHandle, WakeupQueue},
 };
 use alloc::{collections::VecDeque, sync::Arc};
-use bytes::Bytes;
+use bytes::{Bytes, BytesMut};
 use core::{
     task::{Context, Poll, Waker},
     time::Duration,
@@ -36,7 +36,7 @@ use s2n_quic_core::{
     application::Error as ApplicationErrorCode,
     frame::{
         stream::StreamRef, DataBlocked, Frame, MaxData, MaxStreamData, MaxStreams, ResetStream,
-        StopSending, Stream as StreamFrame, StreamDataBlocked, StreamsBlocked,
+        ResetStreamAt, StopSending, Stream as StreamFrame, StreamDataBlocked, StreamsBlocked,
     },
     packet::number::{PacketNumberRange, PacketNumberSpace},
     stream::{ops, StreamId, StreamType},
@@ -63,9 +63,12 @@ struct MockStream {
     interests: StreamInterests,
     on_connection_window_available_count: usize,
     on_connection_window_available_retrieve_window: u64,
+    on_send_buffer_budget_available_count: usize,
     on_packet_ack_count: usize,
     on_packet_loss_count: usize,
     update_blocked_sync_period_count: usize,
+    update_receive_window_count: usize,
+    on_memory_pressure_released_count: usize,
     on_timeout_count: usize,
     on_internal_reset_count: usize,
     on_transmit_try_write_frames: usize,
@@ -86,6 +89,8 @@ struct MockStream {
     poll_push_count: usize,
     poll_finish_count: usize,
     reset_count: usize,
+    last_api_error: Option<ApplicationErrorCode>,
+    receive_buffer: Option<BytesMut>,
 }
 
 impl MockStream {
@@ -134,9 +139,12 @@ impl StreamTrait for MockStream {
             },
             on_connection_window_available_count: 0,
             on_connection_window_available_retrieve_window: 0,
+            on_send_buffer_budget_available_count: 0,
             on_packet_ack_count: 0,
             on_packet_loss_count: 0,
             update_blocked_sync_period_count: 0,
+            update_receive_window_count: 0,
+            on_memory_pressure_released_count: 0,
             on_timeout_count: 0,
             on_internal_reset_count: 0,
             on_data_count: 0,
@@ -159,6 +167,8 @@ impl StreamTrait for MockStream {
             poll_push_count: 0,
             poll_finish_count: 0,
             reset_count: 0,
+            last_api_error: None,
+            receive_buffer: None,
         }
     }
 
@@ -166,6 +176,19 @@ impl StreamTrait for MockStream {
         self.config.stream_id
     }
 
+    fn offer_receive_buffer(
+        &mut self,
+        frame: &StreamRef,
+        buffer: BytesMut,
+    ) -> Result<(), BytesMut> {
+        assert_eq!(self.stream_id(), StreamId::from_varint(frame.stream_id));
+        if self.receive_buffer.is_some() {
+            return Err(buffer);
+        }
+        self.receive_buffer = Some(buffer);
+        Ok(())
+    }
+
     fn on_data(
         &mut self,
         frame: &StreamRef,
@@ -210,6 +233,20 @@ impl StreamTrait for MockStream {
         Ok(())
     }
 
+    fn on_reset_at(
+        &mut self,
+        frame: &ResetStreamAt,
+        events: &mut StreamEvents,
+    ) -> Result<(), TransportError> {
+        assert_eq!(self.stream_id(), StreamId::from_varint(frame.stream_id));
+        self.on_reset_count += 1;
+        self.store_wakers(events);
+        if let Some(err) = self.next_packet_error {
+            return Err(err);
+        };
+        Ok(())
+    }
+
     fn on_max_stream_data(
         &mut self,
         frame: &MaxStreamData,
@@ -254,6 +291,15 @@ impl StreamTrait for MockStream {
         self.update_blocked_sync_period_count += 1;
     }
 
+    fn update_receive_window(&mut self, desired_flow_control_window: u32) {
+        self.config.desired_flow_control_window = desired_flow_control_window;
+        self.update_receive_window_count += 1;
+    }
+
+    fn on_memory_pressure_released(&mut self) {
+        self.on_memory_pressure_released_count += 1;
+    }
+
     fn on_timeout(&mut self, _now: Timestamp) {
         self.on_timeout_count += 1;
     }
@@ -301,6 +347,11 @@ impl StreamTrait for MockStream {
         self.on_connection_window_available_retrieve_window -= Into::<u64>::into(acquired_window);
     }
 
+    fn on_send_buffer_budget_available(&mut self, events: &mut StreamEvents) {
+        self.on_send_buffer_budget_available_count += 1;
+        self.store_wakers(events);
+    }
+
     fn poll_request(
         &mut self,
         request: &mut ops::Request,
@@ -317,8 +368,9 @@ impl StreamTrait for MockStream {
                 self.poll_finish_count += 1;
             }
 
-            if tx.reset.is_some() {
+            if let Some(error) = tx.reset {
                 self.reset_count += 1;
+                self.last_api_error = Some(error);
             }
 
             response.tx = Some(ops::tx::Response::default());
@@ -329,8 +381,9 @@ impl StreamTrait for MockStream {
                 self.poll_pop_count += 1;
             }
 
-            if rx.stop_sending.is_some() {
+            if let Some(error) = rx.stop_sending {
                 self.stop_sending_count += 1;
+                self.last_api_error = Some(error);
             }
 
             response.rx = Some(ops::rx::Response::default());
@@ -529,6 +582,72 @@ fn opens_locally_initiated_streams() {
     }
 }
 
+#[test]
+fn on_data_hands_out_registered_receive_buffers() {
+    let mut manager = create_stream_manager(endpoint::Type::Server);
+
+    let stream_1 = StreamId::nth(endpoint::Type::Client, StreamType::Bidirectional, 0).unwrap();
+    let stream_2 = StreamId::nth(endpoint::Type::Client, StreamType::Bidirectional, 1).unwrap();
+
+    // Buffers without any capacity are ignored
+    manager.register_receive_buffer(BytesMut::new());
+    manager.register_receive_buffer(BytesMut::with_capacity(10));
+    manager.register_receive_buffer(BytesMut::with_capacity(20));
+    assert_eq!(2, manager.inner.receive_buffers.len());
+
+    let receive_buffer_capacity = |manager: &mut AbstractStreamManager<MockStream>, stream_id| {
+        manager.with_asserted_stream(stream_id, |stream| {
+            stream
+                .receive_buffer
+                .as_ref()
+                .map(|buffer| buffer.capacity())
+        })
+    };
+
+    assert_eq!(
+        Ok(()),
+        manager.on_data(&stream_data(
+            stream_1,
+            VarInt::from_u32(0),
+            &[1, 2, 3],
+            false
+        ))
+    );
+    assert_eq!(Some(10), receive_buffer_capacity(&mut manager, stream_1));
+
+    // Buffers which are refused by the stream are kept for other streams
+    assert_eq!(
+        Ok(()),
+        manager.on_data(&stream_data(
+            stream_1,
+            VarInt::from_u32(3),
+            &[4, 5, 6],
+            false
+        ))
+    );
+    assert_eq!(1, manager.inner.receive_buffers.len());
+
+    assert_eq!(
+        Ok(()),
+        manager.on_data(&stream_data(
+            stream_2,
+            VarInt::from_u32(0),
+            &[1, 2, 3],
+            false
+        ))
+    );
+    assert_eq!(Some(20), receive_buffer_capacity(&mut manager, stream_2));
+    assert!(manager.inner.receive_buffers.is_empty());
+
+    // Closing the manager releases the buffers which weren't handed out
+    manager.register_receive_buffer(BytesMut::with_capacity(30));
+    manager.close(connection::Error::unspecified());
+    assert!(manager.inner.receive_buffers.is_empty());
+
+    manager.register_receive_buffer(BytesMut::with_capacity(30));
+    assert!(manager.inner.receive_buffers.is_empty());
+}
+
 #[test]
 fn open_returns_error_after_close() {
     let mut manager = create_stream_manager(endpoint::Type::Server);
@@ -804,6 +923,388 @@ fn peer_closing_streams_transmits_max_streams() {
     }
 }
 
+#[test]
+fn update_limit_max_open_remote_streams() {
+    let mut manager = create_stream_manager(endpoint::Type::Server);
+
+    for stream_type in [StreamType::Bidirectional, StreamType::Unidirectional] {
+        let (raised, lowered) = match stream_type {
+            StreamType::Bidirectional => (
+                limits::Update::MaxOpenRemoteBidirectionalStreams(200),
+                limits::Update::MaxOpenRemoteBidirectionalStreams(10),
+            ),
+            StreamType::Unidirectional => (
+                limits::Update::MaxOpenRemoteUnidirectionalStreams(200),
+                limits::Update::MaxOpenRemoteUnidirectionalStreams(10),
+            ),
+        };
+
+        // Raising the limit advertises the additional credit immediately
+        manager.update_limit(raised);
+
+        assert_eq!(
+            transmission::Interest::NewData,
+            manager.get_transmission_interest()
+        );
+
+        let mut frame_buffer = OutgoingFrameBuffer::new();
+        let mut write_context = MockWriteContext::new(
+            s2n_quic_platform::time::now(),
+            &mut frame_buffer,
+            transmission::Constraint::None,
+            transmission::Mode::Normal,
+            endpoint::Type::Server,
+        );
+        let packet_number = write_context.packet_number();
+        assert!(manager.on_transmit(&mut write_context).is_ok());
+
+        assert_eq!(
+            Frame::MaxStreams(MaxStreams {
+                stream_type,
+                maximum_streams: VarInt::from_u8(200),
+            }),
+            write_context.frame_buffer.pop_front().unwrap().as_frame()
+        );
+
+        manager.on_packet_ack(&PacketNumberRange::new(packet_number, packet_number));
+
+        // Lowering the limit can't revoke the credit which was already granted
+        manager.update_limit(lowered);
+
+        assert_eq!(
+            transmission::Interest::None,
+            manager.get_transmission_interest()
+        );
+
+        // The peer can still open all of the streams it was granted
+        for i in 0..200 {
+            let stream_id = StreamId::nth(endpoint::Type::Client, stream_type, i).unwrap();
+            assert_eq!(
+                Ok(()),
+                manager.on_data(&stream_data(stream_id, VarInt::from_u32(0), &[], false))
+            );
+        }
+
+        // Closing streams doesn't grant additional credit until the number of open
+        // streams drops below the lowered limit
+        for i in 0..190 {
+            let stream_id = StreamId::nth(endpoint::Type::Client, stream_type, i).unwrap();
+            manager.with_asserted_stream(stream_id, |stream| {
+                stream.interests.retained = false;
+            });
+        }
+
+        assert_eq!(
+            transmission::Interest::None,
+            manager.get_transmission_interest()
+        );
+        assert_eq!(
+            VarInt::from_u8(200),
+            manager.with_stream_controller(|ctrl| {
+                ctrl.remote_initiated_max_streams_latest_value(stream_type)
+            })
+        );
+
+        for i in 190..200 {
+            let stream_id = StreamId::nth(endpoint::Type::Client, stream_type, i).unwrap();
+            manager.with_asserted_stream(stream_id, |stream| {
+                stream.interests.retained = false;
+            });
+        }
+
+        assert_eq!(
+            transmission::Interest::NewData,
+            manager.get_transmission_interest()
+        );
+        assert_eq!(
+            VarInt::from_u8(210),
+            manager.with_stream_controller(|ctrl| {
+                ctrl.remote_initiated_max_streams_latest_value(stream_type)
+            })
+        );
+
+        let packet_number = write_context.packet_number();
+        assert!(manager.on_transmit(&mut write_context).is_ok());
+        manager.on_packet_ack(&PacketNumberRange::new(packet_number, packet_number));
+    }
+}
+
+#[test]
+fn drain_refuses_new_remote_streams() {
+    let mut manager = create_stream_manager(endpoint::Type::Server);
+    let (accept_waker, accept_wake_counter) = new_count_waker();
+
+    let bidi_0 = StreamId::nth(endpoint::Type::Client, StreamType::Bidirectional, 0).unwrap();
+    let bidi_1 = StreamId::nth(endpoint::Type::Client, StreamType::Bidirectional, 1).unwrap();
+    let uni_0 = StreamId::nth(endpoint::Type::Client, StreamType::Unidirectional, 0).unwrap();
+
+    // Open a Stream before draining starts
+    assert_eq!(
+        Ok(()),
+        manager.on_data(&stream_data(bidi_0, VarInt::from_u32(0), &[], false))
+    );
+
+    assert_eq!(
+        Poll::Pending,
+        manager.poll_accept(
+            Some(StreamType::Unidirectional),
+            &Context::from_waker(&accept_waker)
+        )
+    );
+
+    assert_eq!(Poll::Pending, manager.poll_drain());
+    manager.drain();
+
+    // Draining must wake up pending accepts
+    assert_eq!(accept_wake_counter, 1);
+    assert_eq!(Poll::Pending, manager.poll_drain());
+
+    // Streams opened after draining started are refused
+    for stream_id in [bidi_1, uni_0] {
+        assert_eq!(
+            Ok(()),
+            manager.on_data(&stream_data(stream_id, VarInt::from_u32(0), &[], false))
+        );
+    }
+
+    manager.with_asserted_stream(bidi_1, |stream| {
+        assert_eq!(stream.reset_count, 1);
+        assert_eq!(stream.stop_sending_count, 1);
+    });
+    manager.with_asserted_stream(uni_0, |stream| {
+        assert_eq!(stream.reset_count, 0);
+        assert_eq!(stream.stop_sending_count, 1);
+    });
+    manager.with_asserted_stream(bidi_0, |stream| {
+        assert_eq!(stream.reset_count, 0);
+        assert_eq!(stream.stop_sending_count, 0);
+    });
+
+    // Streams opened before draining can still be accepted
+    assert_eq!(
+        Poll::Ready(Ok(Some(bidi_0))),
+        manager.poll_accept(
+            Some(StreamType::Bidirectional),
+            &Context::from_waker(&accept_waker)
+        )
+    );
+    for stream_type in [StreamType::Bidirectional, StreamType::Unidirectional] {
+        assert_eq!(
+            Poll::Ready(Ok(None)),
+            manager.poll_accept(Some(stream_type), &Context::from_waker(&accept_waker))
+        );
+    }
+    assert_eq!(
+        Poll::Ready(Ok(None)),
+        manager.poll_accept(None, &Context::from_waker(&accept_waker))
+    );
+
+    // The connection is drained once all of the Streams have finished
+    for stream_id in [bidi_0, bidi_1, uni_0] {
+        assert_eq!(Poll::Pending, manager.poll_drain());
+        manager.with_asserted_stream(stream_id, |stream| {
+            stream.interests.retained = false;
+        });
+    }
+
+    assert_eq!(Poll::Ready(()), manager.poll_drain());
+
+    // No additional stream credit is issued to the peer
+    assert_eq!(
+        transmission::Interest::None,
+        manager.get_transmission_interest()
+    );
+}
+
+#[test]
+fn drain_refusal_error() {
+    let limits = ConnectionLimits::default()
+        .with_drain_refusal_error(ApplicationErrorCode::new(0x10c).unwrap())
+        .unwrap();
+    let mut manager = AbstractStreamManager::<MockStream>::new(
+        &limits,
+        endpoint::Type::Server,
+        create_default_initial_flow_control_limits(),
+        create_default_initial_flow_control_limits(),
+    );
+
+    manager.drain();
+
+    let bidi_0 = StreamId::nth(endpoint::Type::Client, StreamType::Bidirectional, 0).unwrap();
+    let uni_0 = StreamId::nth(endpoint::Type::Client, StreamType::Unidirectional, 0).unwrap();
+
+    for stream_id in [bidi_0, uni_0] {
+        assert_eq!(
+            Ok(()),
+            manager.on_data(&stream_data(stream_id, VarInt::from_u32(0), &[], false))
+        );
+
+        // Refused streams use the configured error code
+        manager.with_asserted_stream(stream_id, |stream| {
+            assert_eq!(stream.stop_sending_count, 1);
+            assert_eq!(
+                stream.last_api_error,
+                Some(ApplicationErrorCode::new(0x10c).unwrap())
+            );
+        });
+    }
+}
+
+#[test]
+fn update_limit_data_window() {
+    let mut manager = create_stream_manager(endpoint::Type::Server);
+
+    // Growing the window advertises the additional credit immediately
+    manager.update_limit(limits::Update::DataWindow(128 * 1024));
+
+    assert_eq!(
+        transmission::Interest::NewData,
+        manager.get_transmission_interest()
+    );
+
+    let mut frame_buffer = OutgoingFrameBuffer::new();
+    let mut write_context = MockWriteContext::new(
+        s2n_quic_platform::time::now(),
+        &mut frame_buffer,
+        transmission::Constraint::None,
+        transmission::Mode::Normal,
+        endpoint::Type::Server,
+    );
+    assert!(manager.on_transmit(&mut write_context).is_ok());
+
+    assert_eq!(
+        Frame::MaxData(MaxData {
+            maximum_data: VarInt::from_u32(128 * 1024),
+        }),
+        write_context.frame_buffer.pop_front().unwrap().as_frame()
+    );
+
+    // Shrinking the window only reduces future credit
+    manager.update_limit(limits::Update::DataWindow(1024));
+
+    assert_eq!(
+        1024,
+        manager
+            .inner
+            .incoming_connection_flow_controller
+            .desired_flow_control_window()
+    );
+    assert_eq!(
+        VarInt::from_u32(128 * 1024),
+        manager
+            .inner
+            .incoming_connection_flow_controller
+            .current_receive_window()
+    );
+}
+
+#[test]
+fn memory_pressure_stops_extending_data_window() {
+    let mut manager = create_stream_manager(endpoint::Type::Server);
+    let stream_id = try_open(&mut manager, StreamType::Bidirectional).unwrap();
+    let initial_window = manager
+        .inner
+        .incoming_connection_flow_controller
+        .current_receive_window();
+    let desired_window = manager
+        .inner
+        .incoming_connection_flow_controller
+        .desired_flow_control_window();
+
+    // Received data is accounted for until it is consumed
+    assert!(manager
+        .inner
+        .incoming_connection_flow_controller
+        .acquire_window(VarInt::from_u32(10_000))
+        .is_ok());
+    assert_eq!(10_000, manager.buffered_bytes());
+
+    // No additional credit is granted while under memory pressure
+    manager.on_memory_pressure(true);
+    manager
+        .inner
+        .incoming_connection_flow_controller
+        .release_window(VarInt::from_u32(10_000));
+    assert_eq!(0, manager.buffered_bytes());
+    assert_eq!(
+        initial_window,
+        manager
+            .inner
+            .incoming_connection_flow_controller
+            .current_receive_window()
+    );
+    assert_eq!(
+        transmission::Interest::None,
+        manager.get_transmission_interest()
+    );
+
+    // Entering memory pressure again doesn't notify the streams
+    manager.on_memory_pressure(true);
+    manager.with_asserted_stream(stream_id, |stream| {
+        assert_eq!(stream.on_memory_pressure_released_count, 0);
+    });
+
+    // The withheld credit is granted once the pressure is relieved
+    manager.on_memory_pressure(false);
+    manager.with_asserted_stream(stream_id, |stream| {
+        assert_eq!(stream.on_memory_pressure_released_count, 1);
+    });
+    assert_eq!(
+        VarInt::from_u32(10_000 + desired_window),
+        manager
+            .inner
+            .incoming_connection_flow_controller
+            .current_receive_window()
+    );
+    assert_eq!(
+        transmission::Interest::NewData,
+        manager.get_transmission_interest()
+    );
+}
+
+#[test]
+fn update_limit_stream_data_windows() {
+    let mut manager = create_stream_manager(endpoint::Type::Server);
+
+    let local_bidi = try_open(&mut manager, StreamType::Bidirectional).unwrap();
+    let local_uni = try_open(&mut manager, StreamType::Unidirectional).unwrap();
+
+    manager.update_limit(limits::Update::BidirectionalLocalDataWindow(8192));
+
+    // The window is applied to the open streams of the matching type
+    manager.with_asserted_stream(local_bidi, |stream| {
+        assert_eq!(stream.config.desired_flow_control_window, 8192);
+        // The initial window which was advertised to the peer is unchanged
+        assert_eq!(stream.config.initial_receive_window, VarInt::from_u32(4096));
+    });
+    manager.with_asserted_stream(local_uni, |stream| {
+        assert_eq!(stream.config.desired_flow_control_window, 4096);
+    });
+
+    manager.update_limit(limits::Update::BidirectionalRemoteDataWindow(1024));
+    manager.update_limit(limits::Update::UnidirectionalDataWindow(16 * 1024));
+
+    manager.with_asserted_stream(local_uni, |stream| {
+        assert_eq!(stream.config.desired_flow_control_window, 16 * 1024);
+    });
+
+    // Newly opened streams use the updated windows
+    let remote_bidi = StreamId::initial(endpoint::Type::Client, StreamType::Bidirectional);
+    assert_eq!(
+        Ok(()),
+        manager.on_data(&stream_data(remote_bidi, VarInt::from_u32(0), &[], false))
+    );
+    manager.with_asserted_stream(remote_bidi, |stream| {
+        assert_eq!(stream.config.desired_flow_control_window, 1024);
+        assert_eq!(stream.config.initial_receive_window, VarInt::from_u32(4096));
+    });
+
+    let local_bidi = try_open(&mut manager, StreamType::Bidirectional).unwrap();
+    manager.with_asserted_stream(local_bidi, |stream| {
+        assert_eq!(stream.config.desired_flow_control_window, 8192);
+    });
+}
+
 //= https://www.rfc-editor.org/rfc/rfc9000#section-4.6
 //= type=test
 //# An endpoint that is unable to open a new stream due to the peer's
@@ -2140,6 +2641,81 @@ fn on_packet_ack_and_loss_is_forwarded_to_interested_streams() {
     });
 }
 
+#[test]
+fn on_packet_ack_notifies_streams_blocked_on_send_buffer_budget() {
+    let mut manager = create_stream_manager(endpoint::Type::Server);
+
+    let (write_waker, write_wake_counter) = new_count_waker();
+
+    let stream_1 = try_open(&mut manager, StreamType::Bidirectional).unwrap();
+    let stream_2 = try_open(&mut manager, StreamType::Unidirectional).unwrap();
+    let stream_3 = try_open(&mut manager, StreamType::Bidirectional).unwrap();
+
+    for stream_id in &[stream_1, stream_3] {
+        let write_waker = write_waker.clone();
+        manager.with_asserted_stream(*stream_id, |stream| {
+            stream.interests.send_buffer_budget = true;
+            stream.write_waker_to_return = Some(write_waker);
+        });
+    }
+
+    manager.on_packet_ack(&pn(1));
+
+    assert_eq!(write_wake_counter, 2);
+
+    for stream_id in &[stream_1, stream_3] {
+        manager.with_asserted_stream(*stream_id, |stream| {
+            assert_eq!(1, stream.on_send_buffer_budget_available_count);
+            stream.interests.send_buffer_budget = false;
+        });
+    }
+    manager.with_asserted_stream(stream_2, |stream| {
+        assert_eq!(0, stream.on_send_buffer_budget_available_count);
+    });
+
+    manager.on_packet_ack(&pn(2));
+
+    for stream_id in &[stream_1, stream_3] {
+        manager.with_asserted_stream(*stream_id, |stream| {
+            assert_eq!(1, stream.on_send_buffer_budget_available_count);
+        });
+    }
+}
+
+#[test]
+fn endpoint_send_buffer_unblock_notifies_streams() {
+    let mut manager = create_stream_manager(endpoint::Type::Server);
+
+    let (write_waker, write_wake_counter) = new_count_waker();
+
+    let stream_1 = try_open(&mut manager, StreamType::Bidirectional).unwrap();
+    manager.with_asserted_stream(stream_1, |stream| {
+        stream.interests.send_buffer_budget = true;
+        stream.write_waker_to_return = Some(write_waker);
+    });
+
+    let endpoint_send_buffer = EndpointSendBuffer::default();
+    manager.set_endpoint_send_buffer(&endpoint_send_buffer);
+    assert!(!manager.is_blocked_on_endpoint_send_buffer());
+
+    // Blocking the streams doesn't notify them
+    endpoint_send_buffer.set_blocked(true);
+    assert!(manager.is_blocked_on_endpoint_send_buffer());
+    assert_eq!(write_wake_counter, 0);
+
+    // Acknowledgements don't unblock the streams while the endpoint budget is exhausted
+    manager.on_packet_ack(&pn(1));
+    assert_eq!(write_wake_counter, 0);
+
+    endpoint_send_buffer.set_blocked(false);
+    assert!(!manager.is_blocked_on_endpoint_send_buffer());
+    manager.on_send_buffer_available();
+    assert_eq!(write_wake_counter, 1);
+    manager.with_asserted_stream(stream_1, |stream| {
+        assert_eq!(1, stream.on_send_buffer_budget_available_count);
+    });
+}
+
 #[test]
 fn close_is_forwarded_to_all_streams() {
     let (read_waker, read_wake_counter) = new_count_waker();
@@ -2796,6 +3372,96 @@ fn forwards_on_reset() {
     assert_eq!(write_wake_counter, 2);
 }
 
+#[test]
+fn reliable_reset_requires_negotiation() {
+    let (_wakeup_queue, wakeup_handle) = create_wakeup_queue_and_handle();
+    let mut manager = create_stream_manager(endpoint::Type::Server);
+
+    let stream_1 = try_open(&mut manager, StreamType::Bidirectional).unwrap();
+    let error = ApplicationErrorCode::new(0x12_3456).unwrap();
+
+    assert_matches!(
+        manager.poll_request(
+            stream_1,
+            &mut ConnectionApiCallContext::from_wakeup_handle(&wakeup_handle),
+            ops::Request::default().reset_at(error, 10),
+            None
+        ),
+        Err(StreamError::ReliableResetUnsupported { .. }),
+    );
+
+    // resets without a reliable size don't require the extension
+    assert!(manager
+        .poll_request(
+            stream_1,
+            &mut ConnectionApiCallContext::from_wakeup_handle(&wakeup_handle),
+            ops::Request::default().reset_at(error, 0),
+            None
+        )
+        .is_ok());
+
+    let frame = ResetStreamAt {
+        stream_id: stream_1.into(),
+        application_error_code: VarInt::from_u8(1),
+        final_size: VarInt::from_u8(10),
+        reliable_size: VarInt::from_u8(5),
+    };
+    assert_is_transport_error(
+        manager.on_reset_stream_at(&frame),
+        TransportError::PROTOCOL_VIOLATION,
+    );
+
+    // streams can be reliably reset once the extension was negotiated
+    let limits = ConnectionLimits::default()
+        .with_reliable_stream_reset(true)
+        .unwrap();
+    let mut manager = AbstractStreamManager::<MockStream>::new(
+        &limits,
+        endpoint::Type::Server,
+        create_default_initial_flow_control_limits(),
+        create_default_initial_flow_control_limits(),
+    );
+    let stream_1 = try_open(&mut manager, StreamType::Bidirectional).unwrap();
+
+    assert!(manager
+        .poll_request(
+            stream_1,
+            &mut ConnectionApiCallContext::from_wakeup_handle(&wakeup_handle),
+            ops::Request::default().reset_at(error, 10),
+            None
+        )
+        .is_ok());
+    assert!(manager
+        .on_reset_stream_at(&ResetStreamAt {
+            stream_id: stream_1.into(),
+            ..frame
+        })
+        .is_ok());
+    manager.with_asserted_stream(stream_1, |stream| {
+        assert_eq!(stream.on_reset_count, 1);
+    });
+
+    // the peer can reliably reset streams if we advertised the extension, even if it didn't
+    manager.inner.reliable_stream_reset = false;
+    let stream_2 = try_open(&mut manager, StreamType::Bidirectional).unwrap();
+
+    assert_matches!(
+        manager.poll_request(
+            stream_2,
+            &mut ConnectionApiCallContext::from_wakeup_handle(&wakeup_handle),
+            ops::Request::default().reset_at(error, 10),
+            None
+        ),
+        Err(StreamError::ReliableResetUnsupported { .. }),
+    );
+    assert!(manager
+        .on_reset_stream_at(&ResetStreamAt {
+            stream_id: stream_2.into(),
+            ..frame
+        })
+        .is_ok());
+}
+
 #[test]
 fn forwards_poll_pop() {
     let (mut wakeup_queue, wakeup_handle) = create_wakeup_queue_and_handle();
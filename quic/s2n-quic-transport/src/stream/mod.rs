@@ -9,6 +9,7 @@ mod incoming_connection_flow_controller;
 mod manager;
 mod outgoing_connection_flow_controller;
 mod receive_stream;
+mod send_buffer_budget;
 mod send_stream;
 mod stream_container;
 mod stream_events;
@@ -22,6 +23,7 @@ pub use api::*;
 pub use controller::Controller;
 pub use manager::AbstractStreamManager;
 pub use s2n_quic_core::stream::limits::Limits;
+pub use send_buffer_budget::EndpointSendBuffer;
 pub use stream_events::StreamEvents;
 pub use stream_impl::{StreamImpl, StreamTrait};
 
@@ -12,6 +12,7 @@ use crate::{
     sync::{IncrementalValueSync, OnceSync, ValueToFrameWriter},
     transmission::interest::Provider as _,
 };
+use bytes::BytesMut;
 use core::{
     convert::TryFrom,
     task::{Context, Poll, Waker},
@@ -21,7 +22,10 @@ use s2n_quic_core::{
     buffer::{
         ReceiveBuffer as StreamReceiveBuffer, ReceiveBufferError as StreamReceiveBufferError,
     },
-    frame::{stream::StreamRef, MaxStreamData, ResetStream, StopSending, StreamDataBlocked},
+    frame::{
+        stream::StreamRef, MaxStreamData, ResetStream, ResetStreamAt, StopSending,
+        StreamDataBlocked,
+    },
     packet::number::PacketNumber,
     stream::{ops, StreamId},
     transport,
@@ -194,7 +198,9 @@ impl ReceiveStreamFlowController {
         Self {
             connection_flow_controller,
             read_window_sync: IncrementalValueSync::new(
-                VarInt::from_u32(desired_flow_control_window),
+                // The desired window may have been lowered below the initial window
+                // which was advertised in the transport parameters
+                VarInt::from_u32(desired_flow_control_window).max(initial_window),
                 initial_window,
                 VarInt::from_u32(desired_flow_control_window / 10),
             ),
@@ -282,16 +288,50 @@ impl ReceiveStreamFlowController {
         //# sender being blocked for the rest of the connection.
 
         // Enqueue Stream window updates by increasing the latest value on
-        // the read window synchronisation component
-        self.read_window_sync.update_latest_value(
-            self.released_connection_window
-                .saturating_add(VarInt::from_u32(self.desired_flow_control_window)),
-        );
+        // the read window synchronisation component. No additional credit is
+        // granted while the endpoint is under memory pressure.
+        if !self.connection_flow_controller.is_under_memory_pressure() {
+            self.grant_window();
+        }
 
         // Notify the connection flow controller about the consumed data
         self.connection_flow_controller.release_window(amount);
     }
 
+    /// Extends the Streams window up to the desired window past the consumed data
+    fn grant_window(&mut self) {
+        let window = self
+            .released_connection_window
+            .saturating_add(VarInt::from_u32(self.desired_flow_control_window));
+        // The desired window may have been lowered, in which case no additional
+        // credit is granted until enough data has been consumed.
+        if window > self.read_window_sync.latest_value() {
+            self.read_window_sync.update_latest_value(window);
+        }
+    }
+
+    /// Updates the relative flow control window we want to maintain
+    ///
+    /// Growing the window immediately advertises the additional credit to the peer.
+    /// Since the advertised limit can never decrease, shrinking the window only
+    /// reduces the credit which is granted as data is consumed.
+    fn set_desired_flow_control_window(&mut self, desired_flow_control_window: u32) {
+        self.desired_flow_control_window = desired_flow_control_window;
+        self.read_window_sync
+            .set_threshold(VarInt::from_u32(desired_flow_control_window / 10));
+
+        if self.connection_flow_controller.is_under_memory_pressure() {
+            return;
+        }
+
+        let window = self
+            .released_connection_window
+            .saturating_add(VarInt::from_u32(desired_flow_control_window));
+        if window > self.read_window_sync.latest_value() {
+            self.read_window_sync.force_update_latest_value(window);
+        }
+    }
+
     /// Releases all flow credits which had been acquired but not yet released
     /// through previous [`release_window`] calls.
     fn release_outstanding_window(&mut self) {
@@ -349,6 +389,9 @@ pub struct ReceiveStream {
     final_state_observed: bool,
     /// Marks the stream as detached from the application
     detached: bool,
+    /// A reset which was received via a `RESET_STREAM_AT` frame and takes effect once the
+    /// application has read the given number of bytes
+    reliable_reset: Option<(StreamError, u64)>,
 }
 
 impl ReceiveStream {
@@ -378,6 +421,7 @@ impl ReceiveStream {
             read_waiter: None,
             final_state_observed: is_closed,
             detached: is_closed,
+            reliable_reset: None,
         };
 
         if is_closed {
@@ -390,6 +434,33 @@ impl ReceiveStream {
 
     // These functions are called from the packet delivery thread
 
+    /// Offers a connection-level receive buffer to the stream before `frame` is received
+    ///
+    /// The buffer is only registered if the data of the frame starts at or before the current
+    /// offset of the stream and no buffer is allocated for that offset yet. Otherwise the buffer
+    /// is returned to the caller.
+    pub fn offer_receive_buffer(
+        &mut self,
+        frame: &StreamRef,
+        buffer: BytesMut,
+    ) -> Result<(), BytesMut> {
+        if !matches!(self.state, ReceiveStreamState::Receiving(_)) {
+            return Err(buffer);
+        }
+
+        let offset = self.receive_buffer.consumed_len();
+        let frame_start = frame.offset.as_u64();
+        let frame_end = frame_start.saturating_add(frame.data.len() as u64);
+
+        if !(frame_start..frame_end).contains(&offset)
+            || self.receive_buffer.is_allocated_at_offset()
+        {
+            return Err(buffer);
+        }
+
+        self.receive_buffer.register_buffer(buffer)
+    }
+
     pub fn on_data(
         &mut self,
         frame: &StreamRef,
@@ -551,6 +622,22 @@ impl ReceiveStream {
         Ok(())
     }
 
+    /// Updates the flow control window which is maintained for receiving data
+    pub fn update_receive_window(&mut self, desired_flow_control_window: u32) {
+        // Only streams which are still waiting for data need additional credit
+        if let ReceiveStreamState::Receiving(None) = self.state {
+            self.flow_controller
+                .set_desired_flow_control_window(desired_flow_control_window);
+        }
+    }
+
+    /// Grants the credit which was withheld while the endpoint was under memory pressure
+    pub fn on_memory_pressure_released(&mut self) {
+        if let ReceiveStreamState::Receiving(None) = self.state {
+            self.flow_controller.grant_window();
+        }
+    }
+
     /// This is called when a `STREAM_DATA_BLOCKED` frame had been received for
     /// this stream
     pub fn on_stream_data_blocked(
@@ -600,6 +687,60 @@ impl ReceiveStream {
         Ok(())
     }
 
+    /// This is called when a `RESET_STREAM_AT` frame had been received for
+    /// this stream
+    pub fn on_reset_at(
+        &mut self,
+        frame: &ResetStreamAt,
+        events: &mut StreamEvents,
+    ) -> Result<(), transport::Error> {
+        if frame.reliable_size > frame.final_size {
+            return Err(transport::Error::FRAME_ENCODING_ERROR
+                .with_reason("Reliable size exceeds the final size")
+                .with_frame_type(frame.tag().into()));
+        }
+
+        let error = StreamError::stream_reset(frame.application_error_code.into());
+        let reliable_size = frame.reliable_size.as_u64();
+
+        let total_size = match self.state {
+            // Without any reliable data left to deliver this is equivalent to `RESET_STREAM`
+            ReceiveStreamState::Receiving(total_size)
+                if reliable_size > self.receive_buffer.consumed_len() =>
+            {
+                total_size
+            }
+            _ => {
+                self.init_reset(error, Some(frame.final_size), Some(frame.tag()))?;
+                self.stop_sending_sync.stop_sync();
+                self.wake(events);
+                return Ok(());
+            }
+        };
+
+        if let Some(total_size) = total_size {
+            if frame.final_size.as_u64() != total_size {
+                return Err(transport::Error::FINAL_SIZE_ERROR
+                    .with_reason("Final size in reset frame did not match previous final size")
+                    .with_frame_type(frame.tag().into()));
+            }
+        } else {
+            self.flow_controller
+                .acquire_window_up_to(frame.final_size, Some(frame.tag()))?;
+            self.state = ReceiveStreamState::Receiving(Some(frame.final_size.as_u64()));
+            // The final size is known so the peer doesn't need any more credits
+            self.flow_controller.stop_sync();
+        }
+
+        // The reliable size can only be reduced by subsequent frames
+        let reliable_size = self
+            .reliable_reset
+            .map_or(reliable_size, |(_, prev)| prev.min(reliable_size));
+        self.reliable_reset = Some((error, reliable_size));
+
+        Ok(())
+    }
+
     /// Starts the reset procedure if the Stream has not been in a RESET state
     /// before.
     fn init_reset(
@@ -836,6 +977,14 @@ impl ReceiveStream {
             ReceiveStreamState::Receiving(total_size) => total_size,
         };
 
+        if let Some(buffer) = request.buffer_mut() {
+            let target = core::mem::take(buffer);
+            if let Err(target) = self.receive_buffer.register_buffer(target) {
+                // the buffer couldn't be registered so give it back to the caller
+                *buffer = target;
+            }
+        }
+
         let low_watermark = &mut request.low_watermark;
         let high_watermark = &mut request.high_watermark;
         let mut should_wake = false;
@@ -887,6 +1036,22 @@ impl ReceiveStream {
             should_wake = true;
         }
 
+        // A reliable reset takes effect as soon as the reliable data has been read
+        if let Some((error, reliable_size)) = self.reliable_reset {
+            if self.receive_buffer.consumed_len() >= reliable_size {
+                self.reliable_reset = None;
+                let reset_result = self.init_reset(error, None, None);
+                // Resets without a final size never fail
+                debug_assert!(reset_result.is_ok());
+
+                if let ReceiveStreamState::Reset(error) = self.state {
+                    self.read_waiter = None;
+                    response.status = ops::Status::Reset(error);
+                    return Ok(response);
+                }
+            }
+        }
+
         // Check for the end of stream and transition to
         // [`ReceiveStreamState::DataRead`] if necessary.
         if let Some(total_size) = total_size {
@@ -12,7 +12,7 @@ use core::{convert::TryFrom, task::Poll};
 use s2n_quic_core::{
     application::Error as ApplicationErrorCode,
     connection, endpoint,
-    frame::{Frame, MaxData, MaxStreamData, ResetStream, StopSending},
+    frame::{Frame, MaxData, MaxStreamData, ResetStream, ResetStreamAt, StopSending},
     stream::{ops, StreamError, StreamType},
     transport::Error as TransportError,
     varint::VarInt,
@@ -612,6 +612,127 @@ fn reset_has_no_impact_if_all_data_had_been_received() {
     }
 }
 
+#[test]
+fn reliable_reset_delivers_data_before_the_reliable_size() {
+    let mut test_env = setup_receive_only_test_env();
+
+    let mut events = StreamEvents::new();
+    assert!(test_env
+        .stream
+        .on_data(
+            &stream_data(
+                test_env.stream.stream_id,
+                VarInt::from_u8(0),
+                &[0, 1],
+                false
+            ),
+            &mut events
+        )
+        .is_ok());
+
+    let reset_frame = ResetStreamAt {
+        stream_id: test_env.stream.stream_id.into(),
+        application_error_code: VarInt::from_u8(1),
+        final_size: VarInt::from_u8(10),
+        reliable_size: VarInt::from_u8(4),
+    };
+    assert!(test_env
+        .stream
+        .on_reset_at(&reset_frame, &mut events)
+        .is_ok());
+
+    // The data before the reliable size is still delivered
+    test_env.assert_receive_data(&[0, 1]);
+    test_env.assert_no_read_data();
+
+    assert!(test_env
+        .stream
+        .on_data(
+            &stream_data(
+                test_env.stream.stream_id,
+                VarInt::from_u8(2),
+                &[2, 3],
+                false
+            ),
+            &mut events
+        )
+        .is_ok());
+    events.wake_all();
+    test_env.assert_receive_data(&[2, 3]);
+
+    // The reset takes effect once the reliable data was read
+    test_env.assert_pop_error();
+}
+
+#[test]
+fn reliable_reset_is_immediate_if_reliable_data_was_consumed() {
+    let mut test_env = setup_receive_only_test_env();
+
+    let mut events = StreamEvents::new();
+    assert!(test_env
+        .stream
+        .on_data(
+            &stream_data(
+                test_env.stream.stream_id,
+                VarInt::from_u8(0),
+                &[0, 1],
+                false
+            ),
+            &mut events
+        )
+        .is_ok());
+    test_env.assert_receive_data(&[0, 1]);
+
+    let reset_frame = ResetStreamAt {
+        stream_id: test_env.stream.stream_id.into(),
+        application_error_code: VarInt::from_u8(1),
+        final_size: VarInt::from_u8(10),
+        reliable_size: VarInt::from_u8(2),
+    };
+    assert!(test_env
+        .stream
+        .on_reset_at(&reset_frame, &mut events)
+        .is_ok());
+    test_env.assert_pop_error();
+}
+
+#[test]
+fn reliable_reset_errors_on_invalid_sizes() {
+    // the reliable size may not exceed the final size
+    let mut test_env = setup_receive_only_test_env();
+    let reset_frame = ResetStreamAt {
+        stream_id: test_env.stream.stream_id.into(),
+        application_error_code: VarInt::from_u8(1),
+        final_size: VarInt::from_u8(4),
+        reliable_size: VarInt::from_u8(5),
+    };
+    let mut events = StreamEvents::new();
+    assert_is_transport_error(
+        test_env.stream.on_reset_at(&reset_frame, &mut events),
+        TransportError::FRAME_ENCODING_ERROR,
+    );
+
+    // the final size may not change
+    let mut test_env = setup_receive_only_test_env();
+    assert!(test_env
+        .stream
+        .on_data(
+            &stream_data(test_env.stream.stream_id, VarInt::from_u8(0), &[0, 1], true),
+            &mut events
+        )
+        .is_ok());
+    let reset_frame = ResetStreamAt {
+        stream_id: test_env.stream.stream_id.into(),
+        application_error_code: VarInt::from_u8(1),
+        final_size: VarInt::from_u8(4),
+        reliable_size: VarInt::from_u8(1),
+    };
+    assert_is_transport_error(
+        test_env.stream.on_reset_at(&reset_frame, &mut events),
+        TransportError::FINAL_SIZE_ERROR,
+    );
+}
+
 #[test]
 fn exceed_stream_flow_control_window() {
     let mut test_env = setup_receive_only_test_env();
@@ -775,6 +896,71 @@ fn receiving_data_will_lead_to_a_stream_flow_control_window_update() {
     );
 }
 
+#[test]
+fn memory_pressure_withholds_stream_flow_control_window_update() {
+    let mut test_env = setup_receive_only_test_env();
+
+    let old_window: u64 = test_env
+        .stream
+        .receive_stream
+        .flow_controller
+        .current_stream_receive_window()
+        .into();
+
+    test_env
+        .rx_connection_flow_controller
+        .set_memory_pressure(true);
+
+    // Fill and drain the flow control window while under memory pressure
+    test_env.feed_data(VarInt::from_u32(0), old_window as usize);
+    assert_eq!(old_window as usize, test_env.consume_all_data());
+
+    // No additional credit is granted to the peer
+    assert_eq!(
+        old_window,
+        Into::<u64>::into(
+            test_env
+                .stream
+                .receive_stream
+                .flow_controller
+                .current_stream_receive_window()
+        )
+    );
+    assert_eq!(
+        stream_interests(&[]),
+        test_env.stream.get_stream_interests()
+    );
+    test_env.assert_write_frames(0);
+
+    // The withheld credit is granted once the pressure is relieved
+    test_env
+        .rx_connection_flow_controller
+        .set_memory_pressure(false);
+    test_env.stream.on_memory_pressure_released();
+
+    let expected_window = old_window
+        + u64::from(
+            test_env
+                .stream
+                .receive_stream
+                .flow_controller
+                .desired_flow_control_window,
+        );
+    assert_eq!(
+        stream_interests(&["tx"]),
+        test_env.stream.get_stream_interests()
+    );
+    test_env.assert_write_frames(1);
+    let sent_frame = test_env.sent_frames.pop_front().expect("Frame is written");
+    assert_eq!(
+        Frame::MaxStreamData(MaxStreamData {
+            stream_id: test_env.stream.stream_id.into(),
+            maximum_stream_data: VarInt::new(expected_window).unwrap(),
+        }),
+        sent_frame.as_frame()
+    );
+}
+
 #[test]
 fn receiving_data_will_lead_to_a_connection_flow_control_window_update() {
     let test_env_config = conn_flow_control_test_env_config();
@@ -2548,3 +2734,159 @@ fn receiving_into_non_empty_buffers_returns_an_error() {
         "data should not be lost when returning an error"
     );
 }
+
+#[test]
+fn update_receive_window() {
+    let mut test_env = setup_receive_only_test_env();
+
+    let initial_window: u64 = test_env
+        .stream
+        .receive_stream
+        .flow_controller
+        .current_stream_receive_window()
+        .into();
+
+    // Growing the window advertises the additional credit immediately
+    let grown_window = initial_window * 2;
+    test_env.stream.update_receive_window(grown_window as u32);
+    assert_eq!(
+        stream_interests(&["tx"]),
+        test_env.stream.get_stream_interests()
+    );
+
+    test_env.assert_write_frames(1);
+    let mut sent_frame = test_env.sent_frames.pop_front().expect("Frame is written");
+    assert_eq!(
+        Frame::MaxStreamData(MaxStreamData {
+            stream_id: test_env.stream.stream_id.into(),
+            maximum_stream_data: VarInt::new(grown_window).unwrap(),
+        }),
+        sent_frame.as_frame()
+    );
+    test_env.ack_packet(sent_frame.packet_nr, ExpectWakeup(Some(false)));
+
+    // Shrinking the window doesn't revoke the advertised credit
+    let shrunk_window = initial_window / 4;
+    test_env.stream.update_receive_window(shrunk_window as u32);
+    assert_eq!(
+        stream_interests(&[]),
+        test_env.stream.get_stream_interests()
+    );
+    assert_eq!(
+        VarInt::new(grown_window).unwrap(),
+        test_env
+            .stream
+            .receive_stream
+            .flow_controller
+            .current_stream_receive_window()
+    );
+
+    // Consuming data only grants credit once it exceeds the advertised window
+    test_env.feed_data(VarInt::from_u8(0), initial_window as usize);
+    assert_eq!(initial_window as usize, test_env.consume_all_data());
+    assert_eq!(
+        stream_interests(&[]),
+        test_env.stream.get_stream_interests()
+    );
+
+    test_env.feed_data(
+        VarInt::new(initial_window).unwrap(),
+        initial_window as usize,
+    );
+    assert_eq!(initial_window as usize, test_env.consume_all_data());
+    assert_eq!(
+        stream_interests(&["tx"]),
+        test_env.stream.get_stream_interests()
+    );
+
+    test_env.assert_write_frames(1);
+    let mut sent_frame = test_env.sent_frames.pop_front().expect("Frame is written");
+    assert_eq!(
+        Frame::MaxStreamData(MaxStreamData {
+            stream_id: test_env.stream.stream_id.into(),
+            maximum_stream_data: VarInt::new(grown_window + shrunk_window).unwrap(),
+        }),
+        sent_frame.as_frame()
+    );
+}
+
+#[test]
+fn receive_into_registered_buffer() {
+    let mut test_env = setup_receive_only_test_env();
+
+    let mut buffer = bytes::BytesMut::with_capacity(1000);
+    let buffer_ptr = buffer.as_ptr();
+
+    assert!(test_env
+        .run_request(ops::Request::default().receive_into(&mut buffer), false)
+        .is_ok());
+    // The buffer is replaced with an empty one once it was registered
+    assert_eq!(0, buffer.capacity());
+
+    test_env.feed_data(VarInt::from_u8(0), 1500);
+
+    // Received chunks are views into the registered buffer
+    let chunk = match test_env.poll_pop() {
+        Poll::Ready(Ok(Some(chunk))) => chunk,
+        result => panic!("Unexpected read result {:?}", result),
+    };
+    assert_eq!(1000, chunk.len());
+    assert_eq!(buffer_ptr, chunk.as_ptr());
+
+    // The remaining data is stored in the internal buffers of the stream
+    assert_eq!(500, test_env.consume_all_data());
+
+    // Buffers can't be registered if data is already buffered at the current offset
+    test_env.feed_data(VarInt::from_u32(1500), 100);
+    let mut buffer = bytes::BytesMut::with_capacity(1000);
+    assert!(test_env
+        .run_request(ops::Request::default().receive_into(&mut buffer), false)
+        .is_ok());
+    assert_eq!(1000, buffer.capacity());
+    assert_eq!(100, test_env.consume_all_data());
+}
+
+#[test]
+fn offer_receive_buffer() {
+    let mut test_env = setup_receive_only_test_env();
+    let stream_id = test_env.stream.stream_id;
+
+    let offer = |test_env: &mut TestEnvironment, offset: u32, len: usize, buffer: BytesMut| {
+        let data = vec![0u8; len];
+        test_env.stream.offer_receive_buffer(
+            &stream_data(stream_id, VarInt::from_u32(offset), &data[..], false),
+            buffer,
+        )
+    };
+
+    // Buffers are only used if the frame is written at the current offset of the stream
+    assert!(offer(&mut test_env, 100, 100, BytesMut::with_capacity(1000)).is_err());
+    assert!(offer(&mut test_env, 0, 0, BytesMut::with_capacity(1000)).is_err());
+
+    let buffer = BytesMut::with_capacity(1000);
+    let buffer_ptr = buffer.as_ptr();
+    assert!(offer(&mut test_env, 0, 1500, buffer).is_ok());
+
+    // The stream already has a buffer for the current offset
+    assert!(offer(&mut test_env, 0, 1500, BytesMut::with_capacity(1000)).is_err());
+
+    test_env.feed_data(VarInt::from_u8(0), 1500);
+
+    // Received chunks are views into the offered buffer
+    let chunk = match test_env.poll_pop() {
+        Poll::Ready(Ok(Some(chunk))) => chunk,
+        result => panic!("Unexpected read result {:?}", result),
+    };
+    assert_eq!(1000, chunk.len());
+    assert_eq!(buffer_ptr, chunk.as_ptr());
+    assert_eq!(500, test_env.consume_all_data());
+
+    // Streams which no longer receive data don't use the buffer
+    assert!(test_env
+        .run_request(
+            ops::Request::default().stop_sending(ApplicationErrorCode::new(1).unwrap()),
+            false
+        )
+        .is_ok());
+    assert!(offer(&mut test_env, 1500, 100, BytesMut::with_capacity(1000)).is_err());
+}
@@ -0,0 +1,188 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use alloc::{rc::Rc, sync::Arc};
+use core::{
+    cell::RefCell,
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+/// The state of the send buffer budget of the endpoint, which is shared across all of
+/// its connections
+///
+/// Connections read the state directly, which allows the endpoint to block all of them
+/// without notifying each connection individually.
+#[derive(Clone, Debug, Default)]
+pub struct EndpointSendBuffer {
+    blocked: Arc<AtomicBool>,
+}
+
+impl EndpointSendBuffer {
+    /// Returns `true` if the send buffer budget of the endpoint is exhausted
+    pub fn is_blocked(&self) -> bool {
+        self.blocked.load(Ordering::Relaxed)
+    }
+
+    /// Blocks or unblocks buffering data on all connections of the endpoint
+    pub fn set_blocked(&self, blocked: bool) {
+        self.blocked.store(blocked, Ordering::Relaxed);
+    }
+}
+
+/// The actual implementation/state of the per Connection send buffer budget
+#[derive(Debug)]
+struct SendBufferBudgetImpl {
+    /// The maximum amount of data which can be buffered across all `Stream`s
+    limit: u64,
+    /// The amount of data which is currently buffered across all `Stream`s
+    buffered: u64,
+    /// The send buffer budget of the endpoint
+    endpoint: EndpointSendBuffer,
+}
+
+/// Limits the amount of data which can be buffered for sending across all
+/// `Stream`s of a connection.
+///
+/// Each `Stream` holds a [`SendBufferReservation`] for the data in its send
+/// buffer. Data is released from the budget once it has been acknowledged by
+/// the peer or the `Stream` was reset.
+#[derive(Clone, Debug)]
+pub struct SendBufferBudget {
+    inner: Rc<RefCell<SendBufferBudgetImpl>>,
+}
+
+impl SendBufferBudget {
+    /// Creates a new `SendBufferBudget` with the provided limit
+    pub fn new(limit: u64) -> Self {
+        Self {
+            inner: Rc::new(RefCell::new(SendBufferBudgetImpl {
+                limit,
+                buffered: 0,
+                endpoint: EndpointSendBuffer::default(),
+            })),
+        }
+    }
+
+    /// Returns the amount of data which can additionally be buffered
+    pub fn available(&self) -> u64 {
+        let inner = self.inner.borrow();
+        if inner.endpoint.is_blocked() {
+            return 0;
+        }
+        inner.limit.saturating_sub(inner.buffered)
+    }
+
+    /// Returns `true` if buffering data is blocked by the endpoint
+    pub fn is_blocked(&self) -> bool {
+        self.inner.borrow().endpoint.is_blocked()
+    }
+
+    /// Applies the send buffer budget of the endpoint, which blocks buffering any data
+    /// while it is exhausted, regardless of the connection limit
+    pub fn set_endpoint(&mut self, endpoint: &EndpointSendBuffer) {
+        let mut inner = self.inner.borrow_mut();
+        if !Arc::ptr_eq(&inner.endpoint.blocked, &endpoint.blocked) {
+            inner.endpoint = endpoint.clone();
+        }
+    }
+
+    /// Returns `true` if at least 1 byte can be buffered
+    pub fn has_capacity(&self) -> bool {
+        self.available() > 0
+    }
+
+    /// Returns the amount of data which is currently buffered
+    pub fn buffered(&self) -> u64 {
+        self.inner.borrow().buffered
+    }
+
+    /// Creates a new reservation which initially doesn't hold any data
+    pub fn reservation(&self) -> SendBufferReservation {
+        SendBufferReservation {
+            budget: self.clone(),
+            reserved: 0,
+        }
+    }
+}
+
+/// The part of a [`SendBufferBudget`] which is used by a single `Stream`
+#[derive(Debug)]
+pub struct SendBufferReservation {
+    budget: SendBufferBudget,
+    reserved: u64,
+}
+
+impl SendBufferReservation {
+    /// Returns the budget the reservation is part of
+    pub fn budget(&self) -> &SendBufferBudget {
+        &self.budget
+    }
+
+    /// Updates the reservation to the amount of data buffered by the `Stream`
+    pub fn update(&mut self, buffered: u64) {
+        if buffered == self.reserved {
+            return;
+        }
+
+        let mut inner = self.budget.inner.borrow_mut();
+        inner.buffered = inner.buffered - self.reserved + buffered;
+        self.reserved = buffered;
+    }
+}
+
+impl Drop for SendBufferReservation {
+    fn drop(&mut self) {
+        self.update(0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reservation_test() {
+        let budget = SendBufferBudget::new(100);
+        let mut a = budget.reservation();
+        let mut b = budget.reservation();
+
+        a.update(60);
+        assert_eq!(budget.available(), 40);
+
+        // the budget can be exceeded by a single push
+        b.update(50);
+        assert_eq!(budget.buffered(), 110);
+        assert_eq!(budget.available(), 0);
+        assert!(!budget.has_capacity());
+
+        a.update(10);
+        assert_eq!(budget.available(), 40);
+
+        // dropping the reservation releases all of its data
+        drop(b);
+        assert_eq!(budget.available(), 90);
+        assert!(budget.has_capacity());
+    }
+
+    #[test]
+    fn blocked_test() {
+        let mut budget = SendBufferBudget::new(100);
+        let mut a = budget.reservation();
+        a.update(10);
+
+        let endpoint = EndpointSendBuffer::default();
+        budget.set_endpoint(&endpoint);
+        assert!(!budget.is_blocked());
+
+        // the endpoint budget blocks the connection even if it has capacity left
+        endpoint.set_blocked(true);
+        assert!(budget.is_blocked());
+        assert_eq!(budget.available(), 0);
+        assert!(!budget.has_capacity());
+        assert_eq!(budget.buffered(), 10);
+
+        endpoint.set_blocked(false);
+        assert_eq!(budget.available(), 90);
+        assert!(budget.has_capacity());
+    }
+}
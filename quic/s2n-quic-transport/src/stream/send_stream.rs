@@ -5,6 +5,7 @@ use crate::{
     contexts::{OnTransmitError, WriteContext},
     stream::{
         outgoing_connection_flow_controller::OutgoingConnectionFlowController,
+        send_buffer_budget::{SendBufferBudget, SendBufferReservation},
         stream_events::StreamEvents,
         stream_interests::{StreamInterestProvider, StreamInterests},
         StreamError,
@@ -24,7 +25,7 @@ use core::{
 };
 use s2n_quic_core::{
     ack, application,
-    frame::{MaxStreamData, ResetStream, StopSending, StreamDataBlocked},
+    frame::{MaxStreamData, ResetStream, ResetStreamAt, StopSending, StreamDataBlocked},
     packet::number::PacketNumber,
     stream::{ops, StreamId},
     time::{timer, Timestamp},
@@ -95,6 +96,10 @@ pub struct OutgoingResetData {
     final_size: VarInt,
     /// The error code which should get transmitted in the RESET frame
     application_error_code: application::Error,
+    /// The amount of data which is still delivered to the peer
+    ///
+    /// If this is non-zero, a `RESET_STREAM_AT` frame is transmitted instead of `RESET_STREAM`.
+    reliable_size: VarInt,
 }
 
 /// Writes the `RESET` frames based on the streams flow control window.
@@ -108,6 +113,15 @@ impl ValueToFrameWriter<OutgoingResetData> for ResetStreamToFrameWriter {
         stream_id: StreamId,
         context: &mut W,
     ) -> Option<PacketNumber> {
+        if value.reliable_size > VarInt::from_u8(0) {
+            return context.write_frame(&ResetStreamAt {
+                stream_id: stream_id.into(),
+                application_error_code: value.application_error_code.into(),
+                final_size: value.final_size,
+                reliable_size: value.reliable_size,
+            });
+        }
+
         context.write_frame(&ResetStream {
             stream_id: stream_id.into(),
             application_error_code: value.application_error_code.into(),
@@ -132,6 +146,9 @@ enum ResetSource {
     /// The reset had been initiated as an internal reset. Likely caused by a
     /// connection error or termination.
     InternalReset,
+    /// The reset had been initiated because lost data passed its deadline
+    /// before it could be retransmitted.
+    DeadlineExpired,
 }
 
 impl ResetSource {
@@ -457,6 +474,11 @@ pub struct SendStream {
     final_state_observed: bool,
     /// Marks the stream as detached from the application
     detached: bool,
+    /// The error code of a reliable reset which is waiting for the reliable data to be
+    /// transmitted before the `RESET_STREAM_AT` frame can be sent
+    pending_reliable_reset: Option<application::Error>,
+    /// The part of the connection-wide send buffer budget which is used by this stream
+    buffer_reservation: SendBufferReservation,
 }
 
 impl SendStream {
@@ -465,6 +487,7 @@ impl SendStream {
         is_closed: bool,
         initial_window: VarInt,
         max_buffer_capacity: u32,
+        send_buffer_budget: SendBufferBudget,
     ) -> SendStream {
         // If the stream is created in closed state directly move into the
         // terminal state.
@@ -485,6 +508,8 @@ impl SendStream {
             write_waiter: None,
             final_state_observed: is_closed,
             detached: is_closed,
+            pending_reliable_reset: None,
+            buffer_reservation: send_buffer_budget.reservation(),
         };
 
         if is_closed {
@@ -509,6 +534,12 @@ impl SendStream {
         // The reason for this is that we allow users to enqueue more data than
         // the maximum flow control window.
 
+        if self.is_delivering_reliable_data() {
+            self.data_sender
+                .flow_controller_mut()
+                .set_max_stream_data(frame.maximum_stream_data);
+        }
+
         if let SendStreamState::Sending = self.state {
             self.data_sender
                 .flow_controller_mut()
@@ -524,7 +555,7 @@ impl SendStream {
             // the buffer space. It can only allow us to transmit again if we
             // were previously not able to do this.
             // Therefore me might want to remove this.
-            if self.data_sender.available_buffer_space() > 0
+            if self.available_buffer_space() > 0
                 && self.data_sender.state() == data_sender::State::Sending
             {
                 self.wake(events);
@@ -580,6 +611,7 @@ impl SendStream {
     /// This method gets called when a packet delivery got acknowledged
     pub fn on_packet_ack<A: ack::Set>(&mut self, ack_set: &A, events: &mut StreamEvents) {
         self.data_sender.on_packet_ack(ack_set);
+        self.update_buffer_reservation();
         self.data_sender
             .flow_controller_mut()
             .on_packet_ack(ack_set);
@@ -621,7 +653,15 @@ impl SendStream {
                 }
             }
             SendStreamState::ResetSent(error_code) => {
-                if self.reset_sync.on_packet_ack(ack_set).is_ready() {
+                // A reliable reset also needs all of the reliable data to be acknowledged
+                let is_data_acked = matches!(
+                    self.data_sender.state(),
+                    data_sender::State::Finished | data_sender::State::Cancelled(_)
+                );
+
+                let _ = self.reset_sync.on_packet_ack(ack_set);
+
+                if is_data_acked && self.reset_sync.is_delivered() {
                     // A reset had been acknowledged. Enter the terminal state.
                     self.state = SendStreamState::ResetAcknowledged(error_code);
 
@@ -652,6 +692,27 @@ impl SendStream {
         stream_id: StreamId,
         context: &mut W,
     ) -> Result<(), OnTransmitError> {
+        if let SendStreamState::Sending = self.state {
+            // Lost data which passed its deadline is not retransmitted. Since QUIC streams can't
+            // express gaps, the peer is notified by resetting the stream instead.
+            if let Some(error) = self.data_sender.expired_error(context.current_time()) {
+                // The application is notified once the reset has been acknowledged
+                let _ = self.init_reset(
+                    ResetSource::DeadlineExpired,
+                    StreamError::stream_reset(error),
+                );
+            }
+        }
+
+        if self.is_delivering_reliable_data() {
+            self.data_sender.on_transmit(stream_id.into(), context)?;
+            self.data_sender
+                .flow_controller_mut()
+                .on_transmit(stream_id, context)?;
+            self.try_request_reliable_reset();
+            return self.reset_sync.on_transmit(stream_id, context);
+        }
+
         self.reset_sync.on_transmit(stream_id, context)?;
         self.data_sender.on_transmit(stream_id.into(), context)?;
         self.data_sender
@@ -701,13 +762,23 @@ impl SendStream {
     pub fn on_connection_window_available(&mut self) {
         // Outstanding flow control requests are only fulfilled if the Stream
         // was still trying to send data.
-        if let SendStreamState::Sending = self.state {
+        if matches!(self.state, SendStreamState::Sending) || self.is_delivering_reliable_data() {
             self.data_sender
                 .flow_controller_mut()
                 .try_acquire_connection_window();
         }
     }
 
+    /// This method is called when the connection send buffer budget is available
+    pub fn on_send_buffer_budget_available(&mut self, events: &mut StreamEvents) {
+        if matches!(self.state, SendStreamState::Sending)
+            && self.data_sender.state() == data_sender::State::Sending
+            && self.can_push()
+        {
+            self.wake(events);
+        }
+    }
+
     /// Wakes up the application on progress updates
     ///
     /// If there is not a registered waker and the stream is in a terminal state,
@@ -774,10 +845,14 @@ impl SendStream {
 
         if let Some(error_code) = request.reset {
             // reset is a best effort operation so ignore the result
-            let _ = self.init_reset(
-                ResetSource::LocalApplication,
-                StreamError::stream_reset(error_code),
-            );
+            let _ = if request.reliable_size > 0 {
+                self.init_reliable_reset(error_code, request.reliable_size)
+            } else {
+                self.init_reset(
+                    ResetSource::LocalApplication,
+                    StreamError::stream_reset(error_code),
+                )
+            };
 
             // mark the stream as resetting
             response.status = ops::Status::Resetting;
@@ -832,8 +907,16 @@ impl SendStream {
                 response.bytes.consumed += chunk.len();
                 response.chunks.consumed += 1;
 
-                self.data_sender
-                    .push(core::mem::replace(chunk, Bytes::new()));
+                let chunk = core::mem::replace(chunk, Bytes::new());
+
+                if let Some(deadline) = request.deadline {
+                    self.data_sender
+                        .push_with_deadline(chunk, deadline.lifetime, deadline.error);
+                } else {
+                    self.data_sender.push(chunk);
+                }
+
+                self.update_buffer_reservation();
             }
         } else if !request.finish && !request.flush && context.is_some() {
             // if `chunks` are `None` or `Some(&[])` and we're not ending or flushing the stream,
@@ -884,7 +967,7 @@ impl SendStream {
         match self.data_sender.state() {
             data_sender::State::Sending => {
                 // inform the caller of the available space to send
-                response.bytes.available = self.data_sender.available_buffer_space();
+                response.bytes.available = self.available_buffer_space();
                 // assume chunks are 1 bytes
                 response.chunks.available = response.bytes.available;
             }
@@ -925,7 +1008,28 @@ impl SendStream {
     fn can_push(&self) -> bool {
         // We accept the data if there is at least 1 byte of space
         // available in the flow control window.
-        self.data_sender.available_buffer_space() > 0
+        self.available_buffer_space() > 0
+    }
+
+    /// Returns the amount of data that can be additionally buffered, which is limited by the
+    /// stream and the connection send buffer
+    fn available_buffer_space(&self) -> usize {
+        let budget = self.buffer_reservation.budget().available();
+        let budget = usize::try_from(budget).unwrap_or(usize::MAX);
+        self.data_sender.available_buffer_space().min(budget)
+    }
+
+    /// Returns true if the caller is blocked on the connection send buffer budget
+    fn is_blocked_on_send_buffer_budget(&self) -> bool {
+        self.write_waiter.is_some()
+            && self.data_sender.state() == data_sender::State::Sending
+            && !self.buffer_reservation.budget().has_capacity()
+    }
+
+    /// Releases the data which is no longer buffered from the connection send buffer budget
+    fn update_buffer_reservation(&mut self) {
+        self.buffer_reservation
+            .update(self.data_sender.buffered_len().as_u64());
     }
 
     /// Ensures a potential push operation would be valid
@@ -984,6 +1088,7 @@ impl SendStream {
         // Clear the send buffer. Since we initiated a RESET, there is no need
         // to send or resend the remaining data.
         self.data_sender.stop_sending(error);
+        self.update_buffer_reservation();
 
         // For an internal reset (which provides no error_code) we do not need
         // to transmit the reset frame
@@ -1003,6 +1108,7 @@ impl SendStream {
                         .data_sender
                         .flow_controller()
                         .acquired_connection_flow_controller_window(),
+                    reliable_size: VarInt::from_u8(0),
                 });
             }
             (false, _) => {
@@ -1015,6 +1121,68 @@ impl SendStream {
 
         InitResetResult::ResetInitiated
     }
+
+    /// Starts a reset which still delivers the first `reliable_size` bytes of the Stream to the
+    /// peer before the stream is reset with a `RESET_STREAM_AT` frame.
+    fn init_reliable_reset(
+        &mut self,
+        error_code: application::Error,
+        reliable_size: u64,
+    ) -> InitResetResult {
+        match self.state {
+            SendStreamState::ResetSent(_) | SendStreamState::ResetAcknowledged(_) => {
+                return InitResetResult::ResetNotNecessary
+            }
+            SendStreamState::Sending
+                if self.data_sender.state() == data_sender::State::Finished =>
+            {
+                return InitResetResult::ResetNotNecessary
+            }
+            SendStreamState::Sending => {}
+        }
+
+        self.state = SendStreamState::ResetSent(StreamError::stream_reset(error_code));
+        self.final_state_observed = true;
+
+        // Only the reliable data is delivered. If the application asked for more data than it
+        // enqueued, the reliable size is reduced to the length of the stream.
+        let reliable_size = VarInt::new(reliable_size).unwrap_or(VarInt::MAX);
+        self.data_sender.truncate(reliable_size);
+        self.update_buffer_reservation();
+
+        self.pending_reliable_reset = Some(error_code);
+        self.try_request_reliable_reset();
+
+        InitResetResult::ResetInitiated
+    }
+
+    /// Requests delivery of the `RESET_STREAM_AT` frame once all of the reliable data has been
+    /// transmitted
+    fn try_request_reliable_reset(&mut self) {
+        // The final size can't be lower than the amount of data that was transmitted. Waiting
+        // for all of the reliable data to be transmitted ensures the flow control window which
+        // was acquired for it is covered by the final size.
+        if !self.data_sender.is_transmitted() {
+            return;
+        }
+
+        if let Some(error_code) = self.pending_reliable_reset.take() {
+            self.reset_sync.request_delivery(OutgoingResetData {
+                application_error_code: error_code,
+                final_size: self
+                    .data_sender
+                    .flow_controller()
+                    .acquired_connection_flow_controller_window(),
+                reliable_size: self.data_sender.total_enqueued_len(),
+            });
+        }
+    }
+
+    /// Returns `true` if the stream was reset but still delivers reliable data to the peer
+    fn is_delivering_reliable_data(&self) -> bool {
+        matches!(self.state, SendStreamState::ResetSent(_))
+            && matches!(self.data_sender.state(), data_sender::State::Finishing(_))
+    }
 }
 
 impl timer::Provider for SendStream {
@@ -1044,6 +1212,15 @@ impl StreamInterestProvider for SendStream {
             //# A sender MUST NOT send a STREAM or
             //# STREAM_DATA_BLOCKED frame for a stream in the "Reset Sent" state or
             //# any terminal state -- that is, after sending a RESET_STREAM frame.
+            SendStreamState::ResetSent(_) if self.is_delivering_reliable_data() => interests
+                .with_transmission(|query| {
+                    self.data_sender.transmission_interest(query)?;
+                    self.data_sender
+                        .flow_controller()
+                        .transmission_interest(query)?;
+                    self.reset_sync.transmission_interest(query)?;
+                    Ok(())
+                }),
             SendStreamState::ResetSent(_) => {
                 interests.with_transmission(|query| self.reset_sync.transmission_interest(query))
             }
@@ -1072,6 +1249,8 @@ impl StreamInterestProvider for SendStream {
             _ => {}
         }
 
+        interests.send_buffer_budget = self.is_blocked_on_send_buffer_budget();
+
         interests.delivery_notifications |=
             self.data_sender.is_inflight() || self.reset_sync.is_inflight();
     }
@@ -13,7 +13,7 @@ use s2n_codec::DecoderBufferMut;
 use s2n_quic_core::{
     application::Error as ApplicationErrorCode,
     connection, endpoint,
-    frame::{Frame, MaxData, MaxStreamData, StopSending},
+    frame::{Frame, MaxData, MaxStreamData, ResetStreamAt, StopSending},
     packet::number::PacketNumber,
     stream::{ops, StreamType},
     transmission,
@@ -1333,6 +1333,112 @@ fn push_data_after_stream_is_reset_due_to_stop_sending() {
     }
 }
 
+#[test]
+fn lost_data_after_deadline_resets_the_stream() {
+    let mut test_env = setup_send_only_test_env();
+    let error_code = ApplicationErrorCode::new(7).unwrap();
+    let lifetime = Duration::from_millis(100);
+
+    let mut chunks = [Bytes::from(gen_pattern_test_data(VarInt::from_u32(0), 100))];
+    assert!(test_env
+        .stream
+        .poll_request(
+            ops::Request::default()
+                .send(&mut chunks)
+                .with_deadline(lifetime, error_code),
+            None
+        )
+        .is_ok());
+
+    // The deadline starts with the first transmission
+    test_env.current_time += lifetime;
+    test_env.assert_write_of(VarInt::from_u32(0), 100, false, false, pn(0));
+
+    // Data lost before the deadline is retransmitted
+    test_env.nack_packet(pn(0));
+    test_env.assert_write_of(VarInt::from_u32(0), 100, false, false, pn(1));
+
+    // Data lost after the deadline resets the stream instead
+    test_env.current_time += lifetime;
+    test_env.nack_packet(pn(1));
+    test_env.assert_write_reset_frame(error_code, pn(2), VarInt::from_u32(100));
+    assert_eq!(
+        stream_interests(&["ack"]),
+        test_env.stream.get_stream_interests()
+    );
+
+    test_env.ack_packet(pn(2), ExpectWakeup(Some(false)));
+    assert_matches!(
+        test_env.poll_push(Bytes::from_static(b"1")),
+        Poll::Ready(Err(StreamError::StreamReset { .. })),
+    );
+}
+
+#[test]
+fn reliable_reset_delivers_data_up_to_the_reliable_size() {
+    let mut test_env = setup_send_only_test_env();
+    let error_code = ApplicationErrorCode::new(3).unwrap();
+
+    let mut chunks = [Bytes::from(gen_pattern_test_data(VarInt::from_u32(0), 100))];
+    assert!(test_env
+        .stream
+        .poll_request(ops::Request::default().send(&mut chunks), None)
+        .is_ok());
+    assert!(test_env
+        .stream
+        .poll_request(ops::Request::default().reset_at(error_code, 40), None)
+        .is_ok());
+
+    let expected_reset = Frame::ResetStreamAt(ResetStreamAt {
+        stream_id: test_env.stream.stream_id.into(),
+        application_error_code: error_code.into(),
+        final_size: VarInt::from_u32(40),
+        reliable_size: VarInt::from_u32(40),
+    });
+
+    let assert_write_reliable_reset = |test_env: &mut TestEnvironment, data_pn, reset_pn| {
+        test_env.assert_write_frames(2);
+
+        let mut sent_frame = test_env.sent_frames.pop_front().unwrap();
+        assert_eq!(data_pn, sent_frame.packet_nr);
+        if let Frame::Stream(frame) = sent_frame.as_frame() {
+            assert_eq!(VarInt::from_u32(0), frame.offset);
+            assert_eq!(40, frame.data.len());
+            assert!(!frame.is_fin);
+        } else {
+            panic!("expected a Stream frame");
+        }
+
+        let mut sent_frame = test_env.sent_frames.pop_front().unwrap();
+        assert_eq!(reset_pn, sent_frame.packet_nr);
+        assert_eq!(expected_reset, sent_frame.as_frame());
+    };
+
+    // Only the reliable data is transmitted, followed by the RESET_STREAM_AT frame
+    assert_write_reliable_reset(&mut test_env, pn(0), pn(1));
+
+    // The reliable data is retransmitted if it's lost
+    test_env.nack_packet(pn(0));
+    test_env.nack_packet(pn(1));
+    assert_write_reliable_reset(&mut test_env, pn(2), pn(3));
+
+    // The reset is only complete once the reliable data is acknowledged as well
+    test_env.ack_packet(pn(3), ExpectWakeup(Some(false)));
+    assert_eq!(
+        stream_interests(&["ack"]),
+        test_env.stream.get_stream_interests()
+    );
+    test_env.ack_packet(pn(2), ExpectWakeup(Some(false)));
+    assert_eq!(
+        stream_interests(&["fin"]),
+        test_env.stream.get_stream_interests()
+    );
+    assert_matches!(
+        test_env.poll_push(Bytes::from_static(b"1")),
+        Poll::Ready(Err(StreamError::StreamReset { .. })),
+    );
+}
+
 #[test]
 fn writes_finish_packet_in_dedicated_packet() {
     let test_configs = &[
@@ -2854,3 +2960,75 @@ fn can_query_stream_readiness() {
         }
     }
 }
+
+#[test]
+fn connection_send_buffer_budget_blocks_streams() {
+    let test_env_config = TestEnvironmentConfig {
+        max_send_buffer_size: 1500,
+        max_connection_send_buffer_size: 1000,
+        stream_id: StreamId::initial(endpoint::Type::Client, StreamType::Unidirectional),
+        local_endpoint_type: endpoint::Type::Client,
+        ..Default::default()
+    };
+    let mut test_env = setup_stream_test_env_with_config(test_env_config);
+
+    // another stream on the connection uses most of the budget
+    let mut other_stream = test_env.send_buffer_budget.reservation();
+    other_stream.update(800);
+
+    let response = test_env
+        .run_request(ops::Request::default().send(&mut []), true)
+        .unwrap();
+    assert_eq!(200, response.tx().unwrap().bytes.available);
+
+    // the budget can be exceeded by a single chunk, as with the stream buffer
+    assert_eq!(
+        test_env.poll_push(Bytes::from(gen_pattern_test_data(VarInt::from_u8(0), 500))),
+        Poll::Ready(Ok(()))
+    );
+    assert_eq!(1300, test_env.send_buffer_budget.buffered());
+    assert_eq!(
+        test_env.poll_push(Bytes::from(gen_pattern_test_data(VarInt::from_u32(500), 1))),
+        Poll::Pending
+    );
+    assert_eq!(
+        stream_interests(&["tx", "budget"]),
+        test_env.stream.get_stream_interests()
+    );
+
+    // releasing data in another stream wakes up the blocked stream
+    other_stream.update(0);
+    let mut events = StreamEvents::new();
+    test_env.stream.on_send_buffer_budget_available(&mut events);
+    assert_eq!(1, events.waker_count());
+    events.wake_all();
+    assert_eq!(
+        stream_interests(&["tx"]),
+        test_env.stream.get_stream_interests()
+    );
+
+    // acknowledged data is released from the budget
+    test_env.assert_write_frames(1);
+    assert_eq!(
+        test_env.poll_push(Bytes::from(gen_pattern_test_data(
+            VarInt::from_u32(500),
+            600
+        ))),
+        Poll::Ready(Ok(()))
+    );
+    assert_eq!(
+        test_env.poll_push(Bytes::from(gen_pattern_test_data(
+            VarInt::from_u32(1100),
+            1
+        ))),
+        Poll::Pending
+    );
+    test_env.ack_packet(pn(0), ExpectWakeup(Some(true)));
+    assert_eq!(600, test_env.send_buffer_budget.buffered());
+
+    // resetting the stream releases all of its data
+    test_env
+        .reset(ApplicationErrorCode::new(1).unwrap())
+        .unwrap();
+    assert_eq!(0, test_env.send_buffer_budget.buffered());
+}
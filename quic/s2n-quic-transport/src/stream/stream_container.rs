@@ -53,6 +53,12 @@ intrusive_adapter!(WaitingForStreamFlowControlCreditsAdapter<S> = Rc<StreamNode<
     waiting_for_stream_flow_control_credits_link: LinkedListLink
 });
 
+// Intrusive list adapter for managing the list of
+// `waiting_for_send_buffer_budget` streams
+intrusive_adapter!(WaitingForSendBufferBudgetAdapter<S> = Rc<StreamNode<S>>: StreamNode<S> {
+    waiting_for_send_buffer_budget_link: LinkedListLink
+});
+
 // Intrusive red black tree adapter for managing all streams in a tree for
 // lookup by Stream ID
 intrusive_adapter!(StreamTreeAdapter<S> = Rc<StreamNode<S>>: StreamNode<S> {
@@ -79,6 +85,8 @@ struct StreamNode<S> {
     waiting_for_connection_flow_control_credits_link: LinkedListLink,
     /// Allows the Stream to be part of the `waiting_for_stream_flow_control_credits` collection
     waiting_for_stream_flow_control_credits_link: LinkedListLink,
+    /// Allows the Stream to be part of the `waiting_for_send_buffer_budget` collection
+    waiting_for_send_buffer_budget_link: LinkedListLink,
 }
 
 impl<S> StreamNode<S> {
@@ -93,6 +101,7 @@ impl<S> StreamNode<S> {
             waiting_for_retransmission_link: LinkedListLink::new(),
             waiting_for_connection_flow_control_credits_link: LinkedListLink::new(),
             waiting_for_stream_flow_control_credits_link: LinkedListLink::new(),
+            waiting_for_send_buffer_budget_link: LinkedListLink::new(),
         }
     }
 }
@@ -145,6 +154,9 @@ struct InterestLists<S> {
     /// stream flow control window to increase
     waiting_for_stream_flow_control_credits:
         LinkedList<WaitingForStreamFlowControlCreditsAdapter<S>>,
+    /// Streams which are blocked on buffering data due to waiting on the
+    /// connection send buffer budget to become available
+    waiting_for_send_buffer_budget: LinkedList<WaitingForSendBufferBudgetAdapter<S>>,
 }
 
 impl<S: StreamTrait> InterestLists<S> {
@@ -160,6 +172,9 @@ impl<S: StreamTrait> InterestLists<S> {
             waiting_for_stream_flow_control_credits: LinkedList::new(
                 WaitingForStreamFlowControlCreditsAdapter::new(),
             ),
+            waiting_for_send_buffer_budget: LinkedList::new(
+                WaitingForSendBufferBudgetAdapter::new(),
+            ),
         }
     }
 
@@ -226,6 +241,11 @@ impl<S: StreamTrait> InterestLists<S> {
             waiting_for_stream_flow_control_credits_link,
             waiting_for_stream_flow_control_credits
         );
+        sync_interests!(
+            interests.send_buffer_budget,
+            waiting_for_send_buffer_budget_link,
+            waiting_for_send_buffer_budget
+        );
 
         if !interests.retained != node.done_streams_link.is_linked() {
             if !interests.retained {
@@ -469,6 +489,10 @@ impl<S: StreamTrait> StreamContainer<S> {
                 waiting_for_stream_flow_control_credits,
                 waiting_for_stream_flow_control_credits_link
             );
+            remove_stream_from_list!(
+                waiting_for_send_buffer_budget,
+                waiting_for_send_buffer_budget_link
+            );
 
             controller.on_close_stream(stream.inner.borrow().stream_id());
         }
@@ -537,6 +561,27 @@ impl<S: StreamTrait> StreamContainer<S> {
         );
     }
 
+    /// Iterates over all `Stream`s which are waiting for the send buffer
+    /// budget, and executes the given function on each `Stream`
+    ///
+    /// The `stream::Controller` will be notified of streams that have been
+    /// closed to allow for further streams to be opened.
+    pub fn iterate_send_buffer_budget_list<F>(
+        &mut self,
+        controller: &mut stream::Controller,
+        mut func: F,
+    ) where
+        F: FnMut(&mut S),
+    {
+        iterate_uninterruptible!(
+            self,
+            waiting_for_send_buffer_budget,
+            waiting_for_send_buffer_budget_link,
+            controller,
+            func
+        );
+    }
+
     /// Iterates over all `Stream`s which are waiting for transmission,
     /// and executes the given function on each `Stream`
     ///
@@ -620,6 +665,14 @@ impl<S: StreamTrait> StreamContainer<S> {
         !self.interest_lists.waiting_for_transmission.is_empty()
             || !self.interest_lists.waiting_for_retransmission.is_empty()
     }
+
+    /// Returns whether or not streams are waiting for the send buffer budget
+    pub fn has_send_buffer_budget_interest(&self) -> bool {
+        !self
+            .interest_lists
+            .waiting_for_send_buffer_budget
+            .is_empty()
+    }
 }
 
 impl<S: StreamTrait> timer::Provider for StreamContainer<S> {
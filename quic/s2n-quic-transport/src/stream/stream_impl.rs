@@ -7,16 +7,21 @@ use crate::{
         incoming_connection_flow_controller::IncomingConnectionFlowController,
         outgoing_connection_flow_controller::OutgoingConnectionFlowController,
         receive_stream::ReceiveStream,
+        send_buffer_budget::SendBufferBudget,
         send_stream::SendStream,
         stream_events::StreamEvents,
         stream_interests::{StreamInterestProvider, StreamInterests},
         StreamError,
     },
 };
+use bytes::BytesMut;
 use core::{task::Context, time::Duration};
 use s2n_quic_core::{
     ack, endpoint,
-    frame::{stream::StreamRef, MaxStreamData, ResetStream, StopSending, StreamDataBlocked},
+    frame::{
+        stream::StreamRef, MaxStreamData, ResetStream, ResetStreamAt, StopSending,
+        StreamDataBlocked,
+    },
     stream::{ops, StreamId},
     time::{timer, Timestamp},
     transport,
@@ -42,6 +47,8 @@ pub struct StreamConfig {
     pub initial_send_window: VarInt,
     /// The maximum buffered amount of data on the sending side
     pub max_send_buffer_size: u32,
+    /// The connection-wide budget for buffered data on the sending side
+    pub send_buffer_budget: SendBufferBudget,
 }
 
 /// A trait which represents an internally used `Stream`
@@ -54,6 +61,12 @@ pub trait StreamTrait: StreamInterestProvider + timer::Provider + core::fmt::Deb
 
     // These functions are called from the packet delivery thread
 
+    /// Offers a connection-level receive buffer to the stream before the
+    /// `STREAM_DATA` frame is received. If the stream doesn't use the buffer it
+    /// is returned.
+    fn offer_receive_buffer(&mut self, frame: &StreamRef, buffer: BytesMut)
+        -> Result<(), BytesMut>;
+
     /// This is called when a `STREAM_DATA` frame had been received for
     /// this stream
     fn on_data(
@@ -78,6 +91,14 @@ pub trait StreamTrait: StreamInterestProvider + timer::Provider + core::fmt::Deb
         events: &mut StreamEvents,
     ) -> Result<(), transport::Error>;
 
+    /// This is called when a `RESET_STREAM_AT` frame had been received for
+    /// this stream
+    fn on_reset_at(
+        &mut self,
+        frame: &ResetStreamAt,
+        events: &mut StreamEvents,
+    ) -> Result<(), transport::Error>;
+
     /// This is called when a `MAX_STREAM_DATA` frame had been received for
     /// this stream
     fn on_max_stream_data(
@@ -104,6 +125,13 @@ pub trait StreamTrait: StreamInterestProvider + timer::Provider + core::fmt::Deb
     /// if the application is blocked by peer limits.
     fn update_blocked_sync_period(&mut self, blocked_sync_period: Duration);
 
+    /// Updates the flow control window which is maintained for receiving data
+    fn update_receive_window(&mut self, desired_flow_control_window: u32);
+
+    /// Grants the receive window which was withheld while the endpoint was under
+    /// memory pressure
+    fn on_memory_pressure_released(&mut self);
+
     /// Called when the connection timer expires
     fn on_timeout(&mut self, now: Timestamp);
 
@@ -122,6 +150,9 @@ pub trait StreamTrait: StreamInterestProvider + timer::Provider + core::fmt::Deb
     /// This method is called when a connection window is available
     fn on_connection_window_available(&mut self);
 
+    /// This method is called when the connection send buffer budget is available
+    fn on_send_buffer_budget_available(&mut self, events: &mut StreamEvents);
+
     // These functions are called from the client API
 
     fn poll_request(
@@ -211,6 +242,7 @@ impl StreamTrait for StreamImpl {
                 send_is_closed,
                 config.initial_send_window,
                 config.max_send_buffer_size,
+                config.send_buffer_budget,
             ),
         }
     }
@@ -222,6 +254,15 @@ impl StreamTrait for StreamImpl {
 
     // These functions are called from the packet delivery thread
 
+    #[inline]
+    fn offer_receive_buffer(
+        &mut self,
+        frame: &StreamRef,
+        buffer: BytesMut,
+    ) -> Result<(), BytesMut> {
+        self.receive_stream.offer_receive_buffer(frame, buffer)
+    }
+
     #[inline]
     fn on_data(
         &mut self,
@@ -249,6 +290,15 @@ impl StreamTrait for StreamImpl {
         self.receive_stream.on_reset(frame, events)
     }
 
+    #[inline]
+    fn on_reset_at(
+        &mut self,
+        frame: &ResetStreamAt,
+        events: &mut StreamEvents,
+    ) -> Result<(), transport::Error> {
+        self.receive_stream.on_reset_at(frame, events)
+    }
+
     #[inline]
     fn on_max_stream_data(
         &mut self,
@@ -294,6 +344,17 @@ impl StreamTrait for StreamImpl {
             .update_blocked_sync_period(blocked_sync_period);
     }
 
+    #[inline]
+    fn update_receive_window(&mut self, desired_flow_control_window: u32) {
+        self.receive_stream
+            .update_receive_window(desired_flow_control_window);
+    }
+
+    #[inline]
+    fn on_memory_pressure_released(&mut self) {
+        self.receive_stream.on_memory_pressure_released();
+    }
+
     #[inline]
     fn on_timeout(&mut self, now: Timestamp) {
         self.send_stream.on_timeout(now)
@@ -327,6 +388,11 @@ impl StreamTrait for StreamImpl {
         self.send_stream.on_connection_window_available()
     }
 
+    #[inline]
+    fn on_send_buffer_budget_available(&mut self, events: &mut StreamEvents) {
+        self.send_stream.on_send_buffer_budget_available(events)
+    }
+
     // These functions are called from the client API
 
     fn poll_request(
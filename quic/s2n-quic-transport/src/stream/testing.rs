@@ -6,8 +6,8 @@ use crate::{
     stream::{
         incoming_connection_flow_controller::IncomingConnectionFlowController,
         outgoing_connection_flow_controller::OutgoingConnectionFlowController,
-        stream_impl::StreamConfig, stream_interests::StreamInterests, StreamEvents, StreamImpl,
-        StreamTrait,
+        send_buffer_budget::SendBufferBudget, stream_impl::StreamConfig,
+        stream_interests::StreamInterests, StreamEvents, StreamImpl, StreamTrait,
     },
     transmission,
 };
@@ -117,6 +117,7 @@ pub fn pn(nr: usize) -> PacketNumber {
 /// - tx => transmission::Interest::NewData
 /// - fin => finalization
 /// - cf => connection_flow_control_credits
+/// - budget => send_buffer_budget
 pub fn stream_interests(interests: &[&str]) -> StreamInterests {
     let mut result = StreamInterests {
         retained: true,
@@ -130,6 +131,7 @@ pub fn stream_interests(interests: &[&str]) -> StreamInterests {
             "fin" => result.retained = false,
             "cf" => result.connection_flow_control_credits = true,
             "sf" => result.stream_flow_control_credits = true,
+            "budget" => result.send_buffer_budget = true,
             other => unreachable!("Unsupported interest {}", other),
         }
     }
@@ -159,6 +161,7 @@ pub struct TestEnvironment {
     pub stream: StreamImpl,
     pub rx_connection_flow_controller: IncomingConnectionFlowController,
     pub tx_connection_flow_controller: OutgoingConnectionFlowController,
+    pub send_buffer_budget: SendBufferBudget,
     pub wake_counter: AwokenCount,
     pub waker: Waker,
     pub current_time: Timestamp,
@@ -484,6 +487,7 @@ pub struct TestEnvironmentConfig {
     pub initial_connection_receive_window_size: u64,
     pub desired_connection_flow_control_window: u32,
     pub max_send_buffer_size: usize,
+    pub max_connection_send_buffer_size: u64,
     pub transmission_constraint: transmission::Constraint,
     pub local_endpoint_type: endpoint::Type,
     pub max_packet_size: Option<usize>,
@@ -513,6 +517,7 @@ impl TestEnvironmentConfig {
             desired_connection_flow_control_window:
                 TestEnvironment::DEFAULT_INITIAL_CONNECTION_RECEIVE_WINDOW as u32,
             max_send_buffer_size: TestEnvironment::DEFAULT_MAX_SEND_BUFFER_SIZE,
+            max_connection_send_buffer_size: u64::MAX,
             transmission_constraint: transmission::Constraint::None,
             max_packet_size: None,
         }
@@ -536,6 +541,8 @@ pub fn setup_stream_test_env_with_config(config: TestEnvironmentConfig) -> TestE
         VarInt::new(config.initial_connection_send_window_size).unwrap(),
     );
 
+    let send_buffer_budget = SendBufferBudget::new(config.max_connection_send_buffer_size);
+
     let stream = StreamImpl::new(StreamConfig {
         incoming_connection_flow_controller: rx_connection_flow_controller.clone(),
         outgoing_connection_flow_controller: tx_connection_flow_controller.clone(),
@@ -545,6 +552,7 @@ pub fn setup_stream_test_env_with_config(config: TestEnvironmentConfig) -> TestE
         desired_flow_control_window: config.desired_flow_control_window,
         initial_send_window: VarInt::new(config.initial_send_window).unwrap(),
         max_send_buffer_size: config.max_send_buffer_size as u32,
+        send_buffer_budget: send_buffer_budget.clone(),
     });
 
     let (waker, wake_counter) = new_count_waker();
@@ -558,6 +566,7 @@ pub fn setup_stream_test_env_with_config(config: TestEnvironmentConfig) -> TestE
         stream,
         rx_connection_flow_controller,
         tx_connection_flow_controller,
+        send_buffer_budget,
         wake_counter,
         waker,
         current_time: s2n_quic_platform::time::now(),
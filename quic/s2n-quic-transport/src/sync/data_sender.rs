@@ -6,10 +6,14 @@ use crate::{
     transmission,
 };
 use bytes::Bytes;
-use core::convert::TryInto;
-use s2n_quic_core::{ack, interval_set::IntervalSet, packet::number::PacketNumber, varint::VarInt};
+use core::{convert::TryInto, time::Duration};
+use s2n_quic_core::{
+    ack, application, interval_set::IntervalSet, packet::number::PacketNumber, time::Timestamp,
+    varint::VarInt,
+};
 
 mod buffer;
+mod deadlines;
 mod traits;
 mod transmissions;
 pub mod writer;
@@ -116,6 +120,8 @@ pub struct DataSender<FlowController, ChunkToFrameWriter> {
     pending: IntervalSet<VarInt>,
     /// All of the intervals that have been declared lost
     lost: IntervalSet<VarInt>,
+    /// Ranges of the buffer which should no longer be retransmitted after a deadline
+    deadlines: deadlines::Deadlines,
     /// The maximum amount of bytes that are buffered within the sending stream.
     /// This capacity will not be exceeded - even if the remote provides us a
     /// bigger flow control window.
@@ -142,6 +148,7 @@ impl<FlowController: OutgoingDataFlowController, Writer: FrameWriter>
             transmission_offset: VarInt::from_u32(0),
             pending: IntervalSet::new(),
             lost: IntervalSet::new(),
+            deadlines: Default::default(),
             max_buffer_capacity: VarInt::from_u32(max_buffer_capacity),
             state: State::Sending,
         }
@@ -185,17 +192,63 @@ impl<FlowController: OutgoingDataFlowController, Writer: FrameWriter>
         self.buffer.clear();
         self.pending.clear();
         self.lost.clear();
+        self.deadlines.clear();
         self.transmissions.finish();
         self.transmission_offset = VarInt::from_u8(0);
         self.check_integrity();
     }
 
+    /// Stops sending any data at or beyond the provided offset.
+    ///
+    /// Data before the offset continues to be transmitted and retransmitted until it has been
+    /// acknowledged, at which point the sender enters the `Finished` state. No `FIN` is sent for
+    /// a truncated stream and no new data can be enqueued.
+    pub fn truncate(&mut self, offset: VarInt) {
+        if matches!(self.state, State::Finished | State::Cancelled(_)) {
+            return;
+        }
+
+        self.buffer.truncate(offset);
+        let total_len = self.buffer.total_len();
+
+        self.pending
+            .remove(total_len..=VarInt::MAX)
+            .expect("pending should not have a limit");
+        self.lost
+            .remove(total_len..=VarInt::MAX)
+            .expect("lost has no interval limit");
+        self.transmission_offset = self.transmission_offset.min(total_len);
+
+        if self.pending.is_empty() {
+            // Everything before the offset had already been acknowledged
+            self.state = State::Finished;
+            self.deadlines.clear();
+            self.transmissions.finish();
+            self.buffer.release_all();
+        } else {
+            // The final size is communicated separately so there's no FIN to wait for
+            self.state = State::Finishing(FinState::Acknowledged);
+        }
+
+        self.check_integrity();
+    }
+
+    /// Returns `true` if all of the enqueued data has been transmitted at least once
+    pub fn is_transmitted(&self) -> bool {
+        self.transmission_offset == self.buffer.total_len()
+    }
+
     /// Returns the amount of bytes that have ever been enqueued for writing on
     /// this Stream. This equals the offset of the highest enqueued byte + 1.
     pub fn total_enqueued_len(&self) -> VarInt {
         self.buffer.total_len()
     }
 
+    /// Returns the amount of data which is held in the buffer until it is acknowledged
+    pub fn buffered_len(&self) -> VarInt {
+        self.buffer.enqueued_len()
+    }
+
     /// Returns true if the data sender doesn't have any data enqueued for sending
     pub fn is_empty(&self) -> bool {
         self.buffer.is_empty()
@@ -245,6 +298,24 @@ impl<FlowController: OutgoingDataFlowController, Writer: FrameWriter>
     /// inside this method. The already enqueued bytes can be retrieved by
     /// calling [`total_enqueued_len()`].
     pub fn push(&mut self, data: Bytes) {
+        self.push_impl(data, None);
+    }
+
+    /// Enqueues the data for transmission with a deadline.
+    ///
+    /// The `lifetime` starts once the data is transmitted for the first time. If any of the data
+    /// is lost after the lifetime has elapsed, it will no longer be retransmitted and
+    /// [`Self::expired_error`] will return the provided `error`.
+    pub fn push_with_deadline(
+        &mut self,
+        data: Bytes,
+        lifetime: Duration,
+        error: application::Error,
+    ) {
+        self.push_impl(data, Some((lifetime, error)));
+    }
+
+    fn push_impl(&mut self, data: Bytes, deadline: Option<(Duration, application::Error)>) {
         //= https://www.rfc-editor.org/rfc/rfc9000#section-4.5
         //# An endpoint MUST NOT send data on a stream at or beyond the final
         //# size.
@@ -261,10 +332,16 @@ impl<FlowController: OutgoingDataFlowController, Writer: FrameWriter>
             return;
         }
 
+        let range = self.buffer.push(data);
+
         self.pending
-            .insert(self.buffer.push(data))
+            .insert(range)
             .expect("pending should not have a limit");
 
+        if let Some((lifetime, error)) = deadline {
+            self.deadlines.push(range, lifetime, error);
+        }
+
         self.check_integrity();
     }
 
@@ -309,9 +386,11 @@ impl<FlowController: OutgoingDataFlowController, Writer: FrameWriter>
                 .expect("lost has no interval limit");
             if let Some(first) = self.pending.min_value() {
                 self.buffer.release(first);
+                self.deadlines.release(first);
             } else {
                 // the pending list was completely cleared
                 self.buffer.release_all();
+                self.deadlines.clear();
                 // We don't need to track transmissions for already acked ranges
                 self.transmissions.clear();
             }
@@ -360,6 +439,14 @@ impl<FlowController: OutgoingDataFlowController, Writer: FrameWriter>
         self.check_integrity();
     }
 
+    /// Returns the error of any lost data which has passed its deadline
+    ///
+    /// Expired data is never retransmitted. Callers are expected to stop sending once this
+    /// returns an error, since the peer would otherwise wait for the missing data forever.
+    pub fn expired_error(&self, now: Timestamp) -> Option<application::Error> {
+        self.deadlines.expired_error(&self.lost, now)
+    }
+
     /// Queries the component for any outgoing frames that need to get sent
     #[inline]
     pub fn on_transmit<W: WriteContext>(
@@ -384,8 +471,8 @@ impl<FlowController: OutgoingDataFlowController, Writer: FrameWriter>
         let constraint = context.transmission_constraint();
 
         let mut transmitted_lost = false;
-        // try to retransmit any lost ranges first
-        if constraint.can_retransmit() {
+        // try to retransmit any lost ranges first, unless they have passed their deadline
+        if constraint.can_retransmit() && self.expired_error(context.current_time()).is_none() {
             transmitted_lost = self.transmissions.transmit_set(
                 &self.buffer,
                 &mut self.lost,
@@ -416,6 +503,11 @@ impl<FlowController: OutgoingDataFlowController, Writer: FrameWriter>
                 .end_exclusive();
         }
 
+        if self.transmission_offset > starting_transmission_offset && !self.deadlines.is_empty() {
+            self.deadlines
+                .on_transmit(self.transmission_offset, context.current_time());
+        }
+
         if Writer::WRITES_FIN && self.state.can_transmit_fin(constraint, is_blocked) {
             self.transmissions.transmit_fin(
                 &self.buffer,
@@ -126,6 +126,39 @@ impl Buffer {
         self.check_integrity();
     }
 
+    /// Removes all of the data at or beyond the provided offset from the buffer
+    ///
+    /// If the offset is before the head of the buffer, all of the enqueued chunks are removed.
+    pub fn truncate(&mut self, len: VarInt) {
+        if len >= self.total_len() {
+            return;
+        }
+
+        let mut excess = self.total_len() - len.max(self.head);
+
+        while excess > VarInt::from_u8(0) {
+            let chunk = self
+                .chunks
+                .back_mut()
+                .expect("excess data should be held in a chunk");
+            let chunk_len = VarInt::try_from(chunk.len()).unwrap();
+
+            if chunk_len <= excess {
+                self.chunks.pop_back();
+                self.pending_len -= chunk_len;
+                excess -= chunk_len;
+            } else {
+                chunk
+                    .data
+                    .truncate((chunk_len - excess).try_into().unwrap());
+                self.pending_len -= excess;
+                excess = VarInt::from_u8(0);
+            }
+        }
+
+        self.check_integrity();
+    }
+
     /// Releases all of the currently enqueued chunks
     pub fn release_all(&mut self) {
         self.chunks.clear();
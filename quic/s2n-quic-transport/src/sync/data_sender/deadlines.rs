@@ -0,0 +1,174 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use alloc::collections::VecDeque;
+use core::time::Duration;
+use s2n_quic_core::{
+    application,
+    interval_set::{Interval, IntervalSet},
+    time::Timestamp,
+    varint::VarInt,
+};
+
+/// Tracks ranges of enqueued data which are only useful to the peer for a limited time
+#[derive(Debug, Default)]
+pub struct Deadlines {
+    /// The tracked ranges, ordered by their offset in the stream
+    entries: VecDeque<Entry>,
+}
+
+#[derive(Debug)]
+struct Entry {
+    range: Interval<VarInt>,
+    lifetime: Duration,
+    error: application::Error,
+    /// The time at which the range expires. This is set on the first transmission of the range.
+    expiration: Option<Timestamp>,
+}
+
+impl Deadlines {
+    /// Tracks a newly enqueued range of data
+    pub fn push(&mut self, range: Interval<VarInt>, lifetime: Duration, error: application::Error) {
+        debug_assert!(
+            self.entries
+                .back()
+                .map_or(true, |prev| prev.range.end_exclusive()
+                    <= range.start_inclusive()),
+            "ranges should be pushed in order"
+        );
+
+        self.entries.push_back(Entry {
+            range,
+            lifetime,
+            error,
+            expiration: None,
+        });
+    }
+
+    /// Starts the lifetime of all ranges which were transmitted for the first time
+    ///
+    /// `transmission_offset` is the offset up to which data has been transmitted at least once.
+    pub fn on_transmit(&mut self, transmission_offset: VarInt, now: Timestamp) {
+        for entry in self
+            .entries
+            .iter_mut()
+            .skip_while(|entry| entry.expiration.is_some())
+            .take_while(|entry| entry.range.start_inclusive() < transmission_offset)
+        {
+            entry.expiration = Some(now + entry.lifetime);
+        }
+    }
+
+    /// Removes all ranges which end before the provided offset
+    ///
+    /// This should be called when data is acknowledged and released from the buffer.
+    pub fn release(&mut self, up_to: VarInt) {
+        while self
+            .entries
+            .front()
+            .map_or(false, |entry| entry.range.end_exclusive() <= up_to)
+        {
+            self.entries.pop_front();
+        }
+    }
+
+    /// Removes all tracked ranges
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    /// Returns `true` if no ranges are tracked
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns the error of the first expired range which overlaps with the `lost` set
+    pub fn expired_error(
+        &self,
+        lost: &IntervalSet<VarInt>,
+        now: Timestamp,
+    ) -> Option<application::Error> {
+        if lost.is_empty() {
+            return None;
+        }
+
+        self.entries
+            .iter()
+            .take_while(|entry| entry.expiration.is_some())
+            .filter(|entry| {
+                entry
+                    .expiration
+                    .map_or(false, |expiration| expiration <= now)
+            })
+            .find(|entry| {
+                lost.intervals().any(|interval| {
+                    interval.start_inclusive() < entry.range.end_exclusive()
+                        && entry.range.start_inclusive() < interval.end_exclusive()
+                })
+            })
+            .map(|entry| entry.error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use s2n_quic_core::time::{Clock, NoopClock};
+
+    fn range(start: u32, end: u32) -> Interval<VarInt> {
+        (VarInt::from_u32(start)..VarInt::from_u32(end)).into()
+    }
+
+    fn lost(start: u32, end: u32) -> IntervalSet<VarInt> {
+        let mut set = IntervalSet::new();
+        set.insert(VarInt::from_u32(start)..VarInt::from_u32(end))
+            .unwrap();
+        set
+    }
+
+    #[test]
+    fn expiration_test() {
+        let error = application::Error::new(1).unwrap();
+        let lifetime = Duration::from_millis(100);
+        let now = NoopClock.get_time();
+
+        let mut deadlines = Deadlines::default();
+        deadlines.push(range(0, 10), lifetime, error);
+        deadlines.push(range(20, 30), lifetime, error);
+
+        // nothing has been transmitted so nothing can expire
+        assert_eq!(deadlines.expired_error(&lost(0, 30), now + lifetime), None);
+
+        // only the first range has been transmitted
+        deadlines.on_transmit(VarInt::from_u32(15), now);
+        let later = now + Duration::from_millis(50);
+        deadlines.on_transmit(VarInt::from_u32(25), later);
+
+        assert_eq!(deadlines.expired_error(&lost(0, 10), now), None);
+        assert_eq!(
+            deadlines.expired_error(&lost(5, 6), now + lifetime),
+            Some(error)
+        );
+        // losing data outside of the tracked ranges doesn't expire anything
+        assert_eq!(
+            deadlines.expired_error(&lost(10, 20), later + lifetime),
+            None
+        );
+        // the second range expires later
+        assert_eq!(deadlines.expired_error(&lost(20, 21), now + lifetime), None);
+        assert_eq!(
+            deadlines.expired_error(&lost(20, 21), later + lifetime),
+            Some(error)
+        );
+
+        deadlines.release(VarInt::from_u32(10));
+        assert_eq!(
+            deadlines.expired_error(&lost(0, 10), later + lifetime),
+            None
+        );
+        assert!(!deadlines.is_empty());
+
+        deadlines.release(VarInt::from_u32(30));
+        assert!(deadlines.is_empty());
+    }
+}
@@ -73,6 +73,32 @@ impl<
         self.request_delivery_if_necessary();
     }
 
+    /// Sets the new value that needs to get synchronized to the peer and
+    /// requests its delivery, even if the update is below the configured `threshold`.
+    pub fn force_update_latest_value(&mut self, value: T) {
+        debug_assert!(value >= self.latest_value);
+        self.latest_value = value;
+
+        if self.delivery.is_cancelled() || self.latest_value == self.value_ackd_up_to {
+            return;
+        }
+
+        // Avoid resending a value which is already in flight
+        if let DeliveryState::InFlight(in_flight) = self.delivery {
+            if in_flight.value == self.latest_value {
+                return;
+            }
+        }
+
+        self.delivery = DeliveryState::Requested(self.latest_value);
+    }
+
+    /// Updates the minimum increase of the value which triggers an update
+    pub fn set_threshold(&mut self, threshold: T) {
+        self.threshold = threshold;
+        self.request_delivery_if_necessary();
+    }
+
     /// Stop to synchronize the value to the peer
     pub fn stop_sync(&mut self) {
         self.delivery.cancel();
@@ -88,6 +88,12 @@ impl<T> DeliveryState<T> {
         matches!(self, Self::InFlight(_))
     }
 
+    /// Returns `true` if the delivery of the value had been acknowledged
+    #[inline]
+    pub fn is_delivered(&self) -> bool {
+        matches!(self, Self::Delivered(_))
+    }
+
     /// Tries to transmit the delivery with the given transmission constraint
     #[inline]
     pub fn try_transmit(&self, constraint: transmission::Constraint) -> Option<&T> {
@@ -8,10 +8,26 @@ use s2n_quic_transport::connection::Connection as Inner;
 mod acceptor;
 #[macro_use]
 mod handle;
+mod router;
 
 pub use acceptor::*;
 pub use handle::*;
-pub use s2n_quic_core::connection::Error;
+pub use router::*;
+pub use s2n_quic_core::{
+    connection::{Error, KeepAlive},
+    recovery::ExpectedSendRate,
+};
+
+pub mod close {
+    pub use s2n_quic_core::connection::close::Reason;
+    pub use s2n_quic_transport::connection::MAX_CLOSE_REASON_LEN as MAX_REASON_LEN;
+}
+
+pub mod tls {
+    pub use s2n_quic_core::crypto::tls::{
+        CipherSuite, HandshakeDetails, NamedGroup, SignatureScheme,
+    };
+}
 
 pub mod error {
     pub use s2n_quic_core::transport::error::Code;
@@ -137,6 +137,58 @@ macro_rules! impl_handle_api {
             Ok(SendStream::new(stream.into())).into()
         }
 
+        /// Opens a new [`LocalStream`](`crate::stream::LocalStream`) which starts with an
+        /// application-defined `header`
+        ///
+        /// The `header` is encoded as a QUIC variable-length integer and is sent before any other
+        /// data on the stream. Peers can dispatch streams based on the header with a
+        /// [`StreamRouter`](`crate::connection::StreamRouter`).
+        ///
+        /// Returns [`stream::Error::InvalidHeader`](crate::stream::Error::InvalidHeader) if the
+        /// `header` can't be encoded as a QUIC variable-length integer (i.e. it exceeds
+        /// `2^62 - 1`).
+        ///
+        /// # Examples
+        ///
+        /// ```rust,no_run
+        /// # async fn test() -> s2n_quic::stream::Result<()> {
+        /// #   use s2n_quic::stream;
+        /// #   let mut handle: s2n_quic::connection::Handle = todo!();
+        /// #
+        /// const CONTROL: u64 = 0x00;
+        ///
+        /// let stream = handle
+        ///     .open_stream_with_header(stream::Type::Unidirectional, CONTROL)
+        ///     .await?;
+        /// println!("Control stream opened with id: {}", stream.id());
+        /// #
+        /// #   Ok(())
+        /// # }
+        /// ```
+        #[inline]
+        pub async fn open_stream_with_header(
+            &mut self,
+            stream_type: $crate::stream::Type,
+            header: u64,
+        ) -> $crate::stream::Result<$crate::stream::LocalStream> {
+            use s2n_codec::{Encoder, EncoderBuffer};
+            use s2n_quic_core::varint::VarInt;
+
+            let header =
+                VarInt::new(header).map_err(|_| $crate::stream::Error::invalid_header())?;
+            let mut buffer = [0u8; 8];
+            let mut encoder = EncoderBuffer::new(&mut buffer);
+            encoder.encode(&header);
+            let len = encoder.len();
+
+            let mut stream = self.open_stream(stream_type).await?;
+            stream
+                .send(bytes::Bytes::copy_from_slice(&buffer[..len]))
+                .await?;
+
+            Ok(stream)
+        }
+
         /// Returns the local address that this connection is bound to.
         #[inline]
         pub fn local_addr(&self) -> $crate::connection::Result<std::net::SocketAddr> {
@@ -161,6 +213,75 @@ macro_rules! impl_handle_api {
             self.0.application_protocol()
         }
 
+        /// Returns the parameters negotiated by the TLS handshake
+        ///
+        /// This includes the DER-encoded certificate chain presented by the peer, which can be
+        /// used to authorize clients when mutual TLS is enabled. `None` is returned if the
+        /// handshake has not completed or the TLS provider does not expose the details.
+        ///
+        /// # Examples
+        ///
+        /// ```rust,no_run
+        /// # fn test() -> s2n_quic::connection::Result<()> {
+        /// #   let handle: s2n_quic::connection::Handle = todo!();
+        /// #
+        /// if let Some(details) = handle.handshake_details()? {
+        ///     println!("Negotiated cipher suite: {:?}", details.cipher_suite);
+        ///
+        ///     if let Some(leaf) = details.peer_cert_chain.first() {
+        ///         println!("Peer certificate is {} bytes", leaf.len());
+        ///     }
+        /// }
+        /// #
+        /// #   Ok(())
+        /// # }
+        /// ```
+        #[inline]
+        pub fn handshake_details(
+            &self,
+        ) -> $crate::connection::Result<Option<$crate::connection::tls::HandshakeDetails>> {
+            self.0.handshake_details()
+        }
+
+        /// Exports keying material from the TLS session of the connection
+        ///
+        /// The returned bytes are derived from the TLS exporter master secret with the provided
+        /// `label` and `context`, as described in
+        /// [RFC 8446 Section 7.5](https://www.rfc-editor.org/rfc/rfc8446#section-7.5). Both
+        /// peers derive the same value, which makes it suitable for binding application-level
+        /// authentication to the connection.
+        ///
+        /// The TLS session is only retained after the handshake if exporters were enabled with
+        /// [`Limits::with_keying_material_exporter`](crate::provider::limits::Limits::with_keying_material_exporter).
+        /// Otherwise, [`Error::KeyingMaterialExporterDisabled`](crate::connection::Error::KeyingMaterialExporterDisabled)
+        /// is returned. Calling this before the handshake completes returns
+        /// [`Error::HandshakeNotComplete`](crate::connection::Error::HandshakeNotComplete).
+        /// Neither error closes the connection.
+        ///
+        /// # Examples
+        ///
+        /// ```rust,no_run
+        /// # fn test() -> s2n_quic::connection::Result<()> {
+        /// #   let handle: s2n_quic::connection::Handle = todo!();
+        /// #
+        /// let binding = handle.export_keying_material(b"EXPORTER-my-protocol", b"", 32)?;
+        /// assert_eq!(binding.len(), 32);
+        /// #
+        /// #   Ok(())
+        /// # }
+        /// ```
+        #[inline]
+        pub fn export_keying_material(
+            &self,
+            label: &[u8],
+            context: &[u8],
+            len: usize,
+        ) -> $crate::connection::Result<::bytes::Bytes> {
+            let mut output = vec![0; len];
+            self.0.export_keying_material(label, context, &mut output)?;
+            Ok(output.into())
+        }
+
         /// Returns the internal identifier for the [`Connection`](`crate::Connection`)
         ///
         /// Note: This internal identifier is not the same as the connection ID included in packet
@@ -170,12 +291,99 @@ macro_rules! impl_handle_api {
             self.0.id()
         }
 
+        /// Waits for the connection to complete the handshake
+        ///
+        /// The method will return
+        ///  - `Ok(())` once the handshake has completed
+        ///  - `Err(connection_error)` if the connection was closed
+        ///
+        /// # Examples
+        ///
+        /// ```rust,no_run
+        /// # async fn test() -> s2n_quic::connection::Result<()> {
+        /// #   let mut handle: s2n_quic::connection::Handle = todo!();
+        /// #
+        /// handle.handshake_complete().await?;
+        /// println!("Connection {} completed the handshake", handle.id());
+        /// #
+        /// #   Ok(())
+        /// # }
+        /// ```
+        #[inline]
+        pub async fn handshake_complete(&mut self) -> $crate::connection::Result<()> {
+            futures::future::poll_fn(|cx| self.poll_handshake_complete(cx)).await
+        }
+
+        /// Polls for the connection to complete the handshake
+        ///
+        /// The method will return
+        /// - `Poll::Ready(Ok(()))` if the handshake has completed
+        /// - `Poll::Ready(Err(connection_error))` if the connection was closed
+        /// - `Poll::Pending` if the handshake is still in progress
+        #[inline]
+        pub fn poll_handshake_complete(
+            &mut self,
+            cx: &mut core::task::Context,
+        ) -> core::task::Poll<$crate::connection::Result<()>> {
+            self.0.poll_handshake_complete(cx)
+        }
+
+        /// Waits for the connection to close
+        ///
+        /// The error which closed the connection is returned. This allows a task to observe the
+        /// connection without holding any of its stream acceptors.
+        ///
+        /// # Examples
+        ///
+        /// ```rust,no_run
+        /// # async fn test() {
+        /// #   let mut handle: s2n_quic::connection::Handle = todo!();
+        /// #
+        /// let error = handle.closed().await;
+        /// println!("Connection {} closed: {}", handle.id(), error);
+        /// # }
+        /// ```
+        #[inline]
+        pub async fn closed(&mut self) -> $crate::connection::Error {
+            futures::future::poll_fn(|cx| self.poll_closed(cx)).await
+        }
+
+        /// Polls for the connection to close
+        ///
+        /// The method will return
+        /// - `Poll::Ready(connection_error)` with the error which closed the connection
+        /// - `Poll::Pending` if the connection is still open
+        #[inline]
+        pub fn poll_closed(
+            &mut self,
+            cx: &mut core::task::Context,
+        ) -> core::task::Poll<$crate::connection::Error> {
+            self.0.poll_closed(cx)
+        }
+
+        /// Returns `true` if the connection has not been closed
+        #[inline]
+        pub fn is_open(&self) -> bool {
+            self.0.is_open()
+        }
+
         /// Sends a Ping frame to the peer
         #[inline]
         pub fn ping(&mut self) -> $crate::connection::Result<()> {
             self.0.ping()
         }
 
+        /// Initiates an update of the 1-RTT packet protection keys
+        ///
+        /// The update is deferred until it is permitted by the protocol: keys are not updated
+        /// before the handshake is confirmed, or while a previous key update is still in
+        /// progress. Periodic key updates can be configured with the
+        /// [`Limits`](crate::provider::limits::Limits) provider instead.
+        #[inline]
+        pub fn initiate_key_update(&mut self) -> $crate::connection::Result<()> {
+            self.0.initiate_key_update()
+        }
+
         /// Enables or disables the connection to actively keep the connection alive with the peer
         ///
         /// This can be useful for maintaining connections beyond the configured idle timeout. The
@@ -186,6 +394,257 @@ macro_rules! impl_handle_api {
             self.0.keep_alive(enabled)
         }
 
+        /// Enables the keep alive with the provided settings
+        ///
+        /// This allows the application to control how often PING frames are sent while the
+        /// connection is idle. The interval is always capped to ensure the connection does not
+        /// exceed the negotiated idle timeout. Jitter can be added to avoid synchronized PINGs
+        /// across many connections, and an exponential backoff can be configured to reduce the
+        /// number of PINGs sent on connections which remain idle for long periods of time.
+        ///
+        /// ```rust,no_run
+        /// # async fn test() -> s2n_quic::connection::Result<()> {
+        /// # use core::time::Duration;
+        /// #   let mut handle: s2n_quic::connection::Handle = todo!();
+        /// use s2n_quic::connection::KeepAlive;
+        ///
+        /// let settings = KeepAlive::default()
+        ///     .with_interval(Duration::from_secs(15))
+        ///     .unwrap()
+        ///     .with_jitter(Duration::from_secs(2))
+        ///     .unwrap()
+        ///     .with_backoff(Duration::from_secs(120))
+        ///     .unwrap();
+        ///
+        /// handle.keep_alive_with(settings)?;
+        /// #
+        /// #   Ok(())
+        /// # }
+        /// ```
+        #[inline]
+        pub fn keep_alive_with(
+            &mut self,
+            settings: $crate::connection::KeepAlive,
+        ) -> $crate::connection::Result<()> {
+            self.0.keep_alive_with(settings)
+        }
+
+        /// Declares the rate at which the application expects to send data on the connection
+        ///
+        /// By default, the congestion controller infers whether the application is limiting
+        /// the send rate from the amount of buffered data at the time packets are transmitted.
+        /// Applications that know they will be idle (e.g. between video frames) or will send at
+        /// a bounded rate can declare it to prevent the congestion window from growing beyond
+        /// what the application will use, and to prevent bandwidth probes from misinterpreting
+        /// application pauses as the capacity of the network.
+        ///
+        /// # Examples
+        ///
+        /// ```rust,no_run
+        /// # async fn test() -> s2n_quic::connection::Result<()> {
+        /// #   let mut connection: s2n_quic::connection::Handle = todo!();
+        /// #
+        /// use s2n_quic::connection::ExpectedSendRate;
+        ///
+        /// // the application will be sending at most 5Mbps
+        /// connection.set_expected_send_rate(ExpectedSendRate::Limited {
+        ///     bytes_per_second: 5_000_000 / 8,
+        /// })?;
+        ///
+        /// // the application is done sending for now
+        /// connection.set_expected_send_rate(ExpectedSendRate::Idle)?;
+        /// #
+        /// #   Ok(())
+        /// # }
+        /// ```
+        #[inline]
+        pub fn set_expected_send_rate(
+            &mut self,
+            expected_send_rate: $crate::connection::ExpectedSendRate,
+        ) -> $crate::connection::Result<()> {
+            self.0.set_expected_send_rate(expected_send_rate)
+        }
+
+        /// Updates the number of bidirectional streams the peer is allowed to have open
+        /// concurrently
+        ///
+        /// Raising the limit immediately sends a `MAX_STREAMS` frame to the peer. Since QUIC
+        /// stream limits can never decrease, lowering the limit doesn't close any streams or
+        /// revoke credit which was already granted. Instead, the peer is given additional credit
+        /// only once the number of open streams drops below the new limit.
+        ///
+        /// Limits greater than `2^60` are rejected.
+        ///
+        /// # Examples
+        ///
+        /// ```rust,no_run
+        /// # async fn test() -> Result<(), s2n_quic::provider::limits::UpdateError> {
+        /// #   let mut connection: s2n_quic::connection::Handle = todo!();
+        /// #
+        /// // throttle a low-priority peer to a single stream and a small receive window
+        /// connection.set_max_open_remote_bidirectional_streams(1)?;
+        /// connection.set_data_window(64 * 1024)?;
+        /// #
+        /// #   Ok(())
+        /// # }
+        /// ```
+        #[inline]
+        pub fn set_max_open_remote_bidirectional_streams(
+            &mut self,
+            value: u64,
+        ) -> Result<(), $crate::provider::limits::UpdateError> {
+            self.update_limit(
+                s2n_quic_core::connection::limits::Update::MaxOpenRemoteBidirectionalStreams(value),
+            )
+        }
+
+        /// Updates the number of unidirectional streams the peer is allowed to have open
+        /// concurrently
+        ///
+        /// See [`Self::set_max_open_remote_bidirectional_streams`] for how the updated limit is
+        /// applied.
+        #[inline]
+        pub fn set_max_open_remote_unidirectional_streams(
+            &mut self,
+            value: u64,
+        ) -> Result<(), $crate::provider::limits::UpdateError> {
+            self.update_limit(
+                s2n_quic_core::connection::limits::Update::MaxOpenRemoteUnidirectionalStreams(
+                    value,
+                ),
+            )
+        }
+
+        /// Updates the connection-wide receive window in bytes
+        ///
+        /// Growing the window immediately sends a `MAX_DATA` frame to the peer. Since QUIC
+        /// flow control limits can never decrease, shrinking the window only reduces the credit
+        /// which is granted to the peer as received data is consumed. Windows larger than
+        /// `u32::MAX` bytes are rejected.
+        #[inline]
+        pub fn set_data_window(
+            &mut self,
+            value: u64,
+        ) -> Result<(), $crate::provider::limits::UpdateError> {
+            self.update_limit(s2n_quic_core::connection::limits::Update::DataWindow(value))
+        }
+
+        /// Updates the receive window in bytes for locally initiated bidirectional streams
+        ///
+        /// The window is applied to open streams as well as to streams opened in the future. See
+        /// [`Self::set_data_window`] for how the updated window is applied.
+        #[inline]
+        pub fn set_bidirectional_local_data_window(
+            &mut self,
+            value: u64,
+        ) -> Result<(), $crate::provider::limits::UpdateError> {
+            self.update_limit(
+                s2n_quic_core::connection::limits::Update::BidirectionalLocalDataWindow(value),
+            )
+        }
+
+        /// Updates the receive window in bytes for peer initiated bidirectional streams
+        ///
+        /// The window is applied to open streams as well as to streams opened in the future. See
+        /// [`Self::set_data_window`] for how the updated window is applied.
+        #[inline]
+        pub fn set_bidirectional_remote_data_window(
+            &mut self,
+            value: u64,
+        ) -> Result<(), $crate::provider::limits::UpdateError> {
+            self.update_limit(
+                s2n_quic_core::connection::limits::Update::BidirectionalRemoteDataWindow(value),
+            )
+        }
+
+        /// Updates the receive window in bytes for peer initiated unidirectional streams
+        ///
+        /// The window is applied to open streams as well as to streams opened in the future. See
+        /// [`Self::set_data_window`] for how the updated window is applied.
+        #[inline]
+        pub fn set_unidirectional_data_window(
+            &mut self,
+            value: u64,
+        ) -> Result<(), $crate::provider::limits::UpdateError> {
+            self.update_limit(
+                s2n_quic_core::connection::limits::Update::UnidirectionalDataWindow(value),
+            )
+        }
+
+        #[inline]
+        fn update_limit(
+            &mut self,
+            update: s2n_quic_core::connection::limits::Update,
+        ) -> Result<(), $crate::provider::limits::UpdateError> {
+            let update = update.validate()?;
+            self.0.update_limit(update)?;
+            Ok(())
+        }
+
+        /// Registers a buffer which received stream data is directly written into
+        ///
+        /// The buffer is handed to the next stream which receives data at its current offset
+        /// without already having a buffer for it, e.g. one registered with
+        /// [`ReceiveStream::register_receive_buffer`](crate::stream::ReceiveStream::register_receive_buffer).
+        /// The stream then writes the received data directly into the spare capacity of the
+        /// buffer and the chunks it returns are views into it. Buffers are handed out in the order
+        /// they were registered and any data contained in them is discarded.
+        ///
+        /// Buffers without any spare capacity are ignored. Buffers which weren't handed to a
+        /// stream yet are released when the connection is closed.
+        ///
+        /// # Examples
+        ///
+        /// ```rust,no_run
+        /// # async fn test() -> s2n_quic::connection::Result<()> {
+        /// #   let mut connection: s2n_quic::connection::Handle = todo!();
+        /// #
+        /// use bytes::BytesMut;
+        ///
+        /// // provide a pool of buffers for the streams the peer opens
+        /// for _ in 0..4 {
+        ///     connection.register_receive_buffer(BytesMut::with_capacity(64 * 1024))?;
+        /// }
+        /// #
+        /// #   Ok(())
+        /// # }
+        /// ```
+        #[inline]
+        pub fn register_receive_buffer(
+            &mut self,
+            buffer: bytes::BytesMut,
+        ) -> $crate::connection::Result<()> {
+            self.0.register_receive_buffer(buffer)
+        }
+
+        /// Gracefully closes the Connection once all of the outstanding streams have finished
+        ///
+        /// The peer is no longer given credit to open new streams and any streams it opens from
+        /// this point on are refused with the error code configured by
+        /// [`Limits::with_drain_refusal_error`](crate::provider::limits::Limits::with_drain_refusal_error).
+        /// Once all of the outstanding streams have finished, the connection is closed with
+        /// `NO_ERROR`. If the streams don't finish before the `timeout` expires, the connection
+        /// is closed anyway. Connections which are still handshaking are drained once the
+        /// handshake completes.
+        ///
+        /// # Examples
+        ///
+        /// ```rust,no_run
+        /// # async fn test() -> s2n_quic::connection::Result<()> {
+        /// #   let mut connection: s2n_quic::connection::Handle = todo!();
+        /// #
+        /// use core::time::Duration;
+        ///
+        /// connection.drain(Duration::from_secs(30))?;
+        /// #
+        /// #   Ok(())
+        /// # }
+        /// ```
+        #[inline]
+        pub fn drain(&mut self, timeout: core::time::Duration) -> $crate::connection::Result<()> {
+            self.0.drain(timeout)
+        }
+
         /// Closes the Connection with the provided error code
         ///
         /// This will immediately terminate all outstanding streams.
@@ -207,6 +666,36 @@ macro_rules! impl_handle_api {
             self.0.close(error_code)
         }
 
+        /// Closes the Connection with the provided error code and reason phrase
+        ///
+        /// This will immediately terminate all outstanding streams. The reason is sent to the
+        /// peer in the CONNECTION_CLOSE frame once the handshake has completed and is truncated
+        /// to [`MAX_REASON_LEN`](crate::connection::close::MAX_REASON_LEN) bytes.
+        ///
+        /// Reasons sent by the peer are returned by
+        /// [`Error::close_reason`](crate::connection::Error::close_reason), truncated to
+        /// [`Reason::MAX_LEN`](crate::connection::close::Reason::MAX_LEN) bytes. The complete
+        /// phrase is reported by the
+        /// [`ConnectionCloseReceived`](crate::provider::event::events::ConnectionCloseReceived)
+        /// event.
+        ///
+        /// # Examples
+        ///
+        /// ```rust,no_run
+        /// # async fn test() -> s2n_quic::connection::Result<()> {
+        /// #   let mut connection: s2n_quic::connection::Handle = todo!();
+        /// #
+        /// const MY_ERROR_CODE:u32 = 99;
+        /// connection.close_with_reason(MY_ERROR_CODE.into(), b"tenant quota exceeded");
+        /// #
+        /// #   Ok(())
+        /// # }
+        /// ```
+        #[inline]
+        pub fn close_with_reason(&self, error_code: $crate::application::Error, reason: &[u8]) {
+            self.0.close_with_reason(error_code, reason)
+        }
+
         /// API for querying the connection's
         /// [`Subscriber::ConnectionContext`](crate::provider::event::Subscriber::ConnectionContext).
         ///
@@ -0,0 +1,432 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    application,
+    connection::{self, StreamAcceptor},
+    stream::PeerStream,
+};
+use bytes::Bytes;
+use core::{
+    fmt,
+    task::{Context, Poll, Waker},
+};
+use futures::task::ArcWake;
+use s2n_codec::{DecoderBuffer, DecoderError};
+use s2n_quic_core::varint::VarInt;
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::{Arc, Mutex, MutexGuard},
+};
+
+impl StreamAcceptor {
+    /// Converts the [`StreamAcceptor`] into a [`StreamRouter`], which dispatches incoming
+    /// streams based on an application-defined stream header
+    ///
+    /// Each peer stream is expected to start with a header, encoded as a QUIC variable-length
+    /// integer, which identifies the kind of data carried on the stream. This is similar to
+    /// the stream types used by unidirectional streams in HTTP/3. Streams with a header that
+    /// was not registered are reset with `unknown_header_error`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # async fn test() -> s2n_quic::connection::Result<()> {
+    /// #   let connection: s2n_quic::connection::Connection = todo!();
+    /// #
+    /// const CONTROL: u64 = 0x00;
+    /// const PUSH: u64 = 0x01;
+    ///
+    /// let (handle, acceptor) = connection.split();
+    /// let mut router = acceptor.route_by_header(s2n_quic::application::Error::new(0x103).unwrap());
+    /// let mut control = router.register(CONTROL).unwrap();
+    /// let mut push = router.register(PUSH).unwrap();
+    ///
+    /// tokio::spawn(async move {
+    ///     while let Ok(Some(stream)) = push.accept().await {
+    ///         println!("Push stream opened from {:?}", stream.connection().remote_addr());
+    ///     }
+    /// });
+    ///
+    /// while let Some(stream) = control.accept().await? {
+    ///     println!("Control stream opened from {:?}", stream.connection().remote_addr());
+    /// }
+    /// #
+    /// #   Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub fn route_by_header(self, unknown_header_error: application::Error) -> StreamRouter {
+        StreamRouter::new(self, unknown_header_error)
+    }
+}
+
+/// Dispatches incoming streams to a [`RoutedStreamAcceptor`] based on the stream's header
+///
+/// Streams are only read and dispatched while at least one of the registered acceptors is
+/// polled. All of the headers should be registered before accepting streams, otherwise
+/// streams with headers registered later may be reset.
+#[derive(Debug)]
+pub struct StreamRouter {
+    shared: Arc<Shared>,
+}
+
+impl StreamRouter {
+    #[inline]
+    fn new(acceptor: StreamAcceptor, unknown_header_error: application::Error) -> Self {
+        let state = State {
+            acceptor,
+            unknown_header_error,
+            routes: HashMap::new(),
+            pending: Vec::new(),
+            outcome: None,
+        };
+        let shared = Shared {
+            state: Mutex::new(state),
+            wakers: Default::default(),
+        };
+        Self {
+            shared: Arc::new(shared),
+        }
+    }
+
+    /// Registers an acceptor for streams starting with `header`
+    ///
+    /// Returns an error if an acceptor is already registered for the `header` or the `header`
+    /// can't be encoded as a QUIC variable-length integer (i.e. it exceeds `2^62 - 1`).
+    #[inline]
+    pub fn register(&mut self, header: u64) -> Result<RoutedStreamAcceptor, RegisterError> {
+        let header = VarInt::new(header).map_err(|_| RegisterError::InvalidHeader)?;
+        let mut state = self.shared.state();
+
+        if state.routes.contains_key(&header) {
+            return Err(RegisterError::AlreadyRegistered);
+        }
+        state.routes.insert(header, VecDeque::new());
+
+        Ok(RoutedStreamAcceptor {
+            header,
+            shared: self.shared.clone(),
+        })
+    }
+}
+
+/// The reason a header couldn't be registered with a [`StreamRouter`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum RegisterError {
+    /// An acceptor is already registered for the header
+    AlreadyRegistered,
+    /// The header can't be encoded as a QUIC variable-length integer
+    InvalidHeader,
+}
+
+impl fmt::Display for RegisterError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::AlreadyRegistered => {
+                write!(f, "An acceptor is already registered for the header")
+            }
+            Self::InvalidHeader => write!(
+                f,
+                "The stream header exceeds the maximum variable-length integer value"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for RegisterError {}
+
+/// Accepts streams from a [`StreamRouter`] which start with a specific header
+///
+/// The header is consumed from the stream before it is returned to the application.
+/// Dropping the acceptor unregisters the header, after which any new streams with the
+/// header are reset.
+#[derive(Debug)]
+pub struct RoutedStreamAcceptor {
+    header: VarInt,
+    shared: Arc<Shared>,
+}
+
+impl RoutedStreamAcceptor {
+    /// Returns the header of the streams accepted by this acceptor
+    #[inline]
+    pub fn header(&self) -> u64 {
+        self.header.as_u64()
+    }
+
+    /// Accepts an incoming [`PeerStream`] starting with the registered header
+    ///
+    /// The method will return
+    /// - `Ok(Some(stream)))` if a [`PeerStream`] was accepted
+    /// - `Ok(None)` if the connection was closed without an error
+    /// - `Err(stream_error)` if no stream could be accepted due to an error
+    #[inline]
+    pub async fn accept(&mut self) -> connection::Result<Option<PeerStream>> {
+        futures::future::poll_fn(|cx| self.poll_accept(cx)).await
+    }
+
+    /// Poll for accepting an incoming [`PeerStream`] starting with the registered header
+    ///
+    /// The method will return
+    /// - `Poll::Ready(Ok(Some(stream)))` if a [`PeerStream`] was accepted
+    /// - `Poll::Ready(Ok(None))` if the connection was closed without an error
+    /// - `Poll::Ready(Err(stream_error))` if no stream could be accepted due to an error
+    /// - `Poll::Pending` if no new [`PeerStream`] was accepted by the connection yet.
+    ///   In this case the caller must retry calling [`Self::poll_accept`].
+    ///   For this purpose the method will save the [`core::task::Waker`]
+    ///   which is provided as part of the [`core::task::Context`] parameter, and notify it
+    ///   as soon as retrying the method will yield a different result.
+    pub fn poll_accept(
+        &mut self,
+        cx: &mut Context,
+    ) -> Poll<connection::Result<Option<PeerStream>>> {
+        self.shared.wakers.register(self.header, cx.waker());
+
+        let (stream, outcome, routed) = {
+            let mut state = self.shared.state();
+
+            // the underlying acceptor and streams notify all of the registered acceptors, since
+            // any of them can be the one to make progress
+            let waker = futures::task::waker(self.shared.wakers.clone());
+            let routed = state.poll(&mut Context::from_waker(&waker));
+
+            let stream = state
+                .routes
+                .get_mut(&self.header)
+                .and_then(|queue| queue.pop_front());
+
+            (stream, state.outcome, routed)
+        };
+
+        // notify the other acceptors outside of the lock, since they may be polled inline
+        for header in routed {
+            if header != self.header {
+                self.shared.wakers.notify(header);
+            }
+        }
+
+        if let Some(stream) = stream {
+            return Poll::Ready(Ok(Some(stream)));
+        }
+
+        match outcome {
+            Some(Ok(())) => Poll::Ready(Ok(None)),
+            Some(Err(error)) => Poll::Ready(Err(error)),
+            None => Poll::Pending,
+        }
+    }
+}
+
+impl futures::stream::Stream for RoutedStreamAcceptor {
+    type Item = connection::Result<PeerStream>;
+
+    #[inline]
+    fn poll_next(
+        mut self: core::pin::Pin<&mut Self>,
+        cx: &mut core::task::Context<'_>,
+    ) -> core::task::Poll<Option<Self::Item>> {
+        match futures::ready!(self.poll_accept(cx)) {
+            Ok(Some(stream)) => Some(Ok(stream)),
+            Ok(None) => None,
+            Err(err) => Some(Err(err)),
+        }
+        .into()
+    }
+}
+
+impl Drop for RoutedStreamAcceptor {
+    #[inline]
+    fn drop(&mut self) {
+        // any streams that weren't accepted yet are dropped along with the route
+        self.shared.state().routes.remove(&self.header);
+        self.shared.wakers.remove(self.header);
+    }
+}
+
+#[derive(Debug)]
+struct Shared {
+    state: Mutex<State>,
+    /// The wakers are stored separately from the state so the acceptors can be notified
+    /// while the state is locked.
+    ///
+    /// The wakers are also used to notify all of the acceptors from the underlying connection,
+    /// which means they can't hold a reference to the state without creating a cycle.
+    wakers: Arc<Wakers>,
+}
+
+impl Shared {
+    #[inline]
+    fn state(&self) -> MutexGuard<State> {
+        // the state is kept consistent across panics so it's fine to use it after poisoning
+        self.state.lock().unwrap_or_else(|err| err.into_inner())
+    }
+}
+
+#[derive(Debug, Default)]
+struct Wakers(Mutex<HashMap<VarInt, Waker>>);
+
+impl Wakers {
+    #[inline]
+    fn lock(&self) -> MutexGuard<HashMap<VarInt, Waker>> {
+        self.0.lock().unwrap_or_else(|err| err.into_inner())
+    }
+
+    #[inline]
+    fn register(&self, header: VarInt, waker: &Waker) {
+        let mut wakers = self.lock();
+        match wakers.get_mut(&header) {
+            Some(prev) if prev.will_wake(waker) => {}
+            Some(prev) => *prev = waker.clone(),
+            None => {
+                wakers.insert(header, waker.clone());
+            }
+        }
+    }
+
+    #[inline]
+    fn remove(&self, header: VarInt) {
+        self.lock().remove(&header);
+    }
+
+    #[inline]
+    fn notify(&self, header: VarInt) {
+        let waker = self.lock().get(&header).cloned();
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+    }
+}
+
+impl ArcWake for Wakers {
+    #[inline]
+    fn wake_by_ref(arc_self: &Arc<Self>) {
+        let wakers: Vec<_> = arc_self.lock().values().cloned().collect();
+        for waker in wakers {
+            waker.wake();
+        }
+    }
+}
+
+#[derive(Debug)]
+struct State {
+    acceptor: StreamAcceptor,
+    unknown_header_error: application::Error,
+    /// Streams which have been routed but not yet accepted, keyed by header
+    routes: HashMap<VarInt, VecDeque<PeerStream>>,
+    /// Streams which have been accepted but the header is still being read
+    pending: Vec<PendingStream>,
+    /// Set once the underlying acceptor stops yielding streams
+    outcome: Option<connection::Result<()>>,
+}
+
+impl State {
+    /// Accepts new streams and routes the pending streams which finished reading their header
+    ///
+    /// Returns the headers of the routes which received new streams.
+    fn poll(&mut self, cx: &mut Context) -> Vec<VarInt> {
+        while self.outcome.is_none() {
+            match self.acceptor.poll_accept(cx) {
+                Poll::Ready(Ok(Some(stream))) => self.pending.push(PendingStream::new(stream)),
+                Poll::Ready(Ok(None)) => self.outcome = Some(Ok(())),
+                Poll::Ready(Err(error)) => self.outcome = Some(Err(error)),
+                Poll::Pending => break,
+            }
+        }
+
+        let mut routed = vec![];
+        let mut index = 0;
+
+        while index < self.pending.len() {
+            let header = match self.pending[index].poll_header(cx) {
+                Poll::Ready(header) => header,
+                Poll::Pending => {
+                    index += 1;
+                    continue;
+                }
+            };
+
+            let mut stream = self.pending.remove(index).stream;
+
+            match header.ok().and_then(|header| {
+                let queue = self.routes.get_mut(&header)?;
+                Some((header, queue))
+            }) {
+                Some((header, queue)) => {
+                    queue.push_back(stream);
+                    if !routed.contains(&header) {
+                        routed.push(header);
+                    }
+                }
+                None => {
+                    // The stream was either closed before the header was read or the header isn't
+                    // registered. Errors are ignored since the stream may already be closed.
+                    let _ = stream.stop_sending(self.unknown_header_error);
+                    let _ = stream.reset(self.unknown_header_error);
+                }
+            }
+        }
+
+        routed
+    }
+}
+
+#[derive(Debug)]
+struct PendingStream {
+    stream: PeerStream,
+    header: [u8; 8],
+    len: usize,
+}
+
+impl PendingStream {
+    #[inline]
+    fn new(stream: PeerStream) -> Self {
+        Self {
+            stream,
+            header: [0; 8],
+            len: 0,
+        }
+    }
+
+    /// Reads the header from the stream without consuming any of the data that follows it
+    fn poll_header(&mut self, cx: &mut Context) -> Poll<Result<VarInt, ()>> {
+        loop {
+            // the first two bits of a variable-length integer encode its length
+            let encoding_len = if self.len == 0 {
+                1
+            } else {
+                1 << (self.header[0] >> 6)
+            };
+
+            if self.len == encoding_len {
+                let buffer = DecoderBuffer::new(&self.header[..self.len]);
+                let result: Result<_, DecoderError> = buffer.decode::<VarInt>();
+                return Poll::Ready(result.map(|(header, _)| header).map_err(|_| ()));
+            }
+
+            let mut chunks = [Bytes::new()];
+            let request = self.stream.rx_request();
+            let response = match request {
+                Ok(mut request) => futures::ready!(request
+                    .receive(&mut chunks)
+                    // only read the remaining header bytes
+                    .with_high_watermark(encoding_len - self.len)
+                    .poll(Some(cx))
+                    .map_err(|_| ())?
+                    .into_poll()),
+                Err(_) => return Poll::Ready(Err(())),
+            };
+
+            for chunk in &chunks[..response.chunks.consumed] {
+                let end = self.len + chunk.len();
+                self.header[self.len..end].copy_from_slice(chunk);
+                self.len = end;
+            }
+
+            // the stream finished before the entire header was received
+            if response.chunks.consumed == 0 && response.status.is_closed() {
+                return Poll::Ready(Err(()));
+            }
+        }
+    }
+}
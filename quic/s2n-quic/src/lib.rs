@@ -22,6 +22,11 @@
 //! that multiple servers handle address tokens, this provider should not be used. Instead, a custom
 //! implementation of [`provider::address_token::Format`] should be specified.
 //!
+//! ### `provider-crypto-aws-lc-rs`
+//!
+//! Uses [`aws-lc-rs`][aws-lc-rs] instead of [`ring`][ring] as the cryptographic backend for
+//! packet protection. See [`provider::crypto`] for details.
+//!
 //! ### `provider-event-tracing`
 //!
 //! Enables event integration with [`tracing`](https://docs.rs/tracing). The
@@ -52,6 +57,8 @@
 //!
 //! [s2n-tls]: https://github.com/aws/s2n-tls
 //! [rustls]: https://github.com/rustls/rustls
+//! [aws-lc-rs]: https://github.com/aws/aws-lc-rs
+//! [ring]: https://github.com/briansmith/ring
 
 #[macro_use]
 pub mod provider;
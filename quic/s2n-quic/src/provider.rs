@@ -9,6 +9,7 @@ mod macros;
 
 pub mod address_token;
 pub mod connection_id;
+pub mod crypto;
 pub mod endpoint_limits;
 pub mod event;
 pub mod io;
@@ -2,7 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 pub use s2n_quic_core::recovery::congestion_controller::{
-    CongestionController, Endpoint, PathInfo,
+    CongestionController, Endpoint, ExpectedSendRate, PathInfo,
 };
 
 /// Provides congestion controller support for an endpoint
@@ -13,7 +13,7 @@ pub trait Provider {
     fn start(self) -> Result<Self::Endpoint, Self::Error>;
 }
 
-pub use s2n_quic_core::recovery::{bbr::Endpoint as Bbr, cubic::Endpoint as Cubic};
+pub use s2n_quic_core::recovery::{bbr::Endpoint as Bbr, cubic::Endpoint as Cubic, SlowStart};
 pub type Default = Cubic;
 
 impl_provider_utils!();
@@ -0,0 +1,48 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Provides packet protection for an endpoint
+//!
+//! Packet protection keys are derived from the secrets negotiated by the [TLS
+//! provider](crate::provider::tls) through a [`Provider`]. The default provider uses the
+//! QUIC-optimized AEAD and header protection implementations in `s2n-quic-crypto`, falling back
+//! to a portable implementation on platforms without hardware acceleration.
+//!
+//! The cryptographic backend is selected at build time:
+//!
+//! * The `provider-crypto-ring` feature, which is enabled by default, uses
+//!   [`ring`](https://github.com/briansmith/ring) for key derivation, header protection, and the
+//!   portable AEAD implementation.
+//! * The `provider-crypto-aws-lc-rs` feature uses [`aws-lc-rs`](https://github.com/aws/aws-lc-rs)
+//!   instead. It takes precedence if both features are enabled, so `ring` can be dropped from the
+//!   build by disabling the default features.
+//!
+//! The backend is an implementation detail and doesn't change any public types, so enabling
+//! either feature anywhere in the dependency graph is safe. At least one of them must be enabled.
+//!
+//! The `s2n-tls` provider derives all of its keys through a crypto provider, which defaults to
+//! [`default::Provider`] and can be replaced with the `with_crypto_provider` method on its
+//! client and server builders. Using the replaced provider with an endpoint requires the
+//! `provider-tls-s2n` feature.
+//!
+//! The `rustls` provider derives the Handshake and 1-RTT keys itself, since `rustls` does not
+//! expose the negotiated traffic secrets, and only uses the crypto backend for Initial and Retry
+//! packets. It therefore can't be combined with a different crypto provider.
+
+pub use s2n_quic_core::crypto::provider::{Provider, SecretPair};
+
+pub mod default {
+    //! Provides the recommended packet protection implementation
+    pub use s2n_quic_crypto::Suite as Provider;
+}
+
+#[cfg(test)]
+mod tests {
+    use s2n_quic_core::crypto::provider::testing;
+
+    /// Runs the RFC 9001 test vectors against the backend selected by the enabled features
+    #[test]
+    fn provider_conformance() {
+        testing::conformance::<super::default::Provider>();
+    }
+}
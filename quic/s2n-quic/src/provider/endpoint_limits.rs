@@ -4,7 +4,7 @@
 //! Allows applications to limit peer's ability to open new connections
 
 pub use s2n_quic_core::endpoint::{
-    limits::{ConnectionAttempt, Outcome},
+    limits::{ConnectionAttempt, MemoryPressure, MemoryUsage, Outcome},
     Limiter,
 };
 use s2n_quic_core::{event::Timestamp, path::THROTTLED_PORTS_LEN};
@@ -33,6 +33,9 @@ impl<T: 'static + Limiter> Provider for T {
 
 const THROTTLED_PORT_LIMIT: usize = 10;
 const THROTTLE_FREQUENCY: Duration = Duration::from_secs(1);
+/// The default amount of time a connection needs to be idle before it can be closed
+/// due to memory pressure
+const DEFAULT_MEMORY_CLOSE_IDLE_TIME: Duration = Duration::from_secs(1);
 
 #[derive(Default, Debug, Clone, Copy)]
 struct BasicRateLimiter {
@@ -180,6 +183,10 @@ pub mod default {
     #[derive(Default)]
     pub struct Builder {
         max_inflight_handshake_limit: Option<usize>,
+        memory_limit: Option<u64>,
+        memory_close_limit: Option<u64>,
+        memory_close_idle_time: Option<Duration>,
+        max_send_buffer_size: Option<u64>,
     }
 
     impl Builder {
@@ -189,10 +196,58 @@ pub mod default {
             Ok(self)
         }
 
+        /// Sets the number of buffered bytes at which the endpoint is under memory pressure
+        ///
+        /// While under memory pressure, new connection attempts are deferred with Retry
+        /// packets and the flow control windows advertised to peers are no longer extended.
+        pub fn with_memory_limit(mut self, limit: u64) -> Result<Self, Infallible> {
+            self.memory_limit = Some(limit);
+            Ok(self)
+        }
+
+        /// Sets the number of buffered bytes at which the connection buffering the most
+        /// data is closed
+        ///
+        /// This also puts the endpoint under memory pressure, as described in
+        /// [`Self::with_memory_limit`].
+        pub fn with_memory_close_limit(mut self, limit: u64) -> Result<Self, Infallible> {
+            self.memory_close_limit = Some(limit);
+            Ok(self)
+        }
+
+        /// Sets the amount of time a connection needs to go without stream progress before it
+        /// can be closed for exceeding the limit set with [`Self::with_memory_close_limit`]
+        ///
+        /// Connections which are still sending or receiving stream data are expected to
+        /// release their buffers and are not closed. Defaults to 1 second.
+        pub fn with_memory_close_idle_time(
+            mut self,
+            idle_time: Duration,
+        ) -> Result<Self, Infallible> {
+            self.memory_close_idle_time = Some(idle_time);
+            Ok(self)
+        }
+
+        /// Sets the maximum number of bytes buffered for sending across all connections
+        ///
+        /// Streams are blocked from buffering more data once the limit is reached, until data
+        /// is acknowledged by the peers. This applies in addition to
+        /// [`Limits::with_max_connection_send_buffer_size`](crate::provider::limits::Limits::with_max_connection_send_buffer_size).
+        pub fn with_max_send_buffer_size(mut self, limit: u64) -> Result<Self, Infallible> {
+            self.max_send_buffer_size = Some(limit);
+            Ok(self)
+        }
+
         /// Build the limits
         pub fn build(self) -> Result<Limits, Infallible> {
             Ok(Limits {
                 max_inflight_handshake_limit: self.max_inflight_handshake_limit,
+                memory_limit: self.memory_limit,
+                memory_close_limit: self.memory_close_limit,
+                memory_close_idle_time: self
+                    .memory_close_idle_time
+                    .unwrap_or(DEFAULT_MEMORY_CLOSE_IDLE_TIME),
+                max_send_buffer_size: self.max_send_buffer_size,
                 rate_limiter: [BasicRateLimiter::default(); THROTTLED_PORTS_LEN],
             })
         }
@@ -202,9 +257,31 @@ pub mod default {
     pub struct Limits {
         /// Maximum number of handshakes to allow before Retry packets are queued
         max_inflight_handshake_limit: Option<usize>,
+        /// Number of buffered bytes at which the endpoint is under memory pressure
+        memory_limit: Option<u64>,
+        /// Number of buffered bytes at which the largest consumer is closed
+        memory_close_limit: Option<u64>,
+        /// Amount of time without stream progress before a connection can be closed
+        memory_close_idle_time: Duration,
+        /// Maximum number of bytes buffered for sending across all connections
+        max_send_buffer_size: Option<u64>,
         rate_limiter: [BasicRateLimiter; THROTTLED_PORTS_LEN],
     }
 
+    impl Limits {
+        fn memory_pressure(&self, buffered_bytes: u64) -> MemoryPressure {
+            let exceeds = |limit: Option<u64>| limit.map_or(false, |limit| buffered_bytes >= limit);
+
+            if exceeds(self.memory_close_limit) {
+                MemoryPressure::close_largest_consumer(self.memory_close_idle_time)
+            } else if exceeds(self.memory_limit) {
+                MemoryPressure::shrink_windows()
+            } else {
+                MemoryPressure::none()
+            }
+        }
+    }
+
     impl Limits {
         pub fn builder() -> Builder {
             Builder::default()
@@ -233,8 +310,20 @@ pub mod default {
                 }
             }
 
+            if self.memory_pressure(info.buffered_bytes).shrinks_windows() {
+                return Outcome::retry();
+            }
+
             Outcome::allow()
         }
+
+        fn on_memory_usage(&mut self, info: &MemoryUsage) -> MemoryPressure {
+            self.memory_pressure(info.buffered_bytes)
+        }
+
+        fn max_send_buffer_size(&self) -> Option<u64> {
+            self.max_send_buffer_size
+        }
     }
 
     /// Default limit values are as non-intrusive as possible
@@ -242,6 +331,10 @@ pub mod default {
         fn default() -> Self {
             Self {
                 max_inflight_handshake_limit: None,
+                memory_limit: None,
+                memory_close_limit: None,
+                memory_close_idle_time: DEFAULT_MEMORY_CLOSE_IDLE_TIME,
+                max_send_buffer_size: None,
                 rate_limiter: [BasicRateLimiter::default(); THROTTLED_PORTS_LEN],
             }
         }
@@ -255,6 +348,54 @@ pub mod default {
             .build()
             .unwrap();
         assert_eq!(elp.max_inflight_handshake_limit, Some(100));
+        assert_eq!(elp.max_send_buffer_size(), None);
+
+        let elp = Limits::builder()
+            .with_max_send_buffer_size(1 << 20)
+            .unwrap()
+            .build()
+            .unwrap();
+        assert_eq!(elp.max_send_buffer_size(), Some(1 << 20));
+    }
+
+    #[test]
+    fn memory_pressure_test() {
+        use s2n_quic_core::{
+            event::IntoEvent,
+            inet::SocketAddress,
+            time::{testing::Clock as MockClock, Clock},
+        };
+
+        let mut limits = Limits::builder()
+            .with_memory_limit(1000)
+            .unwrap()
+            .with_memory_close_limit(2000)
+            .unwrap()
+            .with_memory_close_idle_time(Duration::from_millis(500))
+            .unwrap()
+            .build()
+            .unwrap();
+        let mut remote_address = SocketAddress::default();
+        remote_address.set_port(4433);
+        let timestamp = MockClock::default().get_time().into_event();
+
+        for (buffered_bytes, pressure, outcome) in [
+            (0, MemoryPressure::none(), Outcome::allow()),
+            (999, MemoryPressure::none(), Outcome::allow()),
+            (1000, MemoryPressure::shrink_windows(), Outcome::retry()),
+            (
+                2000,
+                MemoryPressure::close_largest_consumer(Duration::from_millis(500)),
+                Outcome::retry(),
+            ),
+        ] {
+            let usage = MemoryUsage::new(buffered_bytes, 1, timestamp);
+            assert_eq!(limits.on_memory_usage(&usage), pressure);
+
+            let info = ConnectionAttempt::new(0, 1, &remote_address, timestamp)
+                .with_buffered_bytes(buffered_bytes);
+            assert_eq!(limits.on_connection_attempt(&info), outcome);
+        }
     }
 
     #[test]
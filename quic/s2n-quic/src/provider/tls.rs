@@ -2,6 +2,20 @@
 // SPDX-License-Identifier: Apache-2.0
 
 //! Provides TLS support for an endpoint
+//!
+//! # Unsupported extensions
+//!
+//! The following TLS extensions are not implemented by either provider:
+//!
+//! * Certificate compression ([RFC 8879](https://www.rfc-editor.org/rfc/rfc8879)) and raw
+//!   public keys ([RFC 7250](https://www.rfc-editor.org/rfc/rfc7250)). Large certificate chains
+//!   may require additional round trips when the server is blocked by the anti-amplification
+//!   limit. These round trips are reported with the
+//!   [`HandshakeAmplificationLimited`](crate::provider::event::events::HandshakeAmplificationLimited)
+//!   event.
+//! * Encrypted Client Hello ([draft-ietf-tls-esni](https://datatracker.ietf.org/doc/draft-ietf-tls-esni/)).
+//!   The server name is sent in cleartext in the client's Initial packets, so applications
+//!   which need to hide it can't rely on these providers.
 
 use cfg_if::cfg_if;
 use s2n_quic_core::crypto;
@@ -276,11 +290,14 @@ pub mod rustls {
 #[cfg(feature = "s2n-quic-tls")]
 pub mod s2n_tls {
     //! Provides the [s2n-tls](https://github.com/aws/s2n-tls) implementation of TLS
+    use s2n_quic_core::crypto::provider::Provider as CryptoProvider;
     pub use s2n_quic_tls::*;
 
-    impl super::Provider for Server {
+    // The endpoints are generic over the crypto provider selected on their builders
+
+    impl<L: ConfigLoader, C: 'static + CryptoProvider> super::Provider for Server<L, C> {
         type Server = Self;
-        type Client = Client;
+        type Client = Client<L, C>;
         type Error = core::convert::Infallible;
 
         fn start_server(self) -> Result<Self::Server, Self::Error> {
@@ -292,8 +309,8 @@ pub mod s2n_tls {
         }
     }
 
-    impl super::Provider for Client {
-        type Server = Server;
+    impl<L: ConfigLoader, C: 'static + CryptoProvider> super::Provider for Client<L, C> {
+        type Server = Server<L, C>;
         type Client = Self;
         type Error = core::convert::Infallible;
 
@@ -5,6 +5,7 @@ use crate::{connection::Connection, provider::*};
 use core::{
     fmt,
     task::{Context, Poll},
+    time::Duration,
 };
 use s2n_quic_transport::endpoint::handle::Acceptor;
 
@@ -101,6 +102,37 @@ impl Server {
         }
     }
 
+    /// Gracefully shuts down the [`Server`]
+    ///
+    /// The server stops accepting new connections and all of the active connections are
+    /// drained, as described in [`Handle::drain`](crate::connection::Handle::drain). The call
+    /// returns once all of the connections have closed, which happens no later than `timeout`
+    /// after the shutdown was started.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use std::{error::Error, path::Path, time::Duration};
+    /// # use s2n_quic::Server;
+    /// #
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn Error>> {
+    /// let mut server = Server::builder()
+    ///     .with_tls((Path::new("./certs/cert.pem"), Path::new("./certs/key.pem")))?
+    ///     .with_io("127.0.0.1:443")?
+    ///     .start()?;
+    ///
+    /// server.shutdown_gracefully(Duration::from_secs(30)).await?;
+    /// #    Ok(())
+    /// # }
+    /// ```
+    pub async fn shutdown_gracefully(
+        &mut self,
+        timeout: Duration,
+    ) -> Result<(), crate::connection::Error> {
+        futures::future::poll_fn(|cx| self.acceptor.poll_shutdown(timeout, cx)).await
+    }
+
     /// Returns the local address that this listener is bound to.
     ///
     /// This can be useful, for example, when binding to port `0` to figure out which
@@ -153,6 +153,69 @@ macro_rules! impl_receive_stream_api {
             $dispatch_body
         }
 
+        /// Registers a buffer which received data is directly written into
+        ///
+        /// Stream data which arrives after the buffer was registered is copied directly from the
+        /// decrypted packet into the spare capacity of the buffer, rather than into an internal
+        /// buffer of the stream. The chunks returned by [`receive`](Self::receive) and
+        /// [`receive_vectored`](Self::receive_vectored) are views into the registered buffer
+        /// until its capacity is exhausted, at which point the stream falls back to its internal
+        /// buffers. Any data contained in the buffer is discarded. Buffers which are shared
+        /// across all of the streams of a connection can be registered with
+        /// [`Handle::register_receive_buffer`](crate::connection::Handle::register_receive_buffer).
+        ///
+        /// Note that the `AsyncRead` implementations still copy the received chunks into the
+        /// slices provided by the caller, since those slices can't be handed to the stream.
+        /// Applications which want to avoid that copy should use [`receive`](Self::receive) or
+        /// [`receive_vectored`](Self::receive_vectored) instead.
+        ///
+        /// # Return value
+        ///
+        /// The function returns:
+        ///
+        /// - `Ok(true)` if the buffer was registered. In this case `buffer` is replaced with
+        ///   an empty buffer.
+        /// - `Ok(false)` if the buffer could not be registered, because data is already
+        ///   buffered at the current offset of the stream. In this case `buffer` is left
+        ///   untouched.
+        /// - `Err(e)` if the stream encountered a [`stream::Error`](crate::stream::Error).
+        ///
+        /// # Examples
+        ///
+        /// ```rust,no_run
+        /// # async fn test() -> s2n_quic::stream::Result<()> {
+        /// #   let mut stream: s2n_quic::stream::ReceiveStream = todo!();
+        /// #
+        /// use bytes::BytesMut;
+        ///
+        /// let mut buffer = BytesMut::with_capacity(64 * 1024);
+        /// stream.register_receive_buffer(&mut buffer)?;
+        ///
+        /// while let Some(chunk) = stream.receive().await? {
+        ///     println!("received: {:?}", chunk);
+        /// }
+        /// #
+        /// #   Ok(())
+        /// # }
+        /// ```
+        #[inline]
+        pub fn register_receive_buffer(
+            &mut self,
+            buffer: &mut bytes::BytesMut,
+        ) -> $crate::stream::Result<bool> {
+            macro_rules! $dispatch {
+                () => {
+                    Err($crate::stream::Error::non_readable())
+                };
+                ($variant: expr) => {
+                    $variant.register_receive_buffer(buffer)
+                };
+            }
+
+            let $stream = self;
+            $dispatch_body
+        }
+
         /// Notifies the peer to stop sending data on the stream.
         ///
         /// This requests the peer to finish the stream as soon as possible
@@ -199,6 +199,43 @@ macro_rules! impl_send_stream_api {
             $dispatch_body
         }
 
+        /// Sends data on the stream with a deadline without blocking the task.
+        ///
+        /// The `lifetime` of the data starts once it is first transmitted to the peer. If the
+        /// data is lost after the lifetime has elapsed, it is not retransmitted. Instead, the
+        /// stream is reset with the provided `error_code`, which notifies the peer that the
+        /// data will not be delivered. This is useful for data which is no longer relevant
+        /// after a period of time, like frames of a live video.
+        ///
+        /// [`poll_send_ready`](Self::poll_send_ready) _must_ be called before calling this method.
+        ///
+        /// # Return value
+        ///
+        /// The function returns:
+        /// - `Ok(())` if the data was enqueued for sending.
+        /// - `Err(SendingBlocked)` if the stream did not have enough capacity to enqueue the
+        ///   `chunk`.
+        /// - `Err(e)` if the stream encountered a [`stream::Error`](crate::stream::Error).
+        #[inline]
+        pub fn send_data_with_deadline(
+            &mut self,
+            chunk: bytes::Bytes,
+            lifetime: core::time::Duration,
+            error_code: $crate::application::Error,
+        ) -> $crate::stream::Result<()> {
+            macro_rules! $dispatch {
+                () => {
+                    Err($crate::stream::Error::non_writable())
+                };
+                ($variant: expr) => {
+                    $variant.send_data_with_deadline(chunk, lifetime, error_code)
+                };
+            }
+
+            let $stream = self;
+            $dispatch_body
+        }
+
         /// Flushes the stream and waits for the peer to receive all outstanding data.
         ///
         /// # Return value
@@ -376,6 +413,44 @@ macro_rules! impl_send_stream_api {
             let $stream = self;
             $dispatch_body
         }
+
+        /// Closes the stream with an [error code](crate::application::Error) after delivering the
+        /// first `reliable_size` bytes of the stream.
+        ///
+        /// This is useful for protocols which write a header at the start of the stream which the
+        /// peer needs to receive, even if the rest of the stream is abandoned. The peer will
+        /// receive all of the data up to `reliable_size` before being notified of the [error
+        /// code](crate::application::Error).
+        ///
+        /// Both peers must enable reliable stream resets in their connection
+        /// [`Limits`](crate::provider::limits::Limits), otherwise
+        /// [`stream::Error::ReliableResetUnsupported`](crate::stream::Error::ReliableResetUnsupported)
+        /// is returned.
+        ///
+        /// # Return value
+        ///
+        /// The function returns:
+        /// - `Ok(())` if the stream was reset successfully.
+        /// - `Err(e)` if the stream encountered a [`stream::Error`](crate::stream::Error). The
+        ///   stream may have been reset previously, or the connection itself was closed.
+        #[inline]
+        pub fn reset_at(
+            &mut self,
+            error_code: $crate::application::Error,
+            reliable_size: u64,
+        ) -> $crate::stream::Result<()> {
+            macro_rules! $dispatch {
+                () => {
+                    Err($crate::stream::Error::non_writable())
+                };
+                ($variant: expr) => {
+                    $variant.reset_at(error_code, reliable_size)
+                };
+            }
+
+            let $stream = self;
+            $dispatch_body
+        }
     };
 }
 
@@ -5,8 +5,15 @@ use crate::{
     client::Connect,
     provider::{
         self,
-        event::{events::PacketSent, ConnectionInfo, ConnectionMeta, Subscriber},
-        io::testing::{rand, spawn, test, time::delay, Model},
+        event::{
+            events::{ConnectionCloseReceived, PacketSent, PacketsOpened, PacketsSealed},
+            ConnectionInfo, ConnectionMeta, Subscriber,
+        },
+        io::testing::{
+            rand, spawn, test,
+            time::{delay, now},
+            Model,
+        },
         packet_interceptor::Loss,
     },
     Client, Server,
@@ -302,6 +309,125 @@ fn local_stream_open_notify_test() {
     .unwrap();
 }
 
+/// Ensures peer streams are routed based on their headers
+///
+/// # Client expectations
+/// * The client opens a unidirectional stream with a 1-byte header
+/// * The client opens a bidirectional stream with a 4-byte header and reads the echoed data
+/// * The client opens a bidirectional stream with an unknown header, which is reset
+///
+/// # Server expectations
+/// * The server routes the known streams to their acceptors, without the headers
+/// * The server echoes data on the bidirectional stream
+#[test]
+fn stream_router_test() {
+    use crate::stream::{PeerStream, Type};
+
+    const CONTROL: u64 = 0x00;
+    const ECHO: u64 = 0x4000;
+    const UNKNOWN: u64 = 0x07;
+    const INVALID: u64 = 1 << 62;
+    const UNKNOWN_ERROR: u8 = 123;
+
+    let model = Model::default();
+    test(model, |handle| {
+        let mut server = build_server(handle)?;
+        let server_addr = server.local_addr()?;
+
+        spawn(async move {
+            while let Some(connection) = server.accept().await {
+                let (handle, acceptor) = connection.split();
+                let mut router = acceptor.route_by_header(UNKNOWN_ERROR.into());
+                let mut control = router.register(CONTROL).unwrap();
+                let mut echo = router.register(ECHO).unwrap();
+                assert_eq!(
+                    router.register(ECHO).unwrap_err(),
+                    crate::connection::RegisterError::AlreadyRegistered
+                );
+                // headers must be encodable as variable-length integers
+                assert_eq!(
+                    router.register(INVALID).unwrap_err(),
+                    crate::connection::RegisterError::InvalidHeader
+                );
+
+                primary::spawn(async move {
+                    let mut stream = control.accept().await.unwrap().unwrap();
+                    assert!(matches!(stream, PeerStream::Receive(_)));
+
+                    let mut data = vec![];
+                    while let Some(chunk) = stream.receive().await.unwrap() {
+                        data.extend_from_slice(&chunk);
+                    }
+                    assert_eq!(data, b"control");
+                });
+
+                primary::spawn(async move {
+                    let mut stream = echo.accept().await.unwrap().unwrap();
+                    while let Some(chunk) = stream.receive().await.unwrap() {
+                        stream.send(chunk).await.unwrap();
+                    }
+                    stream.finish().unwrap();
+
+                    // keep the connection open until the streams are done
+                    drop(handle);
+                });
+            }
+        });
+
+        let client = build_client(handle)?;
+
+        primary::spawn(async move {
+            let connect = Connect::new(server_addr).with_server_name("localhost");
+            let mut connection = client.connect(connect).await.unwrap();
+
+            let error = connection
+                .open_stream_with_header(Type::Unidirectional, INVALID)
+                .await
+                .unwrap_err();
+            assert!(matches!(error, crate::stream::Error::InvalidHeader { .. }));
+
+            let mut stream = connection
+                .open_stream_with_header(Type::Unidirectional, CONTROL)
+                .await
+                .unwrap();
+            stream.send(Bytes::from_static(b"control")).await.unwrap();
+            stream.finish().unwrap();
+
+            let mut stream = connection
+                .open_stream_with_header(Type::Bidirectional, UNKNOWN)
+                .await
+                .unwrap();
+            stream.send(Bytes::from_static(b"unknown")).await.unwrap();
+            let error = loop {
+                if let Err(error) = stream.receive().await {
+                    break error;
+                }
+            };
+            assert!(matches!(
+                error,
+                crate::stream::Error::StreamReset { error, .. }
+                    if error == crate::application::Error::from(UNKNOWN_ERROR)
+            ));
+
+            let mut stream = connection
+                .open_stream_with_header(Type::Bidirectional, ECHO)
+                .await
+                .unwrap();
+            stream.send(Bytes::from_static(b"echo")).await.unwrap();
+            stream.finish().unwrap();
+
+            let mut data = vec![];
+            while let Some(chunk) = stream.receive().await.unwrap() {
+                data.extend_from_slice(&chunk);
+            }
+            assert_eq!(data, b"echo");
+        });
+
+        Ok(())
+    })
+    .unwrap();
+}
+
 macro_rules! event_recorder {
     ($sub:ident, $con:ident, $event:ty, $method:ident) => {
         struct $sub {
@@ -362,6 +488,18 @@ event_recorder!(
     MtuUpdated,
     on_mtu_updated
 );
+event_recorder!(
+    PacketsSealedRecorder,
+    PacketsSealedRecorderContext,
+    PacketsSealed,
+    on_packets_sealed
+);
+event_recorder!(
+    PacketsOpenedRecorder,
+    PacketsOpenedRecorderContext,
+    PacketsOpened,
+    on_packets_opened
+);
 
 #[test]
 fn packet_sent_event_test() {
@@ -413,6 +551,33 @@ fn packet_sent_event_test() {
     assert!(events.is_empty());
 }
 
+/// Ensures 1-RTT packets are sealed and opened in batches during a bulk transfer
+#[test]
+fn batch_crypto_test() {
+    let sealed = PacketsSealedRecorder::new();
+    let sealed_events = sealed.events();
+    let opened = PacketsOpenedRecorder::new();
+    let opened_events = opened.events();
+
+    test(Model::default(), |handle| {
+        let server = Server::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(SERVER_CERTS)?
+            .with_event((events(), (sealed, opened)))?
+            .start()?;
+        let addr = start_server(server)?;
+        let client = build_client(handle)?;
+        start_client(client, addr, Data::new(1_000_000))
+    })
+    .unwrap();
+
+    // the server echos the data so it both seals and opens batches of packets
+    let sealed_events = sealed_events.lock().unwrap();
+    assert!(sealed_events.iter().any(|event| event.count > 1));
+    let opened_events = opened_events.lock().unwrap();
+    assert!(opened_events.iter().any(|event| event.count > 1));
+}
+
 // Construct a simulation where a client sends some data, which the server echos
 // back. The MtuUpdated events that the server experiences are recorded and
 // returns at the end of the simulation.
@@ -479,3 +644,261 @@ fn mtu_probe_jumbo_frame_unsupported_test() {
     // ETHERNET_MTU - UDP_HEADER_LEN - IPV4_HEADER_LEN
     assert_eq!(last_mtu.mtu, 1472);
 }
+
+#[derive(Default)]
+struct CloseReceivedRecorder {
+    events: Arc<Mutex<Vec<(crate::connection::Error, Option<Vec<u8>>)>>>,
+}
+
+impl Subscriber for CloseReceivedRecorder {
+    type ConnectionContext = ();
+
+    fn create_connection_context(
+        &mut self,
+        _meta: &ConnectionMeta,
+        _info: &ConnectionInfo,
+    ) -> Self::ConnectionContext {
+    }
+
+    fn on_connection_close_received(
+        &mut self,
+        _context: &mut Self::ConnectionContext,
+        _meta: &ConnectionMeta,
+        event: &ConnectionCloseReceived,
+    ) {
+        let reason = event.reason.map(|reason| reason.to_vec());
+        self.events.lock().unwrap().push((event.error, reason));
+    }
+}
+
+/// Ensures the reason passed to `close_with_reason` is delivered to the peer
+#[test]
+fn close_with_reason_test() {
+    const ERROR: u8 = 42;
+
+    let subscriber = CloseReceivedRecorder::default();
+    let events = subscriber.events.clone();
+    let accept_error = Arc::new(Mutex::new(None));
+    let server_accept_error = accept_error.clone();
+
+    test(Model::default(), |handle| {
+        let mut server = Server::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(SERVER_CERTS)?
+            .with_event((events(), subscriber))?
+            .start()?;
+        let server_addr = server.local_addr()?;
+
+        spawn(async move {
+            while let Some(mut connection) = server.accept().await {
+                let accept_error = server_accept_error.clone();
+                primary::spawn(async move {
+                    // wait for the client to close the connection
+                    if let Err(error) = connection.accept().await {
+                        *accept_error.lock().unwrap() = Some(error);
+                    }
+                });
+            }
+        });
+
+        let client = build_client(handle)?;
+
+        primary::spawn(async move {
+            let connect = Connect::new(server_addr).with_server_name("localhost");
+            let connection = client.connect(connect).await.unwrap();
+            connection.close_with_reason(ERROR.into(), b"shutting down");
+
+            // give the CONNECTION_CLOSE time to reach the server
+            delay(Duration::from_secs(1)).await;
+        });
+
+        Ok(())
+    })
+    .unwrap();
+
+    let events = events.lock().unwrap();
+    assert_eq!(events.len(), 1);
+    let (error, reason) = &events[0];
+    assert!(matches!(
+        error,
+        crate::connection::Error::Application { error, .. }
+            if *error == crate::application::Error::from(ERROR)
+    ));
+    assert_eq!(reason.as_deref(), Some(&b"shutting down"[..]));
+
+    // the reason is also returned to the application with the connection error
+    let error = accept_error
+        .lock()
+        .unwrap()
+        .expect("connection should be closed with an error");
+    assert_eq!(
+        error.close_reason().and_then(|reason| reason.as_str()),
+        Some("shutting down")
+    );
+}
+
+/// Ensures a connection which is still handshaking when the server shuts down is drained
+/// instead of being closed immediately
+#[test]
+fn drain_handshaking_connection_test() {
+    let model = Model::default();
+    model.set_delay(Duration::from_millis(100));
+
+    test(model, |handle| {
+        let mut server = build_server(handle)?;
+        let server_addr = server.local_addr()?;
+
+        primary::spawn(async move {
+            // the server receives the client's Initial after 100ms and completes the handshake
+            // after 300ms, so the shutdown starts while the connection is handshaking
+            delay(Duration::from_millis(150)).await;
+
+            let start = now();
+            let timeout = Duration::from_secs(10);
+            server.shutdown_gracefully(timeout).await.unwrap();
+
+            // the connection closes once the handshake completes, since it has no streams
+            assert!(now() - start < timeout);
+        });
+
+        let client = build_client(handle)?;
+
+        primary::spawn(async move {
+            let connect = Connect::new(server_addr).with_server_name("localhost");
+            let mut connection = client.connect(connect).await.unwrap();
+
+            let error = connection.closed().await;
+            // the server closes the connection without an error once it's drained
+            assert!(matches!(
+                error,
+                crate::connection::Error::Closed { initiator, .. } if initiator.is_remote()
+            ));
+        });
+
+        Ok(())
+    })
+    .unwrap();
+}
+
+/// Ensures data still flows when the endpoint-wide send buffer budget is smaller than the
+/// amount of data which is sent
+#[test]
+fn endpoint_send_buffer_budget_test() {
+    use crate::provider::endpoint_limits;
+
+    test(Model::default(), |handle| {
+        let limits = endpoint_limits::Default::builder()
+            .with_max_send_buffer_size(4096)?
+            .build()?;
+        let server = Server::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(SERVER_CERTS)?
+            .with_event(events())?
+            .with_endpoint_limits(limits)?
+            .start()?;
+        let addr = start_server(server)?;
+        let client = build_client(handle)?;
+
+        primary::spawn(async move {
+            let connect = Connect::new(addr).with_server_name("localhost");
+            let mut connection = client.connect(connect).await.unwrap();
+            let stream = connection.open_bidirectional_stream().await.unwrap();
+            let (mut recv, mut send) = stream.split();
+
+            let mut send_data = Data::new(1_000_000);
+            let mut recv_data = send_data;
+
+            primary::spawn(async move {
+                while let Some(chunk) = send_data.send_one(usize::MAX) {
+                    send.send(chunk).await.unwrap();
+                }
+                send.finish().unwrap();
+            });
+
+            // all of the data is echoed back by the server, despite its small send budget
+            while let Some(chunk) = recv.receive().await.unwrap() {
+                recv_data.receive(&[chunk]);
+            }
+            assert!(recv_data.is_finished());
+        });
+
+        Ok(())
+    })
+    .unwrap();
+}
+
+/// Ensures both peers export the same keying material once they opt in to retaining the
+/// TLS session
+#[test]
+fn export_keying_material_test() {
+    let server_outputs = Arc::new(Mutex::new(vec![]));
+    let client_output = Arc::new(Mutex::new(None));
+
+    test(Model::default(), |handle| {
+        let limits = provider::limits::Limits::default()
+            .with_keying_material_exporter(true)
+            .unwrap();
+
+        let mut server = Server::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(SERVER_CERTS)?
+            .with_event(events())?
+            .with_limits(limits)?
+            .start()?;
+        let server_addr = server.local_addr()?;
+
+        let outputs = server_outputs.clone();
+        spawn(async move {
+            while let Some(mut connection) = server.accept().await {
+                let output = connection
+                    .export_keying_material(b"EXPORTER-test", b"context", 32)
+                    .unwrap();
+                outputs.lock().unwrap().push(output);
+
+                spawn(async move {
+                    // wait for the client to close the connection
+                    let _ = connection.accept().await;
+                });
+            }
+        });
+
+        let client = Client::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(certificates::CERT_PEM)?
+            .with_event(events())?
+            .with_limits(limits)?
+            .start()?;
+        let default_client = build_client(handle)?;
+
+        let output = client_output.clone();
+        primary::spawn(async move {
+            let connect = Connect::new(server_addr).with_server_name("localhost");
+            let connection = client.connect(connect).await.unwrap();
+            *output.lock().unwrap() = Some(
+                connection
+                    .export_keying_material(b"EXPORTER-test", b"context", 32)
+                    .unwrap(),
+            );
+
+            // the session isn't retained unless the application opts in
+            let connect = Connect::new(server_addr).with_server_name("localhost");
+            let mut default_connection = default_client.connect(connect).await.unwrap();
+            assert!(matches!(
+                default_connection.export_keying_material(b"EXPORTER-test", b"context", 32),
+                Err(crate::connection::Error::KeyingMaterialExporterDisabled { .. })
+            ));
+            // the connection stays open after the failed export
+            assert!(default_connection.ping().is_ok());
+
+            // keep the connections open until the server has exported its keying material
+            delay(Duration::from_secs(1)).await;
+        });
+
+        Ok(())
+    })
+    .unwrap();
+
+    let client_output = client_output.lock().unwrap().clone().unwrap();
+    assert_eq!(client_output.len(), 32);
+    assert!(server_outputs.lock().unwrap().contains(&client_output));
+}